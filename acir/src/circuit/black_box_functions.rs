@@ -48,6 +48,21 @@ pub enum BlackBoxFunc {
     /// Compute a recursive aggregation object when verifying a proof inside another circuit.
     /// This outputted aggregation object will then be either checked in a top-level verifier or aggregated upon again.
     RecursiveAggregation,
+    /// Adds two points on the embedded curve on which [`FieldElement`][acir_field::FieldElement] is defined.
+    EmbeddedCurveAdd,
+    /// Performs scalar multiplication of an arbitrary point on the embedded curve on which
+    /// [`FieldElement`][acir_field::FieldElement] is defined.
+    VariableBaseScalarMul,
+    /// Calculates the SHA512 hash of the inputs.
+    SHA512,
+    /// Calculates the Blake3 hash of the inputs.
+    Blake3,
+    /// Verifies an EdDSA signature over the Ed25519 curve.
+    EddsaEd25519,
+    /// Verifies an EdDSA signature over the BabyJubJub curve, as produced by circomlib.
+    EddsaBabyJubJub,
+    /// Recovers the public key which produced an ECDSA signature over the secp256k1 curve.
+    EcdsaSecp256k1Recover,
 }
 
 impl std::fmt::Display for BlackBoxFunc {
@@ -72,6 +87,13 @@ impl BlackBoxFunc {
             BlackBoxFunc::Keccak256 => "keccak256",
             BlackBoxFunc::RecursiveAggregation => "recursive_aggregation",
             BlackBoxFunc::EcdsaSecp256r1 => "ecdsa_secp256r1",
+            BlackBoxFunc::EmbeddedCurveAdd => "embedded_curve_add",
+            BlackBoxFunc::VariableBaseScalarMul => "variable_base_scalar_mul",
+            BlackBoxFunc::SHA512 => "sha512",
+            BlackBoxFunc::Blake3 => "blake3",
+            BlackBoxFunc::EddsaEd25519 => "eddsa_ed25519",
+            BlackBoxFunc::EddsaBabyJubJub => "eddsa_babyjubjub",
+            BlackBoxFunc::EcdsaSecp256k1Recover => "ecdsa_secp256k1_recover",
         }
     }
     pub fn lookup(op_name: &str) -> Option<BlackBoxFunc> {
@@ -89,6 +111,13 @@ impl BlackBoxFunc {
             "range" => Some(BlackBoxFunc::RANGE),
             "keccak256" => Some(BlackBoxFunc::Keccak256),
             "recursive_aggregation" => Some(BlackBoxFunc::RecursiveAggregation),
+            "embedded_curve_add" => Some(BlackBoxFunc::EmbeddedCurveAdd),
+            "variable_base_scalar_mul" => Some(BlackBoxFunc::VariableBaseScalarMul),
+            "sha512" => Some(BlackBoxFunc::SHA512),
+            "blake3" => Some(BlackBoxFunc::Blake3),
+            "eddsa_ed25519" => Some(BlackBoxFunc::EddsaEd25519),
+            "eddsa_babyjubjub" => Some(BlackBoxFunc::EddsaBabyJubJub),
+            "ecdsa_secp256k1_recover" => Some(BlackBoxFunc::EcdsaSecp256k1Recover),
             _ => None,
         }
     }
@@ -1,4 +1,5 @@
 use crate::native_types::{Expression, Witness};
+use acir_field::FieldElement;
 use brillig::ForeignCallResult;
 use brillig::Opcode as BrilligOpcode;
 use serde::{Deserialize, Serialize};
@@ -6,9 +7,9 @@ use serde::{Deserialize, Serialize};
 /// Inputs for the Brillig VM. These are the initial inputs
 /// that the Brillig VM will use to start.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
-pub enum BrilligInputs {
-    Single(Expression),
-    Array(Vec<Expression>),
+pub enum BrilligInputs<F = FieldElement> {
+    Single(Expression<F>),
+    Array(Vec<Expression<F>>),
 }
 
 /// Outputs for the Brillig VM. Once the VM has completed
@@ -20,14 +21,14 @@ pub enum BrilligOutputs {
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
-pub struct Brillig {
-    pub inputs: Vec<BrilligInputs>,
+pub struct Brillig<F = FieldElement> {
+    pub inputs: Vec<BrilligInputs<F>>,
     pub outputs: Vec<BrilligOutputs>,
     /// Results of oracles/functions external to brillig like a database read.
     // Each element of this vector corresponds to a single foreign call but may contain several values.
-    pub foreign_call_results: Vec<ForeignCallResult>,
+    pub foreign_call_results: Vec<ForeignCallResult<F>>,
     /// The Brillig VM bytecode to be executed by this ACIR opcode.
-    pub bytecode: Vec<BrilligOpcode>,
+    pub bytecode: Vec<BrilligOpcode<F>>,
     /// Predicate of the Brillig execution - indicates if it should be skipped
-    pub predicate: Option<Expression>,
+    pub predicate: Option<Expression<F>>,
 }
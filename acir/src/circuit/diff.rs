@@ -0,0 +1,799 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use acir_field::AcirField;
+
+use super::{
+    brillig::{BrilligInputs, BrilligOutputs},
+    directives::{Directive, QuotientDirective},
+    opcodes::{BlackBoxFuncCall, FunctionInput, MemOp},
+    Circuit, Opcode,
+};
+use crate::{
+    native_types::{Expression, Witness},
+    BlackBoxFunc,
+};
+
+/// Options controlling how two [`Circuit`]s are compared by [`Circuit::diff`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// Whether opcodes which only differ in the numbering of their witnesses should be considered equal.
+    ///
+    /// When set, a consistent mapping from the witnesses of the old circuit to those of the new circuit is built
+    /// from the matched opcodes. Opcodes whose witnesses conflict with this mapping are reported as modified.
+    pub match_witnesses: bool,
+}
+
+/// A change to a single opcode between two circuits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OpcodeChange<'a, F: AcirField> {
+    /// An opcode which is only present in the new circuit.
+    Added { index: usize, opcode: &'a Opcode<F> },
+    /// An opcode which is only present in the old circuit.
+    Removed { index: usize, opcode: &'a Opcode<F> },
+    /// An opcode of the old circuit which has been replaced with an opcode of the same kind in the new circuit.
+    Modified { old_index: usize, new_index: usize, old: &'a Opcode<F>, new: &'a Opcode<F> },
+}
+
+/// The witnesses which have been added to or removed from a set of public inputs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WitnessSetChange {
+    pub added: BTreeSet<Witness>,
+    pub removed: BTreeSet<Witness>,
+}
+
+impl WitnessSetChange {
+    fn new(old: impl IntoIterator<Item = Witness>, new: &BTreeSet<Witness>) -> Self {
+        let old: BTreeSet<Witness> = old.into_iter().collect();
+        WitnessSetChange {
+            added: new.difference(&old).copied().collect(),
+            removed: old.difference(new).copied().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// A change in the number of calls to a black box function between two circuits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlackBoxCountChange {
+    pub function: BlackBoxFunc,
+    pub old: usize,
+    pub new: usize,
+}
+
+/// The structural differences between two circuits, as returned by [`Circuit::diff`].
+///
+/// Implements [`Display`][std::fmt::Display] to produce a human readable report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitDiff<'a, F: AcirField> {
+    /// The changes to the opcodes of the circuit, in the order in which they appear.
+    pub opcodes: Vec<OpcodeChange<'a, F>>,
+    pub public_parameters: WitnessSetChange,
+    pub return_values: WitnessSetChange,
+    /// The black box functions which are called a different number of times, sorted by name.
+    pub black_box_counts: Vec<BlackBoxCountChange>,
+    /// The mapping from witnesses of the old circuit to witnesses of the new circuit.
+    ///
+    /// This is empty unless [`DiffOptions::match_witnesses`] is set.
+    pub witness_mapping: BTreeMap<Witness, Witness>,
+}
+
+impl<'a, F: AcirField> CircuitDiff<'a, F> {
+    /// Returns `true` if no differences were found between the two circuits.
+    pub fn is_empty(&self) -> bool {
+        self.opcodes.is_empty()
+            && self.public_parameters.is_empty()
+            && self.return_values.is_empty()
+            && self.black_box_counts.is_empty()
+    }
+}
+
+impl<F: AcirField> Circuit<F> {
+    /// Compares `self` against `new`, returning the opcodes which have been added, removed or modified
+    /// along with any changes to the circuit's public inputs and black box function usage.
+    ///
+    /// Opcodes are aligned using a longest common subsequence, so inserting an opcode in the middle
+    /// of a circuit is reported as a single addition rather than a change to every following opcode.
+    pub fn diff<'a>(&'a self, new: &'a Circuit<F>, options: DiffOptions) -> CircuitDiff<'a, F> {
+        let (old_opcodes, new_opcodes) = (&self.opcodes, &new.opcodes);
+
+        let (edits, witness_mapping) = if options.match_witnesses {
+            let old_shapes: Vec<_> = old_opcodes.iter().map(Shape::new).collect();
+            let new_shapes: Vec<_> = new_opcodes.iter().map(Shape::new).collect();
+            let mut edits = align(old_shapes.len(), new_shapes.len(), |i, j| {
+                old_shapes[i].opcode == new_shapes[j].opcode
+            });
+            let mut witness_mapping = WitnessMapping::default();
+            for edit in &mut edits {
+                if let Edit::Equal(i, j) = *edit {
+                    if !witness_mapping.extend(&old_shapes[i].witnesses, &new_shapes[j].witnesses) {
+                        *edit = Edit::Conflict(i, j);
+                    }
+                }
+            }
+            (edits, witness_mapping.forward)
+        } else {
+            let edits = align(old_opcodes.len(), new_opcodes.len(), |i, j| {
+                old_opcodes[i] == new_opcodes[j]
+            });
+            (edits, BTreeMap::new())
+        };
+        let opcodes = opcode_changes(old_opcodes, new_opcodes, edits);
+
+        let translate = |witnesses: &BTreeSet<Witness>| -> Vec<Witness> {
+            witnesses
+                .iter()
+                .map(|witness| *witness_mapping.get(witness).unwrap_or(witness))
+                .collect()
+        };
+        let public_parameters =
+            WitnessSetChange::new(translate(&self.public_parameters.0), &new.public_parameters.0);
+        let return_values =
+            WitnessSetChange::new(translate(&self.return_values.0), &new.return_values.0);
+
+        CircuitDiff {
+            opcodes,
+            public_parameters,
+            return_values,
+            black_box_counts: black_box_count_changes(self, new),
+            witness_mapping,
+        }
+    }
+}
+
+impl<F: AcirField> std::fmt::Display for CircuitDiff<'_, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "circuits are structurally identical");
+        }
+
+        if !self.opcodes.is_empty() {
+            let count = |predicate: fn(&OpcodeChange<'_, F>) -> bool| {
+                self.opcodes.iter().filter(|change| predicate(change)).count()
+            };
+            writeln!(
+                f,
+                "opcodes: {} added, {} removed, {} modified",
+                count(|change| matches!(change, OpcodeChange::Added { .. })),
+                count(|change| matches!(change, OpcodeChange::Removed { .. })),
+                count(|change| matches!(change, OpcodeChange::Modified { .. })),
+            )?;
+            for change in &self.opcodes {
+                match change {
+                    OpcodeChange::Added { index, opcode } => writeln!(f, "+ [{index}] {opcode}")?,
+                    OpcodeChange::Removed { index, opcode } => writeln!(f, "- [{index}] {opcode}")?,
+                    OpcodeChange::Modified { old_index, new_index, old, new } => {
+                        writeln!(f, "~ [{old_index} -> {new_index}]")?;
+                        writeln!(f, "    - {old}")?;
+                        writeln!(f, "    + {new}")?;
+                    }
+                }
+            }
+        }
+
+        let write_witness_set_change = |f: &mut std::fmt::Formatter<'_>,
+                                        name: &str,
+                                        change: &WitnessSetChange|
+         -> std::fmt::Result {
+            if change.is_empty() {
+                return Ok(());
+            }
+            let format_witnesses = |witnesses: &BTreeSet<Witness>| {
+                let witnesses: Vec<_> = witnesses
+                    .iter()
+                    .map(|witness| format!("_{}", witness.witness_index()))
+                    .collect();
+                format!("[{}]", witnesses.join(", "))
+            };
+            writeln!(
+                f,
+                "{name}: added {}, removed {}",
+                format_witnesses(&change.added),
+                format_witnesses(&change.removed)
+            )
+        };
+        write_witness_set_change(f, "public parameters", &self.public_parameters)?;
+        write_witness_set_change(f, "return values", &self.return_values)?;
+
+        if !self.black_box_counts.is_empty() {
+            writeln!(f, "black box functions:")?;
+            for BlackBoxCountChange { function, old, new } in &self.black_box_counts {
+                let delta = *new as i64 - *old as i64;
+                writeln!(f, "    {}: {old} -> {new} ({delta:+})", function.name())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A step in the alignment of the opcodes of two circuits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edit {
+    /// The opcodes at these positions in the old and new circuits are equal.
+    Equal(usize, usize),
+    /// The opcodes at these positions have the same shape but their witnesses are inconsistent with the other matches.
+    Conflict(usize, usize),
+    /// The opcode at this position in the old circuit has been removed.
+    Delete(usize),
+    /// The opcode at this position in the new circuit has been inserted.
+    Insert(usize),
+}
+
+/// Aligns two sequences of lengths `old_len` and `new_len` using Myers' diff algorithm,
+/// where `eq(i, j)` returns whether the `i`th element of the old sequence equals the `j`th element of the new sequence.
+fn align(old_len: usize, new_len: usize, eq: impl Fn(usize, usize) -> bool) -> Vec<Edit> {
+    // Circuits being compared often share long runs of opcodes at either end, so these are trimmed
+    // to keep the quadratic memory usage of the search to the region which has changed.
+    let common_len = old_len.min(new_len);
+    let prefix = (0..common_len).take_while(|&i| eq(i, i)).count();
+    let suffix =
+        (0..common_len - prefix).take_while(|&i| eq(old_len - 1 - i, new_len - 1 - i)).count();
+    let n = (old_len - prefix - suffix) as isize;
+    let m = (new_len - prefix - suffix) as isize;
+
+    // `v[offset + k]` holds the furthest reaching `x` on diagonal `k = x - y`.
+    // Before each step `d`, the diagonals which the step may read from are recorded for backtracking.
+    let offset = n + m + 1;
+    let mut v = vec![0_isize; (2 * offset + 1) as usize];
+    let mut trace = Vec::new();
+    'search: for d in 0..=(n + m) {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let index = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && eq(prefix + x as usize, prefix + y as usize) {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let furthest = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let previous_k =
+            if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) { k + 1 } else { k - 1 };
+        let previous_x = furthest(previous_k);
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(prefix + x as usize, prefix + y as usize));
+        }
+        if d > 0 {
+            if x == previous_x {
+                edits.push(Edit::Insert(prefix + (y - 1) as usize));
+            } else {
+                edits.push(Edit::Delete(prefix + (x - 1) as usize));
+            }
+        }
+        (x, y) = (previous_x, previous_y);
+    }
+
+    (0..prefix)
+        .map(|i| Edit::Equal(i, i))
+        .chain(edits.into_iter().rev())
+        .chain((0..suffix).rev().map(|i| Edit::Equal(old_len - 1 - i, new_len - 1 - i)))
+        .collect()
+}
+
+/// Converts an alignment of the opcodes of two circuits into a list of changes.
+///
+/// Within each run of removed and added opcodes, a removed opcode is paired with the first added opcode
+/// of the same kind and reported as modified.
+fn opcode_changes<'a, F: AcirField>(
+    old_opcodes: &'a [Opcode<F>],
+    new_opcodes: &'a [Opcode<F>],
+    edits: Vec<Edit>,
+) -> Vec<OpcodeChange<'a, F>> {
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+
+    let flush = |changes: &mut Vec<OpcodeChange<'a, F>>,
+                 removed: &mut Vec<usize>,
+                 added: &mut Vec<usize>| {
+        let mut paired = vec![false; added.len()];
+        for old_index in removed.drain(..) {
+            let old = &old_opcodes[old_index];
+            let pair = added.iter().zip(&paired).position(|(new_index, paired)| {
+                !paired && new_opcodes[*new_index].name() == old.name()
+            });
+            match pair {
+                Some(position) => {
+                    paired[position] = true;
+                    let new_index = added[position];
+                    changes.push(OpcodeChange::Modified {
+                        old_index,
+                        new_index,
+                        old,
+                        new: &new_opcodes[new_index],
+                    });
+                }
+                None => changes.push(OpcodeChange::Removed { index: old_index, opcode: old }),
+            }
+        }
+        for (new_index, paired) in added.drain(..).zip(paired) {
+            if !paired {
+                changes.push(OpcodeChange::Added {
+                    index: new_index,
+                    opcode: &new_opcodes[new_index],
+                });
+            }
+        }
+    };
+
+    for edit in edits {
+        match edit {
+            Edit::Delete(old_index) => removed.push(old_index),
+            Edit::Insert(new_index) => added.push(new_index),
+            Edit::Equal(..) | Edit::Conflict(..) => {
+                flush(&mut changes, &mut removed, &mut added);
+                if let Edit::Conflict(old_index, new_index) = edit {
+                    changes.push(OpcodeChange::Modified {
+                        old_index,
+                        new_index,
+                        old: &old_opcodes[old_index],
+                        new: &new_opcodes[new_index],
+                    });
+                }
+            }
+        }
+    }
+    flush(&mut changes, &mut removed, &mut added);
+
+    changes
+}
+
+fn black_box_count_changes<F: AcirField>(
+    old: &Circuit<F>,
+    new: &Circuit<F>,
+) -> Vec<BlackBoxCountChange> {
+    let mut counts: HashMap<BlackBoxFunc, (usize, usize)> = HashMap::new();
+    let black_box_funcs = |circuit: &'_ Circuit<F>| -> Vec<BlackBoxFunc> {
+        circuit
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::BlackBoxFuncCall(call) => Some(call.get_black_box_func()),
+                _ => None,
+            })
+            .collect()
+    };
+    for function in black_box_funcs(old) {
+        counts.entry(function).or_default().0 += 1;
+    }
+    for function in black_box_funcs(new) {
+        counts.entry(function).or_default().1 += 1;
+    }
+
+    let mut changes: Vec<_> = counts
+        .into_iter()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(function, (old, new))| BlackBoxCountChange { function, old, new })
+        .collect();
+    changes.sort_by_key(|change| change.function.name());
+    changes
+}
+
+/// A one-to-one mapping between the witnesses of two circuits.
+#[derive(Default)]
+struct WitnessMapping {
+    forward: BTreeMap<Witness, Witness>,
+    backward: BTreeMap<Witness, Witness>,
+}
+
+impl WitnessMapping {
+    /// Maps each of the `old` witnesses to the `new` witness at the same position,
+    /// returning `false` without modifying the mapping if this conflicts with the existing mapping.
+    fn extend(&mut self, old: &[Witness], new: &[Witness]) -> bool {
+        let consistent = old.iter().zip(new).all(|(old, new)| {
+            self.forward.get(old).map_or(true, |mapped| mapped == new)
+                && self.backward.get(new).map_or(true, |mapped| mapped == old)
+        });
+        if consistent {
+            for (old, new) in old.iter().zip(new) {
+                self.forward.insert(*old, *new);
+                self.backward.insert(*new, *old);
+            }
+        }
+        consistent
+    }
+}
+
+/// An opcode with its witnesses renumbered in order of first appearance,
+/// so that opcodes which only differ in the numbering of their witnesses have the same shape.
+struct Shape<F: AcirField> {
+    opcode: Opcode<F>,
+    /// The original witnesses of the opcode in order of first appearance.
+    witnesses: Vec<Witness>,
+}
+
+impl<F: AcirField> Shape<F> {
+    fn new(opcode: &Opcode<F>) -> Self {
+        let mut opcode = opcode.clone();
+        let mut witnesses = Vec::new();
+        let mut renumbering = HashMap::new();
+        visit_witnesses(&mut opcode, &mut |witness| {
+            let renumbered = *renumbering.entry(*witness).or_insert_with(|| {
+                witnesses.push(*witness);
+                Witness(witnesses.len() as u32 - 1)
+            });
+            *witness = renumbered;
+        });
+        Shape { opcode, witnesses }
+    }
+}
+
+/// Calls `visit` on every witness referenced by `opcode`, in a fixed order.
+fn visit_witnesses<F: AcirField>(opcode: &mut Opcode<F>, visit: &mut impl FnMut(&mut Witness)) {
+    match opcode {
+        Opcode::Arithmetic(expr) => visit_expression(expr, visit),
+        Opcode::BlackBoxFuncCall(call) => visit_black_box_call(call, visit),
+        Opcode::Directive(Directive::Quotient(QuotientDirective { a, b, q, r, predicate })) => {
+            visit_expression(a, visit);
+            visit_expression(b, visit);
+            visit(q);
+            visit(r);
+            predicate.iter_mut().for_each(|predicate| visit_expression(predicate, visit));
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, b, radix: _ }) => {
+            visit_expression(a, visit);
+            b.iter_mut().for_each(&mut *visit);
+        }
+        Opcode::Directive(Directive::PermutationSort { inputs, tuple: _, bits, sort_by: _ }) => {
+            inputs.iter_mut().flatten().for_each(|expr| visit_expression(expr, visit));
+            bits.iter_mut().for_each(&mut *visit);
+        }
+        Opcode::Brillig(brillig) => {
+            for input in &mut brillig.inputs {
+                match input {
+                    BrilligInputs::Single(expr) => visit_expression(expr, visit),
+                    BrilligInputs::Array(exprs) => {
+                        exprs.iter_mut().for_each(|expr| visit_expression(expr, visit));
+                    }
+                }
+            }
+            for output in &mut brillig.outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => visit(witness),
+                    BrilligOutputs::Array(witnesses) => witnesses.iter_mut().for_each(&mut *visit),
+                }
+            }
+            brillig.predicate.iter_mut().for_each(|predicate| visit_expression(predicate, visit));
+        }
+        Opcode::MemoryOp { block_id: _, op: MemOp { operation, index, value }, predicate } => {
+            visit_expression(operation, visit);
+            visit_expression(index, visit);
+            visit_expression(value, visit);
+            predicate.iter_mut().for_each(|predicate| visit_expression(predicate, visit));
+        }
+        Opcode::MemoryInit { block_id: _, init } => init.iter_mut().for_each(&mut *visit),
+    }
+}
+
+fn visit_expression<F>(expr: &mut Expression<F>, visit: &mut impl FnMut(&mut Witness)) {
+    for (_, lhs, rhs) in &mut expr.mul_terms {
+        visit(lhs);
+        visit(rhs);
+    }
+    for (_, witness) in &mut expr.linear_combinations {
+        visit(witness);
+    }
+}
+
+fn visit_black_box_call(call: &mut BlackBoxFuncCall, visit: &mut impl FnMut(&mut Witness)) {
+    fn inputs<'a>(
+        inputs: impl IntoIterator<Item = &'a mut FunctionInput>,
+        visit: &mut impl FnMut(&mut Witness),
+    ) {
+        inputs.into_iter().for_each(|input| visit(&mut input.witness));
+    }
+
+    match call {
+        BlackBoxFuncCall::AND { lhs, rhs, output } | BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+            inputs([lhs, rhs], visit);
+            visit(output);
+        }
+        BlackBoxFuncCall::RANGE { input } => inputs([input], visit),
+        BlackBoxFuncCall::SHA256 { inputs: hash_inputs, outputs }
+        | BlackBoxFuncCall::SHA512 { inputs: hash_inputs, outputs }
+        | BlackBoxFuncCall::Blake2s { inputs: hash_inputs, outputs }
+        | BlackBoxFuncCall::Blake3 { inputs: hash_inputs, outputs }
+        | BlackBoxFuncCall::Keccak256 { inputs: hash_inputs, outputs } => {
+            inputs(hash_inputs, visit);
+            outputs.iter_mut().for_each(&mut *visit);
+        }
+        BlackBoxFuncCall::Keccak256VariableLength {
+            inputs: hash_inputs,
+            var_message_size,
+            outputs,
+        } => {
+            inputs(hash_inputs.iter_mut().chain([var_message_size]), visit);
+            outputs.iter_mut().for_each(&mut *visit);
+        }
+        BlackBoxFuncCall::SchnorrVerify {
+            public_key_x,
+            public_key_y,
+            signature,
+            message,
+            output,
+        } => {
+            inputs([public_key_x, public_key_y], visit);
+            inputs(signature.iter_mut().chain(message), visit);
+            visit(output);
+        }
+        BlackBoxFuncCall::Pedersen { inputs: hash_inputs, domain_separator: _, outputs } => {
+            inputs(hash_inputs, visit);
+            visit(&mut outputs.0);
+            visit(&mut outputs.1);
+        }
+        BlackBoxFuncCall::HashToField128Security { inputs: hash_inputs, output } => {
+            inputs(hash_inputs, visit);
+            visit(output);
+        }
+        BlackBoxFuncCall::EcdsaSecp256k1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            output,
+        }
+        | BlackBoxFuncCall::EcdsaSecp256r1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            output,
+        } => {
+            inputs(
+                public_key_x.iter_mut().chain(public_key_y).chain(signature).chain(hashed_message),
+                visit,
+            );
+            visit(output);
+        }
+        BlackBoxFuncCall::EddsaEd25519 { public_key, signature, message, output } => {
+            inputs(public_key.iter_mut().chain(signature).chain(message), visit);
+            visit(output);
+        }
+        BlackBoxFuncCall::EddsaBabyJubJub {
+            public_key_x,
+            public_key_y,
+            signature_s,
+            signature_r8_x,
+            signature_r8_y,
+            message,
+            output,
+        } => {
+            inputs(
+                [public_key_x, public_key_y, signature_s, signature_r8_x, signature_r8_y, message],
+                visit,
+            );
+            visit(output);
+        }
+        BlackBoxFuncCall::EcdsaSecp256k1Recover {
+            signature,
+            recovery_id,
+            hashed_message,
+            public_key_x,
+            public_key_y,
+        } => {
+            inputs(signature.iter_mut().chain([recovery_id]).chain(hashed_message), visit);
+            public_key_x.iter_mut().chain(public_key_y).for_each(&mut *visit);
+        }
+        BlackBoxFuncCall::FixedBaseScalarMul { low, high, outputs } => {
+            inputs([low, high], visit);
+            visit(&mut outputs.0);
+            visit(&mut outputs.1);
+        }
+        BlackBoxFuncCall::EmbeddedCurveAdd { input1_x, input1_y, input2_x, input2_y, outputs } => {
+            inputs([input1_x, input1_y, input2_x, input2_y], visit);
+            visit(&mut outputs.0);
+            visit(&mut outputs.1);
+        }
+        BlackBoxFuncCall::VariableBaseScalarMul {
+            point_x,
+            point_y,
+            scalar_low,
+            scalar_high,
+            outputs,
+        } => {
+            inputs([point_x, point_y, scalar_low, scalar_high], visit);
+            visit(&mut outputs.0);
+            visit(&mut outputs.1);
+        }
+        BlackBoxFuncCall::RecursiveAggregation {
+            verification_key,
+            proof,
+            public_inputs,
+            key_hash,
+            input_aggregation_object,
+            output_aggregation_object,
+        } => {
+            inputs(
+                verification_key
+                    .iter_mut()
+                    .chain(proof)
+                    .chain(public_inputs)
+                    .chain([key_hash])
+                    .chain(input_aggregation_object.iter_mut().flatten()),
+                visit,
+            );
+            output_aggregation_object.iter_mut().for_each(&mut *visit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+
+    use super::{BlackBoxCountChange, DiffOptions, OpcodeChange, WitnessSetChange};
+    use crate::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        BlackBoxFunc,
+    };
+
+    fn circuit(opcodes: Vec<Opcode>, public_parameters: &[u32], return_values: &[u32]) -> Circuit {
+        let witnesses = |indices: &[u32]| indices.iter().copied().map(Witness).collect();
+        Circuit {
+            current_witness_index: 10,
+            opcodes,
+            private_parameters: BTreeSet::new(),
+            public_parameters: PublicInputs(witnesses(public_parameters)),
+            return_values: PublicInputs(witnesses(return_values)),
+            assert_messages: Default::default(),
+        }
+    }
+
+    fn range(witness: u32, num_bits: u32) -> Opcode {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: Witness(witness), num_bits },
+        })
+    }
+
+    fn and(lhs: u32, rhs: u32, output: u32) -> Opcode {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+            lhs: FunctionInput { witness: Witness(lhs), num_bits: 8 },
+            rhs: FunctionInput { witness: Witness(rhs), num_bits: 8 },
+            output: Witness(output),
+        })
+    }
+
+    /// `lhs * rhs - output = 0`
+    fn mul(lhs: u32, rhs: u32, output: u32) -> Opcode {
+        Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(lhs), Witness(rhs))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(output))],
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    #[test]
+    fn identical_circuits_have_no_differences() {
+        let old = circuit(vec![mul(1, 2, 3), range(3, 8)], &[1], &[3]);
+        let new = old.clone();
+        let diff = old.diff(&new, DiffOptions::default());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "circuits are structurally identical\n");
+    }
+
+    #[test]
+    fn reports_added_removed_and_modified_opcodes() {
+        let old = circuit(vec![mul(1, 2, 3), range(3, 8), mul(3, 3, 4), and(1, 2, 5)], &[], &[]);
+        let new = circuit(vec![mul(1, 2, 3), range(3, 16), mul(3, 3, 4), range(4, 8)], &[], &[]);
+
+        let diff = old.diff(&new, DiffOptions::default());
+        assert_eq!(
+            diff.opcodes,
+            vec![
+                OpcodeChange::Modified {
+                    old_index: 1,
+                    new_index: 1,
+                    old: &old.opcodes[1],
+                    new: &new.opcodes[1]
+                },
+                OpcodeChange::Removed { index: 3, opcode: &old.opcodes[3] },
+                OpcodeChange::Added { index: 3, opcode: &new.opcodes[3] },
+            ]
+        );
+        assert_eq!(
+            diff.black_box_counts,
+            vec![
+                BlackBoxCountChange { function: BlackBoxFunc::AND, old: 1, new: 0 },
+                BlackBoxCountChange { function: BlackBoxFunc::RANGE, old: 1, new: 2 },
+            ]
+        );
+
+        let report = diff.to_string();
+        assert!(report.starts_with("opcodes: 1 added, 1 removed, 1 modified\n"));
+        assert!(report.contains("    and: 1 -> 0 (-1)\n"));
+        assert!(report.contains("    range: 1 -> 2 (+1)\n"));
+    }
+
+    #[test]
+    fn insertions_do_not_shift_following_opcodes() {
+        let old = circuit(vec![mul(1, 2, 3), mul(3, 3, 4), mul(4, 4, 5)], &[], &[]);
+        let new = circuit(vec![mul(1, 2, 3), range(3, 8), mul(3, 3, 4), mul(4, 4, 5)], &[], &[]);
+
+        let diff = old.diff(&new, DiffOptions::default());
+        assert_eq!(diff.opcodes, vec![OpcodeChange::Added { index: 1, opcode: &new.opcodes[1] }]);
+    }
+
+    #[test]
+    fn reports_changes_to_public_inputs() {
+        let old = circuit(vec![mul(1, 2, 3)], &[1, 2], &[3]);
+        let new = circuit(vec![mul(1, 2, 3)], &[1], &[2, 3]);
+
+        let diff = old.diff(&new, DiffOptions::default());
+        assert!(diff.opcodes.is_empty());
+        assert_eq!(
+            diff.public_parameters,
+            WitnessSetChange { added: BTreeSet::new(), removed: BTreeSet::from([Witness(2)]) }
+        );
+        assert_eq!(
+            diff.return_values,
+            WitnessSetChange { added: BTreeSet::from([Witness(2)]), removed: BTreeSet::new() }
+        );
+        assert_eq!(
+            diff.to_string(),
+            "public parameters: added [], removed [_2]\nreturn values: added [_2], removed []\n"
+        );
+    }
+
+    #[test]
+    fn matches_witnesses_modulo_renumbering() {
+        let old = circuit(vec![mul(1, 2, 3), range(3, 8), and(1, 2, 4)], &[1, 2], &[4]);
+        let new = circuit(vec![mul(5, 6, 7), range(7, 8), and(5, 6, 8)], &[5, 6], &[8]);
+
+        assert_eq!(old.diff(&new, DiffOptions::default()).opcodes.len(), 3);
+
+        let diff = old.diff(&new, DiffOptions { match_witnesses: true });
+        assert!(diff.is_empty());
+        assert_eq!(
+            diff.witness_mapping,
+            [(1, 5), (2, 6), (3, 7), (4, 8)]
+                .into_iter()
+                .map(|(old, new)| (Witness(old), Witness(new)))
+                .collect()
+        );
+    }
+
+    #[test]
+    fn inconsistent_renumberings_are_modifications() {
+        // The range constraint moves from the output of the multiplication onto one of its inputs.
+        let old = circuit(vec![mul(1, 2, 3), range(3, 8)], &[], &[]);
+        let new = circuit(vec![mul(5, 6, 7), range(5, 8)], &[], &[]);
+
+        let diff = old.diff(&new, DiffOptions { match_witnesses: true });
+        assert_eq!(
+            diff.opcodes,
+            vec![OpcodeChange::Modified {
+                old_index: 1,
+                new_index: 1,
+                old: &old.opcodes[1],
+                new: &new.opcodes[1]
+            }]
+        );
+    }
+}
@@ -1,9 +1,11 @@
 pub mod black_box_functions;
 pub mod brillig;
+pub mod diff;
 pub mod directives;
 pub mod opcodes;
 
 use crate::native_types::Witness;
+use acir_field::{AcirField, FieldElement};
 pub use opcodes::Opcode;
 use thiserror::Error;
 
@@ -14,12 +16,12 @@ use flate2::Compression;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
-pub struct Circuit {
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Circuit<F = FieldElement> {
     // current_witness_index is the highest witness index in the circuit. The next witness to be added to this circuit
     // will take on this value. (The value is cached here as an optimization.)
     pub current_witness_index: u32,
-    pub opcodes: Vec<Opcode>,
+    pub opcodes: Vec<Opcode<F>>,
 
     /// The set of private inputs to the circuit.
     pub private_parameters: BTreeSet<Witness>,
@@ -40,7 +42,21 @@ pub struct Circuit {
     pub assert_messages: Vec<(OpcodeLocation, String)>,
 }
 
-impl Circuit {
+// Implemented by hand as deriving `Default` would require `F: Default`.
+impl<F> Default for Circuit<F> {
+    fn default() -> Self {
+        Circuit {
+            current_witness_index: 0,
+            opcodes: Vec::new(),
+            private_parameters: BTreeSet::new(),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs::default(),
+            assert_messages: Vec::new(),
+        }
+    }
+}
+
+impl<F: AcirField> Circuit<F> {
     /// Returns the assert message associated with the provided [`OpcodeLocation`].
     /// Returns `None` if no such assert message exists.
     pub fn get_assert_message(&self, opcode_location: OpcodeLocation) -> Option<&str> {
@@ -107,7 +123,7 @@ impl FromStr for OpcodeLocation {
     }
 }
 
-impl Circuit {
+impl<F: AcirField> Circuit<F> {
     pub fn num_vars(&self) -> u32 {
         self.current_witness_index + 1
     }
@@ -161,7 +177,7 @@ impl Circuit {
     }
 }
 
-impl std::fmt::Display for Circuit {
+impl<F: AcirField> std::fmt::Display for Circuit<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "current witness index : {}", self.current_witness_index)?;
 
@@ -192,7 +208,7 @@ impl std::fmt::Display for Circuit {
     }
 }
 
-impl std::fmt::Debug for Circuit {
+impl<F: AcirField> std::fmt::Debug for Circuit<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         std::fmt::Display::fmt(self, f)
     }
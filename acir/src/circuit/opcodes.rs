@@ -3,6 +3,7 @@ use super::{
     directives::{Directive, QuotientDirective},
 };
 use crate::native_types::{Expression, Witness};
+use acir_field::{AcirField, FieldElement};
 use serde::{Deserialize, Serialize};
 
 mod black_box_function_call;
@@ -12,20 +13,20 @@ pub use black_box_function_call::{BlackBoxFuncCall, FunctionInput};
 pub use memory_operation::{BlockId, MemOp};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Opcode {
-    Arithmetic(Expression),
+pub enum Opcode<F = FieldElement> {
+    Arithmetic(Expression<F>),
     /// Calls to "gadgets" which rely on backends implementing support for specialized constraints.
     ///
     /// Often used for exposing more efficient implementations of SNARK-unfriendly computations.  
     BlackBoxFuncCall(BlackBoxFuncCall),
-    Directive(Directive),
-    Brillig(Brillig),
+    Directive(Directive<F>),
+    Brillig(Brillig<F>),
     /// Atomic operation on a block of memory
     MemoryOp {
         block_id: BlockId,
-        op: MemOp,
+        op: MemOp<F>,
         /// Predicate of the memory operation - indicates if it should be skipped
-        predicate: Option<Expression>,
+        predicate: Option<Expression<F>>,
     },
     MemoryInit {
         block_id: BlockId,
@@ -48,7 +49,7 @@ impl std::fmt::Display for UnsupportedMemoryOpcode {
     }
 }
 
-impl Opcode {
+impl<F: AcirField> Opcode<F> {
     // TODO We can add a domain separator by doing something like:
     // TODO concat!("directive:", directive.name)
     pub fn name(&self) -> &str {
@@ -77,7 +78,7 @@ impl Opcode {
         matches!(self, Opcode::Arithmetic(_))
     }
 
-    pub fn arithmetic(self) -> Option<Expression> {
+    pub fn arithmetic(self) -> Option<Expression<F>> {
         match self {
             Opcode::Arithmetic(expr) => Some(expr),
             _ => None,
@@ -85,7 +86,7 @@ impl Opcode {
     }
 }
 
-impl std::fmt::Display for Opcode {
+impl<F: AcirField> std::fmt::Display for Opcode<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Opcode::Arithmetic(expr) => {
@@ -172,7 +173,7 @@ impl std::fmt::Display for Opcode {
     }
 }
 
-impl std::fmt::Debug for Opcode {
+impl<F: AcirField> std::fmt::Debug for Opcode<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         std::fmt::Display::fmt(self, f)
     }
@@ -112,6 +112,61 @@ pub enum BlackBoxFuncCall {
         /// will be the input aggregation object of the next recursive aggregation.
         output_aggregation_object: Vec<Witness>,
     },
+    /// Adds the points `(input1_x, input1_y)` and `(input2_x, input2_y)` on the embedded curve.
+    EmbeddedCurveAdd {
+        input1_x: FunctionInput,
+        input1_y: FunctionInput,
+        input2_x: FunctionInput,
+        input2_y: FunctionInput,
+        outputs: (Witness, Witness),
+    },
+    /// Multiplies the point `(point_x, point_y)` on the embedded curve by the scalar `low + high * 2^128`.
+    VariableBaseScalarMul {
+        point_x: FunctionInput,
+        point_y: FunctionInput,
+        scalar_low: FunctionInput,
+        scalar_high: FunctionInput,
+        outputs: (Witness, Witness),
+    },
+    SHA512 {
+        inputs: Vec<FunctionInput>,
+        outputs: Vec<Witness>,
+    },
+    Blake3 {
+        inputs: Vec<FunctionInput>,
+        outputs: Vec<Witness>,
+    },
+    /// Verifies an Ed25519 `signature` over the bytes of `message`, where `public_key` and `signature`
+    /// are the 32 and 64 byte encodings defined in RFC 8032.
+    EddsaEd25519 {
+        public_key: Vec<FunctionInput>,
+        signature: Vec<FunctionInput>,
+        message: Vec<FunctionInput>,
+        output: Witness,
+    },
+    /// Verifies a BabyJubJub signature `(signature_r8, signature_s)` by the public key `public_key` over the
+    /// field element `message`.
+    EddsaBabyJubJub {
+        public_key_x: FunctionInput,
+        public_key_y: FunctionInput,
+        signature_s: FunctionInput,
+        signature_r8_x: FunctionInput,
+        signature_r8_y: FunctionInput,
+        message: FunctionInput,
+        output: Witness,
+    },
+    /// Recovers the public key which produced the secp256k1 ECDSA `signature` over `hashed_message`.
+    ///
+    /// `signature` holds the 32 byte big-endian encodings of `r` and `s` and `recovery_id` is zero or one,
+    /// according to whether the y-coordinate of the signature's nonce point is even or odd.
+    /// The big-endian bytes of the public key's coordinates are written to `public_key_x` and `public_key_y`.
+    EcdsaSecp256k1Recover {
+        signature: Vec<FunctionInput>,
+        recovery_id: FunctionInput,
+        hashed_message: Vec<FunctionInput>,
+        public_key_x: Vec<Witness>,
+        public_key_y: Vec<Witness>,
+    },
 }
 
 impl BlackBoxFuncCall {
@@ -176,6 +231,44 @@ impl BlackBoxFuncCall {
                 input_aggregation_object: None,
                 output_aggregation_object: vec![],
             },
+            BlackBoxFunc::EmbeddedCurveAdd => BlackBoxFuncCall::EmbeddedCurveAdd {
+                input1_x: FunctionInput::dummy(),
+                input1_y: FunctionInput::dummy(),
+                input2_x: FunctionInput::dummy(),
+                input2_y: FunctionInput::dummy(),
+                outputs: (Witness(0), Witness(0)),
+            },
+            BlackBoxFunc::VariableBaseScalarMul => BlackBoxFuncCall::VariableBaseScalarMul {
+                point_x: FunctionInput::dummy(),
+                point_y: FunctionInput::dummy(),
+                scalar_low: FunctionInput::dummy(),
+                scalar_high: FunctionInput::dummy(),
+                outputs: (Witness(0), Witness(0)),
+            },
+            BlackBoxFunc::SHA512 => BlackBoxFuncCall::SHA512 { inputs: vec![], outputs: vec![] },
+            BlackBoxFunc::Blake3 => BlackBoxFuncCall::Blake3 { inputs: vec![], outputs: vec![] },
+            BlackBoxFunc::EddsaEd25519 => BlackBoxFuncCall::EddsaEd25519 {
+                public_key: vec![],
+                signature: vec![],
+                message: vec![],
+                output: Witness(0),
+            },
+            BlackBoxFunc::EddsaBabyJubJub => BlackBoxFuncCall::EddsaBabyJubJub {
+                public_key_x: FunctionInput::dummy(),
+                public_key_y: FunctionInput::dummy(),
+                signature_s: FunctionInput::dummy(),
+                signature_r8_x: FunctionInput::dummy(),
+                signature_r8_y: FunctionInput::dummy(),
+                message: FunctionInput::dummy(),
+                output: Witness(0),
+            },
+            BlackBoxFunc::EcdsaSecp256k1Recover => BlackBoxFuncCall::EcdsaSecp256k1Recover {
+                signature: vec![],
+                recovery_id: FunctionInput::dummy(),
+                hashed_message: vec![],
+                public_key_x: vec![],
+                public_key_y: vec![],
+            },
         }
     }
 
@@ -195,6 +288,13 @@ impl BlackBoxFuncCall {
             BlackBoxFuncCall::Keccak256 { .. } => BlackBoxFunc::Keccak256,
             BlackBoxFuncCall::Keccak256VariableLength { .. } => BlackBoxFunc::Keccak256,
             BlackBoxFuncCall::RecursiveAggregation { .. } => BlackBoxFunc::RecursiveAggregation,
+            BlackBoxFuncCall::EmbeddedCurveAdd { .. } => BlackBoxFunc::EmbeddedCurveAdd,
+            BlackBoxFuncCall::VariableBaseScalarMul { .. } => BlackBoxFunc::VariableBaseScalarMul,
+            BlackBoxFuncCall::SHA512 { .. } => BlackBoxFunc::SHA512,
+            BlackBoxFuncCall::Blake3 { .. } => BlackBoxFunc::Blake3,
+            BlackBoxFuncCall::EddsaEd25519 { .. } => BlackBoxFunc::EddsaEd25519,
+            BlackBoxFuncCall::EddsaBabyJubJub { .. } => BlackBoxFunc::EddsaBabyJubJub,
+            BlackBoxFuncCall::EcdsaSecp256k1Recover { .. } => BlackBoxFunc::EcdsaSecp256k1Recover,
         }
     }
 
@@ -205,7 +305,9 @@ impl BlackBoxFuncCall {
     pub fn get_inputs_vec(&self) -> Vec<FunctionInput> {
         match self {
             BlackBoxFuncCall::SHA256 { inputs, .. }
+            | BlackBoxFuncCall::SHA512 { inputs, .. }
             | BlackBoxFuncCall::Blake2s { inputs, .. }
+            | BlackBoxFuncCall::Blake3 { inputs, .. }
             | BlackBoxFuncCall::Keccak256 { inputs, .. }
             | BlackBoxFuncCall::Pedersen { inputs, .. }
             | BlackBoxFuncCall::HashToField128Security { inputs, .. } => inputs.to_vec(),
@@ -213,6 +315,18 @@ impl BlackBoxFuncCall {
                 vec![*lhs, *rhs]
             }
             BlackBoxFuncCall::FixedBaseScalarMul { low, high, .. } => vec![*low, *high],
+            BlackBoxFuncCall::EmbeddedCurveAdd {
+                input1_x, input1_y, input2_x, input2_y, ..
+            } => {
+                vec![*input1_x, *input1_y, *input2_x, *input2_y]
+            }
+            BlackBoxFuncCall::VariableBaseScalarMul {
+                point_x,
+                point_y,
+                scalar_low,
+                scalar_high,
+                ..
+            } => vec![*point_x, *point_y, *scalar_low, *scalar_high],
             BlackBoxFuncCall::RANGE { input } => vec![*input],
             BlackBoxFuncCall::SchnorrVerify {
                 public_key_x,
@@ -266,6 +380,42 @@ impl BlackBoxFuncCall {
                 inputs.extend(hashed_message.iter().copied());
                 inputs
             }
+            BlackBoxFuncCall::EddsaEd25519 { public_key, signature, message, .. } => {
+                let mut inputs =
+                    Vec::with_capacity(public_key.len() + signature.len() + message.len());
+                inputs.extend(public_key.iter().copied());
+                inputs.extend(signature.iter().copied());
+                inputs.extend(message.iter().copied());
+                inputs
+            }
+            BlackBoxFuncCall::EddsaBabyJubJub {
+                public_key_x,
+                public_key_y,
+                signature_s,
+                signature_r8_x,
+                signature_r8_y,
+                message,
+                ..
+            } => vec![
+                *public_key_x,
+                *public_key_y,
+                *signature_s,
+                *signature_r8_x,
+                *signature_r8_y,
+                *message,
+            ],
+            BlackBoxFuncCall::EcdsaSecp256k1Recover {
+                signature,
+                recovery_id,
+                hashed_message,
+                ..
+            } => {
+                let mut inputs = Vec::with_capacity(signature.len() + 1 + hashed_message.len());
+                inputs.extend(signature.iter().copied());
+                inputs.push(*recovery_id);
+                inputs.extend(hashed_message.iter().copied());
+                inputs
+            }
             BlackBoxFuncCall::Keccak256VariableLength { inputs, var_message_size, .. } => {
                 let mut inputs = inputs.clone();
                 inputs.push(*var_message_size);
@@ -294,7 +444,9 @@ impl BlackBoxFuncCall {
     pub fn get_outputs_vec(&self) -> Vec<Witness> {
         match self {
             BlackBoxFuncCall::SHA256 { outputs, .. }
+            | BlackBoxFuncCall::SHA512 { outputs, .. }
             | BlackBoxFuncCall::Blake2s { outputs, .. }
+            | BlackBoxFuncCall::Blake3 { outputs, .. }
             | BlackBoxFuncCall::Keccak256 { outputs, .. }
             | BlackBoxFuncCall::RecursiveAggregation {
                 output_aggregation_object: outputs, ..
@@ -304,9 +456,18 @@ impl BlackBoxFuncCall {
             | BlackBoxFuncCall::HashToField128Security { output, .. }
             | BlackBoxFuncCall::SchnorrVerify { output, .. }
             | BlackBoxFuncCall::EcdsaSecp256k1 { output, .. }
-            | BlackBoxFuncCall::EcdsaSecp256r1 { output, .. } => vec![*output],
+            | BlackBoxFuncCall::EcdsaSecp256r1 { output, .. }
+            | BlackBoxFuncCall::EddsaEd25519 { output, .. }
+            | BlackBoxFuncCall::EddsaBabyJubJub { output, .. } => vec![*output],
             BlackBoxFuncCall::FixedBaseScalarMul { outputs, .. }
-            | BlackBoxFuncCall::Pedersen { outputs, .. } => vec![outputs.0, outputs.1],
+            | BlackBoxFuncCall::Pedersen { outputs, .. }
+            | BlackBoxFuncCall::EmbeddedCurveAdd { outputs, .. }
+            | BlackBoxFuncCall::VariableBaseScalarMul { outputs, .. } => {
+                vec![outputs.0, outputs.1]
+            }
+            BlackBoxFuncCall::EcdsaSecp256k1Recover { public_key_x, public_key_y, .. } => {
+                public_key_x.iter().chain(public_key_y).copied().collect()
+            }
             BlackBoxFuncCall::RANGE { .. } => vec![],
             BlackBoxFuncCall::Keccak256VariableLength { outputs, .. } => outputs.to_vec(),
         }
@@ -0,0 +1,336 @@
+use std::collections::BTreeMap;
+
+use acir_field::AcirField;
+#[cfg(test)]
+use acir_field::FieldElement;
+use thiserror::Error;
+
+use crate::native_types::{Witness, WitnessMap};
+
+use super::Expression;
+
+/// The error returned when an operation on [`Expression`]s would produce terms of degree greater than two,
+/// which an [`Expression`] is unable to represent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("the product of expressions of degree {lhs_degree} and {rhs_degree} exceeds the maximum degree of 2")]
+pub struct DegreeOverflow {
+    pub lhs_degree: u32,
+    pub rhs_degree: u32,
+}
+
+impl<F: AcirField> Expression<F> {
+    /// Returns the degree of the polynomial which the expression represents.
+    ///
+    /// Terms with zero coefficients are ignored, however terms which cancel each other out are not,
+    /// e.g. f(x,y) = xy - xy has degree 2 until it is [normalized][Expression::normalize].
+    ///
+    /// Examples:
+    /// - f(x,y) = 5 has degree 0
+    /// - f(x,y) = x + y has degree 1
+    /// - f(x,y) = xy + x has degree 2
+    pub fn degree(&self) -> u32 {
+        if self.mul_terms.iter().any(|(coefficient, _, _)| !coefficient.is_zero()) {
+            2
+        } else if self.linear_combinations.iter().any(|(coefficient, _)| !coefficient.is_zero()) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Puts the expression into canonical form.
+    ///
+    /// Terms over the same witnesses are merged, terms with zero coefficients are removed
+    /// and the remaining terms are sorted, so that expressions which represent the same polynomial are equal once normalized.
+    pub fn normalize(&mut self) {
+        let mut mul_terms: BTreeMap<(Witness, Witness), F> = BTreeMap::new();
+        for &(coefficient, lhs, rhs) in &self.mul_terms {
+            let witnesses = if lhs <= rhs { (lhs, rhs) } else { (rhs, lhs) };
+            *mul_terms.entry(witnesses).or_insert_with(F::zero) += coefficient;
+        }
+        let mut linear_combinations: BTreeMap<Witness, F> = BTreeMap::new();
+        for &(coefficient, witness) in &self.linear_combinations {
+            *linear_combinations.entry(witness).or_insert_with(F::zero) += coefficient;
+        }
+
+        self.mul_terms = mul_terms
+            .into_iter()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|((lhs, rhs), coefficient)| (coefficient, lhs, rhs))
+            .collect();
+        self.linear_combinations = linear_combinations
+            .into_iter()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(witness, coefficient)| (coefficient, witness))
+            .collect();
+    }
+
+    /// Returns `self * rhs` in normalized form,
+    /// or an error if the product would contain terms of degree greater than two.
+    pub fn checked_mul(&self, rhs: &Expression<F>) -> Result<Expression<F>, DegreeOverflow> {
+        let (lhs_degree, rhs_degree) = (self.degree(), rhs.degree());
+        if lhs_degree + rhs_degree > 2 {
+            return Err(DegreeOverflow { lhs_degree, rhs_degree });
+        }
+
+        let mut lhs = self.clone();
+        lhs.normalize();
+        let mut rhs = rhs.clone();
+        rhs.normalize();
+        let mut product = (&lhs * &rhs).expect("product of expressions should be at most degree 2");
+        product.normalize();
+        Ok(product)
+    }
+
+    /// Returns the expression in normalized form with every occurrence of `witness` replaced by `replacement`,
+    /// or an error if the result would contain terms of degree greater than two.
+    pub fn substitute(
+        &self,
+        witness: Witness,
+        replacement: &Expression<F>,
+    ) -> Result<Expression<F>, DegreeOverflow> {
+        let replace =
+            |term: Witness| if term == witness { replacement.clone() } else { term.into() };
+
+        let mut result = Expression::from_field(self.q_c);
+        for &(coefficient, lhs, rhs) in &self.mul_terms {
+            if coefficient.is_zero() {
+                continue;
+            } else if lhs != witness && rhs != witness {
+                result.push_multiplication_term(coefficient, lhs, rhs);
+            } else {
+                result = result.add_mul(coefficient, &replace(lhs).checked_mul(&replace(rhs))?);
+            }
+        }
+        for &(coefficient, term) in &self.linear_combinations {
+            if term == witness {
+                result = result.add_mul(coefficient, replacement);
+            } else {
+                result.push_addition_term(coefficient, term);
+            }
+        }
+
+        result.normalize();
+        Ok(result)
+    }
+
+    /// Factors `witness` out of the expression, returning `(quotient, remainder)` in normalized form
+    /// such that `self = witness * quotient + remainder`.
+    ///
+    /// The quotient is at most degree 1 and the remainder does not contain `witness`.
+    pub fn factor(&self, witness: Witness) -> (Expression<F>, Expression<F>) {
+        let mut quotient = Expression::zero();
+        let mut remainder = Expression::from_field(self.q_c);
+        for &(coefficient, lhs, rhs) in &self.mul_terms {
+            if lhs == witness {
+                quotient.push_addition_term(coefficient, rhs);
+            } else if rhs == witness {
+                quotient.push_addition_term(coefficient, lhs);
+            } else {
+                remainder.push_multiplication_term(coefficient, lhs, rhs);
+            }
+        }
+        for &(coefficient, term) in &self.linear_combinations {
+            if term == witness {
+                quotient.q_c += coefficient;
+            } else {
+                remainder.push_addition_term(coefficient, term);
+            }
+        }
+
+        quotient.normalize();
+        remainder.normalize();
+        (quotient, remainder)
+    }
+
+    /// Partially evaluates the expression using the values of the witnesses in `witness_map`.
+    ///
+    /// The result only contains the witnesses which are missing from `witness_map`,
+    /// so an expression over known witnesses evaluates to a [constant][Expression::to_const].
+    pub fn evaluate(&self, witness_map: &WitnessMap<F>) -> Expression<F> {
+        let mut result = Expression::default();
+        for &(coefficient, lhs, rhs) in &self.mul_terms {
+            match (witness_map.get(&lhs), witness_map.get(&rhs)) {
+                (Some(lhs), Some(rhs)) => result.q_c += coefficient * *lhs * *rhs,
+                (None, Some(known)) | (Some(known), None) => {
+                    let unknown = if witness_map.contains_key(&lhs) { rhs } else { lhs };
+                    let coefficient = coefficient * *known;
+                    if !coefficient.is_zero() {
+                        result.push_addition_term(coefficient, unknown);
+                    }
+                }
+                (None, None) => {
+                    if !coefficient.is_zero() {
+                        result.push_multiplication_term(coefficient, lhs, rhs);
+                    }
+                }
+            }
+        }
+        for &(coefficient, witness) in &self.linear_combinations {
+            match witness_map.get(&witness) {
+                Some(value) => result.q_c += coefficient * *value,
+                None => {
+                    if !coefficient.is_zero() {
+                        result.push_addition_term(coefficient, witness);
+                    }
+                }
+            }
+        }
+        result.q_c += self.q_c;
+        result
+    }
+}
+
+#[cfg(test)]
+fn field(value: i128) -> FieldElement {
+    FieldElement::from(value)
+}
+
+#[test]
+fn normalize_merges_equivalent_terms() {
+    let mut expr = Expression {
+        mul_terms: vec![
+            (field(2), Witness(2), Witness(1)),
+            (field(3), Witness(1), Witness(2)),
+            (field(4), Witness(3), Witness(3)),
+            (field(-4), Witness(3), Witness(3)),
+        ],
+        linear_combinations: vec![
+            (field(1), Witness(4)),
+            (field(5), Witness(1)),
+            (field(-1), Witness(4)),
+        ],
+        q_c: field(7),
+    };
+    assert_eq!(expr.degree(), 2);
+
+    expr.normalize();
+    assert_eq!(
+        expr,
+        Expression {
+            mul_terms: vec![(field(5), Witness(1), Witness(2))],
+            linear_combinations: vec![(field(5), Witness(1))],
+            q_c: field(7),
+        }
+    );
+
+    let mut cancelling = Expression {
+        mul_terms: vec![(field(1), Witness(1), Witness(2)), (field(-1), Witness(2), Witness(1))],
+        ..Default::default()
+    };
+    cancelling.normalize();
+    assert_eq!(cancelling.degree(), 0);
+}
+
+#[test]
+fn checked_mul_reports_degree_overflow() {
+    // (x1 + 1) * (2*x2 - 1) = 2*x1*x2 - x1 + 2*x2 - 1
+    let a = &Expression::from(Witness(1)) + &Expression::one();
+    let b = &(field(2) * &Expression::from(Witness(2))) - &Expression::one();
+    let product = a.checked_mul(&b).unwrap();
+    assert_eq!(
+        product,
+        Expression {
+            mul_terms: vec![(field(2), Witness(1), Witness(2))],
+            linear_combinations: vec![(field(-1), Witness(1)), (field(2), Witness(2))],
+            q_c: field(-1),
+        }
+    );
+
+    assert_eq!(product.checked_mul(&a), Err(DegreeOverflow { lhs_degree: 2, rhs_degree: 1 }));
+    // Multiplying by a constant never increases the degree.
+    assert_eq!(product.checked_mul(&Expression::from_field(field(3))), Ok(&product * field(3)));
+}
+
+#[test]
+fn substitute_replaces_every_occurrence() {
+    // x1*x2 + 3*x1 + x3, with x1 = x3 + 2
+    let expr = Expression {
+        mul_terms: vec![(field(1), Witness(1), Witness(2))],
+        linear_combinations: vec![(field(3), Witness(1)), (field(1), Witness(3))],
+        q_c: field(0),
+    };
+    let replacement = &Expression::from(Witness(3)) + &Expression::from_field(field(2));
+
+    // x2*x3 + 2*x2 + 4*x3 + 6
+    assert_eq!(
+        expr.substitute(Witness(1), &replacement),
+        Ok(Expression {
+            mul_terms: vec![(field(1), Witness(2), Witness(3))],
+            linear_combinations: vec![(field(2), Witness(2)), (field(4), Witness(3))],
+            q_c: field(6),
+        })
+    );
+
+    let quadratic =
+        Expression { mul_terms: vec![(field(1), Witness(3), Witness(4))], ..Default::default() };
+    assert_eq!(
+        expr.substitute(Witness(1), &quadratic),
+        Err(DegreeOverflow { lhs_degree: 2, rhs_degree: 1 })
+    );
+    // Linear occurrences can be replaced by quadratic expressions.
+    assert!(expr.substitute(Witness(3), &quadratic).is_ok());
+}
+
+#[test]
+fn factor_splits_out_a_witness() {
+    // 2*x1*x2 + 3*x1*x1 + 4*x1 + x2*x3 + 5*x3 + 6
+    let expr = Expression {
+        mul_terms: vec![
+            (field(2), Witness(1), Witness(2)),
+            (field(3), Witness(1), Witness(1)),
+            (field(1), Witness(2), Witness(3)),
+        ],
+        linear_combinations: vec![(field(4), Witness(1)), (field(5), Witness(3))],
+        q_c: field(6),
+    };
+
+    let (quotient, remainder) = expr.factor(Witness(1));
+    assert_eq!(
+        quotient,
+        Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(field(3), Witness(1)), (field(2), Witness(2))],
+            q_c: field(4),
+        }
+    );
+    assert_eq!(
+        remainder,
+        Expression {
+            mul_terms: vec![(field(1), Witness(2), Witness(3))],
+            linear_combinations: vec![(field(5), Witness(3))],
+            q_c: field(6),
+        }
+    );
+
+    let mut recombined = &Expression::from(Witness(1)).checked_mul(&quotient).unwrap() + &remainder;
+    recombined.normalize();
+    let mut expr = expr;
+    expr.normalize();
+    assert_eq!(recombined, expr);
+}
+
+#[test]
+fn evaluate_substitutes_known_witnesses() {
+    // 2*x1*x2 + 3*x2*x3 + x3 + 1, with x1 = 2 and x2 = 5
+    let expr = Expression {
+        mul_terms: vec![(field(2), Witness(1), Witness(2)), (field(3), Witness(2), Witness(3))],
+        linear_combinations: vec![(field(1), Witness(3))],
+        q_c: field(1),
+    };
+    let mut witness_map = WitnessMap::new();
+    witness_map.insert(Witness(1), field(2));
+    witness_map.insert(Witness(2), field(5));
+
+    assert_eq!(
+        expr.evaluate(&witness_map),
+        Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(field(15), Witness(3)), (field(1), Witness(3))],
+            q_c: field(21),
+        }
+    );
+
+    witness_map.insert(Witness(3), field(1));
+    assert_eq!(expr.evaluate(&witness_map).to_const(), Some(field(37)));
+}
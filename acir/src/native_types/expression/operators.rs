@@ -1,5 +1,10 @@
 use crate::native_types::Witness;
+#[cfg(test)]
 use acir_field::FieldElement;
+use acir_field::{
+    generic_ark::{self, PrimeField},
+    AcirField,
+};
 use std::{
     cmp::Ordering,
     ops::{Add, Mul, Neg, Sub},
@@ -9,8 +14,8 @@ use super::Expression;
 
 // Negation
 
-impl Neg for &Expression {
-    type Output = Expression;
+impl<F: AcirField> Neg for &Expression<F> {
+    type Output = Expression<F>;
     fn neg(self) -> Self::Output {
         // XXX(med) : Implement an efficient way to do this
 
@@ -27,9 +32,9 @@ impl Neg for &Expression {
 
 // FieldElement
 
-impl Add<FieldElement> for Expression {
-    type Output = Expression;
-    fn add(self, rhs: FieldElement) -> Self::Output {
+impl<F: AcirField> Add<F> for Expression<F> {
+    type Output = Expression<F>;
+    fn add(self, rhs: F) -> Self::Output {
         // Increase the constant
         let q_c = self.q_c + rhs;
 
@@ -37,17 +42,18 @@ impl Add<FieldElement> for Expression {
     }
 }
 
-impl Add<Expression> for FieldElement {
-    type Output = Expression;
+// Implementations with the field element on the left hand side must name a concrete field type to satisfy the orphan rules.
+impl<P: PrimeField> Add<Expression<generic_ark::FieldElement<P>>> for generic_ark::FieldElement<P> {
+    type Output = Expression<generic_ark::FieldElement<P>>;
     #[inline]
-    fn add(self, rhs: Expression) -> Self::Output {
+    fn add(self, rhs: Expression<generic_ark::FieldElement<P>>) -> Self::Output {
         rhs + self
     }
 }
 
-impl Sub<FieldElement> for Expression {
-    type Output = Expression;
-    fn sub(self, rhs: FieldElement) -> Self::Output {
+impl<F: AcirField> Sub<F> for Expression<F> {
+    type Output = Expression<F>;
+    fn sub(self, rhs: F) -> Self::Output {
         // Increase the constant
         let q_c = self.q_c - rhs;
 
@@ -55,17 +61,18 @@ impl Sub<FieldElement> for Expression {
     }
 }
 
-impl Sub<Expression> for FieldElement {
-    type Output = Expression;
+// Implementations with the field element on the left hand side must name a concrete field type to satisfy the orphan rules.
+impl<P: PrimeField> Sub<Expression<generic_ark::FieldElement<P>>> for generic_ark::FieldElement<P> {
+    type Output = Expression<generic_ark::FieldElement<P>>;
     #[inline]
-    fn sub(self, rhs: Expression) -> Self::Output {
+    fn sub(self, rhs: Expression<generic_ark::FieldElement<P>>) -> Self::Output {
         rhs - self
     }
 }
 
-impl Mul<FieldElement> for &Expression {
-    type Output = Expression;
-    fn mul(self, rhs: FieldElement) -> Self::Output {
+impl<F: AcirField> Mul<F> for &Expression<F> {
+    type Output = Expression<F>;
+    fn mul(self, rhs: F) -> Self::Output {
         // Scale the mul terms
         let mul_terms: Vec<_> =
             self.mul_terms.iter().map(|(q_m, w_l, w_r)| (*q_m * rhs, *w_l, *w_r)).collect();
@@ -81,42 +88,44 @@ impl Mul<FieldElement> for &Expression {
     }
 }
 
-impl Mul<&Expression> for FieldElement {
-    type Output = Expression;
+impl<P: PrimeField> Mul<&Expression<generic_ark::FieldElement<P>>>
+    for generic_ark::FieldElement<P>
+{
+    type Output = Expression<generic_ark::FieldElement<P>>;
     #[inline]
-    fn mul(self, rhs: &Expression) -> Self::Output {
+    fn mul(self, rhs: &Expression<generic_ark::FieldElement<P>>) -> Self::Output {
         rhs * self
     }
 }
 
 // Witness
 
-impl Add<Witness> for &Expression {
-    type Output = Expression;
-    fn add(self, rhs: Witness) -> Expression {
+impl<F: AcirField> Add<Witness> for &Expression<F> {
+    type Output = Expression<F>;
+    fn add(self, rhs: Witness) -> Expression<F> {
         self + &Expression::from(rhs)
     }
 }
 
-impl Add<&Expression> for Witness {
-    type Output = Expression;
+impl<F: AcirField> Add<&Expression<F>> for Witness {
+    type Output = Expression<F>;
     #[inline]
-    fn add(self, rhs: &Expression) -> Expression {
+    fn add(self, rhs: &Expression<F>) -> Expression<F> {
         rhs + self
     }
 }
 
-impl Sub<Witness> for &Expression {
-    type Output = Expression;
-    fn sub(self, rhs: Witness) -> Expression {
+impl<F: AcirField> Sub<Witness> for &Expression<F> {
+    type Output = Expression<F>;
+    fn sub(self, rhs: Witness) -> Expression<F> {
         self - &Expression::from(rhs)
     }
 }
 
-impl Sub<&Expression> for Witness {
-    type Output = Expression;
+impl<F: AcirField> Sub<&Expression<F>> for Witness {
+    type Output = Expression<F>;
     #[inline]
-    fn sub(self, rhs: &Expression) -> Expression {
+    fn sub(self, rhs: &Expression<F>) -> Expression<F> {
         rhs - self
     }
 }
@@ -125,25 +134,25 @@ impl Sub<&Expression> for Witness {
 
 // Expression
 
-impl Add<&Expression> for &Expression {
-    type Output = Expression;
-    fn add(self, rhs: &Expression) -> Expression {
-        self.add_mul(FieldElement::one(), rhs)
+impl<F: AcirField> Add<&Expression<F>> for &Expression<F> {
+    type Output = Expression<F>;
+    fn add(self, rhs: &Expression<F>) -> Expression<F> {
+        self.add_mul(F::one(), rhs)
     }
 }
 
-impl Sub<&Expression> for &Expression {
-    type Output = Expression;
-    fn sub(self, rhs: &Expression) -> Expression {
-        self.add_mul(-FieldElement::one(), rhs)
+impl<F: AcirField> Sub<&Expression<F>> for &Expression<F> {
+    type Output = Expression<F>;
+    fn sub(self, rhs: &Expression<F>) -> Expression<F> {
+        self.add_mul(-F::one(), rhs)
     }
 }
 
-impl Mul<&Expression> for &Expression {
-    type Output = Option<Expression>;
-    fn mul(self, rhs: &Expression) -> Option<Expression> {
+impl<F: AcirField> Mul<&Expression<F>> for &Expression<F> {
+    type Output = Option<Expression<F>>;
+    fn mul(self, rhs: &Expression<F>) -> Option<Expression<F>> {
         if self.is_const() {
-            return Some(self.q_c * rhs);
+            return Some(rhs * self.q_c);
         } else if rhs.is_const() {
             return Some(self * rhs.q_c);
         } else if !(self.is_linear() && rhs.is_linear()) {
@@ -215,7 +224,7 @@ impl Mul<&Expression> for &Expression {
 }
 
 /// Returns `w*b.linear_combinations`
-fn single_mul(w: Witness, b: &Expression) -> Expression {
+fn single_mul<F: AcirField>(w: Witness, b: &Expression<F>) -> Expression<F> {
     Expression {
         mul_terms: b
             .linear_combinations
@@ -1,4 +1,5 @@
 use crate::native_types::Witness;
+use acir_field::AcirField;
 use std::cmp::Ordering;
 
 use super::Expression;
@@ -6,7 +7,7 @@ use super::Expression;
 // TODO: It's undecided whether `Expression` should implement `Ord/PartialOrd`.
 // This is currently used in ACVM in the compiler.
 
-impl Ord for Expression {
+impl<F: AcirField> Ord for Expression<F> {
     fn cmp(&self, other: &Self) -> Ordering {
         let mut i1 = self.get_max_idx();
         let mut i2 = other.get_max_idx();
@@ -17,13 +18,13 @@ impl Ord for Expression {
             if m1.is_none() && m2.is_none() {
                 return Ordering::Equal;
             }
-            result = Expression::cmp_max(m1, m2);
+            result = Self::cmp_max(m1, m2);
         }
         result
     }
 }
 
-impl PartialOrd for Expression {
+impl<F: AcirField> PartialOrd for Expression<F> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -35,7 +36,7 @@ struct WitnessIdx {
     second_term: bool,
 }
 
-impl Expression {
+impl<F: AcirField> Expression<F> {
     fn get_max_idx(&self) -> WitnessIdx {
         WitnessIdx {
             linear: self.linear_combinations.len(),
@@ -2,7 +2,7 @@ mod expression;
 mod witness;
 mod witness_map;
 
-pub use expression::Expression;
+pub use expression::{DegreeOverflow, Expression};
 pub use witness::Witness;
 pub use witness_map::WitnessMap;
-pub use witness_map::WitnessMapError;
+pub use witness_map::{WitnessMapError, WitnessMapReader, WitnessMapWriter};
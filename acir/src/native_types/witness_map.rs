@@ -4,7 +4,7 @@ use std::{
     ops::Index,
 };
 
-use acir_field::FieldElement;
+use acir_field::{AcirField, FieldElement};
 use flate2::bufread::GzDecoder;
 use flate2::bufread::GzEncoder;
 use flate2::Compression;
@@ -13,24 +13,42 @@ use thiserror::Error;
 
 use crate::native_types::Witness;
 
-#[cfg(feature = "serialize-messagepack")]
+mod human_readable;
+mod stream;
+
+pub use stream::{WitnessMapReader, WitnessMapWriter};
+
 #[derive(Debug, Error)]
 enum SerializationError {
+    #[cfg(feature = "serialize-messagepack")]
     #[error(transparent)]
     MsgpackEncode(#[from] rmp_serde::encode::Error),
 
+    #[cfg(feature = "serialize-messagepack")]
     #[error(transparent)]
     MsgpackDecode(#[from] rmp_serde::decode::Error),
 
+    #[cfg(not(feature = "serialize-messagepack"))]
     #[error(transparent)]
-    Deflate(#[from] std::io::Error),
-}
+    Bincode(#[from] bincode::Error),
 
-#[cfg(not(feature = "serialize-messagepack"))]
-#[derive(Debug, Error)]
-enum SerializationError {
     #[error(transparent)]
     Deflate(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    TomlEncode(#[from] toml::ser::Error),
+
+    #[error(transparent)]
+    TomlDecode(#[from] toml::de::Error),
+
+    #[error("invalid witness index {0:?}")]
+    InvalidWitnessIndex(String),
+
+    #[error("invalid field element {0:?} for witness {1}")]
+    InvalidFieldElement(String, u32),
 }
 
 #[derive(Debug, Error)]
@@ -39,64 +57,74 @@ pub struct WitnessMapError(#[from] SerializationError);
 
 /// A map from the witnesses in a constraint system to the field element values
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
-pub struct WitnessMap(BTreeMap<Witness, FieldElement>);
+pub struct WitnessMap<F = FieldElement>(BTreeMap<Witness, F>);
 
-impl WitnessMap {
+impl<F: AcirField> WitnessMap<F> {
     pub fn new() -> Self {
         Self(BTreeMap::new())
     }
-    pub fn get(&self, witness: &Witness) -> Option<&FieldElement> {
+    pub fn get(&self, witness: &Witness) -> Option<&F> {
         self.0.get(witness)
     }
-    pub fn get_index(&self, index: u32) -> Option<&FieldElement> {
+    pub fn get_index(&self, index: u32) -> Option<&F> {
         self.0.get(&index.into())
     }
     pub fn contains_key(&self, key: &Witness) -> bool {
         self.0.contains_key(key)
     }
-    pub fn insert(&mut self, key: Witness, value: FieldElement) -> Option<FieldElement> {
+    pub fn insert(&mut self, key: Witness, value: F) -> Option<F> {
         self.0.insert(key, value)
     }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Returns an iterator over the witness assignments, ordered by witness index.
+    pub fn iter(&self) -> impl Iterator<Item = (&Witness, &F)> {
+        self.0.iter()
+    }
 }
 
-impl Index<&Witness> for WitnessMap {
-    type Output = FieldElement;
+impl<F: AcirField> Index<&Witness> for WitnessMap<F> {
+    type Output = F;
 
     fn index(&self, index: &Witness) -> &Self::Output {
         &self.0[index]
     }
 }
 
-pub struct IntoIter(btree_map::IntoIter<Witness, FieldElement>);
+pub struct IntoIter<F = FieldElement>(btree_map::IntoIter<Witness, F>);
 
-impl Iterator for IntoIter {
-    type Item = (Witness, FieldElement);
+impl<F: AcirField> Iterator for IntoIter<F> {
+    type Item = (Witness, F);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next()
     }
 }
 
-impl IntoIterator for WitnessMap {
-    type Item = (Witness, FieldElement);
-    type IntoIter = IntoIter;
+impl<F: AcirField> IntoIterator for WitnessMap<F> {
+    type Item = (Witness, F);
+    type IntoIter = IntoIter<F>;
 
     fn into_iter(self) -> Self::IntoIter {
         IntoIter(self.0.into_iter())
     }
 }
 
-impl From<BTreeMap<Witness, FieldElement>> for WitnessMap {
-    fn from(value: BTreeMap<Witness, FieldElement>) -> Self {
+impl<F: AcirField> From<BTreeMap<Witness, F>> for WitnessMap<F> {
+    fn from(value: BTreeMap<Witness, F>) -> Self {
         Self(value)
     }
 }
 
 #[cfg(feature = "serialize-messagepack")]
-impl TryFrom<WitnessMap> for Vec<u8> {
+impl<F: AcirField> TryFrom<WitnessMap<F>> for Vec<u8> {
     type Error = WitnessMapError;
 
-    fn try_from(val: WitnessMap) -> Result<Self, Self::Error> {
+    fn try_from(val: WitnessMap<F>) -> Result<Self, Self::Error> {
         let buf = rmp_serde::to_vec(&val).map_err(|err| WitnessMapError(err.into()))?;
         let mut deflater = flate2::write::DeflateEncoder::new(buf.as_slice(), Compression::best());
         let mut buf_c = Vec::new();
@@ -106,11 +134,11 @@ impl TryFrom<WitnessMap> for Vec<u8> {
 }
 
 #[cfg(not(feature = "serialize-messagepack"))]
-impl TryFrom<WitnessMap> for Vec<u8> {
+impl<F: AcirField> TryFrom<WitnessMap<F>> for Vec<u8> {
     type Error = WitnessMapError;
 
-    fn try_from(val: WitnessMap) -> Result<Self, Self::Error> {
-        let buf = bincode::serialize(&val).unwrap();
+    fn try_from(val: WitnessMap<F>) -> Result<Self, Self::Error> {
+        let buf = bincode::serialize(&val).map_err(|err| WitnessMapError(err.into()))?;
         let mut deflater = GzEncoder::new(buf.as_slice(), Compression::best());
         let mut buf_c = Vec::new();
         deflater.read_to_end(&mut buf_c).map_err(|err| WitnessMapError(err.into()))?;
@@ -119,7 +147,7 @@ impl TryFrom<WitnessMap> for Vec<u8> {
 }
 
 #[cfg(feature = "serialize-messagepack")]
-impl TryFrom<&[u8]> for WitnessMap {
+impl<F: AcirField> TryFrom<&[u8]> for WitnessMap<F> {
     type Error = WitnessMapError;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
@@ -133,14 +161,93 @@ impl TryFrom<&[u8]> for WitnessMap {
 }
 
 #[cfg(not(feature = "serialize-messagepack"))]
-impl TryFrom<&[u8]> for WitnessMap {
+impl<F: AcirField> TryFrom<&[u8]> for WitnessMap<F> {
     type Error = WitnessMapError;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
         let mut deflater = GzDecoder::new(bytes);
         let mut buf_d = Vec::new();
         deflater.read_to_end(&mut buf_d).map_err(|err| WitnessMapError(err.into()))?;
-        let witness_map = bincode::deserialize(buf_d.as_slice()).unwrap();
+        let witness_map =
+            bincode::deserialize(buf_d.as_slice()).map_err(|err| WitnessMapError(err.into()))?;
         Ok(Self(witness_map))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use acir_field::FieldElement;
+
+    use super::{WitnessMap, WitnessMapReader, WitnessMapWriter};
+    use crate::native_types::Witness;
+
+    fn witness_map() -> WitnessMap {
+        let mut witness_map = WitnessMap::new();
+        witness_map.insert(Witness(1), FieldElement::from(10_i128));
+        witness_map.insert(Witness(2), -FieldElement::one());
+        witness_map.insert(Witness(10), FieldElement::zero());
+        witness_map
+    }
+
+    #[test]
+    fn binary_roundtrip() {
+        let bytes: Vec<u8> = witness_map().try_into().unwrap();
+        assert_eq!(WitnessMap::try_from(bytes.as_slice()).unwrap(), witness_map());
+    }
+
+    #[test]
+    fn invalid_binary_is_an_error() {
+        assert!(WitnessMap::<FieldElement>::try_from([1, 2, 3].as_slice()).is_err());
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = witness_map().to_json().unwrap();
+        assert_eq!(WitnessMap::from_json(&json).unwrap(), witness_map());
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let toml = witness_map().to_toml().unwrap();
+        assert_eq!(WitnessMap::from_toml(&toml).unwrap(), witness_map());
+    }
+
+    #[test]
+    fn accepts_decimal_values() {
+        let witness_map: WitnessMap = WitnessMap::from_toml("1 = \"10\"").unwrap();
+        assert_eq!(witness_map[&Witness(1)], FieldElement::from(10_i128));
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        assert!(WitnessMap::<FieldElement>::from_json(r#"{ "x": "0x01" }"#).is_err());
+        assert!(WitnessMap::<FieldElement>::from_json(r#"{ "1": "hello" }"#).is_err());
+        assert!(WitnessMap::<FieldElement>::from_toml("1 = 5").is_err());
+        assert!(WitnessMap::<FieldElement>::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn stream_roundtrip() {
+        let mut writer = WitnessMapWriter::new(Vec::new()).unwrap();
+        writer.write_witness_map(&witness_map()).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        // The streamed encoding must be readable as a regular JSON witness map.
+        let json = String::from_utf8(bytes.clone()).unwrap();
+        assert_eq!(WitnessMap::from_json(&json).unwrap(), witness_map());
+
+        let read = WitnessMapReader::new(bytes.as_slice()).read_witness_map().unwrap();
+        assert_eq!(read, witness_map());
+    }
+
+    #[test]
+    fn stream_rejects_invalid_input() {
+        let read = |input: &str| {
+            WitnessMapReader::new(input.as_bytes()).read_witness_map::<FieldElement>()
+        };
+        assert!(read(r#"{ "1": "0x01" } trailing"#).is_err());
+        assert!(read(r#"{ "1": "0x01""#).is_err());
+        assert!(read(r#"{ "-1": "0x01" }"#).is_err());
+        assert_eq!(read("{}").unwrap(), WitnessMap::new());
+    }
+}
@@ -0,0 +1,72 @@
+//! Human-readable encodings of a [`WitnessMap`].
+//!
+//! Both JSON and TOML map the index of each witness to its value as a hex string, e.g. `"3" = "0x...0a"`.
+//! When decoding, values may also be given in decimal.
+
+use std::collections::BTreeMap;
+
+use acir_field::AcirField;
+use serde::{Serialize, Serializer};
+
+use super::{SerializationError, WitnessMap, WitnessMapError};
+use crate::native_types::Witness;
+
+/// Serializes a [`WitnessMap`] as a map from witness indices to hex encoded field elements.
+struct HexEncodedWitnessMap<'a, F>(&'a WitnessMap<F>);
+
+impl<F: AcirField> Serialize for HexEncodedWitnessMap<'_, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(witness, value)| encode_entry(witness, value)))
+    }
+}
+
+pub(super) fn encode_entry<F: AcirField>(witness: &Witness, value: &F) -> (String, String) {
+    (witness.witness_index().to_string(), format!("0x{}", value.to_hex()))
+}
+
+pub(super) fn decode_entry<F: AcirField>(
+    index: &str,
+    value: &str,
+) -> Result<(Witness, F), SerializationError> {
+    let index: u32 =
+        index.parse().map_err(|_| SerializationError::InvalidWitnessIndex(index.to_owned()))?;
+    let value = F::try_from_str(value)
+        .ok_or_else(|| SerializationError::InvalidFieldElement(value.to_owned(), index))?;
+    Ok((Witness(index), value))
+}
+
+fn decode_entries<F: AcirField>(
+    entries: BTreeMap<String, String>,
+) -> Result<WitnessMap<F>, WitnessMapError> {
+    let mut witness_map = WitnessMap::new();
+    for (index, value) in entries {
+        let (witness, value) = decode_entry(&index, &value)?;
+        witness_map.insert(witness, value);
+    }
+    Ok(witness_map)
+}
+
+impl<F: AcirField> WitnessMap<F> {
+    /// Encodes the witness map as a JSON object mapping witness indices to hex encoded field elements.
+    pub fn to_json(&self) -> Result<String, WitnessMapError> {
+        serde_json::to_string_pretty(&HexEncodedWitnessMap(self))
+            .map_err(|err| WitnessMapError(err.into()))
+    }
+
+    /// Decodes a witness map from a JSON object mapping witness indices to field elements.
+    pub fn from_json(json: &str) -> Result<Self, WitnessMapError> {
+        let entries = serde_json::from_str(json).map_err(|err| WitnessMapError(err.into()))?;
+        decode_entries(entries)
+    }
+
+    /// Encodes the witness map as a TOML table mapping witness indices to hex encoded field elements.
+    pub fn to_toml(&self) -> Result<String, WitnessMapError> {
+        toml::to_string(&HexEncodedWitnessMap(self)).map_err(|err| WitnessMapError(err.into()))
+    }
+
+    /// Decodes a witness map from a TOML table mapping witness indices to field elements.
+    pub fn from_toml(toml: &str) -> Result<Self, WitnessMapError> {
+        let entries = toml::from_str(toml).map_err(|err| WitnessMapError(err.into()))?;
+        decode_entries(entries)
+    }
+}
@@ -0,0 +1,113 @@
+//! Streaming JSON encoding of a [`WitnessMap`] for witnesses which are too large to comfortably hold as a string.
+//!
+//! The format is identical to [`WitnessMap::to_json`] so either side can be used with the other.
+
+use std::io::{BufReader, Read, Write};
+use std::marker::PhantomData;
+
+use acir_field::AcirField;
+use serde::de::{Deserializer, Error, MapAccess, Visitor};
+
+use super::{
+    human_readable::{decode_entry, encode_entry},
+    SerializationError, WitnessMap, WitnessMapError,
+};
+use crate::native_types::Witness;
+
+/// Writes witness assignments as a JSON object one entry at a time.
+///
+/// [`WitnessMapWriter::finish`] must be called once all entries have been written to close the object.
+pub struct WitnessMapWriter<W: Write> {
+    writer: W,
+    entries_written: usize,
+}
+
+impl<W: Write> WitnessMapWriter<W> {
+    pub fn new(mut writer: W) -> Result<Self, WitnessMapError> {
+        writer.write_all(b"{").map_err(|err| WitnessMapError(err.into()))?;
+        Ok(WitnessMapWriter { writer, entries_written: 0 })
+    }
+
+    /// Writes the assignment of `value` to `witness`.
+    pub fn write_entry<F: AcirField>(
+        &mut self,
+        witness: &Witness,
+        value: &F,
+    ) -> Result<(), WitnessMapError> {
+        let separator = if self.entries_written == 0 { "\n" } else { ",\n" };
+        let (index, value) = encode_entry(witness, value);
+        write!(self.writer, "{separator}  \"{index}\": \"{value}\"")
+            .map_err(|err| WitnessMapError(err.into()))?;
+        self.entries_written += 1;
+        Ok(())
+    }
+
+    /// Writes every assignment in `witness_map`.
+    pub fn write_witness_map<F: AcirField>(
+        &mut self,
+        witness_map: &WitnessMap<F>,
+    ) -> Result<(), WitnessMapError> {
+        for (witness, value) in witness_map.iter() {
+            self.write_entry(witness, value)?;
+        }
+        Ok(())
+    }
+
+    /// Closes the JSON object and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, WitnessMapError> {
+        self.writer.write_all(b"\n}\n").map_err(|err| WitnessMapError(err.into()))?;
+        self.writer.flush().map_err(|err| WitnessMapError(err.into()))?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads witness assignments from a JSON object without first collecting the entire input into memory.
+pub struct WitnessMapReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> WitnessMapReader<R> {
+    pub fn new(reader: R) -> Self {
+        WitnessMapReader { reader }
+    }
+
+    /// Calls `on_entry` with each witness assignment in the order in which they appear in the input.
+    pub fn for_each_entry<F: AcirField>(
+        self,
+        on_entry: impl FnMut(Witness, F),
+    ) -> Result<(), WitnessMapError> {
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(self.reader));
+        deserializer
+            .deserialize_map(EntryVisitor(on_entry, PhantomData))
+            .and_then(|_| deserializer.end())
+            .map_err(|err| WitnessMapError(err.into()))
+    }
+
+    /// Reads every witness assignment into a [`WitnessMap`].
+    pub fn read_witness_map<F: AcirField>(self) -> Result<WitnessMap<F>, WitnessMapError> {
+        let mut witness_map = WitnessMap::new();
+        self.for_each_entry(|witness, value| {
+            witness_map.insert(witness, value);
+        })?;
+        Ok(witness_map)
+    }
+}
+
+struct EntryVisitor<F, C>(C, PhantomData<F>);
+
+impl<'de, F: AcirField, C: FnMut(Witness, F)> Visitor<'de> for EntryVisitor<F, C> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a map from witness indices to field elements")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<Self::Value, A::Error> {
+        while let Some((index, value)) = map.next_entry::<String, String>()? {
+            let (witness, value) = decode_entry(&index, &value)
+                .map_err(|err: SerializationError| A::Error::custom(err))?;
+            (self.0)(witness, value);
+        }
+        Ok(())
+    }
+}
@@ -20,7 +20,7 @@ use acir::{
     native_types::{Expression, Witness},
 };
 use acir_field::FieldElement;
-use brillig::{HeapArray, RegisterIndex, RegisterOrMemory};
+use brillig::{BinaryIntOp, HeapArray, RegisterIndex, RegisterOrMemory};
 
 #[test]
 fn addition_circuit() {
@@ -59,11 +59,12 @@ fn addition_circuit() {
 
 #[test]
 fn fixed_base_scalar_mul_circuit() {
-    let fixed_base_scalar_mul = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::FixedBaseScalarMul {
-        low: FunctionInput { witness: Witness(1), num_bits: 128 },
-        high: FunctionInput { witness: Witness(2), num_bits: 128 },
-        outputs: (Witness(3), Witness(4)),
-    });
+    let fixed_base_scalar_mul: Opcode =
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::FixedBaseScalarMul {
+            low: FunctionInput { witness: Witness(1), num_bits: 128 },
+            high: FunctionInput { witness: Witness(2), num_bits: 128 },
+            outputs: (Witness(3), Witness(4)),
+        });
 
     let circuit = Circuit {
         current_witness_index: 5,
@@ -87,7 +88,7 @@ fn fixed_base_scalar_mul_circuit() {
 
 #[test]
 fn pedersen_circuit() {
-    let pedersen = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Pedersen {
+    let pedersen: Opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Pedersen {
         inputs: vec![FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() }],
         outputs: (Witness(2), Witness(3)),
         domain_separator: 0,
@@ -127,7 +128,7 @@ fn schnorr_verify_circuit() {
     let output = Witness(3 + 64 + 10);
     let last_input = output.witness_index() - 1;
 
-    let schnorr = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SchnorrVerify {
+    let schnorr: Opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SchnorrVerify {
         public_key_x,
         public_key_y,
         signature,
@@ -173,7 +174,7 @@ fn simple_brillig_foreign_call() {
     let w_input = Witness(1);
     let w_inverted = Witness(2);
 
-    let brillig_data = Brillig {
+    let brillig_data: Brillig = Brillig {
         inputs: vec![
             BrilligInputs::Single(w_input.into()), // Input Register 0,
         ],
@@ -227,7 +228,7 @@ fn complex_brillig_foreign_call() {
     let a_plus_b_plus_c = Witness(7);
     let a_plus_b_plus_c_times_2 = Witness(8);
 
-    let brillig_data = Brillig {
+    let brillig_data: Brillig = Brillig {
         inputs: vec![
             // Input Register 0
             BrilligInputs::Array(vec![
@@ -328,3 +329,65 @@ fn memory_op_circuit() {
 
     assert_eq!(bytes, expected_serialization)
 }
+
+#[test]
+fn brillig_integer_ops() {
+    let bit_size = 32;
+    let lhs = RegisterIndex::from(0);
+    let rhs = RegisterIndex::from(1);
+
+    let int_op = |op, destination: usize| brillig::Opcode::BinaryIntOp {
+        destination: RegisterIndex::from(destination),
+        op,
+        bit_size,
+        lhs,
+        rhs,
+    };
+
+    let brillig_data: Brillig = Brillig {
+        inputs: vec![
+            BrilligInputs::Single(Witness(1).into()), // Input Register 0
+            BrilligInputs::Single(Witness(2).into()), // Input Register 1
+        ],
+        outputs: (3..=9).map(|witness| BrilligOutputs::Simple(Witness(witness))).collect(),
+        foreign_call_results: vec![],
+        bytecode: vec![
+            int_op(BinaryIntOp::SignedLessThan, 2),
+            int_op(BinaryIntOp::SignedLessThanEquals, 3),
+            int_op(BinaryIntOp::UnsignedRem, 4),
+            int_op(BinaryIntOp::SignedRem, 5),
+            int_op(BinaryIntOp::SignedShr, 6),
+            brillig::Opcode::Not { destination: RegisterIndex::from(7), source: lhs, bit_size },
+            brillig::Opcode::Cast {
+                destination: RegisterIndex::from(8),
+                source: lhs,
+                bit_size: 8,
+                sign_extend_from: None,
+            },
+            brillig::Opcode::Stop,
+        ],
+        predicate: None,
+    };
+
+    let circuit = Circuit {
+        current_witness_index: 9,
+        opcodes: vec![Opcode::Brillig(brillig_data)],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        ..Circuit::default()
+    };
+
+    let mut bytes = Vec::new();
+    circuit.write(&mut bytes).unwrap();
+
+    let expected_serialization: Vec<u8> = vec![
+        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 181, 145, 75, 14, 128, 32, 12, 68, 11, 248, 41, 11, 127,
+        55, 240, 8, 120, 3, 239, 127, 42, 33, 118, 76, 163, 200, 70, 156, 132, 76, 2, 67, 121, 45,
+        158, 136, 12, 157, 114, 113, 89, 122, 10, 231, 187, 120, 248, 166, 205, 212, 171, 21, 254,
+        228, 180, 21, 57, 123, 197, 233, 196, 27, 241, 86, 188, 19, 71, 150, 197, 189, 186, 203,
+        170, 87, 252, 213, 16, 215, 154, 153, 131, 81, 111, 141, 133, 12, 56, 166, 66, 6, 140, 115,
+        33, 3, 254, 229, 37, 147, 234, 235, 57, 144, 228, 82, 77, 190, 237, 51, 160, 175, 46, 117,
+        199, 57, 29, 114, 163, 241, 158, 202, 2, 0, 0,
+    ];
+
+    assert_eq!(bytes, expected_serialization)
+}
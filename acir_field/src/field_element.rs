@@ -0,0 +1,73 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// The interface which ACIR and the ACVM require of the field over which a circuit is defined.
+///
+/// Circuits, witness maps and the ACVM are generic over this trait so that a single process can work with circuits
+/// defined over different fields. Each field enabled through a cargo feature is exported as an implementation of this trait,
+/// with [`FieldElement`][crate::FieldElement] naming the default field.
+pub trait AcirField:
+    'static
+    + Copy
+    + Debug
+    + Display
+    + Eq
+    + Ord
+    + Hash
+    + Send
+    + Sync
+    + Neg<Output = Self>
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + From<u128>
+    + From<i128>
+    + From<bool>
+    + Serialize
+    + for<'de> Deserialize<'de>
+{
+    fn one() -> Self;
+    fn zero() -> Self;
+    fn is_zero(&self) -> bool;
+    fn is_one(&self) -> bool;
+    fn pow(&self, exponent: &Self) -> Self;
+
+    /// Maximum number of bits needed to represent a field element.
+    fn max_num_bits() -> u32;
+    /// Maximum number of bytes needed to represent a field element.
+    fn max_num_bytes() -> u32;
+    fn modulus() -> BigUint;
+
+    /// Parses a field element from either its hex or decimal representation.
+    fn try_from_str(input: &str) -> Option<Self>;
+
+    /// The number of bits required to represent this specific field element.
+    fn num_bits(&self) -> u32;
+    fn fits_in_u128(&self) -> bool;
+    /// Returns the lowest 128 bits of the field element.
+    fn to_u128(self) -> u128;
+    fn try_into_u128(self) -> Option<u128>;
+    fn try_to_u64(&self) -> Option<u64>;
+
+    /// Computes the inverse or returns zero if the inverse does not exist.
+    fn inverse(&self) -> Self;
+    fn try_inverse(self) -> Option<Self>;
+
+    fn to_hex(self) -> String;
+    fn from_hex(hex_str: &str) -> Option<Self>;
+    fn to_be_bytes(self) -> Vec<u8>;
+    /// Converts bytes into a field element, applying a reduction if needed.
+    fn from_be_bytes_reduce(bytes: &[u8]) -> Self;
+    fn bits(&self) -> Vec<bool>;
+    fn fetch_nearest_bytes(&self, num_bits: usize) -> Vec<u8>;
+
+    fn and(&self, rhs: &Self, num_bits: u32) -> Self;
+    fn xor(&self, rhs: &Self, num_bits: u32) -> Self;
+}
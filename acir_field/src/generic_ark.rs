@@ -1,8 +1,10 @@
-use ark_ff::PrimeField;
-use ark_ff::Zero;
+pub use ark_ff::PrimeField;
+use ark_ff::{BigInteger, Zero};
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
+use crate::AcirField;
+
 // XXX: Switch out for a trait and proper implementations
 // This implementation is in-efficient, can definitely remove hex usage and Iterator instances for trivial functionality
 #[derive(Clone, Copy, Eq, PartialOrd, Ord)]
@@ -92,22 +94,13 @@ impl<F: PrimeField> PartialEq for FieldElement<F> {
 }
 
 impl<F: PrimeField> From<i128> for FieldElement<F> {
-    fn from(mut a: i128) -> FieldElement<F> {
-        let mut negative = false;
+    fn from(a: i128) -> FieldElement<F> {
+        let result = FieldElement::from(a.unsigned_abs());
         if a < 0 {
-            a = -a;
-            negative = true;
-        }
-
-        let mut result = match F::from_str(&a.to_string()) {
-            Ok(result) => result,
-            Err(_) => panic!("Cannot convert i128 as a string to a field element"),
-        };
-
-        if negative {
-            result = -result;
+            -result
+        } else {
+            result
         }
-        FieldElement(result)
     }
 }
 
@@ -134,12 +127,9 @@ impl<'de, T: ark_ff::PrimeField> Deserialize<'de> for FieldElement<T> {
 }
 
 impl<F: PrimeField> From<u128> for FieldElement<F> {
+    /// Values which are larger than the field modulus are reduced, which can only happen for fields of 128 bits or fewer.
     fn from(a: u128) -> FieldElement<F> {
-        let result = match F::from_str(&a.to_string()) {
-            Ok(result) => result,
-            Err(_) => panic!("Cannot convert u128 as a string to a field element"),
-        };
-        FieldElement(result)
+        FieldElement(F::from(a))
     }
 }
 
@@ -212,25 +202,30 @@ impl<F: PrimeField> FieldElement<F> {
 
     /// This is the number of bits required to represent this specific field element
     pub fn num_bits(&self) -> u32 {
-        let bits = self.bits();
-        // Iterate the number of bits and pop off all leading zeroes
-        let iter = bits.iter().skip_while(|x| !(**x));
-        // Note: count will panic if it goes over usize::MAX.
-        // This may not be suitable for devices whose usize < u16
-        iter.count() as u32
+        self.0.into_bigint().num_bits()
     }
 
     pub fn fits_in_u128(&self) -> bool {
         self.num_bits() <= 128
     }
 
+    /// Returns the lowest 128 bits of the field element.
     pub fn to_u128(self) -> u128 {
-        let bytes = self.to_be_bytes();
-        u128::from_be_bytes(bytes[16..32].try_into().unwrap())
+        let bigint = self.0.into_bigint();
+        // Fields with fewer than 128 bits are represented by a single 64 bit limb.
+        let limbs = bigint.as_ref();
+        let high = limbs.get(1).copied().unwrap_or_default();
+        (u128::from(high) << 64) | u128::from(limbs[0])
     }
 
     pub fn try_into_u128(self) -> Option<u128> {
-        self.fits_in_u128().then(|| self.to_u128())
+        let bigint = self.0.into_bigint();
+        let limbs = bigint.as_ref();
+        if limbs.iter().skip(2).any(|limb| *limb != 0) {
+            return None;
+        }
+        let high = limbs.get(1).copied().unwrap_or_default();
+        Some((u128::from(high) << 64) | u128::from(limbs[0]))
     }
 
     pub fn try_to_u64(&self) -> Option<u64> {
@@ -355,6 +350,81 @@ impl<F: PrimeField> FieldElement<F> {
     }
 }
 
+impl<F: PrimeField> AcirField for FieldElement<F> {
+    fn one() -> Self {
+        Self::one()
+    }
+    fn zero() -> Self {
+        Self::zero()
+    }
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+    fn is_one(&self) -> bool {
+        self.is_one()
+    }
+    fn pow(&self, exponent: &Self) -> Self {
+        self.pow(exponent)
+    }
+    fn max_num_bits() -> u32 {
+        Self::max_num_bits()
+    }
+    fn max_num_bytes() -> u32 {
+        Self::max_num_bytes()
+    }
+    fn modulus() -> BigUint {
+        Self::modulus()
+    }
+    fn try_from_str(input: &str) -> Option<Self> {
+        Self::try_from_str(input)
+    }
+    fn num_bits(&self) -> u32 {
+        self.num_bits()
+    }
+    fn fits_in_u128(&self) -> bool {
+        self.fits_in_u128()
+    }
+    fn to_u128(self) -> u128 {
+        self.to_u128()
+    }
+    fn try_into_u128(self) -> Option<u128> {
+        self.try_into_u128()
+    }
+    fn try_to_u64(&self) -> Option<u64> {
+        self.try_to_u64()
+    }
+    fn inverse(&self) -> Self {
+        self.inverse()
+    }
+    fn try_inverse(self) -> Option<Self> {
+        self.try_inverse()
+    }
+    fn to_hex(self) -> String {
+        self.to_hex()
+    }
+    fn from_hex(hex_str: &str) -> Option<Self> {
+        Self::from_hex(hex_str)
+    }
+    fn to_be_bytes(self) -> Vec<u8> {
+        self.to_be_bytes()
+    }
+    fn from_be_bytes_reduce(bytes: &[u8]) -> Self {
+        Self::from_be_bytes_reduce(bytes)
+    }
+    fn bits(&self) -> Vec<bool> {
+        self.bits()
+    }
+    fn fetch_nearest_bytes(&self, num_bits: usize) -> Vec<u8> {
+        self.fetch_nearest_bytes(num_bits)
+    }
+    fn and(&self, rhs: &Self, num_bits: u32) -> Self {
+        self.and(rhs, num_bits)
+    }
+    fn xor(&self, rhs: &Self, num_bits: u32) -> Self {
+        self.xor(rhs, num_bits)
+    }
+}
+
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 impl<F: PrimeField> Neg for FieldElement<F> {
@@ -441,6 +511,71 @@ mod tests {
         let max_num_bits_bn254 = crate::generic_ark::FieldElement::<ark_bn254::Fr>::max_num_bits();
         assert_eq!(max_num_bits_bn254, 254)
     }
+
+    #[test]
+    fn integer_conversions_at_bounds() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        let max = FieldElement::from(u128::MAX);
+        assert!(max.fits_in_u128());
+        assert_eq!(max.to_u128(), u128::MAX);
+        assert_eq!(max.try_into_u128(), Some(u128::MAX));
+        assert_eq!((max + FieldElement::one()).try_into_u128(), None);
+        assert_eq!((max + FieldElement::one()).num_bits(), 129);
+
+        let min = FieldElement::from(i128::MIN);
+        assert_eq!(-min, FieldElement::from(1u128 << 127));
+    }
+
+    #[test]
+    fn trait_methods_match_inherent_methods() {
+        use crate::AcirField;
+
+        fn minus_one_via_trait<F: AcirField>() -> F {
+            F::zero() - F::one()
+        }
+
+        type Bn254 = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+        type Goldilocks = crate::generic_ark::FieldElement<crate::goldilocks::Goldilocks>;
+
+        assert_eq!(minus_one_via_trait::<Bn254>(), -Bn254::one());
+        assert_eq!(minus_one_via_trait::<Goldilocks>(), -Goldilocks::one());
+        assert_eq!(<Goldilocks as AcirField>::max_num_bits(), 64);
+        assert_eq!(AcirField::to_u128(minus_one_via_trait::<Goldilocks>()), 18446744069414584320);
+    }
+
+    mod goldilocks {
+        type FieldElement = crate::generic_ark::FieldElement<crate::goldilocks::Goldilocks>;
+
+        const MODULUS: u128 = 18446744069414584321;
+
+        #[test]
+        fn field_size() {
+            assert_eq!(FieldElement::max_num_bits(), 64);
+            assert_eq!(FieldElement::max_num_bytes(), 8);
+            assert_eq!(FieldElement::modulus(), MODULUS.into());
+        }
+
+        #[test]
+        fn hex_roundtrip() {
+            let minus_one = -FieldElement::one();
+            assert_eq!(minus_one.to_hex(), "ffffffff00000000");
+            assert_eq!(FieldElement::from_hex(&minus_one.to_hex()), Some(minus_one));
+        }
+
+        #[test]
+        fn integer_conversions_reduce() {
+            // Every field element fits in a u128 but not every u128 fits in a field element.
+            let minus_one = -FieldElement::one();
+            assert!(minus_one.fits_in_u128());
+            assert_eq!(minus_one.to_u128(), MODULUS - 1);
+            assert_eq!(minus_one.try_to_u64(), Some((MODULUS - 1) as u64));
+
+            assert_eq!(FieldElement::from(MODULUS + 5), FieldElement::from(5u128));
+            assert_eq!(FieldElement::from(-1i128), minus_one);
+            assert_eq!(FieldElement::from(u128::MAX).to_u128(), u128::MAX % MODULUS);
+        }
+    }
 }
 
 fn mask_vector_le(bytes: &mut [u8], num_bits: usize) {
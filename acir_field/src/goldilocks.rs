@@ -0,0 +1,16 @@
+//! The 64-bit "Goldilocks" prime field with modulus `p = 2^64 - 2^32 + 1`.
+//!
+//! Unlike the scalar fields of the pairing-friendly curves, this field cannot represent 128-bit integers
+//! so any code which packs integers into a single field element must check [`FieldElement::max_num_bits`][crate::FieldElement::max_num_bits].
+
+// `MontConfig` expands to trait impls inside an anonymous constant.
+#![allow(unknown_lints, non_local_definitions)]
+
+use ark_ff::{Fp64, MontBackend, MontConfig};
+
+#[derive(MontConfig)]
+#[modulus = "18446744069414584321"]
+#[generator = "7"]
+pub struct GoldilocksConfig;
+
+pub type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
@@ -1,16 +1,39 @@
 #![warn(unused_crate_dependencies)]
 #![warn(unreachable_pub)]
 
+mod field_element;
+pub mod generic_ark;
+#[cfg(any(feature = "goldilocks", test))]
+pub mod goldilocks;
+
+pub use field_element::AcirField;
+
+// Each field is enabled by its own feature. These are additive so that a single build can work with circuits over
+// several fields by using the ACIR types with an explicit field parameter.
+#[cfg(feature = "bn254")]
+pub type Bn254FieldElement = generic_ark::FieldElement<ark_bn254::Fr>;
+#[cfg(feature = "bls12_381")]
+pub type Bls12381FieldElement = generic_ark::FieldElement<ark_bls12_381::Fr>;
+#[cfg(feature = "bls12_377")]
+pub type Bls12377FieldElement = generic_ark::FieldElement<ark_bls12_377::Fr>;
+#[cfg(feature = "goldilocks")]
+pub type GoldilocksFieldElement = generic_ark::FieldElement<goldilocks::Goldilocks>;
+
+// The default field is used wherever a field is not given explicitly.
+// If several fields are enabled then the first in this list is chosen.
 cfg_if::cfg_if! {
     if #[cfg(feature = "bn254")] {
-        mod generic_ark;
-        pub type FieldElement = generic_ark::FieldElement<ark_bn254::Fr>;
+        pub type FieldElement = Bn254FieldElement;
         pub const CHOSEN_FIELD : FieldOptions = FieldOptions::BN254;
-
     } else if #[cfg(feature = "bls12_381")] {
-        mod generic_ark;
-        pub type FieldElement = generic_ark::FieldElement<ark_bls12_381::Fr>;
+        pub type FieldElement = Bls12381FieldElement;
         pub const CHOSEN_FIELD : FieldOptions = FieldOptions::BLS12_381;
+    } else if #[cfg(feature = "bls12_377")] {
+        pub type FieldElement = Bls12377FieldElement;
+        pub const CHOSEN_FIELD : FieldOptions = FieldOptions::BLS12_377;
+    } else if #[cfg(feature = "goldilocks")] {
+        pub type FieldElement = GoldilocksFieldElement;
+        pub const CHOSEN_FIELD : FieldOptions = FieldOptions::GOLDILOCKS;
     } else {
         compile_error!("please specify a field to compile with");
     }
@@ -20,21 +43,6 @@ cfg_if::cfg_if! {
 pub enum FieldOptions {
     BN254,
     BLS12_381,
+    BLS12_377,
+    GOLDILOCKS,
 }
-
-// This is needed because features are additive through the dependency graph; if a dependency turns on the bn254, then it
-// will be turned on in all crates that depend on it
-#[macro_export]
-macro_rules! assert_unique_feature {
-    () => {};
-    ($first:tt $(,$rest:tt)*) => {
-        $(
-            #[cfg(all(feature = $first, feature = $rest))]
-            compile_error!(concat!("features \"", $first, "\" and \"", $rest, "\" cannot be used together"));
-        )*
-        assert_unique_feature!($($rest),*);
-    }
-}
-// https://internals.rust-lang.org/t/mutually-exclusive-feature-flags/8601/7
-// If another field/feature is added, we add it here too
-assert_unique_feature!("bn254", "bls12_381");
@@ -0,0 +1,62 @@
+//! Benchmarks solving a circuit in which SHA-256 has been replaced by its fallback implementation,
+//! whose many Brillig opcodes perform the integer operations of the hash.
+//!
+//! Run with `cargo bench -p acvm`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, Instant},
+};
+
+use acir::{
+    circuit::{
+        opcodes::{BlackBoxFuncCall, FunctionInput},
+        Circuit, Opcode,
+    },
+    native_types::Witness,
+    FieldElement,
+};
+use acvm::{
+    compiler::compile,
+    pwg::{ACVMStatus, ACVM},
+    Language,
+};
+use acvm_blackbox_solver::testing::MockSolver;
+
+const MESSAGE_LENGTH: u32 = 64;
+const SAMPLES: u32 = 10;
+
+fn main() {
+    let inputs: Vec<FunctionInput> = (1..=MESSAGE_LENGTH)
+        .map(|index| FunctionInput { witness: Witness(index), num_bits: 8 })
+        .collect();
+    let outputs: Vec<Witness> = (MESSAGE_LENGTH + 1..=MESSAGE_LENGTH + 32).map(Witness).collect();
+    let circuit = Circuit {
+        current_witness_index: MESSAGE_LENGTH + 32,
+        opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 { inputs, outputs })],
+        private_parameters: BTreeSet::new(),
+        ..Circuit::default()
+    };
+    let (circuit, _) = compile(circuit, Language::PLONKCSat { width: 3 }, |opcode| {
+        !matches!(opcode, Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 { .. }))
+    })
+    .expect("circuit should compile");
+
+    let witness: BTreeMap<Witness, FieldElement> = (1..=MESSAGE_LENGTH)
+        .map(|index| (Witness(index), FieldElement::from(u128::from(index))))
+        .collect();
+
+    let solver = MockSolver::new();
+    let mut total = Duration::ZERO;
+    for _ in 0..SAMPLES {
+        let mut acvm = ACVM::new(&solver, circuit.opcodes.clone(), witness.clone().into());
+        let start = Instant::now();
+        assert_eq!(acvm.solve(), ACVMStatus::Solved);
+        total += start.elapsed();
+    }
+    println!(
+        "{:<32} {:>12.3?}",
+        format!("sha256 fallback ({} opcodes)", circuit.opcodes.len()),
+        total / SAMPLES
+    );
+}
@@ -16,7 +16,10 @@ mod optimizers;
 mod transformers;
 
 use optimizers::{GeneralOptimizer, RangeOptimizer};
-use transformers::{CSatTransformer, FallbackTransformer, R1CSTransformer};
+use transformers::{
+    CSatTransformer, DirectiveTransformer, FallbackTransformer, R1CSTransformer,
+    RangeLookupTransformer,
+};
 
 #[derive(PartialEq, Eq, Debug, Error)]
 pub enum CompileError {
@@ -24,6 +27,21 @@ pub enum CompileError {
     UnsupportedBlackBox(BlackBoxFunc),
     #[error("The opcode {0} is not supported by the backend and acvm does not have a fallback implementation")]
     UnsupportedMemoryOpcode(UnsupportedMemoryOpcode),
+    #[error("Range lookup tables of {0} bits are not supported, lookup tables must be between 1 and {max} bits", max = RangeLookupTransformer::MAX_LOOKUP_BITS)]
+    UnsupportedRangeLookupBits(u32),
+}
+
+/// Options which allow a backend to tailor the passes applied by [`compile_with_options`] to its proof system.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// The number of bits covered by the range lookup tables of the backend, for backends which support plookup.
+    ///
+    /// When set, range constraints on more bits than this are lowered into a decomposition into limbs,
+    /// each of which is range constrained to fit in a single lookup table.
+    pub range_lookup_bits: Option<u32>,
+    /// Whether directives are replaced by [`Brillig`][Opcode::Brillig] opcodes which compute the same witnesses,
+    /// so that directives are only encountered when executing circuits compiled without this option.
+    pub replace_directives: bool,
 }
 
 /// This module moves and decomposes acir opcodes. The transformation map allows consumers of this module to map
@@ -77,6 +95,27 @@ pub fn compile(
     np_language: Language,
     is_opcode_supported: impl Fn(&Opcode) -> bool,
 ) -> Result<(Circuit, AcirTransformationMap), CompileError> {
+    compile_with_options(acir, np_language, is_opcode_supported, CompileOptions::default())
+}
+
+/// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`],
+/// configured by the backend's [`CompileOptions`].
+pub fn compile_with_options(
+    acir: Circuit,
+    np_language: Language,
+    is_opcode_supported: impl Fn(&Opcode) -> bool,
+    options: CompileOptions,
+) -> Result<(Circuit, AcirTransformationMap), CompileError> {
+    let range_lookup_transformer = match options.range_lookup_bits {
+        Some(lookup_bits)
+            if lookup_bits == 0 || lookup_bits > RangeLookupTransformer::MAX_LOOKUP_BITS =>
+        {
+            return Err(CompileError::UnsupportedRangeLookupBits(lookup_bits));
+        }
+        Some(lookup_bits) => Some(RangeLookupTransformer::new(lookup_bits)),
+        None => None,
+    };
+
     // Instantiate the optimizer.
     // Currently the optimizer and reducer are one in the same
     // for CSAT
@@ -103,9 +142,21 @@ pub fn compile(
 
     // Range optimization pass
     let range_optimizer = RangeOptimizer::new(acir);
-    let (mut acir, acir_opcode_positions) =
+    let (acir, acir_opcode_positions) =
         range_optimizer.replace_redundant_ranges(acir_opcode_positions);
 
+    // Range lookup lowering pass
+    let (mut acir, acir_opcode_positions) = match range_lookup_transformer {
+        Some(transformer) => transformer.transform(acir, acir_opcode_positions),
+        None => (acir, acir_opcode_positions),
+    };
+
+    // Directive replacement pass
+    // This must follow the passes above as range constraints are inferred from, and lowered into, directives.
+    if options.replace_directives {
+        acir = DirectiveTransformer::transform(acir);
+    }
+
     let mut transformer = match &np_language {
         crate::Language::R1CS => {
             let transformation_map = AcirTransformationMap { acir_opcode_positions };
@@ -172,6 +223,8 @@ pub fn compile(
                     }
                     acir::circuit::opcodes::BlackBoxFuncCall::RANGE { .. } => (),
                     acir::circuit::opcodes::BlackBoxFuncCall::SHA256 { outputs, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::SHA512 { outputs, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::Blake3 { outputs, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::Keccak256 { outputs, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::Keccak256VariableLength {
                         outputs,
@@ -186,11 +239,27 @@ pub fn compile(
                             transformer.mark_solvable(*witness);
                         }
                     }
+                    acir::circuit::opcodes::BlackBoxFuncCall::EcdsaSecp256k1Recover {
+                        public_key_x,
+                        public_key_y,
+                        ..
+                    } => {
+                        for witness in public_key_x.iter().chain(public_key_y) {
+                            transformer.mark_solvable(*witness);
+                        }
+                    }
                     acir::circuit::opcodes::BlackBoxFuncCall::FixedBaseScalarMul {
                         outputs,
                         ..
                     }
-                    | acir::circuit::opcodes::BlackBoxFuncCall::Pedersen { outputs, .. } => {
+                    | acir::circuit::opcodes::BlackBoxFuncCall::Pedersen { outputs, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::EmbeddedCurveAdd {
+                        outputs, ..
+                    }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::VariableBaseScalarMul {
+                        outputs,
+                        ..
+                    } => {
                         transformer.mark_solvable(outputs.0);
                         transformer.mark_solvable(outputs.1)
                     }
@@ -200,6 +269,10 @@ pub fn compile(
                     }
                     | acir::circuit::opcodes::BlackBoxFuncCall::EcdsaSecp256k1 { output, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::EcdsaSecp256r1 { output, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::EddsaEd25519 { output, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::EddsaBabyJubJub {
+                        output, ..
+                    }
                     | acir::circuit::opcodes::BlackBoxFuncCall::SchnorrVerify { output, .. } => {
                         transformer.mark_solvable(*output)
                     }
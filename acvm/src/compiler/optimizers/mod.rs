@@ -1,4 +1,5 @@
 mod general;
+mod range_inference;
 mod redundant_range;
 
 pub(crate) use general::GeneralOptimizer;
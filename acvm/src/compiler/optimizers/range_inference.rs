@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use acir::{
+    circuit::{directives::Directive, opcodes::BlackBoxFuncCall, Circuit, Opcode},
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+/// Infers the bit sizes which witnesses are known to fit in without the range constraints applied to them.
+///
+/// Bit sizes are inferred from:
+/// - the inputs and outputs of `AND` and `XOR` opcodes, which are constrained to fit in the bit size of the operation.
+/// - boolean constraints of the form `x * x - x = 0`.
+/// - radix decompositions, where a [`Directive::ToLeRadix`] with a power of two radix is accompanied by an
+///   arithmetic opcode constraining the witness to equal the sum of its digits, and each digit is known to fit in the radix.
+///
+/// The range constraints in `range_constraints` may be used to show that the digits of a decomposition fit in the radix.
+/// A witness whose range constraint is used in this way is left out of the returned map, as its range constraint must be kept.
+pub(super) fn infer_ranges(
+    circuit: &Circuit,
+    range_constraints: &BTreeMap<Witness, u32>,
+) -> BTreeMap<Witness, u32> {
+    let recompositions: HashSet<Expression> = circuit
+        .opcodes
+        .iter()
+        .filter_map(|opcode| match opcode {
+            Opcode::Arithmetic(expr) => normalize(expr.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let mut bit_sizes = BTreeMap::new();
+    let mut decompositions = Vec::new();
+    for opcode in &circuit.opcodes {
+        match opcode {
+            Opcode::BlackBoxFuncCall(
+                BlackBoxFuncCall::AND { lhs, rhs, output }
+                | BlackBoxFuncCall::XOR { lhs, rhs, output },
+            ) => {
+                restrict(&mut bit_sizes, lhs.witness, lhs.num_bits);
+                restrict(&mut bit_sizes, rhs.witness, rhs.num_bits);
+                restrict(&mut bit_sizes, *output, lhs.num_bits.max(rhs.num_bits));
+            }
+            Opcode::Arithmetic(expr) => {
+                if let Some(witness) = boolean_witness(expr) {
+                    restrict(&mut bit_sizes, witness, 1);
+                }
+            }
+            Opcode::Directive(Directive::ToLeRadix { a, b, radix }) => {
+                if let Some(decomposition) = RadixDecomposition::new(a, b, *radix, &recompositions)
+                {
+                    decompositions.push(decomposition);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // Decompositions may build upon each other (e.g. a word decomposed into bytes which are decomposed into bits)
+    // so we repeat until no more bit sizes can be inferred.
+    let mut pinned_range_constraints = BTreeSet::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for decomposition in &decompositions {
+            let (num_bits, used_range_constraints) =
+                match decomposition.bit_size(&bit_sizes, range_constraints) {
+                    Some(inferred) => inferred,
+                    None => continue,
+                };
+            if restrict(&mut bit_sizes, decomposition.witness, num_bits) {
+                pinned_range_constraints.extend(used_range_constraints);
+                changed = true;
+            }
+        }
+    }
+
+    for witness in pinned_range_constraints {
+        bit_sizes.remove(&witness);
+    }
+    bit_sizes
+}
+
+/// A witness which is constrained to equal the sum of its `digits` in little endian form,
+/// each weighted by a power of `2^digit_bits`.
+struct RadixDecomposition {
+    witness: Witness,
+    digits: Vec<Witness>,
+    digit_bits: u32,
+}
+
+impl RadixDecomposition {
+    /// Returns the decomposition computed by a [`Directive::ToLeRadix`] if the circuit contains an arithmetic opcode
+    /// in `recompositions` which constrains it.
+    fn new(
+        value: &Expression,
+        digits: &[Witness],
+        radix: u32,
+        recompositions: &HashSet<Expression>,
+    ) -> Option<Self> {
+        let witness = value.to_witness()?;
+        if !radix.is_power_of_two() || radix < 2 || digits.is_empty() {
+            return None;
+        }
+
+        let radix_field = FieldElement::from(radix as u128);
+        let mut recomposition = Expression::from(witness);
+        let mut weight = FieldElement::one();
+        for digit in digits {
+            recomposition.push_addition_term(-weight, *digit);
+            weight = weight * radix_field;
+        }
+        if !recompositions.contains(&normalize(recomposition)?) {
+            return None;
+        }
+
+        Some(RadixDecomposition {
+            witness,
+            digits: digits.to_vec(),
+            digit_bits: radix.trailing_zeros(),
+        })
+    }
+
+    /// Returns the bit size which the decomposed witness fits in, given the known bit sizes of its digits.
+    ///
+    /// If range constraints were needed to show that the digits fit in the radix, then their witnesses are also returned.
+    fn bit_size(
+        &self,
+        bit_sizes: &BTreeMap<Witness, u32>,
+        range_constraints: &BTreeMap<Witness, u32>,
+    ) -> Option<(u32, Vec<Witness>)> {
+        let fits_in_digit = |num_bits: &&u32| **num_bits <= self.digit_bits;
+
+        let mut used_range_constraints = Vec::new();
+        let mut last_digit_bits = 0;
+        for digit in &self.digits {
+            last_digit_bits = match bit_sizes.get(digit).filter(fits_in_digit) {
+                Some(num_bits) => *num_bits,
+                None => {
+                    let num_bits = range_constraints.get(digit).filter(fits_in_digit)?;
+                    used_range_constraints.push(*digit);
+                    *num_bits
+                }
+            };
+        }
+
+        // Every digit but the last can contribute at most `digit_bits` bits.
+        let num_bits = self.digit_bits * (self.digits.len() as u32 - 1) + last_digit_bits;
+
+        // The sum of the digits must not wrap around the field's modulus for the bit size to hold.
+        (num_bits < FieldElement::max_num_bits()).then_some((num_bits, used_range_constraints))
+    }
+}
+
+/// Records that `witness` fits in `num_bits` bits, returning `true` if this is tighter than its known bit size.
+fn restrict(bit_sizes: &mut BTreeMap<Witness, u32>, witness: Witness, num_bits: u32) -> bool {
+    match bit_sizes.get(&witness) {
+        Some(known_bits) if *known_bits <= num_bits => false,
+        _ => {
+            bit_sizes.insert(witness, num_bits);
+            true
+        }
+    }
+}
+
+/// Returns the witness `x` if `expr` is a boolean constraint `c * x * x - c * x = 0`.
+fn boolean_witness(expr: &Expression) -> Option<Witness> {
+    match (expr.mul_terms.as_slice(), expr.linear_combinations.as_slice()) {
+        ([(mul_coefficient, lhs, rhs)], [(linear_coefficient, witness)])
+            if lhs == rhs
+                && lhs == witness
+                && !mul_coefficient.is_zero()
+                && *mul_coefficient == -*linear_coefficient
+                && expr.q_c.is_zero() =>
+        {
+            Some(*witness)
+        }
+        _ => None,
+    }
+}
+
+/// Scales a linear expression so that its first term has a coefficient of one.
+///
+/// This allows linear constraints which differ only by a constant factor to be compared.
+fn normalize(mut expr: Expression) -> Option<Expression> {
+    if !expr.mul_terms.is_empty() {
+        return None;
+    }
+    expr.sort();
+    let (coefficient, _) = expr.linear_combinations.first()?;
+    if coefficient.is_zero() {
+        return None;
+    }
+    Some(&expr * coefficient.inverse())
+}
@@ -4,6 +4,8 @@ use acir::{
 };
 use std::collections::{BTreeMap, HashSet};
 
+use super::range_inference::infer_ranges;
+
 /// `RangeOptimizer` will remove redundant range constraints.
 ///
 /// # Example
@@ -23,9 +25,14 @@ use std::collections::{BTreeMap, HashSet};
 ///
 /// This optimization pass will keep the 16-bit range constraint
 /// and remove the 32-bit range constraint opcode.
+///
+/// Range constraints are also removed when other opcodes already imply them,
+/// such as when `x` is an input to a 16-bit `AND` or has been decomposed into 16 constrained bits.
 pub(crate) struct RangeOptimizer {
     /// Maps witnesses to their lowest known bit sizes.
     lists: BTreeMap<Witness, u32>,
+    /// Maps witnesses to the bit sizes which other opcodes constrain them to fit in.
+    implied_ranges: BTreeMap<Witness, u32>,
     circuit: Circuit,
 }
 
@@ -34,7 +41,8 @@ impl RangeOptimizer {
     /// constraints from `Circuit`.
     pub(crate) fn new(circuit: Circuit) -> Self {
         let range_list = Self::collect_ranges(&circuit);
-        Self { circuit, lists: range_list }
+        let implied_ranges = infer_ranges(&circuit, &range_list);
+        Self { circuit, lists: range_list, implied_ranges }
     }
 
     /// Stores the lowest bit range, that a witness
@@ -85,6 +93,15 @@ impl RangeOptimizer {
                     continue;
                 }
             };
+            // If other opcodes already constrain the witness to this bit size then skip this opcode.
+            let is_implied = self
+                .implied_ranges
+                .get(&witness)
+                .map_or(false, |implied_bits| *implied_bits <= num_bits);
+            if is_implied {
+                continue;
+            }
+
             // If we've already applied the range constraint for this witness then skip this opcode.
             let already_added = already_seen_witness.contains(&witness);
             if already_added {
@@ -140,10 +157,12 @@ mod tests {
     use crate::compiler::optimizers::redundant_range::{extract_range_opcode, RangeOptimizer};
     use acir::{
         circuit::{
+            directives::Directive,
             opcodes::{BlackBoxFuncCall, FunctionInput},
             Circuit, Opcode, PublicInputs,
         },
         native_types::{Expression, Witness},
+        FieldElement,
     };
 
     fn test_circuit(ranges: Vec<(Witness, u32)>) -> Circuit {
@@ -235,4 +254,90 @@ mod tests {
         let (optimized_circuit, _) = optimizer.replace_redundant_ranges(acir_opcode_positions);
         assert_eq!(optimized_circuit.opcodes.len(), 5)
     }
+
+    fn optimized_ranges(circuit: Circuit) -> Vec<(Witness, u32)> {
+        let acir_opcode_positions = circuit.opcodes.iter().enumerate().map(|(i, _)| i).collect();
+        let optimizer = RangeOptimizer::new(circuit);
+        let (optimized_circuit, _) = optimizer.replace_redundant_ranges(acir_opcode_positions);
+        optimized_circuit.opcodes.iter().filter_map(extract_range_opcode).collect()
+    }
+
+    /// Returns a decomposition of `witness` into `digits` in base `2^digit_bits`
+    /// along with the constraint that the digits sum to `witness`.
+    fn decomposition(witness: Witness, digits: &[Witness], digit_bits: u32) -> Vec<Opcode> {
+        let mut recomposition = Expression::from(witness);
+        for (i, digit) in digits.iter().enumerate() {
+            let weight =
+                FieldElement::from(2_u128).pow(&FieldElement::from(i as u128 * digit_bits as u128));
+            recomposition.push_addition_term(-weight, *digit);
+        }
+        vec![
+            Opcode::Directive(Directive::ToLeRadix {
+                a: witness.into(),
+                b: digits.to_vec(),
+                radix: 1 << digit_bits,
+            }),
+            Opcode::Arithmetic(recomposition),
+        ]
+    }
+
+    #[test]
+    fn ranges_implied_by_logic_opcodes() {
+        let mut circuit =
+            test_circuit(vec![(Witness(1), 8), (Witness(2), 4), (Witness(3), 16), (Witness(4), 8)]);
+        circuit.opcodes.push(Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+            lhs: FunctionInput { witness: Witness(1), num_bits: 8 },
+            rhs: FunctionInput { witness: Witness(2), num_bits: 8 },
+            output: Witness(3),
+        }));
+
+        // The range constraint on `Witness(2)` is tighter than the one implied by the `AND`.
+        assert_eq!(optimized_ranges(circuit), vec![(Witness(2), 4), (Witness(4), 8)]);
+    }
+
+    #[test]
+    fn ranges_implied_by_bit_decompositions() {
+        let mut circuit = test_circuit(vec![(Witness(1), 8), (Witness(1), 16), (Witness(2), 7)]);
+        circuit.current_witness_index = 2;
+        for witness in [Witness(1), Witness(2)] {
+            let (current_witness_index, opcodes) = stdlib::blackbox_fallbacks::range(
+                witness.into(),
+                8,
+                circuit.current_witness_index + 1,
+            );
+            circuit.current_witness_index = current_witness_index - 1;
+            circuit.opcodes.extend(opcodes);
+        }
+
+        // The decomposition of `Witness(2)` only shows that it fits in 8 bits.
+        assert_eq!(optimized_ranges(circuit), vec![(Witness(2), 7)]);
+    }
+
+    #[test]
+    fn ranges_of_digits_imply_ranges_of_decompositions() {
+        let digits = [Witness(2), Witness(3)];
+        let mut circuit = test_circuit(vec![(Witness(1), 16), (Witness(2), 8), (Witness(3), 8)]);
+        circuit.opcodes.extend(decomposition(Witness(1), &digits, 8));
+
+        assert_eq!(optimized_ranges(circuit), vec![(Witness(2), 8), (Witness(3), 8)]);
+    }
+
+    #[test]
+    fn decomposition_into_unconstrained_digits_implies_nothing() {
+        let digits = [Witness(2), Witness(3)];
+        let mut circuit = test_circuit(vec![(Witness(1), 16), (Witness(2), 8)]);
+        circuit.opcodes.extend(decomposition(Witness(1), &digits, 8));
+
+        assert_eq!(optimized_ranges(circuit), vec![(Witness(1), 16), (Witness(2), 8)]);
+    }
+
+    #[test]
+    fn cyclic_decompositions_keep_a_range_constraint() {
+        // Each witness is a decomposition of the other, so removing both range constraints would leave them unconstrained.
+        let mut circuit = test_circuit(vec![(Witness(1), 8), (Witness(2), 8)]);
+        circuit.opcodes.extend(decomposition(Witness(1), &[Witness(2)], 8));
+        circuit.opcodes.extend(decomposition(Witness(2), &[Witness(1)], 8));
+
+        assert_eq!(optimized_ranges(circuit).len(), 1);
+    }
 }
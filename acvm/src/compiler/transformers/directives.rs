@@ -0,0 +1,764 @@
+use acir::{
+    brillig::{BinaryIntOp, Label, Opcode as BrilligOpcode, RegisterIndex, Value},
+    circuit::{
+        brillig::{Brillig, BrilligInputs, BrilligOutputs},
+        directives::{Directive, QuotientDirective},
+        Circuit, Opcode,
+    },
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+/// Replaces [`Directive`]s with [`Brillig`] opcodes which compute the same witnesses,
+/// so that consumers of the circuit only need to support a single form of non-determinism.
+pub(crate) struct DirectiveTransformer;
+
+impl DirectiveTransformer {
+    pub(crate) fn transform(acir: Circuit) -> Circuit {
+        let opcodes = acir
+            .opcodes
+            .into_iter()
+            .map(|opcode| match opcode {
+                Opcode::Directive(Directive::Quotient(directive)) => {
+                    Opcode::Brillig(quotient_brillig(directive))
+                }
+                Opcode::Directive(Directive::ToLeRadix { a, b, radix }) => {
+                    Opcode::Brillig(to_le_radix_brillig(a, b, radix))
+                }
+                Opcode::Directive(Directive::PermutationSort { inputs, tuple, bits, sort_by }) => {
+                    Opcode::Brillig(permutation_sort_brillig(inputs, tuple, bits, &sort_by))
+                }
+                opcode => opcode,
+            })
+            .collect();
+
+        Circuit { opcodes, ..acir }
+    }
+}
+
+/// Integer operations are performed on the full width of the field as directives act on field elements as integers.
+const FIELD_BIT_SIZE: u32 = FieldElement::max_num_bits();
+
+/// Returns a [`Brillig`] opcode which computes the quotient and remainder of the euclidean division of `a` by `b`.
+///
+/// As with [`Directive::Quotient`], both are zero if `b` or the predicate is zero.
+fn quotient_brillig(directive: QuotientDirective) -> Brillig {
+    let QuotientDirective { a, b, q, r, predicate } = directive;
+
+    // Registers 0 and 1 hold `a` and `b` on entry and `q` and `r` on exit.
+    let (lhs, rhs, zero, is_zero) = (
+        RegisterIndex::from(0),
+        RegisterIndex::from(1),
+        RegisterIndex::from(2),
+        RegisterIndex::from(3),
+    );
+    let bytecode = vec![
+        BrilligOpcode::Const { destination: zero, value: Value::from(0_u128) },
+        BrilligOpcode::BinaryIntOp {
+            destination: is_zero,
+            op: BinaryIntOp::Equals,
+            bit_size: FIELD_BIT_SIZE,
+            lhs: rhs,
+            rhs: zero,
+        },
+        BrilligOpcode::JumpIfNot { condition: is_zero, location: 6 },
+        BrilligOpcode::Mov { destination: lhs, source: zero },
+        BrilligOpcode::Mov { destination: rhs, source: zero },
+        BrilligOpcode::Stop,
+        // 6: `b` is non-zero so we can divide by it.
+        BrilligOpcode::BinaryIntOp {
+            destination: zero,
+            op: BinaryIntOp::UnsignedDiv,
+            bit_size: FIELD_BIT_SIZE,
+            lhs,
+            rhs,
+        },
+        BrilligOpcode::BinaryIntOp {
+            destination: rhs,
+            op: BinaryIntOp::UnsignedRem,
+            bit_size: FIELD_BIT_SIZE,
+            lhs,
+            rhs,
+        },
+        BrilligOpcode::Mov { destination: lhs, source: zero },
+        BrilligOpcode::Stop,
+    ];
+
+    Brillig {
+        inputs: vec![BrilligInputs::Single(a), BrilligInputs::Single(b)],
+        outputs: vec![BrilligOutputs::Simple(q), BrilligOutputs::Simple(r)],
+        foreign_call_results: Vec::new(),
+        bytecode,
+        predicate,
+    }
+}
+
+/// Returns a [`Brillig`] opcode which decomposes `a` into the little endian digits `b` in base `radix`.
+///
+/// As with [`Directive::ToLeRadix`], execution fails if `a` does not fit in `b.len()` digits.
+fn to_le_radix_brillig(a: Expression, b: Vec<Witness>, radix: u32) -> Brillig {
+    // Register 0 holds `a` on entry and the pointer to the digits in memory on exit.
+    let value = RegisterIndex::from(0);
+    let (radix_register, pointer, end, one, condition, digit) = (
+        RegisterIndex::from(1),
+        RegisterIndex::from(2),
+        RegisterIndex::from(3),
+        RegisterIndex::from(4),
+        RegisterIndex::from(5),
+        RegisterIndex::from(6),
+    );
+    let bytecode = vec![
+        BrilligOpcode::Const { destination: radix_register, value: Value::from(radix as u128) },
+        BrilligOpcode::Const { destination: pointer, value: Value::from(0_u128) },
+        BrilligOpcode::Const { destination: end, value: Value::from(b.len() as u128) },
+        BrilligOpcode::Const { destination: one, value: Value::from(1_u128) },
+        // 4: Write the next digit until every digit has been written.
+        BrilligOpcode::BinaryIntOp {
+            destination: condition,
+            op: BinaryIntOp::LessThan,
+            bit_size: 64,
+            lhs: pointer,
+            rhs: end,
+        },
+        BrilligOpcode::JumpIfNot { condition, location: 11 },
+        BrilligOpcode::BinaryIntOp {
+            destination: digit,
+            op: BinaryIntOp::UnsignedRem,
+            bit_size: FIELD_BIT_SIZE,
+            lhs: value,
+            rhs: radix_register,
+        },
+        BrilligOpcode::BinaryIntOp {
+            destination: value,
+            op: BinaryIntOp::UnsignedDiv,
+            bit_size: FIELD_BIT_SIZE,
+            lhs: value,
+            rhs: radix_register,
+        },
+        BrilligOpcode::Store { destination_pointer: pointer, source: digit },
+        BrilligOpcode::BinaryIntOp {
+            destination: pointer,
+            op: BinaryIntOp::Add,
+            bit_size: 64,
+            lhs: pointer,
+            rhs: one,
+        },
+        BrilligOpcode::Jump { location: 4 },
+        // 11: Check that nothing remains of `a` once all digits have been written.
+        BrilligOpcode::Const { destination: digit, value: Value::from(0_u128) },
+        BrilligOpcode::BinaryIntOp {
+            destination: condition,
+            op: BinaryIntOp::Equals,
+            bit_size: FIELD_BIT_SIZE,
+            lhs: value,
+            rhs: digit,
+        },
+        BrilligOpcode::JumpIfNot { condition, location: 16 },
+        BrilligOpcode::Mov { destination: value, source: digit },
+        BrilligOpcode::Stop,
+        // 16: `a` did not fit in the digits.
+        BrilligOpcode::Trap,
+    ];
+
+    Brillig {
+        inputs: vec![BrilligInputs::Single(a)],
+        outputs: vec![BrilligOutputs::Array(b)],
+        foreign_call_results: Vec::new(),
+        bytecode,
+        predicate: None,
+    }
+}
+
+/// Integer operations on memory addresses and indices.
+const INDEX_BIT_SIZE: u32 = 64;
+
+/// Accumulates Brillig bytecode whose jumps target labels, which are resolved to locations once all of the
+/// bytecode has been emitted.
+#[derive(Default)]
+struct BytecodeBuilder {
+    bytecode: Vec<BrilligOpcode>,
+    /// The location of each label, once it has been placed.
+    labels: Vec<Option<usize>>,
+}
+
+impl BytecodeBuilder {
+    fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+        self.labels.len() - 1
+    }
+
+    /// Places `label` at the location of the next opcode.
+    fn place(&mut self, label: Label) {
+        self.labels[label] = Some(self.bytecode.len());
+    }
+
+    fn push(&mut self, opcode: BrilligOpcode) {
+        self.bytecode.push(opcode);
+    }
+
+    fn constant(&mut self, destination: RegisterIndex, value: usize) {
+        self.push(BrilligOpcode::Const { destination, value: Value::from(value as u128) });
+    }
+
+    fn index_op(
+        &mut self,
+        op: BinaryIntOp,
+        destination: RegisterIndex,
+        lhs: RegisterIndex,
+        rhs: RegisterIndex,
+    ) {
+        self.push(BrilligOpcode::BinaryIntOp {
+            destination,
+            op,
+            bit_size: INDEX_BIT_SIZE,
+            lhs,
+            rhs,
+        });
+    }
+
+    fn jump(&mut self, location: Label) {
+        self.push(BrilligOpcode::Jump { location });
+    }
+
+    fn jump_if(&mut self, condition: RegisterIndex, location: Label) {
+        self.push(BrilligOpcode::JumpIf { condition, location });
+    }
+
+    fn jump_if_not(&mut self, condition: RegisterIndex, location: Label) {
+        self.push(BrilligOpcode::JumpIfNot { condition, location });
+    }
+
+    /// Loads `pointer[index]` into `destination`, using `address` as scratch space.
+    fn load(
+        &mut self,
+        destination: RegisterIndex,
+        pointer: RegisterIndex,
+        index: RegisterIndex,
+        address: RegisterIndex,
+    ) {
+        self.index_op(BinaryIntOp::Add, address, pointer, index);
+        self.push(BrilligOpcode::Load { destination, source_pointer: address });
+    }
+
+    /// Stores `source` into `pointer[index]`, using `address` as scratch space.
+    fn store(
+        &mut self,
+        pointer: RegisterIndex,
+        index: RegisterIndex,
+        source: RegisterIndex,
+        address: RegisterIndex,
+    ) {
+        self.index_op(BinaryIntOp::Add, address, pointer, index);
+        self.push(BrilligOpcode::Store { destination_pointer: address, source });
+    }
+
+    /// Returns the bytecode with each label replaced by its location.
+    fn finish(self) -> Vec<BrilligOpcode> {
+        let locate = |label: Label| self.labels[label].expect("label should have been placed");
+        self.bytecode
+            .iter()
+            .cloned()
+            .map(|opcode| match opcode {
+                BrilligOpcode::Jump { location } => {
+                    BrilligOpcode::Jump { location: locate(location) }
+                }
+                BrilligOpcode::JumpIf { condition, location } => {
+                    BrilligOpcode::JumpIf { condition, location: locate(location) }
+                }
+                BrilligOpcode::JumpIfNot { condition, location } => {
+                    BrilligOpcode::JumpIfNot { condition, location: locate(location) }
+                }
+                BrilligOpcode::FrameCall { location, arguments, results } => {
+                    BrilligOpcode::FrameCall { location: locate(location), arguments, results }
+                }
+                opcode => opcode,
+            })
+            .collect()
+    }
+}
+
+/// Returns the number of control bits of a sorting network for `n` inputs, as computed by [`route_function`].
+fn num_control_bits(n: usize) -> usize {
+    match n {
+        0 | 1 => 0,
+        2 => 1,
+        _ => n / 2 + (n - 1) / 2 + num_control_bits(n / 2) + num_control_bits(n - n / 2),
+    }
+}
+
+/// Returns a [`Brillig`] opcode which computes the control bits of the sorting network which sorts `inputs`.
+///
+/// As with [`Directive::PermutationSort`], the tuples are stably sorted by the values at the `sort_by` indices
+/// in turn, compared as integers, and the network is routed so as to move each tuple to its sorted position.
+fn permutation_sort_brillig(
+    inputs: Vec<Vec<Expression>>,
+    tuple: u32,
+    bits: Vec<Witness>,
+    sort_by: &[u32],
+) -> Brillig {
+    let n = inputs.len();
+    let tuple = tuple as usize;
+
+    // Register 0 holds the pointer to the tuples on entry and the pointer to the control bits on exit.
+    let elements = RegisterIndex::from(0);
+    let [order, base, x_positions, y_positions, control_bits, size, i, j, condition, one, lhs, rhs, address, lhs_value, rhs_value, offset, tuple_size, result_end] =
+        std::array::from_fn(|index| RegisterIndex::from(index + 1));
+
+    let mut builder = BytecodeBuilder::default();
+    let route = builder.new_label();
+
+    builder.constant(size, n);
+    builder.constant(one, 1);
+    builder.constant(tuple_size, tuple);
+    builder.push(BrilligOpcode::Alloc { destination: order, size });
+    builder.push(BrilligOpcode::Alloc { destination: base, size });
+    builder.push(BrilligOpcode::Alloc { destination: x_positions, size });
+    builder.push(BrilligOpcode::Alloc { destination: y_positions, size });
+    builder.constant(offset, num_control_bits(n));
+    builder.push(BrilligOpcode::Alloc { destination: control_bits, size: offset });
+
+    // Both the network's inputs and the order in which the tuples are sorted start out as the indices of the tuples.
+    let (init_loop, init_done) = (builder.new_label(), builder.new_label());
+    builder.constant(i, 0);
+    builder.place(init_loop);
+    builder.index_op(BinaryIntOp::LessThan, condition, i, size);
+    builder.jump_if_not(condition, init_done);
+    builder.store(order, i, i, address);
+    builder.store(base, i, i, address);
+    builder.index_op(BinaryIntOp::Add, i, i, one);
+    builder.jump(init_loop);
+    builder.place(init_done);
+
+    // Insertion sort, which is stable.
+    let (outer_loop, inner_loop, next_element, sorted, less, not_less) = (
+        builder.new_label(),
+        builder.new_label(),
+        builder.new_label(),
+        builder.new_label(),
+        builder.new_label(),
+        builder.new_label(),
+    );
+    builder.constant(i, 1);
+    builder.place(outer_loop);
+    builder.index_op(BinaryIntOp::LessThan, condition, i, size);
+    builder.jump_if_not(condition, sorted);
+    builder.push(BrilligOpcode::Mov { destination: j, source: i });
+    builder.place(inner_loop);
+    // Move the tuple at `j` towards the front while it is less than its predecessor.
+    builder.constant(offset, 0);
+    builder.index_op(BinaryIntOp::Equals, condition, j, offset);
+    builder.jump_if(condition, next_element);
+    builder.load(lhs, order, j, address);
+    builder.index_op(BinaryIntOp::Sub, offset, j, one);
+    builder.load(rhs, order, offset, address);
+    for &key in sort_by {
+        let key = key as usize;
+        for (index, value) in [(lhs, lhs_value), (rhs, rhs_value)] {
+            if key == tuple {
+                // The index following the tuple's values is its position, as with the directive.
+                builder.push(BrilligOpcode::Mov { destination: value, source: index });
+            } else {
+                builder.index_op(BinaryIntOp::Mul, offset, index, tuple_size);
+                builder.constant(address, key);
+                builder.index_op(BinaryIntOp::Add, offset, offset, address);
+                builder.load(value, elements, offset, address);
+            }
+        }
+        for (lhs_value, rhs_value, ordered) in
+            [(lhs_value, rhs_value, less), (rhs_value, lhs_value, not_less)]
+        {
+            builder.push(BrilligOpcode::BinaryIntOp {
+                destination: condition,
+                op: BinaryIntOp::LessThan,
+                bit_size: FIELD_BIT_SIZE,
+                lhs: lhs_value,
+                rhs: rhs_value,
+            });
+            builder.jump_if(condition, ordered);
+        }
+    }
+    builder.place(not_less);
+    builder.jump(next_element);
+    builder.place(less);
+    builder.store(order, j, rhs, address);
+    builder.index_op(BinaryIntOp::Sub, j, j, one);
+    builder.store(order, j, lhs, address);
+    builder.jump(inner_loop);
+    builder.place(next_element);
+    builder.index_op(BinaryIntOp::Add, i, i, one);
+    builder.jump(outer_loop);
+    builder.place(sorted);
+
+    builder.push(BrilligOpcode::FrameCall {
+        location: route,
+        arguments: vec![base, order, size, control_bits, x_positions, y_positions],
+        results: vec![result_end],
+    });
+    builder.push(BrilligOpcode::Mov { destination: elements, source: control_bits });
+    builder.push(BrilligOpcode::Stop);
+
+    builder.place(route);
+    route_function(&mut builder, route);
+
+    Brillig {
+        inputs: vec![BrilligInputs::Array(inputs.into_iter().flatten().collect())],
+        outputs: vec![BrilligOutputs::Array(bits)],
+        foreign_call_results: Vec::new(),
+        bytecode: builder.finish(),
+        predicate: None,
+    }
+}
+
+/// The registers of a call to the function emitted by [`route_function`].
+struct RouteRegisters {
+    inputs: RegisterIndex,
+    outputs: RegisterIndex,
+    n: RegisterIndex,
+    control_bits: RegisterIndex,
+    half: RegisterIndex,
+    inner_x: RegisterIndex,
+    inner_y: RegisterIndex,
+    single_y: RegisterIndex,
+    n_is_odd: RegisterIndex,
+    one: RegisterIndex,
+    two: RegisterIndex,
+    condition: RegisterIndex,
+    value: RegisterIndex,
+    inner: RegisterIndex,
+    temp: RegisterIndex,
+    address: RegisterIndex,
+}
+
+/// Emits a function which computes the control bits of a Waksman network, mirroring the directive's solver.
+///
+/// The function is called with a frame holding pointers to the network's `n` inputs and outputs, `n`, a pointer
+/// at which the control bits are written and pointers to two tables of `N` slots, where `N` is the size of the
+/// outermost network, which map each value to its position in the inputs and outputs. The inputs and outputs
+/// are values less than `N`. It returns a pointer to the slot after the last control bit which it wrote.
+///
+/// The outer layer of switches is routed by following loops through the network, after which the function
+/// recurses into the two sub-networks.
+fn route_function(builder: &mut BytecodeBuilder, route: Label) {
+    let [inputs, outputs, n, control_bits, x_positions, y_positions] =
+        std::array::from_fn(RegisterIndex::from);
+    let [half, free_len, inner_x, inner_y, free, free_count, cursor, out_idx, start_sub, switch, start, x, single_y, n_is_odd, one, two, zero, condition, value, inner, temp, address, result_end] =
+        std::array::from_fn(|index| RegisterIndex::from(index + 6));
+    let registers = RouteRegisters {
+        inputs,
+        outputs,
+        n,
+        control_bits,
+        half,
+        inner_x,
+        inner_y,
+        single_y,
+        n_is_odd,
+        one,
+        two,
+        condition,
+        value,
+        inner,
+        temp,
+        address,
+    };
+
+    builder.constant(zero, 0);
+    builder.constant(one, 1);
+    builder.constant(two, 2);
+
+    // Networks of fewer than two inputs have no switches.
+    let (not_trivial, general) = (builder.new_label(), builder.new_label());
+    builder.index_op(BinaryIntOp::LessThan, condition, n, two);
+    builder.jump_if_not(condition, not_trivial);
+    builder.push(BrilligOpcode::Mov { destination: RegisterIndex::from(0), source: control_bits });
+    builder.push(BrilligOpcode::Return);
+
+    // A network of two inputs is a single switch, which is set if the inputs are swapped.
+    builder.place(not_trivial);
+    builder.index_op(BinaryIntOp::Equals, condition, n, two);
+    builder.jump_if_not(condition, general);
+    builder.load(value, inputs, zero, address);
+    builder.load(temp, outputs, zero, address);
+    builder.index_op(BinaryIntOp::Equals, condition, value, temp);
+    builder.index_op(BinaryIntOp::Xor, condition, condition, one);
+    builder.store(control_bits, zero, condition, address);
+    builder.index_op(BinaryIntOp::Add, RegisterIndex::from(0), control_bits, one);
+    builder.push(BrilligOpcode::Return);
+
+    builder.place(general);
+    builder.index_op(BinaryIntOp::UnsignedDiv, half, n, two);
+    builder.index_op(BinaryIntOp::Sub, temp, n, one);
+    builder.index_op(BinaryIntOp::UnsignedDiv, free_len, temp, two);
+    builder.index_op(BinaryIntOp::UnsignedRem, n_is_odd, n, two);
+    // Outputs from `single_y` onwards are not connected to a switch.
+    builder.index_op(BinaryIntOp::Sub, single_y, n, two);
+    builder.index_op(BinaryIntOp::Add, single_y, single_y, n_is_odd);
+    builder.push(BrilligOpcode::Alloc { destination: inner_x, size: n });
+    builder.push(BrilligOpcode::Alloc { destination: inner_y, size: n });
+    builder.push(BrilligOpcode::Alloc { destination: free, size: free_len });
+
+    let loop_label = builder.new_label();
+    let done = builder.new_label();
+    emit_loop(builder, 0, n, &registers, |builder, i| {
+        builder.store(inner_x, i, zero, address);
+        builder.store(inner_y, i, zero, address);
+        builder.load(value, inputs, i, address);
+        builder.store(x_positions, value, i, address);
+        builder.load(value, outputs, i, address);
+        builder.store(y_positions, value, i, address);
+    });
+    // The input switches are followed by the output switches, all of which start out unset.
+    emit_loop(builder, 0, half, &registers, |builder, i| {
+        builder.store(control_bits, i, zero, address);
+    });
+    builder.index_op(BinaryIntOp::Add, temp, control_bits, half);
+    emit_loop(builder, 0, free_len, &registers, |builder, i| {
+        builder.store(temp, i, zero, address);
+        builder.store(free, i, one, address);
+    });
+    builder.push(BrilligOpcode::Mov { destination: free_count, source: free_len });
+    builder.constant(cursor, 0);
+
+    // Route the wires which are not connected to a switch.
+    builder.index_op(BinaryIntOp::Sub, temp, n, one);
+    builder.load(value, outputs, temp, address);
+    builder.store(inner_y, temp, value, address);
+    let (odd, single_wires_routed) = (builder.new_label(), builder.new_label());
+    builder.jump_if(n_is_odd, odd);
+    builder.index_op(BinaryIntOp::Sub, temp, n, two);
+    builder.load(value, outputs, temp, address);
+    builder.index_op(BinaryIntOp::Sub, temp, half, one);
+    builder.store(inner_y, temp, value, address);
+    builder.jump(single_wires_routed);
+    builder.place(odd);
+    builder.load(value, inputs, temp, address);
+    builder.store(inner_x, temp, value, address);
+    builder.place(single_wires_routed);
+
+    // Start from the last output, which is connected to the lower sub-network. `n` stands for no switch.
+    builder.index_op(BinaryIntOp::Sub, out_idx, n, one);
+    builder.constant(start_sub, 1);
+    builder.push(BrilligOpcode::Mov { destination: switch, source: n });
+    builder.push(BrilligOpcode::Mov { destination: start, source: n });
+
+    builder.place(loop_label);
+    builder.index_op(BinaryIntOp::Equals, condition, free_count, zero);
+    builder.jump_if(condition, done);
+
+    // The processed switch is no longer available.
+    let route_wire = builder.new_label();
+    builder.index_op(BinaryIntOp::LessThan, condition, switch, free_len);
+    builder.jump_if_not(condition, route_wire);
+    builder.load(condition, free, switch, address);
+    builder.jump_if_not(condition, route_wire);
+    builder.store(free, switch, zero, address);
+    builder.index_op(BinaryIntOp::Sub, free_count, free_count, one);
+    builder.place(route_wire);
+
+    // Connect the output wire to its matching input.
+    emit_configure_y_unless_single(builder, out_idx, start_sub, &registers);
+    builder.load(value, outputs, out_idx, address);
+    builder.load(x, x_positions, value, address);
+    let (x_is_single, x_routed) = (builder.new_label(), builder.new_label());
+    emit_is_single_x(builder, x, &registers);
+    builder.jump_if(condition, x_is_single);
+    emit_configure_x(builder, x, start_sub, &registers);
+    builder.jump(x_routed);
+
+    // The loop did not complete, so restart it from a free switch using the other sub-network.
+    builder.place(x_is_single);
+    builder.index_op(BinaryIntOp::Xor, start_sub, start_sub, one);
+    emit_new_start(builder, free, free_len, cursor, start, out_idx, &registers);
+    builder.push(BrilligOpcode::Mov { destination: switch, source: start });
+    builder.jump(loop_label);
+
+    // Connect the sibling input to its matching output, using the other sub-network.
+    builder.place(x_routed);
+    emit_sibling(builder, x, &registers);
+    builder.index_op(BinaryIntOp::Xor, start_sub, start_sub, one);
+    emit_configure_x(builder, x, start_sub, &registers);
+    builder.load(value, inputs, x, address);
+    builder.load(out_idx, y_positions, value, address);
+    emit_configure_y_unless_single(builder, out_idx, start_sub, &registers);
+    builder.index_op(BinaryIntOp::Xor, start_sub, start_sub, one);
+    builder.index_op(BinaryIntOp::UnsignedDiv, switch, out_idx, two);
+
+    // The loop is complete once it returns to the switch it started from or reaches an unconnected output.
+    let restart = builder.new_label();
+    builder.index_op(BinaryIntOp::Equals, condition, start, switch);
+    builder.jump_if(condition, restart);
+    builder.index_op(BinaryIntOp::LessThan, condition, out_idx, single_y);
+    builder.jump_if_not(condition, restart);
+    emit_sibling(builder, out_idx, &registers);
+    builder.jump(loop_label);
+    builder.place(restart);
+    emit_new_start(builder, free, free_len, cursor, start, out_idx, &registers);
+    builder.push(BrilligOpcode::Mov { destination: switch, source: start });
+    builder.jump(loop_label);
+
+    // Route the sub-networks, whose control bits follow those of the outer layer.
+    builder.place(done);
+    builder.index_op(BinaryIntOp::Add, result_end, control_bits, half);
+    builder.index_op(BinaryIntOp::Add, result_end, result_end, free_len);
+    builder.push(BrilligOpcode::FrameCall {
+        location: route,
+        arguments: vec![inner_x, inner_y, half, result_end, x_positions, y_positions],
+        results: vec![result_end],
+    });
+    builder.index_op(BinaryIntOp::Add, x, inner_x, half);
+    builder.index_op(BinaryIntOp::Add, value, inner_y, half);
+    builder.index_op(BinaryIntOp::Sub, temp, n, half);
+    builder.push(BrilligOpcode::FrameCall {
+        location: route,
+        arguments: vec![x, value, temp, result_end, x_positions, y_positions],
+        results: vec![result_end],
+    });
+    builder.push(BrilligOpcode::Free { pointer: inner_x });
+    builder.push(BrilligOpcode::Free { pointer: inner_y });
+    builder.push(BrilligOpcode::Free { pointer: free });
+    builder.push(BrilligOpcode::Mov { destination: RegisterIndex::from(0), source: result_end });
+    builder.push(BrilligOpcode::Return);
+}
+
+/// Emits a loop which runs `body` for each index from `start` up to the value of the register `end`.
+fn emit_loop(
+    builder: &mut BytecodeBuilder,
+    start: usize,
+    end: RegisterIndex,
+    registers: &RouteRegisters,
+    body: impl FnOnce(&mut BytecodeBuilder, RegisterIndex),
+) {
+    let (loop_label, done) = (builder.new_label(), builder.new_label());
+    let index = registers.inner;
+    builder.constant(index, start);
+    builder.place(loop_label);
+    builder.index_op(BinaryIntOp::LessThan, registers.condition, index, end);
+    builder.jump_if_not(registers.condition, done);
+    body(builder, index);
+    builder.index_op(BinaryIntOp::Add, index, index, registers.one);
+    builder.jump(loop_label);
+    builder.place(done);
+}
+
+/// Emits code which replaces `index` with the index of the other wire connected to the same switch.
+fn emit_sibling(builder: &mut BytecodeBuilder, index: RegisterIndex, registers: &RouteRegisters) {
+    // sibling = index + 1 - 2 * (index % 2)
+    let RouteRegisters { one, two, temp, .. } = *registers;
+    builder.index_op(BinaryIntOp::UnsignedRem, temp, index, two);
+    builder.index_op(BinaryIntOp::Mul, temp, temp, two);
+    builder.index_op(BinaryIntOp::Add, index, index, one);
+    builder.index_op(BinaryIntOp::Sub, index, index, temp);
+}
+
+/// Emits code which sets the `condition` register if the input `x` is not connected to a switch.
+fn emit_is_single_x(builder: &mut BytecodeBuilder, x: RegisterIndex, registers: &RouteRegisters) {
+    let RouteRegisters { n, one, n_is_odd, condition, temp, .. } = *registers;
+    builder.index_op(BinaryIntOp::Sub, temp, n, one);
+    builder.index_op(BinaryIntOp::Equals, condition, x, temp);
+    builder.index_op(BinaryIntOp::And, condition, condition, n_is_odd);
+}
+
+/// Emits code which computes, into the `inner` register, the position in the sub-networks to which the wire at
+/// `index` is connected when its switch is set to `switch`.
+fn emit_compute_inner(
+    builder: &mut BytecodeBuilder,
+    index: RegisterIndex,
+    switch: RegisterIndex,
+    registers: &RouteRegisters,
+) {
+    // inner = index / 2 + ((switch ^ (index % 2)) * half)
+    let RouteRegisters { half, two, inner, temp, .. } = *registers;
+    builder.index_op(BinaryIntOp::UnsignedRem, temp, index, two);
+    builder.index_op(BinaryIntOp::Xor, temp, temp, switch);
+    builder.index_op(BinaryIntOp::Mul, temp, temp, half);
+    builder.index_op(BinaryIntOp::UnsignedDiv, inner, index, two);
+    builder.index_op(BinaryIntOp::Add, inner, inner, temp);
+}
+
+/// Emits code which connects the input `x` to the sub-network `sub` through its switch.
+fn emit_configure_x(
+    builder: &mut BytecodeBuilder,
+    x: RegisterIndex,
+    sub: RegisterIndex,
+    registers: &RouteRegisters,
+) {
+    let RouteRegisters { inputs, control_bits, inner_x, two, value, inner, temp, address, .. } =
+        *registers;
+    // The switch is set if the wire crosses over to the sub-network which its position does not lead to.
+    builder.index_op(BinaryIntOp::UnsignedRem, temp, x, two);
+    builder.index_op(BinaryIntOp::Xor, value, temp, sub);
+    emit_compute_inner(builder, x, value, registers);
+    builder.index_op(BinaryIntOp::UnsignedDiv, temp, x, two);
+    builder.store(control_bits, temp, value, address);
+    builder.load(value, inputs, x, address);
+    builder.store(inner_x, inner, value, address);
+}
+
+/// Emits code which connects the output `y` to the sub-network `sub` through its switch, unless `y` is not
+/// connected to a switch.
+fn emit_configure_y_unless_single(
+    builder: &mut BytecodeBuilder,
+    y: RegisterIndex,
+    sub: RegisterIndex,
+    registers: &RouteRegisters,
+) {
+    let RouteRegisters {
+        outputs,
+        control_bits,
+        half,
+        inner_y,
+        single_y,
+        two,
+        condition,
+        value,
+        inner,
+        temp,
+        address,
+        ..
+    } = *registers;
+    let single = builder.new_label();
+    builder.index_op(BinaryIntOp::LessThan, condition, y, single_y);
+    builder.jump_if_not(condition, single);
+    builder.index_op(BinaryIntOp::UnsignedRem, temp, y, two);
+    builder.index_op(BinaryIntOp::Xor, value, temp, sub);
+    emit_compute_inner(builder, y, value, registers);
+    // The output switches follow the `half` input switches.
+    builder.index_op(BinaryIntOp::UnsignedDiv, temp, y, two);
+    builder.index_op(BinaryIntOp::Add, temp, temp, half);
+    builder.store(control_bits, temp, value, address);
+    builder.load(value, outputs, y, address);
+    builder.store(inner_y, inner, value, address);
+    builder.place(single);
+}
+
+/// Emits code which finds the first free switch, setting `start` to it and `out_idx` to its first output,
+/// or `start` to `n` and `out_idx` to zero if every switch has been routed.
+fn emit_new_start(
+    builder: &mut BytecodeBuilder,
+    free: RegisterIndex,
+    free_len: RegisterIndex,
+    cursor: RegisterIndex,
+    start: RegisterIndex,
+    out_idx: RegisterIndex,
+    registers: &RouteRegisters,
+) {
+    let RouteRegisters { n, one, two, condition, address, .. } = *registers;
+    let (search, found, none, done) =
+        (builder.new_label(), builder.new_label(), builder.new_label(), builder.new_label());
+    // Switches are never freed again, so the first free switch does not precede the previous one.
+    builder.place(search);
+    builder.index_op(BinaryIntOp::LessThan, condition, cursor, free_len);
+    builder.jump_if_not(condition, none);
+    builder.load(condition, free, cursor, address);
+    builder.jump_if(condition, found);
+    builder.index_op(BinaryIntOp::Add, cursor, cursor, one);
+    builder.jump(search);
+    builder.place(found);
+    builder.push(BrilligOpcode::Mov { destination: start, source: cursor });
+    builder.index_op(BinaryIntOp::Mul, out_idx, cursor, two);
+    builder.jump(done);
+    builder.place(none);
+    builder.push(BrilligOpcode::Mov { destination: start, source: n });
+    builder.constant(out_idx, 0);
+    builder.place(done);
+}
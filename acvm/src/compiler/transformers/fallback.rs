@@ -119,6 +119,20 @@ impl FallbackTransformer {
                 )
             }
             #[cfg(feature = "unstable-fallbacks")]
+            BlackBoxFuncCall::SHA512 { inputs, outputs } => {
+                let mut sha512_inputs = Vec::new();
+                for input in inputs.iter() {
+                    let witness_index = Expression::from(input.witness);
+                    let num_bits = input.num_bits;
+                    sha512_inputs.push((witness_index, num_bits));
+                }
+                stdlib::blackbox_fallbacks::sha512(
+                    sha512_inputs,
+                    outputs.to_vec(),
+                    current_witness_idx,
+                )
+            }
+            #[cfg(feature = "unstable-fallbacks")]
             BlackBoxFuncCall::Blake2s { inputs, outputs } => {
                 let mut blake2s_input = Vec::new();
                 for input in inputs.iter() {
@@ -133,6 +147,20 @@ impl FallbackTransformer {
                 )
             }
             #[cfg(feature = "unstable-fallbacks")]
+            BlackBoxFuncCall::Blake3 { inputs, outputs } => {
+                let mut blake3_input = Vec::new();
+                for input in inputs.iter() {
+                    let witness_index = Expression::from(input.witness);
+                    let num_bits = input.num_bits;
+                    blake3_input.push((witness_index, num_bits));
+                }
+                stdlib::blackbox_fallbacks::blake3(
+                    blake3_input,
+                    outputs.to_vec(),
+                    current_witness_idx,
+                )
+            }
+            #[cfg(feature = "unstable-fallbacks")]
             BlackBoxFuncCall::HashToField128Security { inputs, output } => {
                 let mut blake2s_input = Vec::new();
                 for input in inputs.iter() {
@@ -160,6 +188,48 @@ impl FallbackTransformer {
                     current_witness_idx,
                 )
             }
+            #[cfg(feature = "unstable-fallbacks")]
+            BlackBoxFuncCall::EmbeddedCurveAdd {
+                input1_x,
+                input1_y,
+                input2_x,
+                input2_y,
+                outputs,
+            } => stdlib::blackbox_fallbacks::embedded_curve_add(
+                (input1_x.witness, input1_y.witness),
+                (input2_x.witness, input2_y.witness),
+                *outputs,
+                current_witness_idx,
+            ),
+            #[cfg(feature = "unstable-fallbacks")]
+            BlackBoxFuncCall::VariableBaseScalarMul {
+                point_x,
+                point_y,
+                scalar_low,
+                scalar_high,
+                outputs,
+            } => stdlib::blackbox_fallbacks::variable_base_scalar_mul(
+                (point_x.witness, point_y.witness),
+                scalar_low.witness,
+                scalar_high.witness,
+                *outputs,
+                current_witness_idx,
+            ),
+            #[cfg(feature = "unstable-fallbacks")]
+            BlackBoxFuncCall::EcdsaSecp256k1Recover {
+                signature,
+                recovery_id,
+                hashed_message,
+                public_key_x,
+                public_key_y,
+            } => stdlib::blackbox_fallbacks::ecdsa_secp256k1_recover(
+                signature.iter().map(|input| input.witness).collect(),
+                recovery_id.witness,
+                hashed_message.iter().map(|input| input.witness).collect(),
+                public_key_x.clone(),
+                public_key_y.clone(),
+                current_witness_idx,
+            ),
             _ => {
                 return Err(CompileError::UnsupportedBlackBox(gc.get_black_box_func()));
             }
@@ -1,7 +1,11 @@
 mod csat;
+mod directives;
 mod fallback;
 mod r1cs;
+mod range_lookup;
 
 pub(crate) use csat::CSatTransformer;
+pub(crate) use directives::DirectiveTransformer;
 pub(crate) use fallback::FallbackTransformer;
 pub(crate) use r1cs::R1CSTransformer;
+pub(crate) use range_lookup::RangeLookupTransformer;
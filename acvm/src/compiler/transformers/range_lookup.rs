@@ -0,0 +1,169 @@
+use acir::{
+    circuit::{
+        directives::Directive,
+        opcodes::{BlackBoxFuncCall, FunctionInput},
+        Circuit, Opcode,
+    },
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+/// Lowers range constraints which are wider than the lookup tables supported by a backend
+/// into a decomposition into limbs, each of which can be range constrained by a single table lookup.
+///
+/// A range constraint on `x` to `num_bits` bits is replaced by:
+/// - a [`Directive::ToLeRadix`] which computes the limbs of `x` in base `2^lookup_bits`.
+/// - range constraints on each limb, the last of which only covers the remaining `num_bits % lookup_bits` bits.
+/// - an arithmetic opcode constraining the limbs to sum to `x`.
+pub(crate) struct RangeLookupTransformer {
+    lookup_bits: u32,
+}
+
+impl RangeLookupTransformer {
+    /// The largest lookup table size supported, as limbs are computed using a radix which must fit in a `u32`.
+    pub(crate) const MAX_LOOKUP_BITS: u32 = 31;
+
+    pub(crate) fn new(lookup_bits: u32) -> Self {
+        assert!(
+            (1..=Self::MAX_LOOKUP_BITS).contains(&lookup_bits),
+            "lookup tables must be between 1 and {} bits",
+            Self::MAX_LOOKUP_BITS
+        );
+        Self { lookup_bits }
+    }
+
+    pub(crate) fn transform(
+        &self,
+        acir: Circuit,
+        opcode_positions: Vec<usize>,
+    ) -> (Circuit, Vec<usize>) {
+        let mut new_opcodes = Vec::with_capacity(acir.opcodes.len());
+        let mut new_opcode_positions = Vec::with_capacity(opcode_positions.len());
+        let mut next_witness_index = acir.current_witness_index + 1;
+
+        for (idx, opcode) in acir.opcodes.into_iter().enumerate() {
+            match opcode {
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input })
+                    if input.num_bits > self.lookup_bits =>
+                {
+                    let lowered_opcodes = self.lower_range(input, &mut next_witness_index);
+                    new_opcode_positions.extend(vec![opcode_positions[idx]; lowered_opcodes.len()]);
+                    new_opcodes.extend(lowered_opcodes);
+                }
+                opcode => {
+                    new_opcode_positions.push(opcode_positions[idx]);
+                    new_opcodes.push(opcode);
+                }
+            }
+        }
+
+        (
+            Circuit { current_witness_index: next_witness_index - 1, opcodes: new_opcodes, ..acir },
+            new_opcode_positions,
+        )
+    }
+
+    fn lower_range(&self, input: FunctionInput, next_witness_index: &mut u32) -> Vec<Opcode> {
+        let num_limbs = (input.num_bits + self.lookup_bits - 1) / self.lookup_bits;
+        let limbs: Vec<Witness> = (0..num_limbs)
+            .map(|_| {
+                let limb = Witness(*next_witness_index);
+                *next_witness_index += 1;
+                limb
+            })
+            .collect();
+
+        let mut opcodes = vec![Opcode::Directive(Directive::ToLeRadix {
+            a: input.witness.into(),
+            b: limbs.clone(),
+            radix: 1 << self.lookup_bits,
+        })];
+
+        let limb_weight = FieldElement::from(1_u128 << self.lookup_bits);
+        let mut weight = FieldElement::one();
+        let mut recomposition = Expression::from(input.witness);
+        let mut remaining_bits = input.num_bits;
+        for limb in limbs {
+            let num_bits = remaining_bits.min(self.lookup_bits);
+            remaining_bits -= num_bits;
+            opcodes.push(Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput { witness: limb, num_bits },
+            }));
+
+            recomposition.push_addition_term(-weight, limb);
+            weight = weight * limb_weight;
+        }
+        recomposition.sort();
+        opcodes.push(Opcode::Arithmetic(recomposition));
+
+        opcodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir::{
+        circuit::{
+            directives::Directive,
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    use super::RangeLookupTransformer;
+
+    fn range_opcode(witness: Witness, num_bits: u32) -> Opcode {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness, num_bits },
+        })
+    }
+
+    #[test]
+    fn lowers_wide_ranges_into_limbs() {
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![range_opcode(Witness(1), 20), range_opcode(Witness(2), 8)],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs::default(),
+            assert_messages: Default::default(),
+        };
+
+        let (circuit, opcode_positions) =
+            RangeLookupTransformer::new(8).transform(circuit, vec![0, 1]);
+
+        assert_eq!(circuit.current_witness_index, 5);
+        assert_eq!(opcode_positions, vec![0, 0, 0, 0, 0, 1]);
+        assert_eq!(
+            circuit.opcodes[0],
+            Opcode::Directive(Directive::ToLeRadix {
+                a: Witness(1).into(),
+                b: vec![Witness(3), Witness(4), Witness(5)],
+                radix: 256,
+            })
+        );
+        assert_eq!(
+            circuit.opcodes[1..4],
+            [range_opcode(Witness(3), 8), range_opcode(Witness(4), 8), range_opcode(Witness(5), 4)]
+        );
+        assert_eq!(
+            circuit.opcodes[4],
+            Opcode::Arithmetic(Expression {
+                mul_terms: Vec::new(),
+                linear_combinations: vec![
+                    (FieldElement::one(), Witness(1)),
+                    (-FieldElement::one(), Witness(3)),
+                    (-FieldElement::from(256_u128), Witness(4)),
+                    (-FieldElement::from(65536_u128), Witness(5)),
+                ],
+                q_c: FieldElement::zero(),
+            })
+        );
+        // Ranges which fit in a lookup table are left unchanged.
+        assert_eq!(circuit.opcodes[5], range_opcode(Witness(2), 8));
+    }
+}
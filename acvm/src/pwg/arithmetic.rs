@@ -1,34 +1,37 @@
 use acir::{
     native_types::{Expression, Witness, WitnessMap},
-    FieldElement,
+    AcirField,
 };
 
-use super::{insert_value, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError};
+use super::{
+    insert_value, ConstraintFailure, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError,
+};
 
 /// An Arithmetic solver will take a Circuit's arithmetic opcodes with witness assignments
 /// and create the other witness variables
 pub(super) struct ArithmeticSolver;
 
 #[allow(clippy::enum_variant_names)]
-pub(super) enum OpcodeStatus {
-    OpcodeSatisfied(FieldElement),
-    OpcodeSolvable(FieldElement, (FieldElement, Witness)),
+pub(super) enum OpcodeStatus<F> {
+    OpcodeSatisfied(F),
+    OpcodeSolvable(F, (F, Witness)),
     OpcodeUnsolvable,
 }
 
-pub(crate) enum MulTerm {
-    OneUnknown(FieldElement, Witness), // (qM * known_witness, unknown_witness)
+pub(crate) enum MulTerm<F> {
+    OneUnknown(F, Witness), // (qM * known_witness, unknown_witness)
     TooManyUnknowns,
-    Solved(FieldElement),
+    Solved(F),
 }
 
 impl ArithmeticSolver {
     /// Derives the rest of the witness based on the initial low level variables
-    pub(super) fn solve(
-        initial_witness: &mut WitnessMap,
-        opcode: &Expression,
-    ) -> Result<(), OpcodeResolutionError> {
-        let opcode = &ArithmeticSolver::evaluate(opcode, initial_witness);
+    pub(super) fn solve<F: AcirField>(
+        initial_witness: &mut WitnessMap<F>,
+        opcode: &Expression<F>,
+    ) -> Result<(), OpcodeResolutionError<F>> {
+        let original_opcode = opcode;
+        let opcode = &opcode.evaluate(initial_witness);
         // Evaluate multiplication term
         let mul_result = ArithmeticSolver::solve_mul_term(opcode, initial_witness);
         // Evaluate the fan-in terms
@@ -46,9 +49,7 @@ impl ArithmeticSolver {
                     let total_sum = a + opcode.q_c;
                     if (q + b).is_zero() {
                         if !total_sum.is_zero() {
-                            Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                                opcode_location: ErrorLocation::Unresolved,
-                            })
+                            Err(unsatisfied_expression(original_opcode, opcode, initial_witness))
                         } else {
                             Ok(())
                         }
@@ -76,9 +77,7 @@ impl ArithmeticSolver {
                 let total_sum = sum + opcode.q_c;
                 if partial_prod.is_zero() {
                     if !total_sum.is_zero() {
-                        Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                            opcode_location: ErrorLocation::Unresolved,
-                        })
+                        Err(unsatisfied_expression(original_opcode, opcode, initial_witness))
                     } else {
                         Ok(())
                     }
@@ -93,9 +92,7 @@ impl ArithmeticSolver {
                 // All the variables in the MulTerm are solved and the Fan-in is also solved
                 // There is nothing to solve
                 if !(a + b + opcode.q_c).is_zero() {
-                    Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                        opcode_location: ErrorLocation::Unresolved,
-                    })
+                    Err(unsatisfied_expression(original_opcode, opcode, initial_witness))
                 } else {
                     Ok(())
                 }
@@ -110,9 +107,7 @@ impl ArithmeticSolver {
                 let total_sum = total_prod + partial_sum + opcode.q_c;
                 if coeff.is_zero() {
                     if !total_sum.is_zero() {
-                        Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                            opcode_location: ErrorLocation::Unresolved,
-                        })
+                        Err(unsatisfied_expression(original_opcode, opcode, initial_witness))
                     } else {
                         Ok(())
                     }
@@ -130,11 +125,14 @@ impl ArithmeticSolver {
     /// If the witness values are not known, then the function returns a None
     /// XXX: Do we need to account for the case where 5xy + 6x = 0 ? We do not know y, but it can be solved given x . But I believe x can be solved with another opcode
     /// XXX: What about making a mul opcode = a constant 5xy + 7 = 0 ? This is the same as the above.
-    fn solve_mul_term(arith_opcode: &Expression, witness_assignments: &WitnessMap) -> MulTerm {
+    fn solve_mul_term<F: AcirField>(
+        arith_opcode: &Expression<F>,
+        witness_assignments: &WitnessMap<F>,
+    ) -> MulTerm<F> {
         // First note that the mul term can only contain one/zero term
         // We are assuming it has been optimized.
         match arith_opcode.mul_terms.len() {
-            0 => MulTerm::Solved(FieldElement::zero()),
+            0 => MulTerm::Solved(F::zero()),
             1 => ArithmeticSolver::solve_mul_term_helper(
                 &arith_opcode.mul_terms[0],
                 witness_assignments,
@@ -143,10 +141,10 @@ impl ArithmeticSolver {
         }
     }
 
-    fn solve_mul_term_helper(
-        term: &(FieldElement, Witness, Witness),
-        witness_assignments: &WitnessMap,
-    ) -> MulTerm {
+    fn solve_mul_term_helper<F: AcirField>(
+        term: &(F, Witness, Witness),
+        witness_assignments: &WitnessMap<F>,
+    ) -> MulTerm<F> {
         let (q_m, w_l, w_r) = term;
         // Check if these values are in the witness assignments
         let w_l_value = witness_assignments.get(w_l);
@@ -160,10 +158,10 @@ impl ArithmeticSolver {
         }
     }
 
-    fn solve_fan_in_term_helper(
-        term: &(FieldElement, Witness),
-        witness_assignments: &WitnessMap,
-    ) -> Option<FieldElement> {
+    fn solve_fan_in_term_helper<F: AcirField>(
+        term: &(F, Witness),
+        witness_assignments: &WitnessMap<F>,
+    ) -> Option<F> {
         let (q_l, w_l) = term;
         // Check if we have w_l
         let w_l_value = witness_assignments.get(w_l);
@@ -173,17 +171,17 @@ impl ArithmeticSolver {
     /// Returns the summation of all of the variables, plus the unknown variable
     /// Returns None, if there is more than one unknown variable
     /// We cannot assign
-    pub(super) fn solve_fan_in_term(
-        arith_opcode: &Expression,
-        witness_assignments: &WitnessMap,
-    ) -> OpcodeStatus {
+    pub(super) fn solve_fan_in_term<F: AcirField>(
+        arith_opcode: &Expression<F>,
+        witness_assignments: &WitnessMap<F>,
+    ) -> OpcodeStatus<F> {
         // This is assuming that the fan-in is more than 0
 
         // This is the variable that we want to assign the value to
-        let mut unknown_variable = (FieldElement::zero(), Witness::default());
+        let mut unknown_variable = (F::zero(), Witness::default());
         let mut num_unknowns = 0;
         // This is the sum of all of the known variables
-        let mut result = FieldElement::zero();
+        let mut result = F::zero();
 
         for term in arith_opcode.linear_combinations.iter() {
             let value = ArithmeticSolver::solve_fan_in_term_helper(term, witness_assignments);
@@ -207,38 +205,42 @@ impl ArithmeticSolver {
 
         OpcodeStatus::OpcodeSolvable(result, unknown_variable)
     }
+}
 
-    // Partially evaluate the opcode using the known witnesses
-    pub(super) fn evaluate(expr: &Expression, initial_witness: &WitnessMap) -> Expression {
-        let mut result = Expression::default();
-        for &(c, w1, w2) in &expr.mul_terms {
-            let mul_result = ArithmeticSolver::solve_mul_term_helper(&(c, w1, w2), initial_witness);
-            match mul_result {
-                MulTerm::OneUnknown(v, w) => {
-                    if !v.is_zero() {
-                        result.linear_combinations.push((v, w));
-                    }
-                }
-                MulTerm::TooManyUnknowns => {
-                    if !c.is_zero() {
-                        result.mul_terms.push((c, w1, w2));
-                    }
-                }
-                MulTerm::Solved(f) => result.q_c += f,
-            }
-        }
-        for &(c, w) in &expr.linear_combinations {
-            if let Some(f) = ArithmeticSolver::solve_fan_in_term_helper(&(c, w), initial_witness) {
-                result.q_c += f;
-            } else if !c.is_zero() {
-                result.linear_combinations.push((c, w));
-            }
+/// Builds the error for an arithmetic opcode which the current witness assignments do not satisfy.
+///
+/// The error holds the partially evaluated expression along with the values of the witnesses
+/// which appear in the original expression.
+fn unsatisfied_expression<F: AcirField>(
+    original: &Expression<F>,
+    evaluated: &Expression<F>,
+    initial_witness: &WitnessMap<F>,
+) -> OpcodeResolutionError<F> {
+    let witnesses = original
+        .mul_terms
+        .iter()
+        .flat_map(|(_, w_l, w_r)| [*w_l, *w_r])
+        .chain(original.linear_combinations.iter().map(|(_, witness)| *witness));
+
+    let mut witness_values = WitnessMap::new();
+    for witness in witnesses {
+        if let Some(value) = initial_witness.get(&witness) {
+            witness_values.insert(witness, *value);
         }
-        result.q_c += expr.q_c;
-        result
+    }
+
+    OpcodeResolutionError::UnsatisfiedConstrain {
+        opcode_location: ErrorLocation::Unresolved,
+        reason: Box::new(ConstraintFailure::Arithmetic {
+            expression: evaluated.clone(),
+            witness_values,
+        }),
     }
 }
 
+#[cfg(test)]
+use acir::FieldElement;
+
 #[test]
 fn arithmetic_smoke_test() {
     let a = Witness(0);
@@ -0,0 +1,63 @@
+use acir::{
+    circuit::opcodes::FunctionInput,
+    native_types::{Witness, WitnessMap},
+    AcirField, BlackBoxFunc,
+};
+
+use crate::{
+    pwg::{insert_value, witness_to_value, OpcodeResolutionError},
+    BlackBoxFunctionSolver,
+};
+
+pub(super) fn embedded_curve_add<F: AcirField>(
+    backend: &impl BlackBoxFunctionSolver<F>,
+    initial_witness: &mut WitnessMap<F>,
+    input1_x: FunctionInput,
+    input1_y: FunctionInput,
+    input2_x: FunctionInput,
+    input2_y: FunctionInput,
+    outputs: (Witness, Witness),
+) -> Result<(), OpcodeResolutionError<F>> {
+    let input1_x = witness_to_value(initial_witness, input1_x.witness)?;
+    let input1_y = witness_to_value(initial_witness, input1_y.witness)?;
+    let input2_x = witness_to_value(initial_witness, input2_x.witness)?;
+    let input2_y = witness_to_value(initial_witness, input2_y.witness)?;
+
+    let (res_x, res_y) = backend.embedded_curve_add(input1_x, input1_y, input2_x, input2_y)?;
+
+    insert_value(&outputs.0, res_x, initial_witness)?;
+    insert_value(&outputs.1, res_y, initial_witness)?;
+
+    Ok(())
+}
+
+pub(super) fn variable_base_scalar_mul<F: AcirField>(
+    backend: &impl BlackBoxFunctionSolver<F>,
+    initial_witness: &mut WitnessMap<F>,
+    point_x: FunctionInput,
+    point_y: FunctionInput,
+    scalar_low: FunctionInput,
+    scalar_high: FunctionInput,
+    outputs: (Witness, Witness),
+) -> Result<(), OpcodeResolutionError<F>> {
+    let point_x = witness_to_value(initial_witness, point_x.witness)?;
+    let point_y = witness_to_value(initial_witness, point_y.witness)?;
+    let scalar_low = witness_to_value(initial_witness, scalar_low.witness)?;
+    let scalar_high = witness_to_value(initial_witness, scalar_high.witness)?;
+
+    // As with `FixedBaseScalarMul`, each limb must fit in 128 bits for the scalar's decomposition to be unique.
+    if !scalar_low.fits_in_u128() || !scalar_high.fits_in_u128() {
+        return Err(OpcodeResolutionError::BlackBoxFunctionFailed(
+            BlackBoxFunc::VariableBaseScalarMul,
+            "scalar limbs must each fit in 128 bits".to_string(),
+        ));
+    }
+
+    let (res_x, res_y) =
+        backend.variable_base_scalar_mul(point_x, point_y, scalar_low, scalar_high)?;
+
+    insert_value(&outputs.0, res_x, initial_witness)?;
+    insert_value(&outputs.1, res_y, initial_witness)?;
+
+    Ok(())
+}
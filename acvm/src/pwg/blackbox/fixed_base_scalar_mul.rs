@@ -1,6 +1,7 @@
 use acir::{
     circuit::opcodes::FunctionInput,
     native_types::{Witness, WitnessMap},
+    AcirField, BlackBoxFunc,
 };
 
 use crate::{
@@ -8,16 +9,25 @@ use crate::{
     BlackBoxFunctionSolver,
 };
 
-pub(super) fn fixed_base_scalar_mul(
-    backend: &impl BlackBoxFunctionSolver,
-    initial_witness: &mut WitnessMap,
+pub(super) fn fixed_base_scalar_mul<F: AcirField>(
+    backend: &impl BlackBoxFunctionSolver<F>,
+    initial_witness: &mut WitnessMap<F>,
     low: FunctionInput,
     high: FunctionInput,
     outputs: (Witness, Witness),
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     let low = witness_to_value(initial_witness, low.witness)?;
     let high = witness_to_value(initial_witness, high.witness)?;
 
+    // The scalar is `low + high * 2^128` so each limb must fit in 128 bits for the decomposition to be unique.
+    // Fields of fewer than 128 bits trivially satisfy this but can then only express a subset of all scalars.
+    if !low.fits_in_u128() || !high.fits_in_u128() {
+        return Err(OpcodeResolutionError::BlackBoxFunctionFailed(
+            BlackBoxFunc::FixedBaseScalarMul,
+            "scalar limbs must each fit in 128 bits".to_string(),
+        ));
+    }
+
     let (pub_x, pub_y) = backend.fixed_base_scalar_mul(low, high)?;
 
     insert_value(&outputs.0, pub_x, initial_witness)?;
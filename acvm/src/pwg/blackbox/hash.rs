@@ -1,7 +1,7 @@
 use acir::{
     circuit::opcodes::FunctionInput,
     native_types::{Witness, WitnessMap},
-    BlackBoxFunc, FieldElement,
+    AcirField, BlackBoxFunc,
 };
 use acvm_blackbox_solver::{hash_to_field_128_security, BlackBoxResolutionError};
 
@@ -10,11 +10,11 @@ use crate::OpcodeResolutionError;
 
 /// Attempts to solve a `HashToField128Security` opcode
 /// If successful, `initial_witness` will be mutated to contain the new witness assignment.
-pub(super) fn solve_hash_to_field(
-    initial_witness: &mut WitnessMap,
+pub(super) fn solve_hash_to_field<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
     inputs: &[FunctionInput],
     output: &Witness,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     let message_input = get_hash_input(initial_witness, inputs, None)?;
     let field = hash_to_field_128_security(&message_input)?;
 
@@ -23,23 +23,23 @@ pub(super) fn solve_hash_to_field(
     Ok(())
 }
 
-/// Attempts to solve a 256 bit hash function opcode.
+/// Attempts to solve a hash function opcode with an `N` byte digest.
 /// If successful, `initial_witness` will be mutated to contain the new witness assignment.
-pub(super) fn solve_generic_256_hash_opcode(
-    initial_witness: &mut WitnessMap,
+pub(super) fn solve_generic_hash_opcode<F: AcirField, const N: usize>(
+    initial_witness: &mut WitnessMap<F>,
     inputs: &[FunctionInput],
     var_message_size: Option<&FunctionInput>,
     outputs: &[Witness],
-    hash_function: fn(data: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError>,
+    hash_function: fn(data: &[u8]) -> Result<[u8; N], BlackBoxResolutionError>,
     black_box_func: BlackBoxFunc,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     let message_input = get_hash_input(initial_witness, inputs, var_message_size)?;
-    let digest: [u8; 32] = hash_function(&message_input)?;
+    let digest: [u8; N] = hash_function(&message_input)?;
 
-    let outputs: [Witness; 32] = outputs.try_into().map_err(|_| {
+    let outputs: [Witness; N] = outputs.try_into().map_err(|_| {
         OpcodeResolutionError::BlackBoxFunctionFailed(
             black_box_func,
-            format!("Expected 32 outputs but encountered {}", outputs.len()),
+            format!("Expected {N} outputs but encountered {}", outputs.len()),
         )
     })?;
     write_digest_to_outputs(initial_witness, outputs, digest)?;
@@ -48,11 +48,11 @@ pub(super) fn solve_generic_256_hash_opcode(
 }
 
 /// Reads the hash function input from a [`WitnessMap`].
-fn get_hash_input(
-    initial_witness: &WitnessMap,
+fn get_hash_input<F: AcirField>(
+    initial_witness: &WitnessMap<F>,
     inputs: &[FunctionInput],
     message_size: Option<&FunctionInput>,
-) -> Result<Vec<u8>, OpcodeResolutionError> {
+) -> Result<Vec<u8>, OpcodeResolutionError<F>> {
     // Read witness assignments.
     let mut message_input = Vec::new();
     for input in inputs.iter() {
@@ -86,17 +86,13 @@ fn get_hash_input(
 }
 
 /// Writes a `digest` to the [`WitnessMap`] at witness indices `outputs`.
-fn write_digest_to_outputs(
-    initial_witness: &mut WitnessMap,
-    outputs: [Witness; 32],
-    digest: [u8; 32],
-) -> Result<(), OpcodeResolutionError> {
+fn write_digest_to_outputs<F: AcirField, const N: usize>(
+    initial_witness: &mut WitnessMap<F>,
+    outputs: [Witness; N],
+    digest: [u8; N],
+) -> Result<(), OpcodeResolutionError<F>> {
     for (output_witness, value) in outputs.iter().zip(digest.into_iter()) {
-        insert_value(
-            output_witness,
-            FieldElement::from_be_bytes_reduce(&[value]),
-            initial_witness,
-        )?;
+        insert_value(output_witness, F::from_be_bytes_reduce(&[value]), initial_witness)?;
     }
 
     Ok(())
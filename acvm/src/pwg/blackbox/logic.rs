@@ -1,19 +1,21 @@
-use crate::pwg::{insert_value, witness_to_value};
+use std::collections::BTreeMap;
+
+use crate::pwg::{insert_computed_value, witness_to_value};
 use crate::OpcodeResolutionError;
 use acir::{
     circuit::opcodes::FunctionInput,
     native_types::{Witness, WitnessMap},
-    FieldElement,
+    AcirField,
 };
 
 /// Solves a [`BlackBoxFunc::And`][acir::circuit::black_box_functions::BlackBoxFunc::AND] opcode and inserts
 /// the result into the supplied witness map
-pub(super) fn and(
-    initial_witness: &mut WitnessMap,
+pub(super) fn and<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
     lhs: &FunctionInput,
     rhs: &FunctionInput,
     output: &Witness,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     assert_eq!(
         lhs.num_bits, rhs.num_bits,
         "number of bits specified for each input must be the same"
@@ -25,12 +27,12 @@ pub(super) fn and(
 
 /// Solves a [`BlackBoxFunc::XOR`][acir::circuit::black_box_functions::BlackBoxFunc::XOR] opcode and inserts
 /// the result into the supplied witness map
-pub(super) fn xor(
-    initial_witness: &mut WitnessMap,
+pub(super) fn xor<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
     lhs: &FunctionInput,
     rhs: &FunctionInput,
     output: &Witness,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     assert_eq!(
         lhs.num_bits, rhs.num_bits,
         "number of bits specified for each input must be the same"
@@ -41,16 +43,65 @@ pub(super) fn xor(
 }
 
 /// Derives the rest of the witness based on the initial low level variables
-fn solve_logic_opcode(
-    initial_witness: &mut WitnessMap,
+fn solve_logic_opcode<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
     a: &Witness,
     b: &Witness,
     result: Witness,
-    logic_op: impl Fn(&FieldElement, &FieldElement) -> FieldElement,
-) -> Result<(), OpcodeResolutionError> {
+    logic_op: impl Fn(&F, &F) -> F,
+) -> Result<(), OpcodeResolutionError<F>> {
     let w_l_value = witness_to_value(initial_witness, *a)?;
     let w_r_value = witness_to_value(initial_witness, *b)?;
     let assignment = logic_op(w_l_value, w_r_value);
 
-    insert_value(&result, assignment, initial_witness)
+    let inputs = WitnessMap::from(BTreeMap::from([(*a, *w_l_value), (*b, *w_r_value)]));
+    insert_computed_value(&result, assignment, inputs, initial_witness)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acir::{
+        circuit::opcodes::FunctionInput,
+        native_types::{Witness, WitnessMap},
+        FieldElement,
+    };
+
+    use super::xor;
+    use crate::pwg::{ConstraintFailure, ErrorLocation, OpcodeResolutionError};
+
+    #[test]
+    fn conflicting_output_reports_input_values() {
+        let lhs = FunctionInput { witness: Witness(1), num_bits: 8 };
+        let rhs = FunctionInput { witness: Witness(2), num_bits: 8 };
+        let output = Witness(3);
+        let mut witness_map = WitnessMap::from(BTreeMap::from([
+            (lhs.witness, FieldElement::from(0b1100_u128)),
+            (rhs.witness, FieldElement::from(0b1010_u128)),
+            (output, FieldElement::from(0b1111_u128)),
+        ]));
+
+        let error = xor(&mut witness_map, &lhs, &rhs, &output).unwrap_err();
+
+        assert_eq!(
+            error,
+            OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Unresolved,
+                reason: Box::new(ConstraintFailure::ConflictingAssignment {
+                    witness: output,
+                    old_value: FieldElement::from(0b1111_u128),
+                    new_value: FieldElement::from(0b0110_u128),
+                    witness_values: WitnessMap::from(BTreeMap::from([
+                        (lhs.witness, FieldElement::from(0b1100_u128)),
+                        (rhs.witness, FieldElement::from(0b1010_u128)),
+                    ])),
+                }),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Cannot satisfy constraint: witness _3 was assigned 15 but was computed to be 6 given _1 = 12, _2 = 10"
+        );
+    }
 }
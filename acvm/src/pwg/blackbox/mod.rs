@@ -1,13 +1,14 @@
 use acir::{
     circuit::opcodes::{BlackBoxFuncCall, FunctionInput},
     native_types::{Witness, WitnessMap},
-    FieldElement,
+    AcirField,
 };
-use acvm_blackbox_solver::{blake2s, keccak256, sha256};
+use acvm_blackbox_solver::{blake2s, blake3, keccak256, sha256, sha512};
 
 use super::{insert_value, OpcodeNotSolvable, OpcodeResolutionError};
 use crate::BlackBoxFunctionSolver;
 
+mod embedded_curve;
 mod fixed_base_scalar_mul;
 mod hash;
 mod logic;
@@ -15,22 +16,24 @@ mod pedersen;
 mod range;
 mod signature;
 
+use embedded_curve::{embedded_curve_add, variable_base_scalar_mul};
 use fixed_base_scalar_mul::fixed_base_scalar_mul;
 // Hash functions should eventually be exposed for external consumers.
-use hash::{solve_generic_256_hash_opcode, solve_hash_to_field};
+use hash::{solve_generic_hash_opcode, solve_hash_to_field};
 use logic::{and, xor};
 use pedersen::pedersen;
 use range::solve_range_opcode;
 use signature::{
-    ecdsa::{secp256k1_prehashed, secp256r1_prehashed},
+    ecdsa::{secp256k1_prehashed, secp256k1_recover, secp256r1_prehashed},
+    eddsa::{babyjubjub, ed25519},
     schnorr::schnorr_verify,
 };
 
 /// Check if all of the inputs to the function have assignments
 ///
 /// Returns the first missing assignment if any are missing
-fn first_missing_assignment(
-    witness_assignments: &WitnessMap,
+fn first_missing_assignment<F: AcirField>(
+    witness_assignments: &WitnessMap<F>,
     inputs: &[FunctionInput],
 ) -> Option<Witness> {
     inputs.iter().find_map(|input| {
@@ -43,15 +46,18 @@ fn first_missing_assignment(
 }
 
 /// Check if all of the inputs to the function have assignments
-fn contains_all_inputs(witness_assignments: &WitnessMap, inputs: &[FunctionInput]) -> bool {
+fn contains_all_inputs<F: AcirField>(
+    witness_assignments: &WitnessMap<F>,
+    inputs: &[FunctionInput],
+) -> bool {
     inputs.iter().all(|input| witness_assignments.contains_key(&input.witness))
 }
 
-pub(crate) fn solve(
-    backend: &impl BlackBoxFunctionSolver,
-    initial_witness: &mut WitnessMap,
+pub(crate) fn solve<F: AcirField>(
+    backend: &impl BlackBoxFunctionSolver<F>,
+    initial_witness: &mut WitnessMap<F>,
     bb_func: &BlackBoxFuncCall,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     let inputs = bb_func.get_inputs_vec();
     if !contains_all_inputs(initial_witness, &inputs) {
         let unassigned_witness = first_missing_assignment(initial_witness, &inputs)
@@ -65,7 +71,7 @@ pub(crate) fn solve(
         BlackBoxFuncCall::AND { lhs, rhs, output } => and(initial_witness, lhs, rhs, output),
         BlackBoxFuncCall::XOR { lhs, rhs, output } => xor(initial_witness, lhs, rhs, output),
         BlackBoxFuncCall::RANGE { input } => solve_range_opcode(initial_witness, input),
-        BlackBoxFuncCall::SHA256 { inputs, outputs } => solve_generic_256_hash_opcode(
+        BlackBoxFuncCall::SHA256 { inputs, outputs } => solve_generic_hash_opcode(
             initial_witness,
             inputs,
             None,
@@ -73,7 +79,7 @@ pub(crate) fn solve(
             sha256,
             bb_func.get_black_box_func(),
         ),
-        BlackBoxFuncCall::Blake2s { inputs, outputs } => solve_generic_256_hash_opcode(
+        BlackBoxFuncCall::Blake2s { inputs, outputs } => solve_generic_hash_opcode(
             initial_witness,
             inputs,
             None,
@@ -81,7 +87,23 @@ pub(crate) fn solve(
             blake2s,
             bb_func.get_black_box_func(),
         ),
-        BlackBoxFuncCall::Keccak256 { inputs, outputs } => solve_generic_256_hash_opcode(
+        BlackBoxFuncCall::SHA512 { inputs, outputs } => solve_generic_hash_opcode(
+            initial_witness,
+            inputs,
+            None,
+            outputs,
+            sha512,
+            bb_func.get_black_box_func(),
+        ),
+        BlackBoxFuncCall::Blake3 { inputs, outputs } => solve_generic_hash_opcode(
+            initial_witness,
+            inputs,
+            None,
+            outputs,
+            blake3,
+            bb_func.get_black_box_func(),
+        ),
+        BlackBoxFuncCall::Keccak256 { inputs, outputs } => solve_generic_hash_opcode(
             initial_witness,
             inputs,
             None,
@@ -90,7 +112,7 @@ pub(crate) fn solve(
             bb_func.get_black_box_func(),
         ),
         BlackBoxFuncCall::Keccak256VariableLength { inputs, var_message_size, outputs } => {
-            solve_generic_256_hash_opcode(
+            solve_generic_hash_opcode(
                 initial_witness,
                 inputs,
                 Some(var_message_size),
@@ -148,14 +170,75 @@ pub(crate) fn solve(
             message,
             *output,
         ),
+        BlackBoxFuncCall::EcdsaSecp256k1Recover {
+            signature,
+            recovery_id,
+            hashed_message,
+            public_key_x,
+            public_key_y,
+        } => secp256k1_recover(
+            initial_witness,
+            signature,
+            *recovery_id,
+            hashed_message,
+            public_key_x,
+            public_key_y,
+        ),
+        BlackBoxFuncCall::EddsaEd25519 { public_key, signature, message, output } => {
+            ed25519(initial_witness, public_key, signature, message, *output)
+        }
+        BlackBoxFuncCall::EddsaBabyJubJub {
+            public_key_x,
+            public_key_y,
+            signature_s,
+            signature_r8_x,
+            signature_r8_y,
+            message,
+            output,
+        } => babyjubjub(
+            initial_witness,
+            *public_key_x,
+            *public_key_y,
+            *signature_s,
+            *signature_r8_x,
+            *signature_r8_y,
+            *message,
+            *output,
+        ),
         BlackBoxFuncCall::FixedBaseScalarMul { low, high, outputs } => {
             fixed_base_scalar_mul(backend, initial_witness, *low, *high, *outputs)
         }
+        BlackBoxFuncCall::EmbeddedCurveAdd { input1_x, input1_y, input2_x, input2_y, outputs } => {
+            embedded_curve_add(
+                backend,
+                initial_witness,
+                *input1_x,
+                *input1_y,
+                *input2_x,
+                *input2_y,
+                *outputs,
+            )
+        }
+        BlackBoxFuncCall::VariableBaseScalarMul {
+            point_x,
+            point_y,
+            scalar_low,
+            scalar_high,
+            outputs,
+        } => variable_base_scalar_mul(
+            backend,
+            initial_witness,
+            *point_x,
+            *point_y,
+            *scalar_low,
+            *scalar_high,
+            *outputs,
+        ),
         BlackBoxFuncCall::RecursiveAggregation { output_aggregation_object, .. } => {
             // Solve the output of the recursive aggregation to zero to prevent missing assignment errors
             // The correct value will be computed by the backend
             for witness in output_aggregation_object {
-                insert_value(witness, FieldElement::zero(), initial_witness)?;
+                insert_value(witness, F::zero(), initial_witness)?;
             }
             Ok(())
         }
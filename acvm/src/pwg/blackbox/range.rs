@@ -1,17 +1,22 @@
 use crate::{
-    pwg::{witness_to_value, ErrorLocation},
+    pwg::{witness_to_value, ConstraintFailure, ErrorLocation},
     OpcodeResolutionError,
 };
-use acir::{circuit::opcodes::FunctionInput, native_types::WitnessMap};
+use acir::{circuit::opcodes::FunctionInput, native_types::WitnessMap, AcirField};
 
-pub(super) fn solve_range_opcode(
-    initial_witness: &mut WitnessMap,
+pub(super) fn solve_range_opcode<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
     input: &FunctionInput,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     let w_value = witness_to_value(initial_witness, input.witness)?;
     if w_value.num_bits() > input.num_bits {
         return Err(OpcodeResolutionError::UnsatisfiedConstrain {
             opcode_location: ErrorLocation::Unresolved,
+            reason: Box::new(ConstraintFailure::Range {
+                witness: input.witness,
+                value: *w_value,
+                num_bits: input.num_bits,
+            }),
         });
     }
     Ok(())
@@ -1,22 +1,27 @@
 use acir::{
     circuit::opcodes::FunctionInput,
     native_types::{Witness, WitnessMap},
-    FieldElement,
+    AcirField,
+};
+use acvm_blackbox_solver::{
+    ecdsa_secp256k1_recover, ecdsa_secp256k1_verify, ecdsa_secp256r1_verify,
 };
-use acvm_blackbox_solver::{ecdsa_secp256k1_verify, ecdsa_secp256r1_verify};
 
-use crate::{pwg::insert_value, OpcodeResolutionError};
+use crate::{
+    pwg::{insert_value, witness_to_value},
+    OpcodeResolutionError,
+};
 
 use super::to_u8_vec;
 
-pub(crate) fn secp256k1_prehashed(
-    initial_witness: &mut WitnessMap,
+pub(crate) fn secp256k1_prehashed<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
     public_key_x_inputs: &[FunctionInput],
     public_key_y_inputs: &[FunctionInput],
     signature_inputs: &[FunctionInput],
     hashed_message_inputs: &[FunctionInput],
     output: Witness,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     let hashed_message = to_u8_vec(initial_witness, hashed_message_inputs)?;
 
     // These errors should never be emitted in practice as they would imply malformed ACIR generation.
@@ -46,18 +51,18 @@ pub(crate) fn secp256k1_prehashed(
 
     let is_valid = ecdsa_secp256k1_verify(&hashed_message, &pub_key_x, &pub_key_y, &signature)?;
 
-    insert_value(&output, FieldElement::from(is_valid), initial_witness)?;
+    insert_value(&output, F::from(is_valid), initial_witness)?;
     Ok(())
 }
 
-pub(crate) fn secp256r1_prehashed(
-    initial_witness: &mut WitnessMap,
+pub(crate) fn secp256r1_prehashed<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
     public_key_x_inputs: &[FunctionInput],
     public_key_y_inputs: &[FunctionInput],
     signature_inputs: &[FunctionInput],
     hashed_message_inputs: &[FunctionInput],
     output: Witness,
-) -> Result<(), OpcodeResolutionError> {
+) -> Result<(), OpcodeResolutionError<F>> {
     let hashed_message = to_u8_vec(initial_witness, hashed_message_inputs)?;
 
     let pub_key_x: [u8; 32] =
@@ -86,6 +91,53 @@ pub(crate) fn secp256r1_prehashed(
 
     let is_valid = ecdsa_secp256r1_verify(&hashed_message, &pub_key_x, &pub_key_y, &signature)?;
 
-    insert_value(&output, FieldElement::from(is_valid), initial_witness)?;
+    insert_value(&output, F::from(is_valid), initial_witness)?;
+    Ok(())
+}
+
+pub(crate) fn secp256k1_recover<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
+    signature_inputs: &[FunctionInput],
+    recovery_id_input: FunctionInput,
+    hashed_message_inputs: &[FunctionInput],
+    public_key_x_outputs: &[Witness],
+    public_key_y_outputs: &[Witness],
+) -> Result<(), OpcodeResolutionError<F>> {
+    let hashed_message = to_u8_vec(initial_witness, hashed_message_inputs)?;
+    let recovery_id = match *witness_to_value(initial_witness, recovery_id_input.witness)? {
+        recovery_id if recovery_id.is_zero() => 0,
+        recovery_id if recovery_id.is_one() => 1,
+        recovery_id => {
+            return Err(OpcodeResolutionError::BlackBoxFunctionFailed(
+                acir::BlackBoxFunc::EcdsaSecp256k1Recover,
+                format!("recovery id must be 0 or 1 but received {}", recovery_id.to_hex()),
+            ))
+        }
+    };
+
+    // These errors should never be emitted in practice as they would imply malformed ACIR generation.
+    let signature: [u8; 64] =
+        to_u8_vec(initial_witness, signature_inputs)?.try_into().map_err(|_| {
+            OpcodeResolutionError::BlackBoxFunctionFailed(
+                acir::BlackBoxFunc::EcdsaSecp256k1Recover,
+                format!("expected signature size 64 but received {}", signature_inputs.len()),
+            )
+        })?;
+    for outputs in [public_key_x_outputs, public_key_y_outputs] {
+        if outputs.len() != 32 {
+            return Err(OpcodeResolutionError::BlackBoxFunctionFailed(
+                acir::BlackBoxFunc::EcdsaSecp256k1Recover,
+                format!("expected pubkey coordinate size 32 but received {}", outputs.len()),
+            ));
+        }
+    }
+
+    let (pub_key_x, pub_key_y) = ecdsa_secp256k1_recover(&hashed_message, &signature, recovery_id)?;
+
+    for (outputs, bytes) in [(public_key_x_outputs, pub_key_x), (public_key_y_outputs, pub_key_y)] {
+        for (output, byte) in outputs.iter().zip(bytes) {
+            insert_value(output, F::from(byte as u128), initial_witness)?;
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,73 @@
+use acir::{
+    circuit::opcodes::FunctionInput,
+    native_types::{Witness, WitnessMap},
+    AcirField,
+};
+use acvm_blackbox_solver::{eddsa_babyjubjub_verify, eddsa_ed25519_verify};
+
+use crate::pwg::{insert_value, witness_to_value, OpcodeResolutionError};
+
+use super::to_u8_vec;
+
+pub(crate) fn ed25519<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
+    public_key_inputs: &[FunctionInput],
+    signature_inputs: &[FunctionInput],
+    message_inputs: &[FunctionInput],
+    output: Witness,
+) -> Result<(), OpcodeResolutionError<F>> {
+    let message = to_u8_vec(initial_witness, message_inputs)?;
+
+    // These errors should never be emitted in practice as they would imply malformed ACIR generation.
+    let public_key: [u8; 32] =
+        to_u8_vec(initial_witness, public_key_inputs)?.try_into().map_err(|_| {
+            OpcodeResolutionError::BlackBoxFunctionFailed(
+                acir::BlackBoxFunc::EddsaEd25519,
+                format!("expected pubkey size 32 but received {}", public_key_inputs.len()),
+            )
+        })?;
+
+    let signature: [u8; 64] =
+        to_u8_vec(initial_witness, signature_inputs)?.try_into().map_err(|_| {
+            OpcodeResolutionError::BlackBoxFunctionFailed(
+                acir::BlackBoxFunc::EddsaEd25519,
+                format!("expected signature size 64 but received {}", signature_inputs.len()),
+            )
+        })?;
+
+    let is_valid = eddsa_ed25519_verify(&public_key, &signature, &message)?;
+
+    insert_value(&output, F::from(is_valid), initial_witness)?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn babyjubjub<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
+    public_key_x: FunctionInput,
+    public_key_y: FunctionInput,
+    signature_s: FunctionInput,
+    signature_r8_x: FunctionInput,
+    signature_r8_y: FunctionInput,
+    message: FunctionInput,
+    output: Witness,
+) -> Result<(), OpcodeResolutionError<F>> {
+    let public_key_x: &F = witness_to_value(initial_witness, public_key_x.witness)?;
+    let public_key_y: &F = witness_to_value(initial_witness, public_key_y.witness)?;
+    let signature_s: &F = witness_to_value(initial_witness, signature_s.witness)?;
+    let signature_r8_x: &F = witness_to_value(initial_witness, signature_r8_x.witness)?;
+    let signature_r8_y: &F = witness_to_value(initial_witness, signature_r8_y.witness)?;
+    let message: &F = witness_to_value(initial_witness, message.witness)?;
+
+    let is_valid = eddsa_babyjubjub_verify(
+        public_key_x,
+        public_key_y,
+        signature_s,
+        signature_r8_x,
+        signature_r8_y,
+        message,
+    )?;
+
+    insert_value(&output, F::from(is_valid), initial_witness)?;
+    Ok(())
+}
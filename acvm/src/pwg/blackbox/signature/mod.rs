@@ -1,11 +1,11 @@
-use acir::{circuit::opcodes::FunctionInput, native_types::WitnessMap};
+use acir::{circuit::opcodes::FunctionInput, native_types::WitnessMap, AcirField};
 
 use crate::pwg::{witness_to_value, OpcodeResolutionError};
 
-fn to_u8_vec(
-    initial_witness: &WitnessMap,
+fn to_u8_vec<F: AcirField>(
+    initial_witness: &WitnessMap<F>,
     inputs: &[FunctionInput],
-) -> Result<Vec<u8>, OpcodeResolutionError> {
+) -> Result<Vec<u8>, OpcodeResolutionError<F>> {
     let mut result = Vec::with_capacity(inputs.len());
     for input in inputs {
         let witness_value_bytes = witness_to_value(initial_witness, input.witness)?.to_be_bytes();
@@ -18,4 +18,5 @@ fn to_u8_vec(
 }
 
 pub(super) mod ecdsa;
+pub(super) mod eddsa;
 pub(super) mod schnorr;
@@ -6,21 +6,21 @@ use crate::{
 use acir::{
     circuit::opcodes::FunctionInput,
     native_types::{Witness, WitnessMap},
-    FieldElement,
+    AcirField,
 };
 
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn schnorr_verify(
-    backend: &impl BlackBoxFunctionSolver,
-    initial_witness: &mut WitnessMap,
+pub(crate) fn schnorr_verify<F: AcirField>(
+    backend: &impl BlackBoxFunctionSolver<F>,
+    initial_witness: &mut WitnessMap<F>,
     public_key_x: FunctionInput,
     public_key_y: FunctionInput,
     signature: &[FunctionInput],
     message: &[FunctionInput],
     output: Witness,
-) -> Result<(), OpcodeResolutionError> {
-    let public_key_x: &FieldElement = witness_to_value(initial_witness, public_key_x.witness)?;
-    let public_key_y: &FieldElement = witness_to_value(initial_witness, public_key_y.witness)?;
+) -> Result<(), OpcodeResolutionError<F>> {
+    let public_key_x: &F = witness_to_value(initial_witness, public_key_x.witness)?;
+    let public_key_y: &F = witness_to_value(initial_witness, public_key_y.witness)?;
 
     let signature = to_u8_vec(initial_witness, signature)?;
 
@@ -29,7 +29,7 @@ pub(crate) fn schnorr_verify(
     let valid_signature =
         backend.schnorr_verify(public_key_x, public_key_y, &signature, &message)?;
 
-    insert_value(&output, FieldElement::from(valid_signature), initial_witness)?;
+    insert_value(&output, F::from(valid_signature), initial_witness)?;
 
     Ok(())
 }
@@ -1,33 +1,89 @@
+use std::collections::HashMap;
+
 use acir::{
-    brillig::{RegisterIndex, Value},
+    brillig::{abi::AbiType, ForeignCallParam, RegisterIndex, Value},
     circuit::{
         brillig::{Brillig, BrilligInputs, BrilligOutputs},
-        OpcodeLocation,
+        Opcode, OpcodeLocation,
     },
     native_types::WitnessMap,
-    FieldElement,
+    AcirField, FieldElement,
 };
 use acvm_blackbox_solver::BlackBoxFunctionSolver;
-use brillig_vm::{Registers, VMStatus, VM};
+use brillig_vm::{DecodedBytecode, Registers, VMStatus, VM};
 
 use crate::{pwg::OpcodeNotSolvable, OpcodeResolutionError};
 
 use super::{get_value, insert_value};
 
+/// The decoded bytecode of each [Brillig][Opcode::Brillig] opcode in a circuit, keyed by the opcode's index.
+///
+/// A circuit's bytecode only needs to be decoded once: passing the same cache to each [ACVM][super::ACVM]
+/// which executes the circuit shares the decoded bytecode between executions rather than decoding it again.
+/// Cloning the cache is cheap as the decoded bytecode is reference counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrilligBytecodeCache<F = FieldElement> {
+    bytecode: HashMap<usize, DecodedBytecode<F>>,
+}
+
+impl<F: AcirField> BrilligBytecodeCache<F> {
+    /// Decodes the bytecode of every Brillig opcode in `opcodes`.
+    pub fn new(opcodes: &[Opcode<F>]) -> Self {
+        let bytecode = opcodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, opcode)| match opcode {
+                Opcode::Brillig(brillig) => {
+                    Some((index, DecodedBytecode::new(brillig.bytecode.clone())))
+                }
+                _ => None,
+            })
+            .collect();
+        Self { bytecode }
+    }
+
+    /// Returns the decoded bytecode of `brillig`, the opcode at index `acir_index`,
+    /// decoding it if it is not already cached.
+    pub(super) fn get_or_decode(
+        &mut self,
+        acir_index: usize,
+        brillig: &Brillig<F>,
+    ) -> &DecodedBytecode<F> {
+        self.bytecode
+            .entry(acir_index)
+            .or_insert_with(|| DecodedBytecode::new(brillig.bytecode.clone()))
+    }
+}
+
+impl<F> Default for BrilligBytecodeCache<F> {
+    fn default() -> Self {
+        Self { bytecode: HashMap::default() }
+    }
+}
+
+impl<F> BrilligBytecodeCache<F> {
+    /// Returns the decoded bytecode of the Brillig opcode at index `acir_index`, if it has been decoded.
+    pub fn get(&self, acir_index: usize) -> Option<&DecodedBytecode<F>> {
+        self.bytecode.get(&acir_index)
+    }
+}
+
 pub(super) struct BrilligSolver;
 
 impl BrilligSolver {
-    pub(super) fn solve<B: BlackBoxFunctionSolver>(
-        initial_witness: &mut WitnessMap,
-        brillig: &Brillig,
+    /// Executes `brillig`, whose bytecode has already been decoded into `bytecode`.
+    pub(super) fn solve<F: AcirField, B: BlackBoxFunctionSolver<F>>(
+        initial_witness: &mut WitnessMap<F>,
+        brillig: &Brillig<F>,
+        bytecode: &DecodedBytecode<F>,
         bb_solver: &B,
         acir_index: usize,
-    ) -> Result<Option<ForeignCallWaitInfo>, OpcodeResolutionError> {
+    ) -> Result<Option<ForeignCallWaitInfo<F>>, OpcodeResolutionError<F>> {
         // If the predicate is `None`, then we simply return the value 1
         // If the predicate is `Some` but we cannot find a value, then we return stalled
         let pred_value = match &brillig.predicate {
             Some(pred) => get_value(pred, initial_witness),
-            None => Ok(FieldElement::one()),
+            None => Ok(F::one()),
         }?;
 
         // A zero predicate indicates the oracle should be skipped, and its outputs zeroed.
@@ -37,8 +93,8 @@ impl BrilligSolver {
         }
 
         // Set input values
-        let mut input_register_values: Vec<Value> = Vec::new();
-        let mut input_memory: Vec<Value> = Vec::new();
+        let mut input_register_values: Vec<Value<F>> = Vec::new();
+        let mut input_memory: Vec<Value<F>> = Vec::new();
         // Each input represents an expression or array of expressions to evaluate.
         // Iterate over each input and evaluate the expression(s) associated with it.
         // Push the results into registers and/or memory.
@@ -68,7 +124,7 @@ impl BrilligSolver {
                     }
 
                     // Push value of the array pointer as a register
-                    input_register_values.push(Value::from(memory_pointer));
+                    input_register_values.push(F::from(memory_pointer as u128).into());
                 }
             }
         }
@@ -79,7 +135,7 @@ impl BrilligSolver {
         let mut vm = VM::new(
             input_registers,
             input_memory,
-            brillig.bytecode.clone(),
+            bytecode.clone(),
             brillig.foreign_call_results.clone(),
             bb_solver,
         );
@@ -123,25 +179,25 @@ impl BrilligSolver {
                         .collect(),
                 })
             }
-            VMStatus::ForeignCallWait { function, inputs } => {
-                Ok(Some(ForeignCallWaitInfo { function, inputs }))
+            VMStatus::ForeignCallWait { function, inputs, input_types, destination_types } => {
+                Ok(Some(ForeignCallWaitInfo { function, inputs, input_types, destination_types }))
             }
         }
     }
 
     /// Assigns the zero value to all outputs of the given [`Brillig`] bytecode.
-    fn zero_out_brillig_outputs(
-        initial_witness: &mut WitnessMap,
-        brillig: &Brillig,
-    ) -> Result<(), OpcodeResolutionError> {
+    fn zero_out_brillig_outputs<F: AcirField>(
+        initial_witness: &mut WitnessMap<F>,
+        brillig: &Brillig<F>,
+    ) -> Result<(), OpcodeResolutionError<F>> {
         for output in &brillig.outputs {
             match output {
                 BrilligOutputs::Simple(witness) => {
-                    insert_value(witness, FieldElement::zero(), initial_witness)?
+                    insert_value(witness, F::zero(), initial_witness)?
                 }
                 BrilligOutputs::Array(witness_arr) => {
                     for witness in witness_arr {
-                        insert_value(witness, FieldElement::zero(), initial_witness)?
+                        insert_value(witness, F::zero(), initial_witness)?
                     }
                 }
             }
@@ -155,9 +211,13 @@ impl BrilligSolver {
 ///
 /// The caller must resolve this opcode externally based upon the information in the request.
 #[derive(Debug, PartialEq, Clone)]
-pub struct ForeignCallWaitInfo {
+pub struct ForeignCallWaitInfo<F = FieldElement> {
     /// An identifier interpreted by the caller process
     pub function: String,
     /// Resolved inputs to a foreign call computed in the previous steps of a Brillig VM process
-    pub inputs: Vec<Vec<Value>>,
+    pub inputs: Vec<ForeignCallParam<F>>,
+    /// The type of each of the `inputs`, if known
+    pub input_types: Vec<Option<AbiType>>,
+    /// The type of each of the outputs expected by the foreign call, if known
+    pub destination_types: Vec<Option<AbiType>>,
 }
@@ -3,14 +3,14 @@ use std::cmp::Ordering;
 use acir::{
     circuit::directives::{Directive, QuotientDirective},
     native_types::WitnessMap,
-    FieldElement,
+    AcirField,
 };
 use num_bigint::BigUint;
-use num_traits::Zero;
+use num_traits::{ToPrimitive, Zero};
 
 use crate::OpcodeResolutionError;
 
-use super::{get_value, insert_value, ErrorLocation};
+use super::{get_value, insert_value, ConstraintFailure, ErrorLocation};
 
 mod sorting;
 
@@ -20,10 +20,10 @@ mod sorting;
 /// Returns `Ok(OpcodeResolution)` to signal whether the directive was successful solved.
 ///
 /// Returns `Err(OpcodeResolutionError)` if a circuit constraint is unsatisfied.
-pub(super) fn solve_directives(
-    initial_witness: &mut WitnessMap,
-    directive: &Directive,
-) -> Result<(), OpcodeResolutionError> {
+pub(super) fn solve_directives<F: AcirField>(
+    initial_witness: &mut WitnessMap<F>,
+    directive: &Directive<F>,
+) -> Result<(), OpcodeResolutionError<F>> {
     match directive {
         Directive::Quotient(QuotientDirective { a, b, q, r, predicate }) => {
             let val_a = get_value(a, initial_witness)?;
@@ -35,7 +35,7 @@ pub(super) fn solve_directives(
             // If the predicate is `Some` but we cannot find a value, then we return unresolved
             let pred_value = match predicate {
                 Some(pred) => get_value(pred, initial_witness)?,
-                None => FieldElement::one(),
+                None => F::one(),
             };
 
             let (int_r, int_q) = if pred_value.is_zero() || int_b.is_zero() {
@@ -44,16 +44,8 @@ pub(super) fn solve_directives(
                 (&int_a % &int_b, &int_a / &int_b)
             };
 
-            insert_value(
-                q,
-                FieldElement::from_be_bytes_reduce(&int_q.to_bytes_be()),
-                initial_witness,
-            )?;
-            insert_value(
-                r,
-                FieldElement::from_be_bytes_reduce(&int_r.to_bytes_be()),
-                initial_witness,
-            )?;
+            insert_value(q, F::from_be_bytes_reduce(&int_q.to_bytes_be()), initial_witness)?;
+            insert_value(r, F::from_be_bytes_reduce(&int_r.to_bytes_be()), initial_witness)?;
 
             Ok(())
         }
@@ -62,11 +54,16 @@ pub(super) fn solve_directives(
             let big_integer = BigUint::from_bytes_be(&value_a.to_be_bytes());
 
             // Decompose the integer into its radix digits in little endian form.
-            let decomposed_integer = big_integer.to_radix_le(*radix);
+            let decomposed_integer = to_radix_le(big_integer, *radix);
 
             if b.len() < decomposed_integer.len() {
                 return Err(OpcodeResolutionError::UnsatisfiedConstrain {
                     opcode_location: ErrorLocation::Unresolved,
+                    reason: Box::new(ConstraintFailure::RadixDecomposition {
+                        value: value_a,
+                        radix: *radix,
+                        num_limbs: b.len(),
+                    }),
                 });
             }
 
@@ -76,8 +73,8 @@ pub(super) fn solve_directives(
                 // If it is not available, which can happen when the decomposed integer
                 // list is shorter than the witness list, we return 0.
                 let value = match decomposed_integer.get(i) {
-                    Some(digit) => FieldElement::from_be_bytes_reduce(&[*digit]),
-                    None => FieldElement::zero(),
+                    Some(digit) => F::from(*digit as u128),
+                    None => F::zero(),
                 };
 
                 insert_value(witness, value, initial_witness)?
@@ -94,7 +91,7 @@ pub(super) fn solve_directives(
                 for e in element {
                     element_val.push(get_value(e, initial_witness)?);
                 }
-                let field_i = FieldElement::from(i as i128);
+                let field_i = F::from(i as i128);
                 element_val.push(field_i);
                 base.push(field_i);
                 val_a.push(element_val);
@@ -113,7 +110,7 @@ pub(super) fn solve_directives(
             let b = val_a.iter().map(|a| *a.last().unwrap()).collect();
             let control = sorting::route(base, b);
             for (w, value) in bits.iter().zip(control) {
-                let value = if value { FieldElement::one() } else { FieldElement::zero() };
+                let value = if value { F::one() } else { F::zero() };
                 insert_value(w, value, initial_witness)?;
             }
             Ok(())
@@ -121,6 +118,23 @@ pub(super) fn solve_directives(
     }
 }
 
+/// Decomposes `integer` into its digits in base `radix` in little endian form.
+fn to_radix_le(integer: BigUint, radix: u32) -> Vec<u32> {
+    // `BigUint::to_radix_le` only supports radixes of up to 256, so larger radixes are decomposed by repeated division.
+    if radix <= 256 {
+        return integer.to_radix_le(radix).into_iter().map(u32::from).collect();
+    }
+    let radix = BigUint::from(radix);
+    let mut remaining = integer;
+    let mut digits = Vec::new();
+    while !remaining.is_zero() {
+        let digit = &remaining % &radix;
+        digits.push(digit.to_u32().expect("digit is less than the radix"));
+        remaining /= &radix;
+    }
+    digits
+}
+
 #[cfg(test)]
 mod tests {
     use acir::{
@@ -147,4 +161,21 @@ mod tests {
         solve_directives(&mut witness_map, &Directive::Quotient(quotient_directive))
             .expect("expected 0/0 to return 0");
     }
+
+    #[test]
+    fn decomposes_into_radixes_larger_than_a_byte() {
+        let directive = Directive::ToLeRadix {
+            a: Witness(0).into(),
+            b: vec![Witness(1), Witness(2), Witness(3)],
+            radix: 1 << 16,
+        };
+
+        let mut witness_map = WitnessMap::new();
+        witness_map.insert(Witness(0), FieldElement::from(0x1234_5678_9abc_u128));
+
+        solve_directives(&mut witness_map, &directive).expect("expected the value to fit");
+        assert_eq!(witness_map[&Witness(1)], FieldElement::from(0x9abc_u128));
+        assert_eq!(witness_map[&Witness(2)], FieldElement::from(0x5678_u128));
+        assert_eq!(witness_map[&Witness(3)], FieldElement::from(0x1234_u128));
+    }
 }
@@ -1,24 +1,24 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use acir::FieldElement;
+use acir::AcirField;
 
 // A sorting network is a graph of connected switches
 // It is defined recursively so here we only keep track of the outer layer of switches
-struct SortingNetwork {
-    n: usize,                                // size of the network
-    x_inputs: Vec<FieldElement>,             // inputs of the network
-    y_inputs: Vec<FieldElement>,             // outputs of the network
-    x_values: BTreeMap<FieldElement, usize>, // map for matching a y value with a x value
-    y_values: BTreeMap<FieldElement, usize>, // map for matching a x value with a y value
-    inner_x: Vec<FieldElement>,              // positions after the switch_x
-    inner_y: Vec<FieldElement>, // positions after the sub-networks, and before the switch_y
-    switch_x: Vec<bool>,        // outer switches for the inputs
-    switch_y: Vec<bool>,        // outer switches for the outputs
-    free: BTreeSet<usize>,      // outer switches available for looping
+struct SortingNetwork<F> {
+    n: usize,                     // size of the network
+    x_inputs: Vec<F>,             // inputs of the network
+    y_inputs: Vec<F>,             // outputs of the network
+    x_values: BTreeMap<F, usize>, // map for matching a y value with a x value
+    y_values: BTreeMap<F, usize>, // map for matching a x value with a y value
+    inner_x: Vec<F>,              // positions after the switch_x
+    inner_y: Vec<F>,              // positions after the sub-networks, and before the switch_y
+    switch_x: Vec<bool>,          // outer switches for the inputs
+    switch_y: Vec<bool>,          // outer switches for the outputs
+    free: BTreeSet<usize>,        // outer switches available for looping
 }
 
-impl SortingNetwork {
-    fn new(n: usize) -> SortingNetwork {
+impl<F: AcirField> SortingNetwork<F> {
+    fn new(n: usize) -> SortingNetwork<F> {
         let free_len = (n - 1) / 2;
         let mut free = BTreeSet::new();
         for i in 0..free_len {
@@ -38,7 +38,7 @@ impl SortingNetwork {
         }
     }
 
-    fn init(&mut self, inputs: Vec<FieldElement>, outputs: Vec<FieldElement>) {
+    fn init(&mut self, inputs: Vec<F>, outputs: Vec<F>) {
         let n = self.n;
         assert_eq!(inputs.len(), outputs.len());
         assert_eq!(inputs.len(), n);
@@ -51,8 +51,8 @@ impl SortingNetwork {
         }
         self.switch_x = vec![false; n / 2];
         self.switch_y = vec![false; (n - 1) / 2];
-        self.inner_x = vec![FieldElement::zero(); n];
-        self.inner_y = vec![FieldElement::zero(); n];
+        self.inner_x = vec![F::zero(); n];
+        self.inner_y = vec![F::zero(); n];
 
         //Route the single wires so we do not need to handle this case later on
         self.inner_y[n - 1] = self.y_inputs[n - 1];
@@ -158,7 +158,7 @@ impl SortingNetwork {
 
 // Computes the control bits of the sorting network which transform inputs into outputs
 // implementation is based on https://www.mdpi.com/2227-7080/10/1/16
-pub(super) fn route(inputs: Vec<FieldElement>, outputs: Vec<FieldElement>) -> Vec<bool> {
+pub(super) fn route<F: AcirField>(inputs: Vec<F>, outputs: Vec<F>) -> Vec<bool> {
     assert_eq!(inputs.len(), outputs.len());
     match inputs.len() {
         0 => Vec::new(),
@@ -213,7 +213,7 @@ pub(super) fn route(inputs: Vec<FieldElement>, outputs: Vec<FieldElement>) -> Ve
                     }
 
                     // loop from the sibling
-                    let next = SortingNetwork::sibling(in_idx);
+                    let next = SortingNetwork::<F>::sibling(in_idx);
                     // connect the input wire to its matching output, using the other sub-network
                     out_idx = network.route_in_wire(next, !start_sub);
                     switch = Some(out_idx / 2);
@@ -223,7 +223,7 @@ pub(super) fn route(inputs: Vec<FieldElement>, outputs: Vec<FieldElement>) -> Ve
                         switch = start;
                     } else {
                         // we loop back from the sibling
-                        out_idx = SortingNetwork::sibling(out_idx);
+                        out_idx = SortingNetwork::<F>::sibling(out_idx);
                     }
                 }
                 //All the wires are connected, we can now route the sub-networks
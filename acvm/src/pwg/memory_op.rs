@@ -3,27 +3,32 @@ use std::collections::HashMap;
 use acir::{
     circuit::opcodes::MemOp,
     native_types::{Expression, Witness, WitnessMap},
-    FieldElement,
+    AcirField,
 };
 
-use super::{arithmetic::ArithmeticSolver, get_value, insert_value, witness_to_value};
+use super::{get_value, insert_value, witness_to_value};
 use super::{ErrorLocation, OpcodeResolutionError};
 
 type MemoryIndex = u32;
 
 /// Maintains the state for solving [`MemoryInit`][`acir::circuit::Opcode::MemoryInit`] and [`MemoryOp`][`acir::circuit::Opcode::MemoryOp`] opcodes.
-#[derive(Default)]
-pub(super) struct MemoryOpSolver {
-    block_value: HashMap<MemoryIndex, FieldElement>,
+pub(super) struct MemoryOpSolver<F> {
+    block_value: HashMap<MemoryIndex, F>,
     block_len: u32,
 }
 
-impl MemoryOpSolver {
+impl<F> Default for MemoryOpSolver<F> {
+    fn default() -> Self {
+        Self { block_value: HashMap::new(), block_len: 0 }
+    }
+}
+
+impl<F: AcirField> MemoryOpSolver<F> {
     fn write_memory_index(
         &mut self,
         index: MemoryIndex,
-        value: FieldElement,
-    ) -> Result<(), OpcodeResolutionError> {
+        value: F,
+    ) -> Result<(), OpcodeResolutionError<F>> {
         if index >= self.block_len {
             return Err(OpcodeResolutionError::IndexOutOfBounds {
                 opcode_location: ErrorLocation::Unresolved,
@@ -35,7 +40,7 @@ impl MemoryOpSolver {
         Ok(())
     }
 
-    fn read_memory_index(&self, index: MemoryIndex) -> Result<FieldElement, OpcodeResolutionError> {
+    fn read_memory_index(&self, index: MemoryIndex) -> Result<F, OpcodeResolutionError<F>> {
         self.block_value.get(&index).copied().ok_or(OpcodeResolutionError::IndexOutOfBounds {
             opcode_location: ErrorLocation::Unresolved,
             index,
@@ -47,8 +52,8 @@ impl MemoryOpSolver {
     pub(crate) fn init(
         &mut self,
         init: &[Witness],
-        initial_witness: &WitnessMap,
-    ) -> Result<(), OpcodeResolutionError> {
+        initial_witness: &WitnessMap<F>,
+    ) -> Result<(), OpcodeResolutionError<F>> {
         self.block_len = init.len() as u32;
         for (memory_index, witness) in init.iter().enumerate() {
             self.write_memory_index(
@@ -61,10 +66,10 @@ impl MemoryOpSolver {
 
     pub(crate) fn solve_memory_op(
         &mut self,
-        op: &MemOp,
-        initial_witness: &mut WitnessMap,
-        predicate: &Option<Expression>,
-    ) -> Result<(), OpcodeResolutionError> {
+        op: &MemOp<F>,
+        initial_witness: &mut WitnessMap<F>,
+        predicate: &Option<Expression<F>>,
+    ) -> Result<(), OpcodeResolutionError<F>> {
         let operation = get_value(&op.operation, initial_witness)?;
 
         // Find the memory index associated with this memory operation.
@@ -75,7 +80,7 @@ impl MemoryOpSolver {
         //
         // In read operations, this corresponds to the witness index at which the value from memory will be written.
         // In write operations, this corresponds to the expression which will be written to memory.
-        let value = ArithmeticSolver::evaluate(&op.value, initial_witness);
+        let value = op.value.evaluate(initial_witness);
 
         // `operation == 0` implies a read operation. (`operation == 1` implies write operation).
         let is_read_operation = operation.is_zero();
@@ -83,7 +88,7 @@ impl MemoryOpSolver {
         // If the predicate is `None`, then we simply return the value 1
         let pred_value = match predicate {
             Some(pred) => get_value(pred, initial_witness),
-            None => Ok(FieldElement::one()),
+            None => Ok(F::one()),
         }?;
 
         if is_read_operation {
@@ -98,7 +103,7 @@ impl MemoryOpSolver {
             // A zero predicate indicates that we should skip the read operation
             // and zero out the operation's output.
             let value_in_array = if pred_value.is_zero() {
-                FieldElement::zero()
+                F::zero()
             } else {
                 self.read_memory_index(memory_index)?
             };
@@ -6,7 +6,7 @@ use acir::{
     brillig::ForeignCallResult,
     circuit::{opcodes::BlockId, Opcode, OpcodeLocation},
     native_types::{Expression, Witness, WitnessMap},
-    BlackBoxFunc, FieldElement,
+    AcirField, BlackBoxFunc, FieldElement,
 };
 use acvm_blackbox_solver::BlackBoxResolutionError;
 
@@ -27,11 +27,14 @@ mod directives;
 // black box functions
 mod blackbox;
 mod memory_op;
+// Parallel solving of independent opcodes
+#[cfg(feature = "parallel")]
+mod parallel;
 
-pub use brillig::ForeignCallWaitInfo;
+pub use brillig::{BrilligBytecodeCache, ForeignCallWaitInfo};
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum ACVMStatus {
+pub enum ACVMStatus<F = FieldElement> {
     /// All opcodes have been solved.
     Solved,
 
@@ -40,17 +43,17 @@ pub enum ACVMStatus {
 
     /// The ACVM has encountered an irrecoverable error while executing the circuit and can not progress.
     /// Most commonly this will be due to an unsatisfied constraint due to invalid inputs to the circuit.
-    Failure(OpcodeResolutionError),
+    Failure(OpcodeResolutionError<F>),
 
     /// The ACVM has encountered a request for a Brillig [foreign call][acir::brillig_vm::Opcode::ForeignCall]
     /// to retrieve information from outside of the ACVM. The result of the foreign call must be passed back
     /// to the ACVM using [`ACVM::resolve_pending_foreign_call`].
     ///
     /// Once this is done, the ACVM can be restarted to solve the remaining opcodes.
-    RequiresForeignCall(ForeignCallWaitInfo),
+    RequiresForeignCall(ForeignCallWaitInfo<F>),
 }
 
-impl std::fmt::Display for ACVMStatus {
+impl<F: AcirField> std::fmt::Display for ACVMStatus<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ACVMStatus::Solved => write!(f, "Solved"),
@@ -70,11 +73,11 @@ impl std::fmt::Display for ACVMStatus {
 // TODO: we could have a error enum for arithmetic failure cases in that module
 // TODO that can be converted into an OpcodeNotSolvable or OpcodeResolutionError enum
 #[derive(Clone, PartialEq, Eq, Debug, Error)]
-pub enum OpcodeNotSolvable {
+pub enum OpcodeNotSolvable<F = FieldElement> {
     #[error("missing assignment for witness index {0}")]
     MissingAssignment(u32),
     #[error("expression has too many unknowns {0}")]
-    ExpressionHasTooManyUnknowns(Expression),
+    ExpressionHasTooManyUnknowns(Expression<F>),
 }
 
 /// Allows to point to a specific opcode as cause in errors.
@@ -97,14 +100,84 @@ impl std::fmt::Display for ErrorLocation {
     }
 }
 
+/// Describes why the constraint imposed by an opcode could not be satisfied.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ConstraintFailure<F = FieldElement> {
+    /// An arithmetic expression does not evaluate to zero.
+    Arithmetic {
+        /// The expression after substituting in the values of all assigned witnesses.
+        expression: Expression<F>,
+        /// The values of the witnesses in the original expression.
+        witness_values: WitnessMap<F>,
+    },
+    /// A witness does not fit within the number of bits allowed by a range constraint.
+    Range { witness: Witness, value: F, num_bits: u32 },
+    /// A value does not fit in the number of limbs available for its radix decomposition.
+    RadixDecomposition { value: F, radix: u32, num_limbs: usize },
+    /// A witness was computed to have a different value to the one already assigned to it.
+    ConflictingAssignment {
+        witness: Witness,
+        old_value: F,
+        new_value: F,
+        /// The values of the witnesses from which `new_value` was computed, where known.
+        witness_values: WitnessMap<F>,
+    },
+}
+
+impl<F: AcirField> std::fmt::Display for ConstraintFailure<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstraintFailure::Arithmetic { expression, witness_values } => {
+                write!(f, "expression {expression} does not evaluate to zero")?;
+                write_witness_values(f, witness_values)
+            }
+            ConstraintFailure::Range { witness, value, num_bits } => write!(
+                f,
+                "witness _{} has value {value} which does not fit in {num_bits} bits",
+                witness.witness_index()
+            ),
+            ConstraintFailure::RadixDecomposition { value, radix, num_limbs } => {
+                write!(
+                    f,
+                    "value {value} cannot be decomposed into {num_limbs} limbs of radix {radix}"
+                )
+            }
+            ConstraintFailure::ConflictingAssignment {
+                witness,
+                old_value,
+                new_value,
+                witness_values,
+            } => {
+                write!(
+                    f,
+                    "witness _{} was assigned {old_value} but was computed to be {new_value}",
+                    witness.witness_index()
+                )?;
+                write_witness_values(f, witness_values)
+            }
+        }
+    }
+}
+
+fn write_witness_values<F: AcirField>(
+    f: &mut std::fmt::Formatter<'_>,
+    witness_values: &WitnessMap<F>,
+) -> std::fmt::Result {
+    for (i, (witness, value)) in witness_values.iter().enumerate() {
+        let separator = if i == 0 { " given " } else { ", " };
+        write!(f, "{separator}_{} = {value}", witness.witness_index())?;
+    }
+    Ok(())
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Error)]
-pub enum OpcodeResolutionError {
+pub enum OpcodeResolutionError<F = FieldElement> {
     #[error("Cannot solve opcode: {0}")]
-    OpcodeNotSolvable(#[from] OpcodeNotSolvable),
+    OpcodeNotSolvable(#[from] OpcodeNotSolvable<F>),
     #[error("Backend does not currently support the {0} opcode. ACVM does not currently have a fallback for this opcode.")]
     UnsupportedBlackBoxFunc(BlackBoxFunc),
-    #[error("Cannot satisfy constraint")]
-    UnsatisfiedConstrain { opcode_location: ErrorLocation },
+    #[error("Cannot satisfy constraint: {reason}")]
+    UnsatisfiedConstrain { opcode_location: ErrorLocation, reason: Box<ConstraintFailure<F>> },
     #[error("Index out of bounds, array has size {array_size:?}, but index was {index:?}")]
     IndexOutOfBounds { opcode_location: ErrorLocation, index: u32, array_size: u32 },
     #[error("Failed to solve blackbox function: {0}, reason: {1}")]
@@ -113,7 +186,7 @@ pub enum OpcodeResolutionError {
     BrilligFunctionFailed { message: String, call_stack: Vec<OpcodeLocation> },
 }
 
-impl From<BlackBoxResolutionError> for OpcodeResolutionError {
+impl<F: AcirField> From<BlackBoxResolutionError> for OpcodeResolutionError<F> {
     fn from(value: BlackBoxResolutionError) -> Self {
         match value {
             BlackBoxResolutionError::Failed(func, reason) => {
@@ -126,44 +199,76 @@ impl From<BlackBoxResolutionError> for OpcodeResolutionError {
     }
 }
 
-pub struct ACVM<'backend, B: BlackBoxFunctionSolver> {
-    status: ACVMStatus,
+pub struct ACVM<'backend, B: BlackBoxFunctionSolver<F>, F = FieldElement> {
+    status: ACVMStatus<F>,
 
     backend: &'backend B,
 
     /// Stores the solver for memory operations acting on blocks of memory disambiguated by [block][`BlockId`].
-    block_solvers: HashMap<BlockId, MemoryOpSolver>,
+    block_solvers: HashMap<BlockId, MemoryOpSolver<F>>,
 
     /// A list of opcodes which are to be executed by the ACVM.
-    opcodes: Vec<Opcode>,
+    opcodes: Vec<Opcode<F>>,
+    /// The decoded bytecode of each [Brillig][Opcode::Brillig] opcode which has been executed.
+    ///
+    /// This is decoded once and reused whenever the opcode is solved again after resolving a foreign call,
+    /// or may be provided up front through [`ACVM::with_brillig_bytecode`] to reuse it across executions.
+    brillig_bytecode: BrilligBytecodeCache<F>,
     /// Index of the next opcode to be executed.
     instruction_pointer: usize,
 
-    witness_map: WitnessMap,
+    witness_map: WitnessMap<F>,
 }
 
-impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
-    pub fn new(backend: &'backend B, opcodes: Vec<Opcode>, initial_witness: WitnessMap) -> Self {
+impl<'backend, B: BlackBoxFunctionSolver<F>, F: AcirField> ACVM<'backend, B, F> {
+    pub fn new(
+        backend: &'backend B,
+        opcodes: Vec<Opcode<F>>,
+        initial_witness: WitnessMap<F>,
+    ) -> Self {
         let status = if opcodes.is_empty() { ACVMStatus::Solved } else { ACVMStatus::InProgress };
         ACVM {
             status,
             backend,
             block_solvers: HashMap::default(),
             opcodes,
+            brillig_bytecode: BrilligBytecodeCache::default(),
             instruction_pointer: 0,
             witness_map: initial_witness,
         }
     }
 
+    /// Executes the circuit's Brillig opcodes using the bytecode already decoded in `cache`,
+    /// rather than decoding it during this execution.
+    ///
+    /// `cache` should have been built from the same opcodes as are being executed by this ACVM.
+    pub fn with_brillig_bytecode(mut self, cache: BrilligBytecodeCache<F>) -> Self {
+        self.brillig_bytecode = cache;
+        self
+    }
+
+    /// Returns the decoded bytecode of the Brillig opcodes executed so far.
+    ///
+    /// This can be passed to [`ACVM::with_brillig_bytecode`] so that later executions of the same circuit
+    /// do not need to decode the bytecode again.
+    pub fn brillig_bytecode(&self) -> &BrilligBytecodeCache<F> {
+        &self.brillig_bytecode
+    }
+
+    /// Returns the current status of the ACVM.
+    pub fn get_status(&self) -> &ACVMStatus<F> {
+        &self.status
+    }
+
     /// Returns a reference to the current state of the ACVM's [`WitnessMap`].
     ///
     /// Once execution has completed, the witness map can be extracted using [`ACVM::finalize`]
-    pub fn witness_map(&self) -> &WitnessMap {
+    pub fn witness_map(&self) -> &WitnessMap<F> {
         &self.witness_map
     }
 
     /// Returns a slice containing the opcodes of the circuit being executed.
-    pub fn opcodes(&self) -> &[Opcode] {
+    pub fn opcodes(&self) -> &[Opcode<F>] {
         &self.opcodes
     }
 
@@ -173,7 +278,7 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
     }
 
     /// Finalize the ACVM execution, returning the resulting [`WitnessMap`].
-    pub fn finalize(self) -> WitnessMap {
+    pub fn finalize(self) -> WitnessMap<F> {
         if self.status != ACVMStatus::Solved {
             panic!("ACVM execution is not complete: ({})", self.status);
         }
@@ -182,25 +287,25 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
 
     /// Updates the current status of the VM.
     /// Returns the given status.
-    fn status(&mut self, status: ACVMStatus) -> ACVMStatus {
+    fn status(&mut self, status: ACVMStatus<F>) -> ACVMStatus<F> {
         self.status = status.clone();
         status
     }
 
     /// Sets the VM status to [ACVMStatus::Failure] using the provided `error`.
     /// Returns the new status.
-    fn fail(&mut self, error: OpcodeResolutionError) -> ACVMStatus {
+    fn fail(&mut self, error: OpcodeResolutionError<F>) -> ACVMStatus<F> {
         self.status(ACVMStatus::Failure(error))
     }
 
     /// Sets the status of the VM to `RequiresForeignCall`.
     /// Indicating that the VM is now waiting for a foreign call to be resolved.
-    fn wait_for_foreign_call(&mut self, foreign_call: ForeignCallWaitInfo) -> ACVMStatus {
+    fn wait_for_foreign_call(&mut self, foreign_call: ForeignCallWaitInfo<F>) -> ACVMStatus<F> {
         self.status(ACVMStatus::RequiresForeignCall(foreign_call))
     }
 
     /// Return a reference to the arguments for the next pending foreign call, if one exists.
-    pub fn get_pending_foreign_call(&self) -> Option<&ForeignCallWaitInfo> {
+    pub fn get_pending_foreign_call(&self) -> Option<&ForeignCallWaitInfo<F>> {
         if let ACVMStatus::RequiresForeignCall(foreign_call) = &self.status {
             Some(foreign_call)
         } else {
@@ -211,7 +316,7 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
     /// Resolves a foreign call's [result][acir::brillig_vm::ForeignCallResult] using a result calculated outside of the ACVM.
     ///
     /// The ACVM can then be restarted to solve the remaining Brillig VM process as well as the remaining ACIR opcodes.
-    pub fn resolve_pending_foreign_call(&mut self, foreign_call_result: ForeignCallResult) {
+    pub fn resolve_pending_foreign_call(&mut self, foreign_call_result: ForeignCallResult<F>) {
         if !matches!(self.status, ACVMStatus::RequiresForeignCall(_)) {
             panic!("ACVM is not expecting a foreign call response as no call was made");
         }
@@ -233,14 +338,14 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
     /// 1. All opcodes have been executed successfully.
     /// 2. The circuit has been found to be unsatisfiable.
     /// 2. A Brillig [foreign call][`ForeignCallWaitInfo`] has been encountered and must be resolved.
-    pub fn solve(&mut self) -> ACVMStatus {
+    pub fn solve(&mut self) -> ACVMStatus<F> {
         while self.status == ACVMStatus::InProgress {
             self.solve_opcode();
         }
         self.status.clone()
     }
 
-    pub fn solve_opcode(&mut self) -> ACVMStatus {
+    pub fn solve_opcode(&mut self) -> ACVMStatus<F> {
         let opcode = &self.opcodes[self.instruction_pointer];
 
         let resolution = match opcode {
@@ -258,9 +363,12 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
                 solver.solve_memory_op(op, &mut self.witness_map, predicate)
             }
             Opcode::Brillig(brillig) => {
+                let bytecode =
+                    self.brillig_bytecode.get_or_decode(self.instruction_pointer, brillig);
                 match BrilligSolver::solve(
                     &mut self.witness_map,
                     brillig,
+                    bytecode,
                     self.backend,
                     self.instruction_pointer,
                 ) {
@@ -269,6 +377,15 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
                 }
             }
         };
+        self.handle_opcode_resolution(resolution)
+    }
+
+    /// Advances the instruction pointer past a successfully solved opcode or fails the ACVM
+    /// with the error attributed to the opcode at the current instruction pointer.
+    fn handle_opcode_resolution(
+        &mut self,
+        resolution: Result<(), OpcodeResolutionError<F>>,
+    ) -> ACVMStatus<F> {
         match resolution {
             Ok(()) => {
                 self.instruction_pointer += 1;
@@ -289,6 +406,7 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
                     }
                     | OpcodeResolutionError::UnsatisfiedConstrain {
                         opcode_location: opcode_index,
+                        ..
                     } => {
                         *opcode_index = ErrorLocation::Resolved(OpcodeLocation::Acir(
                             self.instruction_pointer(),
@@ -306,10 +424,10 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
 // Returns the concrete value for a particular witness
 // If the witness has no assignment, then
 // an error is returned
-pub fn witness_to_value(
-    initial_witness: &WitnessMap,
+pub fn witness_to_value<F: AcirField>(
+    initial_witness: &WitnessMap<F>,
     witness: Witness,
-) -> Result<&FieldElement, OpcodeResolutionError> {
+) -> Result<&F, OpcodeResolutionError<F>> {
     match initial_witness.get(&witness) {
         Some(value) => Ok(value),
         None => Err(OpcodeNotSolvable::MissingAssignment(witness.0).into()),
@@ -318,11 +436,11 @@ pub fn witness_to_value(
 
 // TODO: There is an issue open to decide on whether we need to get values from Expressions
 // TODO versus just getting values from Witness
-pub fn get_value(
-    expr: &Expression,
-    initial_witness: &WitnessMap,
-) -> Result<FieldElement, OpcodeResolutionError> {
-    let expr = ArithmeticSolver::evaluate(expr, initial_witness);
+pub fn get_value<F: AcirField>(
+    expr: &Expression<F>,
+    initial_witness: &WitnessMap<F>,
+) -> Result<F, OpcodeResolutionError<F>> {
+    let expr = expr.evaluate(initial_witness);
     match expr.to_const() {
         Some(value) => Ok(value),
         None => Err(OpcodeResolutionError::OpcodeNotSolvable(
@@ -335,11 +453,24 @@ pub fn get_value(
 ///
 /// Returns an error if there was already a value in the map
 /// which does not match the value that one is about to insert
-pub fn insert_value(
+pub fn insert_value<F: AcirField>(
+    witness: &Witness,
+    value_to_insert: F,
+    initial_witness: &mut WitnessMap<F>,
+) -> Result<(), OpcodeResolutionError<F>> {
+    insert_computed_value(witness, value_to_insert, WitnessMap::new(), initial_witness)
+}
+
+/// Inserts `value` into the initial witness map under the index `witness`, as with [`insert_value`].
+///
+/// `inputs` holds the values of the witnesses from which `value_to_insert` was computed and
+/// is reported alongside any conflicting assignment.
+pub(crate) fn insert_computed_value<F: AcirField>(
     witness: &Witness,
-    value_to_insert: FieldElement,
-    initial_witness: &mut WitnessMap,
-) -> Result<(), OpcodeResolutionError> {
+    value_to_insert: F,
+    inputs: WitnessMap<F>,
+    initial_witness: &mut WitnessMap<F>,
+) -> Result<(), OpcodeResolutionError<F>> {
     let optional_old_value = initial_witness.insert(*witness, value_to_insert);
 
     let old_value = match optional_old_value {
@@ -350,6 +481,12 @@ pub fn insert_value(
     if old_value != value_to_insert {
         return Err(OpcodeResolutionError::UnsatisfiedConstrain {
             opcode_location: ErrorLocation::Unresolved,
+            reason: Box::new(ConstraintFailure::ConflictingAssignment {
+                witness: *witness,
+                old_value,
+                new_value: value_to_insert,
+                witness_values: inputs,
+            }),
         });
     }
 
@@ -359,7 +496,7 @@ pub fn insert_value(
 // Returns one witness belonging to an expression, in no relevant order
 // Returns None if the expression is const
 // The function is used during partial witness generation to report unsolved witness
-fn any_witness_from_expression(expr: &Expression) -> Option<Witness> {
+fn any_witness_from_expression<F: AcirField>(expr: &Expression<F>) -> Option<Witness> {
     if expr.linear_combinations.is_empty() {
         if expr.mul_terms.is_empty() {
             None
@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use acir::{
+    circuit::{
+        brillig::{BrilligInputs, BrilligOutputs},
+        directives::{Directive, QuotientDirective},
+        opcodes::BlackBoxFuncCall,
+        Opcode,
+    },
+    native_types::{Expression, Witness, WitnessMap},
+    AcirField,
+};
+use rayon::prelude::*;
+
+use super::{blackbox, ACVMStatus, OpcodeResolutionError, ACVM};
+use crate::BlackBoxFunctionSolver;
+
+/// The maximum number of opcodes past the instruction pointer which are searched for black box function calls
+/// to solve ahead of time, bounding the cost of each search.
+const LOOKAHEAD: usize = 4096;
+
+/// The assignments made by solving a black box function call, along with the result of solving it.
+type Resolution<F> = (WitnessMap<F>, Result<(), OpcodeResolutionError<F>>);
+
+impl<'backend, B: BlackBoxFunctionSolver<F> + Sync, F: AcirField> ACVM<'backend, B, F> {
+    /// Executes the ACVM's circuit until execution halts, solving mutually independent black box
+    /// function calls concurrently on the current [rayon] thread pool.
+    ///
+    /// Whenever the next opcode is a black box function call, every upcoming call whose inputs are already
+    /// known, and whose outputs are not written by any opcode preceding it, is solved concurrently. The results
+    /// of each call are applied only once the instruction pointer reaches it, while all other opcodes are solved
+    /// sequentially, exactly as in [`ACVM::solve`]. The resulting witness assignments and [status][ACVMStatus]
+    /// are therefore identical to those of [`ACVM::solve`], including the
+    /// [location][acir::circuit::OpcodeLocation] reported for a failing opcode.
+    ///
+    /// The number of threads used can be controlled by calling this method from within
+    /// [`rayon::ThreadPool::install`].
+    pub fn solve_parallel(&mut self) -> ACVMStatus<F> {
+        let mut solved_ahead: HashMap<usize, Resolution<F>> = HashMap::new();
+        while self.status == ACVMStatus::InProgress {
+            let index = self.instruction_pointer;
+            if matches!(self.opcodes[index], Opcode::BlackBoxFuncCall(_))
+                && !solved_ahead.contains_key(&index)
+            {
+                let ready = self.ready_black_box_calls(&solved_ahead);
+                solved_ahead.extend(self.solve_black_box_calls(ready));
+            }
+
+            match solved_ahead.remove(&index) {
+                Some((solved_witnesses, resolution)) => {
+                    for (witness, value) in solved_witnesses {
+                        self.witness_map.insert(witness, value);
+                    }
+                    self.handle_opcode_resolution(resolution);
+                }
+                None => {
+                    self.solve_opcode();
+                }
+            }
+        }
+        self.status.clone()
+    }
+
+    /// Returns the indices of the upcoming black box function calls which have not yet been solved and
+    /// which can be solved now, in isolation, with the same result as when the instruction pointer reaches them.
+    ///
+    /// A call can be solved early if all of its inputs are already assigned, as assignments are never changed,
+    /// and if none of its outputs may be assigned by an opcode preceding it.
+    fn ready_black_box_calls(&self, solved_ahead: &HashMap<usize, Resolution<F>>) -> Vec<usize> {
+        let end = self.opcodes.len().min(self.instruction_pointer + LOOKAHEAD);
+        let mut pending_witnesses: HashSet<Witness> = HashSet::new();
+        let mut ready = Vec::new();
+        for index in self.instruction_pointer..end {
+            let opcode = &self.opcodes[index];
+            if let Opcode::BlackBoxFuncCall(bb_func) = opcode {
+                let inputs_known = bb_func
+                    .get_inputs_vec()
+                    .iter()
+                    .all(|input| self.witness_map.contains_key(&input.witness));
+                let outputs_pending = bb_func
+                    .get_outputs_vec()
+                    .iter()
+                    .any(|output| pending_witnesses.contains(output));
+                if inputs_known && !outputs_pending && !solved_ahead.contains_key(&index) {
+                    ready.push(index);
+                }
+            }
+            // Calls which are solved ahead have not yet assigned their outputs either.
+            pending_witnesses.extend(
+                opcode_witnesses(opcode)
+                    .into_iter()
+                    .filter(|witness| !self.witness_map.contains_key(witness)),
+            );
+        }
+        ready
+    }
+
+    /// Solves the black box function calls at each of `indices` concurrently.
+    fn solve_black_box_calls(&self, indices: Vec<usize>) -> Vec<(usize, Resolution<F>)> {
+        let witness_map = &self.witness_map;
+        let backend = self.backend;
+        indices
+            .into_par_iter()
+            .map(|index| {
+                let Opcode::BlackBoxFuncCall(bb_func) = &self.opcodes[index] else {
+                    unreachable!("only black box function calls are solved ahead");
+                };
+                (index, solve_in_isolation(backend, witness_map, bb_func))
+            })
+            .collect()
+    }
+}
+
+/// Returns every witness referenced by `opcode`, which includes any witness it may assign when solved.
+fn opcode_witnesses<F>(opcode: &Opcode<F>) -> Vec<Witness> {
+    match opcode {
+        Opcode::Arithmetic(expr) => expression_witnesses(expr).collect(),
+        Opcode::BlackBoxFuncCall(bb_func) => {
+            let inputs = bb_func.get_inputs_vec().into_iter().map(|input| input.witness);
+            inputs.chain(bb_func.get_outputs_vec()).collect()
+        }
+        Opcode::Directive(Directive::Quotient(QuotientDirective { a, b, q, r, predicate })) => {
+            let expressions = [a, b].into_iter().chain(predicate);
+            expressions.flat_map(expression_witnesses).chain([*q, *r]).collect()
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, b, .. }) => {
+            expression_witnesses(a).chain(b.iter().copied()).collect()
+        }
+        Opcode::Directive(Directive::PermutationSort { inputs, bits, .. }) => {
+            let expressions = inputs.iter().flatten();
+            expressions.flat_map(expression_witnesses).chain(bits.iter().copied()).collect()
+        }
+        Opcode::MemoryInit { init, .. } => init.clone(),
+        Opcode::MemoryOp { op, predicate, .. } => {
+            let expressions = [&op.operation, &op.index, &op.value].into_iter().chain(predicate);
+            expressions.flat_map(expression_witnesses).collect()
+        }
+        Opcode::Brillig(brillig) => {
+            let inputs = brillig.inputs.iter().flat_map(|input| match input {
+                BrilligInputs::Single(expr) => std::slice::from_ref(expr),
+                BrilligInputs::Array(exprs) => exprs.as_slice(),
+            });
+            let outputs = brillig.outputs.iter().flat_map(|output| match output {
+                BrilligOutputs::Simple(witness) => std::slice::from_ref(witness),
+                BrilligOutputs::Array(witnesses) => witnesses.as_slice(),
+            });
+            inputs
+                .chain(&brillig.predicate)
+                .flat_map(expression_witnesses)
+                .chain(outputs.copied())
+                .collect()
+        }
+    }
+}
+
+fn expression_witnesses<F>(expr: &Expression<F>) -> impl Iterator<Item = Witness> + '_ {
+    let mul_terms = expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]);
+    mul_terms.chain(expr.linear_combinations.iter().map(|(_, witness)| *witness))
+}
+
+/// Solves `bb_func` against a copy of the assignments for only the witnesses which it reads or writes.
+///
+/// Returns the resulting assignments to be merged back into the ACVM's [`WitnessMap`]. These are returned
+/// even if solving fails, as the sequential solver keeps any assignments made before the failure.
+fn solve_in_isolation<F: AcirField>(
+    backend: &impl BlackBoxFunctionSolver<F>,
+    witness_map: &WitnessMap<F>,
+    bb_func: &BlackBoxFuncCall,
+) -> Resolution<F> {
+    let inputs = bb_func.get_inputs_vec().into_iter().map(|input| input.witness);
+    let mut local_witness_map = WitnessMap::new();
+    for witness in inputs.chain(bb_func.get_outputs_vec()) {
+        if let Some(value) = witness_map.get(&witness) {
+            local_witness_map.insert(witness, *value);
+        }
+    }
+
+    let resolution = blackbox::solve(backend, &mut local_witness_map, bb_func);
+    (local_witness_map, resolution)
+}
@@ -0,0 +1,155 @@
+use acir::{
+    circuit::{
+        directives::{Directive, QuotientDirective},
+        Circuit, Opcode,
+    },
+    native_types::{Expression, Witness, WitnessMap},
+    FieldElement,
+};
+use acvm::{
+    compiler::{compile_with_options, CompileOptions},
+    pwg::{ACVMStatus, ACVM},
+    Language,
+};
+use acvm_blackbox_solver::testing::MockSolver;
+use proptest::prelude::*;
+use std::collections::BTreeMap;
+
+/// Solves `directive` both as a directive and as the Brillig opcode which replaces it,
+/// returning the solved witnesses, or `None` if solving failed.
+fn solve_both_ways(
+    directive: Directive,
+    inputs: BTreeMap<Witness, FieldElement>,
+) -> (Option<WitnessMap>, Option<WitnessMap>) {
+    let circuit = Circuit {
+        current_witness_index: 1000,
+        opcodes: vec![Opcode::Directive(directive)],
+        ..Circuit::default()
+    };
+    let options = CompileOptions { replace_directives: true, ..CompileOptions::default() };
+    let (brillig_circuit, _) =
+        compile_with_options(circuit.clone(), Language::PLONKCSat { width: 3 }, |_| true, options)
+            .unwrap();
+    assert!(matches!(brillig_circuit.opcodes.as_slice(), [Opcode::Brillig(_)]));
+
+    let solve = |opcodes: Vec<Opcode>| {
+        let backend = MockSolver::new();
+        let mut acvm = ACVM::new(&backend, opcodes, inputs.clone().into());
+        match acvm.solve() {
+            ACVMStatus::Solved => Some(acvm.finalize()),
+            ACVMStatus::Failure(_) => None,
+            status => panic!("unexpected solver status {status:?}"),
+        }
+    };
+    (solve(circuit.opcodes), solve(brillig_circuit.opcodes))
+}
+
+fn quotient(predicate: Option<Expression>) -> Directive {
+    Directive::Quotient(QuotientDirective {
+        a: Witness(1).into(),
+        b: Witness(2).into(),
+        q: Witness(3),
+        r: Witness(4),
+        predicate,
+    })
+}
+
+proptest! {
+    #[test]
+    fn quotient_parity(a: u128, b in prop_oneof![Just(0), any::<u128>()], predicate in prop_oneof![Just(None), Just(Some(0_u128)), Just(Some(1))]) {
+        let directive = quotient(predicate.map(|predicate| FieldElement::from(predicate).into()));
+        let inputs = BTreeMap::from([
+            (Witness(1), FieldElement::from(a)),
+            (Witness(2), FieldElement::from(b)),
+        ]);
+
+        let (directive_witness, brillig_witness) = solve_both_ways(directive, inputs);
+        prop_assert!(directive_witness.is_some());
+        prop_assert_eq!(directive_witness, brillig_witness);
+    }
+
+    #[test]
+    fn quotient_of_field_elements_parity(a: u128, b: u128) {
+        // Negated values span the full width of the field.
+        let inputs = BTreeMap::from([
+            (Witness(1), -FieldElement::from(a)),
+            (Witness(2), -FieldElement::from(b)),
+        ]);
+
+        let (directive_witness, brillig_witness) = solve_both_ways(quotient(None), inputs);
+        prop_assert!(directive_witness.is_some());
+        prop_assert_eq!(directive_witness, brillig_witness);
+    }
+
+    #[test]
+    fn to_le_radix_parity(a: u64, radix_bits in 1_u32..=20, num_digits in 1_usize..=8) {
+        let directive = Directive::ToLeRadix {
+            a: Witness(1).into(),
+            b: (2..2 + num_digits as u32).map(Witness).collect(),
+            radix: 1 << radix_bits,
+        };
+        let inputs = BTreeMap::from([(Witness(1), FieldElement::from(a as u128))]);
+
+        // Values which do not fit in the digits must fail to solve in both cases.
+        let (directive_witness, brillig_witness) = solve_both_ways(directive, inputs);
+        prop_assert_eq!(directive_witness, brillig_witness);
+    }
+
+    #[test]
+    fn permutation_sort_parity(
+        tuples in prop::collection::vec(prop::collection::vec(sort_value(), 2), 0..30),
+        sort_by in prop_oneof![Just(vec![0]), Just(vec![1, 0]), Just(vec![0, 1, 2]), Just(vec![2])],
+    ) {
+        // Ties must be broken in the same order.
+        let mut inputs = BTreeMap::new();
+        let mut elements = Vec::new();
+        for (witness, value) in (1..).map(Witness).zip(tuples.iter().flatten()) {
+            inputs.insert(witness, *value);
+            elements.push(Expression::from(witness));
+        }
+        let num_bits = permutation_network_size(tuples.len());
+        let directive = Directive::PermutationSort {
+            inputs: elements.chunks(2).map(<[_]>::to_vec).collect(),
+            tuple: 2,
+            bits: (100..100 + num_bits as u32).map(Witness).collect(),
+            sort_by,
+        };
+
+        let (directive_witness, brillig_witness) = solve_both_ways(directive, inputs);
+        prop_assert!(directive_witness.is_some());
+        prop_assert_eq!(directive_witness, brillig_witness);
+    }
+}
+
+/// Small values leave plenty of ties, while negated values span the full width of the field.
+fn sort_value() -> impl Strategy<Value = FieldElement> {
+    prop_oneof![
+        (0_u128..4).prop_map(FieldElement::from),
+        any::<u128>().prop_map(|value| -FieldElement::from(value)),
+    ]
+}
+
+/// Returns the number of switches in a sorting network for `n` inputs.
+fn permutation_network_size(n: usize) -> usize {
+    match n {
+        0 | 1 => 0,
+        2 => 1,
+        _ => {
+            n / 2
+                + (n - 1) / 2
+                + permutation_network_size(n / 2)
+                + permutation_network_size(n - n / 2)
+        }
+    }
+}
+
+#[test]
+fn to_le_radix_of_field_element_parity() {
+    let directive =
+        Directive::ToLeRadix { a: Witness(1).into(), b: (2..256).map(Witness).collect(), radix: 2 };
+    let inputs = BTreeMap::from([(Witness(1), -FieldElement::one())]);
+
+    let (directive_witness, brillig_witness) = solve_both_ways(directive, inputs);
+    assert!(directive_witness.is_some());
+    assert_eq!(directive_witness, brillig_witness);
+}
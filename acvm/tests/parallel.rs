@@ -0,0 +1,205 @@
+#![cfg(feature = "parallel")]
+
+use std::collections::BTreeMap;
+
+use acir::{
+    circuit::{
+        opcodes::{BlackBoxFuncCall, FunctionInput},
+        Opcode, OpcodeLocation,
+    },
+    native_types::{Expression, Witness, WitnessMap},
+    FieldElement,
+};
+use acvm::pwg::{
+    ACVMStatus, ConstraintFailure, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError, ACVM,
+};
+use acvm_blackbox_solver::testing::MockSolver;
+
+fn byte_input(witness: Witness) -> FunctionInput {
+    FunctionInput { witness, num_bits: 8 }
+}
+
+/// A SHA256 call hashing the single byte held in `input` into the 32 witnesses following `first_output`.
+fn sha256(input: Witness, first_output: u32) -> Opcode {
+    Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 {
+        inputs: vec![byte_input(input)],
+        outputs: (first_output..first_output + 32).map(Witness).collect(),
+    })
+}
+
+fn range(witness: Witness, num_bits: u32) -> Opcode {
+    Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input: FunctionInput { witness, num_bits } })
+}
+
+/// Solves the circuit both sequentially and in parallel, asserting that the two agree.
+fn solve_both_ways(opcodes: Vec<Opcode>, initial_witness: WitnessMap) -> (ACVMStatus, WitnessMap) {
+    let backend = MockSolver::new();
+    let mut sequential = ACVM::new(&backend, opcodes.clone(), initial_witness.clone());
+    let sequential_status = sequential.solve();
+
+    let mut parallel = ACVM::new(&backend, opcodes, initial_witness);
+    let parallel_status = parallel.solve_parallel();
+
+    assert_eq!(parallel_status, sequential_status);
+    assert_eq!(parallel.witness_map(), sequential.witness_map());
+    assert_eq!(parallel.instruction_pointer(), sequential.instruction_pointer());
+    (parallel_status, parallel.witness_map().clone())
+}
+
+#[test]
+fn solves_independent_and_dependent_black_box_calls() {
+    let inputs: Vec<Witness> = (1..=8).map(Witness).collect();
+    let mut opcodes: Vec<Opcode> =
+        inputs.iter().enumerate().map(|(i, input)| sha256(*input, 100 + 32 * i as u32)).collect();
+    // Depends upon the output of the first hash and so cannot share its batch.
+    opcodes.push(sha256(Witness(100), 1000));
+    opcodes.push(range(Witness(1000), 8));
+    opcodes.push(Opcode::Arithmetic(&Expression::<FieldElement>::from(Witness(1)) - Witness(2)));
+    opcodes.push(range(Witness(1), 8));
+    opcodes.push(range(Witness(2), 8));
+
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter(
+        inputs.iter().map(|w| (*w, FieldElement::from(7u128))),
+    ));
+    let (status, witness_map) = solve_both_ways(opcodes, initial_witness);
+
+    assert_eq!(status, ACVMStatus::Solved);
+    assert_eq!(witness_map.len(), 8 + 8 * 32 + 32);
+}
+
+#[test]
+fn solves_black_box_calls_interleaved_with_other_opcodes() {
+    // Each hash is followed by constraints on its input, as in compiled circuits, and the last hash reads
+    // a witness which is only assigned by an arithmetic opcode preceding it.
+    let mut opcodes = Vec::new();
+    for i in 1..=4 {
+        opcodes.push(sha256(Witness(i), 100 * i));
+        opcodes.push(range(Witness(i), 8));
+        opcodes
+            .push(Opcode::Arithmetic(&Expression::<FieldElement>::from(Witness(i)) - Witness(i)));
+    }
+    opcodes.push(Opcode::Arithmetic(&Expression::<FieldElement>::from(Witness(10)) - Witness(1)));
+    opcodes.push(sha256(Witness(10), 1000));
+    opcodes.push(range(Witness(10), 8));
+
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter(
+        (1..=4).map(|i| (Witness(i), FieldElement::from(i as u128))),
+    ));
+    let (status, witness_map) = solve_both_ways(opcodes, initial_witness);
+
+    assert_eq!(status, ACVMStatus::Solved);
+    assert_eq!(witness_map[&Witness(10)], FieldElement::one());
+    assert_eq!(witness_map.len(), 5 + 5 * 32);
+}
+
+#[test]
+fn reports_first_failing_opcode_among_interleaved_opcodes() {
+    let opcodes = vec![
+        sha256(Witness(1), 100),
+        Opcode::Arithmetic(&Expression::<FieldElement>::from(Witness(1)) - Witness(2)),
+        // Solved ahead of the failing arithmetic opcode, but must not be applied.
+        sha256(Witness(3), 300),
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(3u128)),
+    ]));
+    let (status, witness_map) = solve_both_ways(opcodes, initial_witness);
+
+    assert!(matches!(
+        status,
+        ACVMStatus::Failure(OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+            ..
+        })
+    ));
+    assert!(witness_map.contains_key(&Witness(100)));
+    assert!(!witness_map.contains_key(&Witness(300)));
+}
+
+#[test]
+fn reports_first_failing_opcode_in_batch() {
+    let opcodes = vec![
+        sha256(Witness(1), 100),
+        range(Witness(2), 8),
+        sha256(Witness(3), 200),
+        range(Witness(4), 8),
+        sha256(Witness(5), 300),
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(3u128)),
+        (Witness(4), FieldElement::from(256u128)),
+        (Witness(5), FieldElement::from(5u128)),
+    ]));
+    let (status, witness_map) = solve_both_ways(opcodes, initial_witness);
+
+    assert_eq!(
+        status,
+        ACVMStatus::Failure(OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(3)),
+            reason: Box::new(ConstraintFailure::Range {
+                witness: Witness(4),
+                value: FieldElement::from(256u128),
+                num_bits: 8,
+            }),
+        })
+    );
+    // Opcodes preceding the failure have been solved but later ones have not.
+    assert!(witness_map.contains_key(&Witness(200)));
+    assert!(!witness_map.contains_key(&Witness(300)));
+}
+
+#[test]
+fn reports_missing_assignments_in_batch() {
+    let opcodes = vec![sha256(Witness(1), 100), sha256(Witness(2), 200)];
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+    let (status, _) = solve_both_ways(opcodes, initial_witness);
+
+    assert_eq!(
+        status,
+        ACVMStatus::Failure(OpcodeResolutionError::OpcodeNotSolvable(
+            OpcodeNotSolvable::MissingAssignment(2)
+        ))
+    );
+}
+
+#[test]
+fn detects_conflicts_with_existing_assignments() {
+    let opcodes = vec![sha256(Witness(1), 100), sha256(Witness(2), 200)];
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::one()),
+        (Witness(2), FieldElement::one()),
+        // The second hash's first output byte is not zero.
+        (Witness(200), FieldElement::zero()),
+    ]));
+    let (status, _) = solve_both_ways(opcodes, initial_witness);
+
+    assert_eq!(
+        status,
+        ACVMStatus::Failure(OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+            reason: Box::new(ConstraintFailure::ConflictingAssignment {
+                witness: Witness(200),
+                old_value: FieldElement::zero(),
+                new_value: FieldElement::from(0x4bu128),
+                witness_values: WitnessMap::new(),
+            }),
+        })
+    );
+}
+
+#[test]
+fn respects_thread_pool_configuration() {
+    let opcodes: Vec<Opcode> = (1..=4).map(|i| sha256(Witness(i), 100 * i)).collect();
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter(
+        (1..=4).map(|i| (Witness(i), FieldElement::from(i as u128))),
+    ));
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, initial_witness);
+    assert_eq!(pool.install(|| acvm.solve_parallel()), ACVMStatus::Solved);
+}
@@ -1,49 +1,28 @@
 use std::collections::BTreeMap;
 
 use acir::{
-    brillig::{BinaryFieldOp, Opcode as BrilligOpcode, RegisterIndex, RegisterOrMemory, Value},
+    brillig::{
+        BinaryFieldOp, BlackBoxOp, HeapArray, Opcode as BrilligOpcode, RegisterIndex,
+        RegisterOrMemory, Value,
+    },
     circuit::{
         brillig::{Brillig, BrilligInputs, BrilligOutputs},
-        opcodes::{BlockId, MemOp},
-        Opcode, OpcodeLocation,
+        opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+        Circuit, Opcode, OpcodeLocation,
     },
     native_types::{Expression, Witness, WitnessMap},
-    FieldElement,
+    AcirField, BlackBoxFunc, FieldElement,
 };
 
 use acvm::{
-    pwg::{ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, ACVM},
-    BlackBoxFunctionSolver,
+    compiler::{compile_with_options, CompileOptions},
+    pwg::{
+        ACVMStatus, BrilligBytecodeCache, ConstraintFailure, ErrorLocation, ForeignCallWaitInfo,
+        OpcodeResolutionError, ACVM,
+    },
+    Language,
 };
-use acvm_blackbox_solver::BlackBoxResolutionError;
-
-pub(crate) struct StubbedBackend;
-
-impl BlackBoxFunctionSolver for StubbedBackend {
-    fn schnorr_verify(
-        &self,
-        _public_key_x: &FieldElement,
-        _public_key_y: &FieldElement,
-        _signature: &[u8],
-        _message: &[u8],
-    ) -> Result<bool, BlackBoxResolutionError> {
-        panic!("Path not trodden by this test")
-    }
-    fn pedersen(
-        &self,
-        _inputs: &[FieldElement],
-        _domain_separator: u32,
-    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
-        panic!("Path not trodden by this test")
-    }
-    fn fixed_base_scalar_mul(
-        &self,
-        _low: &FieldElement,
-        _high: &FieldElement,
-    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
-        panic!("Path not trodden by this test")
-    }
-}
+use acvm_blackbox_solver::{testing::MockSolver, CacheStatistics, CachingSolver};
 
 // Reenable these test cases once we move the brillig implementation of inversion down into the acvm stdlib.
 
@@ -130,7 +109,8 @@ fn inversion_brillig_oracle_equivalence() {
     ])
     .into();
 
-    let mut acvm = ACVM::new(&StubbedBackend, opcodes, witness_assignments);
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, witness_assignments);
     // use the partial witness generation solver with our acir program
     let solver_status = acvm.solve();
 
@@ -145,7 +125,8 @@ fn inversion_brillig_oracle_equivalence() {
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
     // As caller of VM, need to resolve foreign calls
-    let foreign_call_result = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let foreign_call_result =
+        Value::from(foreign_call_wait_info.inputs[0].values()[0].to_field().inverse());
     // Alter Brillig oracle opcode with foreign call resolution
     acvm.resolve_pending_foreign_call(foreign_call_result.into());
 
@@ -260,7 +241,8 @@ fn double_inversion_brillig_oracle() {
     ])
     .into();
 
-    let mut acvm = ACVM::new(&StubbedBackend, opcodes, witness_assignments);
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, witness_assignments);
 
     // use the partial witness generation solver with our acir program
     let solver_status = acvm.solve();
@@ -274,7 +256,8 @@ fn double_inversion_brillig_oracle() {
         acvm.get_pending_foreign_call().expect("should have a brillig foreign call request");
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
-    let x_plus_y_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let x_plus_y_inverse =
+        Value::from(foreign_call_wait_info.inputs[0].values()[0].to_field().inverse());
 
     // Resolve Brillig foreign call
     acvm.resolve_pending_foreign_call(x_plus_y_inverse.into());
@@ -291,7 +274,8 @@ fn double_inversion_brillig_oracle() {
         acvm.get_pending_foreign_call().expect("should have a brillig foreign call request");
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
-    let i_plus_j_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let i_plus_j_inverse =
+        Value::from(foreign_call_wait_info.inputs[0].values()[0].to_field().inverse());
     assert_ne!(x_plus_y_inverse, i_plus_j_inverse);
 
     // Alter Brillig oracle opcode
@@ -381,7 +365,8 @@ fn oracle_dependent_execution() {
     let witness_assignments =
         BTreeMap::from([(w_x, FieldElement::from(2u128)), (w_y, FieldElement::from(2u128))]).into();
 
-    let mut acvm = ACVM::new(&StubbedBackend, opcodes, witness_assignments);
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, witness_assignments);
 
     // use the partial witness generation solver with our acir program
     let solver_status = acvm.solve();
@@ -396,7 +381,7 @@ fn oracle_dependent_execution() {
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
     // Resolve Brillig foreign call
-    let x_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let x_inverse = Value::from(foreign_call_wait_info.inputs[0].values()[0].to_field().inverse());
     acvm.resolve_pending_foreign_call(x_inverse.into());
 
     // After filling data request, continue solving
@@ -412,7 +397,7 @@ fn oracle_dependent_execution() {
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
     // Resolve Brillig foreign call
-    let y_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let y_inverse = Value::from(foreign_call_wait_info.inputs[0].values()[0].to_field().inverse());
     acvm.resolve_pending_foreign_call(y_inverse.into());
 
     // We've resolved all the brillig foreign calls so we should be able to complete execution now.
@@ -480,7 +465,8 @@ fn brillig_oracle_predicate() {
     ])
     .into();
 
-    let mut acvm = ACVM::new(&StubbedBackend, opcodes, witness_assignments);
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, witness_assignments);
     let solver_status = acvm.solve();
     assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
 
@@ -513,12 +499,17 @@ fn unsatisfied_opcode_resolved() {
     values.insert(d, FieldElement::from(2_i128));
 
     let opcodes = vec![Opcode::Arithmetic(opcode_a)];
-    let mut acvm = ACVM::new(&StubbedBackend, opcodes, values);
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, values.clone());
     let solver_status = acvm.solve();
     assert_eq!(
         solver_status,
         ACVMStatus::Failure(OpcodeResolutionError::UnsatisfiedConstrain {
             opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(0)),
+            reason: Box::new(ConstraintFailure::Arithmetic {
+                expression: Expression::from_field(-FieldElement::one()),
+                witness_values: values,
+            }),
         }),
         "The first opcode is not satisfiable, expected an error indicating this"
     );
@@ -595,7 +586,8 @@ fn unsatisfied_opcode_resolved_brillig() {
 
     let opcodes = vec![brillig_opcode, Opcode::Arithmetic(opcode_a)];
 
-    let mut acvm = ACVM::new(&StubbedBackend, opcodes, values);
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, values);
     let solver_status = acvm.solve();
     assert_eq!(
         solver_status,
@@ -639,10 +631,190 @@ fn memory_operations() {
 
     let opcodes = vec![init, read_op, expression];
 
-    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness);
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, opcodes, initial_witness);
     let solver_status = acvm.solve();
     assert_eq!(solver_status, ACVMStatus::Solved);
     let witness_map = acvm.finalize();
 
     assert_eq!(witness_map[&Witness(8)], FieldElement::from(6u128));
 }
+
+/// Solves `w_sum = w_x + w_y` using a Brillig call and checks the result with an arithmetic opcode.
+fn solve_brillig_sum<F: AcirField>(x: F, y: F) -> WitnessMap<F> {
+    let w_x = Witness(1);
+    let w_y = Witness(2);
+    let w_sum = Witness(3);
+
+    let brillig_opcode = Opcode::Brillig(Brillig {
+        inputs: vec![
+            BrilligInputs::Single(Expression::from(w_x)),
+            BrilligInputs::Single(Expression::from(w_y)),
+        ],
+        outputs: vec![BrilligOutputs::Simple(w_sum)],
+        bytecode: vec![BrilligOpcode::BinaryFieldOp {
+            op: BinaryFieldOp::Add,
+            lhs: RegisterIndex::from(0),
+            rhs: RegisterIndex::from(1),
+            destination: RegisterIndex::from(0),
+        }],
+        predicate: None,
+        foreign_call_results: vec![],
+    });
+    let assert_sum = Opcode::Arithmetic(Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![(F::one(), w_x), (F::one(), w_y), (-F::one(), w_sum)],
+        q_c: F::zero(),
+    });
+
+    let witness_assignments = BTreeMap::from([(w_x, x), (w_y, y)]).into();
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, vec![brillig_opcode, assert_sum], witness_assignments);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    acvm.finalize()
+}
+
+#[test]
+fn solves_circuits_over_multiple_fields() {
+    use acir::acir_field::GoldilocksFieldElement;
+
+    // `-1` wraps around to a different value in each field.
+    let bn254_witness = solve_brillig_sum(-FieldElement::one(), FieldElement::from(3u128));
+    assert_eq!(bn254_witness[&Witness(3)], FieldElement::from(2u128));
+
+    let goldilocks_witness =
+        solve_brillig_sum(-GoldilocksFieldElement::one(), GoldilocksFieldElement::from(3u128));
+    assert_eq!(goldilocks_witness[&Witness(3)], GoldilocksFieldElement::from(2u128));
+    assert_ne!(FieldElement::modulus(), GoldilocksFieldElement::modulus());
+}
+
+#[test]
+fn lowered_range_constraints_are_solvable() {
+    let circuit = Circuit {
+        current_witness_index: 0,
+        opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: Witness(0), num_bits: 20 },
+        })],
+        ..Circuit::default()
+    };
+    let options = CompileOptions { range_lookup_bits: Some(8), ..CompileOptions::default() };
+    let (circuit, _) =
+        compile_with_options(circuit, Language::PLONKCSat { width: 3 }, |_| true, options).unwrap();
+    assert!(circuit.opcodes.iter().all(|opcode| match opcode {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) => input.num_bits <= 8,
+        _ => true,
+    }));
+
+    let solve = |value: u128| {
+        let witness_assignments = BTreeMap::from([(Witness(0), FieldElement::from(value))]).into();
+        let backend = MockSolver::new();
+        let mut acvm = ACVM::new(&backend, circuit.opcodes.clone(), witness_assignments);
+        acvm.solve()
+    };
+    assert_eq!(solve((1 << 20) - 1), ACVMStatus::Solved);
+    assert!(matches!(solve(1 << 20), ACVMStatus::Failure(_)));
+}
+
+#[test]
+fn caching_solver_is_shared_by_acvm_and_brillig() {
+    let [w_low, w_high, w_acir_x, w_acir_y, w_brillig_x, w_brillig_y] =
+        [1, 2, 3, 4, 5, 6].map(Witness);
+
+    let acir_scalar_mul = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::FixedBaseScalarMul {
+        low: FunctionInput { witness: w_low, num_bits: 128 },
+        high: FunctionInput { witness: w_high, num_bits: 128 },
+        outputs: (w_acir_x, w_acir_y),
+    });
+    let brillig_scalar_mul = Opcode::Brillig(Brillig {
+        inputs: vec![
+            BrilligInputs::Single(Expression::from(w_low)),
+            BrilligInputs::Single(Expression::from(w_high)),
+        ],
+        outputs: vec![BrilligOutputs::Array(vec![w_brillig_x, w_brillig_y])],
+        bytecode: vec![
+            BrilligOpcode::Const { destination: RegisterIndex::from(2), value: Value::from(0u128) },
+            BrilligOpcode::BlackBox(BlackBoxOp::FixedBaseScalarMul {
+                low: RegisterIndex::from(0),
+                high: RegisterIndex::from(1),
+                result: HeapArray { pointer: RegisterIndex::from(2), size: 2 },
+            }),
+            BrilligOpcode::Mov {
+                destination: RegisterIndex::from(0),
+                source: RegisterIndex::from(2),
+            },
+        ],
+        predicate: None,
+        foreign_call_results: vec![],
+    });
+
+    // A stand-in for `fixed_base_scalar_mul` which is easy to check.
+    let scalar_mul =
+        MockSolver::new().on_fixed_base_scalar_mul(|low, high| Ok((*low + *high, *low * *high)));
+    let backend = CachingSolver::new(scalar_mul, 16);
+    let witness_assignments =
+        BTreeMap::from([(w_low, FieldElement::from(3u128)), (w_high, FieldElement::from(5u128))])
+            .into();
+    let mut acvm =
+        ACVM::new(&backend, vec![acir_scalar_mul, brillig_scalar_mul], witness_assignments);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+
+    assert_eq!(witness_map[&w_acir_x], FieldElement::from(8u128));
+    assert_eq!(witness_map[&w_acir_y], FieldElement::from(15u128));
+    assert_eq!(witness_map[&w_brillig_x], witness_map[&w_acir_x]);
+    assert_eq!(witness_map[&w_brillig_y], witness_map[&w_acir_y]);
+
+    // The Brillig call is answered from the result cached when solving the ACIR opcode.
+    assert_eq!(
+        backend.statistics(BlackBoxFunc::FixedBaseScalarMul),
+        CacheStatistics { hits: 1, misses: 1 }
+    );
+}
+
+#[test]
+fn brillig_bytecode_is_decoded_once_across_executions() {
+    let w_x = Witness(1);
+    let w_y = Witness(2);
+    let w_sum = Witness(3);
+    let opcodes = vec![Opcode::Brillig(Brillig {
+        inputs: vec![
+            BrilligInputs::Single(Expression::from(w_x)),
+            BrilligInputs::Single(Expression::from(w_y)),
+        ],
+        outputs: vec![BrilligOutputs::Simple(w_sum)],
+        bytecode: vec![BrilligOpcode::BinaryFieldOp {
+            op: BinaryFieldOp::Add,
+            lhs: RegisterIndex::from(0),
+            rhs: RegisterIndex::from(1),
+            destination: RegisterIndex::from(0),
+        }],
+        predicate: None,
+        foreign_call_results: vec![],
+    })];
+
+    let cache = BrilligBytecodeCache::new(&opcodes);
+    let decoded = cache.get(0).expect("the Brillig opcode should be decoded up front");
+    let backend = MockSolver::new();
+
+    for (x, y) in [(1u128, 2u128), (3, 4)] {
+        let witness_assignments =
+            BTreeMap::from([(w_x, FieldElement::from(x)), (w_y, FieldElement::from(y))]).into();
+        let mut acvm = ACVM::new(&backend, opcodes.clone(), witness_assignments)
+            .with_brillig_bytecode(cache.clone());
+        assert_eq!(acvm.solve(), ACVMStatus::Solved);
+
+        // Decoding allocates new bytecode, so sharing the cached allocation shows nothing was decoded again.
+        let executed = acvm.brillig_bytecode().get(0).unwrap();
+        assert!(std::ptr::eq(executed.opcodes().as_ptr(), decoded.opcodes().as_ptr()));
+        assert_eq!(acvm.witness_map()[&w_sum], FieldElement::from(x + y));
+    }
+
+    // Without a cache, each execution decodes the bytecode itself.
+    let witness_assignments =
+        BTreeMap::from([(w_x, FieldElement::one()), (w_y, FieldElement::one())]).into();
+    let mut acvm = ACVM::new(&backend, opcodes, witness_assignments);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let executed = acvm.brillig_bytecode().get(0).unwrap();
+    assert_eq!(executed, decoded);
+    assert!(!std::ptr::eq(executed.opcodes().as_ptr(), decoded.opcodes().as_ptr()));
+}
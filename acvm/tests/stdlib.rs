@@ -1,20 +1,21 @@
 #![cfg(feature = "testing")]
-mod solver;
-use crate::solver::StubbedBackend;
 use acir::{
     circuit::{
         opcodes::{BlackBoxFuncCall, FunctionInput},
         Circuit, Opcode,
     },
     native_types::{Expression, Witness},
-    FieldElement,
+    BlackBoxFunc, FieldElement,
 };
 use acvm::{
     compiler::compile,
-    pwg::{ACVMStatus, ACVM},
+    pwg::{ACVMStatus, OpcodeResolutionError, ACVM},
     Language,
 };
-use acvm_blackbox_solver::{blake2s, hash_to_field_128_security, keccak256, sha256};
+use acvm_blackbox_solver::{
+    blake2s, blake3, ecdsa_secp256k1_recover, embedded_curve_add, hash_to_field_128_security,
+    keccak256, sha256, sha512, testing::MockSolver, variable_base_scalar_mul,
+};
 use paste::paste;
 use proptest::prelude::*;
 use std::collections::{BTreeMap, BTreeSet};
@@ -74,7 +75,8 @@ macro_rules! test_uint_inner {
                 let uint = $uint::new(w);
                 let (w, extra_opcodes, _) = uint.rol(y, 2);
                 let witness_assignments = BTreeMap::from([(Witness(1), fe)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, extra_opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, extra_opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&w.get_inner()).unwrap(), &FieldElement::from(result as u128));
@@ -89,7 +91,8 @@ macro_rules! test_uint_inner {
                 let uint = $uint::new(w);
                 let (w, extra_opcodes, _) = uint.ror(y, 2);
                 let witness_assignments = BTreeMap::from([(Witness(1), fe)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, extra_opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, extra_opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&w.get_inner()).unwrap(), &FieldElement::from(result as u128));
@@ -109,7 +112,8 @@ macro_rules! test_uint_inner {
                 let u32_2 = $uint::new(w2);
                 let (q_w, r_w, extra_opcodes, _) = $uint::euclidean_division(&u32_1, &u32_2, 3);
                 let witness_assignments = BTreeMap::from([(Witness(1), lhs),(Witness(2), rhs)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, extra_opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, extra_opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&q_w.get_inner()).unwrap(), &FieldElement::from(q as u128));
@@ -135,7 +139,8 @@ macro_rules! test_uint_inner {
                 let (w2, extra_opcodes, _) = w.add(&u32_3, num_witness);
                 opcodes.extend(extra_opcodes);
                 let witness_assignments = BTreeMap::from([(Witness(1), lhs), (Witness(2), rhs), (Witness(3), rhs_z)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&w2.get_inner()).unwrap(), &result);
@@ -160,7 +165,8 @@ macro_rules! test_uint_inner {
                 let (w2, extra_opcodes, _) = w.sub(&u32_3, num_witness);
                 opcodes.extend(extra_opcodes);
                 let witness_assignments = BTreeMap::from([(Witness(1), lhs), (Witness(2), rhs), (Witness(3), rhs_z)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&w2.get_inner()).unwrap(), &result);
@@ -175,7 +181,8 @@ macro_rules! test_uint_inner {
                 let u32_1 = $uint::new(w1);
                 let (w, extra_opcodes, _) = u32_1.leftshift(y, 2);
                 let witness_assignments = BTreeMap::from([(Witness(1), lhs)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, extra_opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, extra_opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&w.get_inner()).unwrap(), &FieldElement::from(result as u128));
@@ -190,7 +197,8 @@ macro_rules! test_uint_inner {
                 let u32_1 = $uint::new(w1);
                 let (w, extra_opcodes, _) = u32_1.rightshift(y, 2);
                 let witness_assignments = BTreeMap::from([(Witness(1), lhs)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, extra_opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, extra_opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&w.get_inner()).unwrap(), &FieldElement::from(result as u128));
@@ -208,7 +216,8 @@ macro_rules! test_uint_inner {
                 let u32_2 = $uint::new(w2);
                 let (w, extra_opcodes, _) = u32_1.less_than_comparison(&u32_2, 3);
                 let witness_assignments = BTreeMap::from([(Witness(1), lhs), (Witness(2), rhs)]).into();
-                let mut acvm = ACVM::new(&StubbedBackend, extra_opcodes, witness_assignments);
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, extra_opcodes, witness_assignments);
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(acvm.witness_map().get(&w.get_inner()).unwrap(), &FieldElement::from(result as u128));
@@ -219,15 +228,89 @@ macro_rules! test_uint_inner {
 }
 
 test_hashes!(test_sha256, sha256, SHA256, does_not_support_sha256);
+test_hashes!(test_sha512, sha512, SHA512, does_not_support_sha512);
 test_hashes!(test_blake2s, blake2s, Blake2s, does_not_support_blake2s);
+test_hashes!(test_blake3, blake3, Blake3, does_not_support_blake3);
 test_hashes!(test_keccak, keccak256, Keccak256, does_not_support_keccak);
 
+/// Checks that the fallback for a hash agrees with `hasher` on messages of each of `lengths`, which are chosen
+/// to straddle the block boundaries that the short random messages of `test_hashes!` do not reach.
+fn test_hash_fallback_at_lengths(
+    hasher: impl Fn(&[u8]) -> Vec<u8>,
+    opcode: impl Fn(Vec<FunctionInput>, Vec<Witness>) -> BlackBoxFuncCall,
+    opcode_support: fn(&Opcode) -> bool,
+    lengths: &[usize],
+) {
+    for &length in lengths {
+        let message: Vec<u8> = (0..length).map(|i| (i * 31 + 7) as u8).collect();
+        let expected = hasher(&message);
+
+        let inputs: Vec<FunctionInput> = (1..=length as u32)
+            .map(|witness| FunctionInput { witness: Witness(witness), num_bits: 8 })
+            .collect();
+        let outputs: Vec<Witness> =
+            (length as u32 + 1..=(length + expected.len()) as u32).map(Witness).collect();
+        let witness_assignments: BTreeMap<Witness, FieldElement> = inputs
+            .iter()
+            .zip(&message)
+            .map(|(input, byte)| (input.witness, FieldElement::from(*byte as u128)))
+            .collect();
+
+        // Constrain each output to the expected digest.
+        let mut opcodes = vec![Opcode::BlackBoxFuncCall(opcode(inputs, outputs.clone()))];
+        for (output, byte) in outputs.iter().zip(&expected) {
+            let mut output_constraint = Expression::from(*output);
+            output_constraint.q_c = -FieldElement::from(*byte as u128);
+            opcodes.push(Opcode::Arithmetic(output_constraint));
+        }
+
+        let circuit = Circuit {
+            current_witness_index: (length + expected.len()) as u32,
+            opcodes,
+            ..Circuit::default()
+        };
+        let circuit = compile(circuit, Language::PLONKCSat { width: 3 }, opcode_support).unwrap().0;
+
+        let backend = MockSolver::new();
+        let mut acvm = ACVM::new(&backend, circuit.opcodes, witness_assignments.into());
+        assert_eq!(acvm.solve(), ACVMStatus::Solved, "failed to hash {length} bytes");
+    }
+}
+
+#[test]
+fn test_sha512_padding_block_boundaries() {
+    // Messages of 112 bytes or more no longer leave room for the length in their last block.
+    test_hash_fallback_at_lengths(
+        |message| sha512(message).unwrap().to_vec(),
+        |inputs, outputs| BlackBoxFuncCall::SHA512 { inputs, outputs },
+        does_not_support_sha512,
+        &[111, 112, 127, 128, 129],
+    );
+}
+
+#[test]
+fn test_blake3_chunk_boundaries() {
+    // Chunks of 1024 bytes span several 64 byte blocks, and messages of several chunks are hashed as a tree.
+    test_hash_fallback_at_lengths(
+        |message| blake3(message).unwrap().to_vec(),
+        |inputs, outputs| BlackBoxFuncCall::Blake3 { inputs, outputs },
+        does_not_support_blake3,
+        &[64, 65, 1024, 1025, 2049],
+    );
+}
+
 fn does_not_support_sha256(opcode: &Opcode) -> bool {
     !matches!(opcode, Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 { .. }))
 }
+fn does_not_support_sha512(opcode: &Opcode) -> bool {
+    !matches!(opcode, Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA512 { .. }))
+}
 fn does_not_support_blake2s(opcode: &Opcode) -> bool {
     !matches!(opcode, Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Blake2s { .. }))
 }
+fn does_not_support_blake3(opcode: &Opcode) -> bool {
+    !matches!(opcode, Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Blake3 { .. }))
+}
 fn does_not_support_keccak(opcode: &Opcode) -> bool {
     !matches!(opcode, Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Keccak256 { .. }))
 }
@@ -266,7 +349,7 @@ macro_rules! test_hashes {
                     witness_assignments.insert(Witness(counter), FieldElement::from(o_v as u128));
                 }
 
-                for _ in 0..32 {
+                for _ in 0..output.len() {
                     counter += 1;
                     output_witnesses.push(Witness(counter));
                 }
@@ -282,7 +365,7 @@ macro_rules! test_hashes {
 
                 // compile circuit
                 let circuit = Circuit {
-                    current_witness_index: witness_assignments.len() as u32 + 32,
+                    current_witness_index: witness_assignments.len() as u32 + output.len() as u32,
                     opcodes,
                     private_parameters: BTreeSet::new(), // This is not correct but is unused in this test.
                     ..Circuit::default()
@@ -290,7 +373,8 @@ macro_rules! test_hashes {
                 let circuit = compile(circuit, Language::PLONKCSat{ width: 3 }, $opcode_support).unwrap().0;
 
                 // solve witnesses
-                let mut acvm = ACVM::new(&StubbedBackend, circuit.opcodes, witness_assignments.into());
+                let backend = MockSolver::new();
+                let mut acvm = ACVM::new(&backend, circuit.opcodes, witness_assignments.into());
                 let solver_status = acvm.solve();
 
                 prop_assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
@@ -346,9 +430,252 @@ proptest! {
         let circuit = compile(circuit, Language::PLONKCSat{ width: 3 }, does_not_support_hash_to_field).unwrap().0;
 
         // solve witnesses
-        let mut acvm = ACVM::new(&StubbedBackend, circuit.opcodes, witness_assignments.into());
+        let backend = MockSolver::new();
+        let mut acvm = ACVM::new(&backend, circuit.opcodes, witness_assignments.into());
         let solver_status = acvm.solve();
 
         prop_assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
     }
 }
+
+/// The generator of Grumpkin, the embedded curve of BN254.
+fn grumpkin_generator() -> (FieldElement, FieldElement) {
+    let y = FieldElement::from_hex(
+        "0x0000000000000002cf135e7506a45d632d270d45f1181294833fc48d823f272c",
+    )
+    .unwrap();
+    (FieldElement::one(), y)
+}
+
+fn function_input(witness: u32) -> FunctionInput {
+    FunctionInput { witness: Witness(witness), num_bits: FieldElement::max_num_bits() }
+}
+
+/// Compiles `blackbox` with its fallback and solves it, returning the values of the witnesses `outputs`.
+fn solve_curve_fallback(
+    blackbox: BlackBoxFuncCall,
+    inputs: Vec<FieldElement>,
+    outputs: (Witness, Witness),
+) -> (FieldElement, FieldElement) {
+    let witness_assignments: BTreeMap<Witness, FieldElement> =
+        inputs.into_iter().enumerate().map(|(i, value)| (Witness(i as u32 + 1), value)).collect();
+    let circuit = Circuit {
+        current_witness_index: outputs.1.witness_index(),
+        opcodes: vec![Opcode::BlackBoxFuncCall(blackbox)],
+        private_parameters: BTreeSet::new(), // This is not correct but is unused in this test.
+        ..Circuit::default()
+    };
+    let circuit = compile(circuit, Language::PLONKCSat { width: 3 }, |opcode| {
+        !matches!(opcode, Opcode::BlackBoxFuncCall(_))
+    })
+    .unwrap()
+    .0;
+
+    let backend = MockSolver::new();
+    let mut acvm = ACVM::new(&backend, circuit.opcodes, witness_assignments.into());
+    assert_eq!(acvm.solve(), ACVMStatus::Solved, "should be fully solved");
+    let witness_map = acvm.finalize();
+    (witness_map[&outputs.0], witness_map[&outputs.1])
+}
+
+fn solve_embedded_curve_add(
+    (x1, y1): (FieldElement, FieldElement),
+    (x2, y2): (FieldElement, FieldElement),
+) -> (FieldElement, FieldElement) {
+    let outputs = (Witness(5), Witness(6));
+    let blackbox = BlackBoxFuncCall::EmbeddedCurveAdd {
+        input1_x: function_input(1),
+        input1_y: function_input(2),
+        input2_x: function_input(3),
+        input2_y: function_input(4),
+        outputs,
+    };
+    solve_curve_fallback(blackbox, vec![x1, y1, x2, y2], outputs)
+}
+
+fn solve_variable_base_scalar_mul(
+    (x, y): (FieldElement, FieldElement),
+    low: FieldElement,
+    high: FieldElement,
+) -> (FieldElement, FieldElement) {
+    let outputs = (Witness(5), Witness(6));
+    let blackbox = BlackBoxFuncCall::VariableBaseScalarMul {
+        point_x: function_input(1),
+        point_y: function_input(2),
+        scalar_low: function_input(3),
+        scalar_high: function_input(4),
+        outputs,
+    };
+    solve_curve_fallback(blackbox, vec![x, y, low, high], outputs)
+}
+
+#[test]
+fn test_embedded_curve_add() {
+    let (gx, gy) = grumpkin_generator();
+    let zero = FieldElement::zero();
+    let (dx, dy) = variable_base_scalar_mul(&gx, &gy, &FieldElement::from(2u128), &zero).unwrap();
+
+    let result = solve_embedded_curve_add((gx, gy), (dx, dy));
+
+    assert_eq!(result, embedded_curve_add(&gx, &gy, &dx, &dy).unwrap());
+}
+
+#[test]
+fn test_embedded_curve_add_doubles_equal_points() {
+    let (gx, gy) = grumpkin_generator();
+
+    let result = solve_embedded_curve_add((gx, gy), (gx, gy));
+
+    assert_eq!(result, embedded_curve_add(&gx, &gy, &gx, &gy).unwrap());
+    assert_ne!(result, (FieldElement::zero(), FieldElement::zero()));
+}
+
+#[test]
+fn test_embedded_curve_add_inverse_points() {
+    let (gx, gy) = grumpkin_generator();
+    let infinity = (FieldElement::zero(), FieldElement::zero());
+
+    assert_eq!(solve_embedded_curve_add((gx, gy), (gx, -gy)), infinity);
+}
+
+#[test]
+fn test_embedded_curve_add_point_at_infinity() {
+    let (gx, gy) = grumpkin_generator();
+    let infinity = (FieldElement::zero(), FieldElement::zero());
+
+    assert_eq!(solve_embedded_curve_add(infinity, (gx, gy)), (gx, gy));
+    assert_eq!(solve_embedded_curve_add((gx, gy), infinity), (gx, gy));
+    assert_eq!(solve_embedded_curve_add(infinity, infinity), infinity);
+}
+
+#[test]
+fn test_variable_base_scalar_mul_edge_cases() {
+    let (gx, gy) = grumpkin_generator();
+    let zero = FieldElement::zero();
+    let infinity = (zero, zero);
+
+    assert_eq!(solve_variable_base_scalar_mul((gx, gy), zero, zero), infinity);
+    assert_eq!(solve_variable_base_scalar_mul((gx, gy), FieldElement::one(), zero), (gx, gy));
+    assert_eq!(solve_variable_base_scalar_mul(infinity, FieldElement::from(5u128), zero), infinity);
+
+    // The order of Grumpkin minus one, for which the last addition discarded by the double-and-add loop is of
+    // the inverse points `-P` and `P`.
+    let low = FieldElement::from(0x97816a916871ca8d3c208c16d87cfd46_u128);
+    let high = FieldElement::from(0x30644e72e131a029b85045b68181585d_u128);
+    assert_eq!(solve_variable_base_scalar_mul((gx, gy), low, high), (gx, -gy));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(3))]
+    #[test]
+    fn test_variable_base_scalar_mul(low in 1..u128::MAX, high in 0..u128::MAX) {
+        let (gx, gy) = grumpkin_generator();
+        let (low, high) = (FieldElement::from(low), FieldElement::from(high));
+
+        let result = solve_variable_base_scalar_mul((gx, gy), low, high);
+
+        prop_assert_eq!(result, variable_base_scalar_mul(&gx, &gy, &low, &high).unwrap());
+    }
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+#[test]
+fn test_ecdsa_secp256k1_recover() {
+    let hashed_message =
+        from_hex("3a73f4123a5cd2121f21cd7e8d358835476949d035d9c2da6806b4633ac8c1e2");
+    let signature = from_hex(
+        "e5081c80ab427dc370346f4a0e31aa2bad8d9798c38061db9ae55a4e8df454fd28119894344e71b78770cc931d61f480ecbb0b89d6eb69690161e49a715fcd55",
+    );
+    let byte_input = |witness: u32| FunctionInput { witness: Witness(witness), num_bits: 8 };
+
+    for recovery_id in [0, 1] {
+        let blackbox = BlackBoxFuncCall::EcdsaSecp256k1Recover {
+            signature: (1..=64).map(byte_input).collect(),
+            recovery_id: byte_input(65),
+            hashed_message: (66..=97).map(byte_input).collect(),
+            public_key_x: (98..=129).map(Witness).collect(),
+            public_key_y: (130..=161).map(Witness).collect(),
+        };
+        let witness_assignments: BTreeMap<Witness, FieldElement> = signature
+            .iter()
+            .chain(&[recovery_id])
+            .chain(&hashed_message)
+            .enumerate()
+            .map(|(i, &byte)| (Witness(i as u32 + 1), FieldElement::from(byte as u128)))
+            .collect();
+        let circuit = Circuit {
+            current_witness_index: 161,
+            opcodes: vec![Opcode::BlackBoxFuncCall(blackbox)],
+            private_parameters: BTreeSet::new(), // This is not correct but is unused in this test.
+            ..Circuit::default()
+        };
+        let circuit = compile(circuit, Language::PLONKCSat { width: 3 }, |opcode| {
+            !matches!(
+                opcode,
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::EcdsaSecp256k1Recover { .. })
+            )
+        })
+        .unwrap()
+        .0;
+
+        let backend = MockSolver::new();
+        let mut acvm = ACVM::new(&backend, circuit.opcodes, witness_assignments.into());
+        assert_eq!(acvm.solve(), ACVMStatus::Solved, "should be fully solved");
+        let witness_map = acvm.finalize();
+
+        let (expected_x, expected_y) = ecdsa_secp256k1_recover(
+            &hashed_message,
+            &signature.clone().try_into().unwrap(),
+            recovery_id,
+        )
+        .unwrap();
+        for (i, byte) in expected_x.iter().chain(&expected_y).enumerate() {
+            assert_eq!(witness_map[&Witness(98 + i as u32)], FieldElement::from(*byte as u128));
+        }
+    }
+}
+
+#[test]
+fn test_ecdsa_secp256k1_recover_rejects_non_boolean_recovery_id() {
+    let hashed_message =
+        from_hex("3a73f4123a5cd2121f21cd7e8d358835476949d035d9c2da6806b4633ac8c1e2");
+    let signature = from_hex(
+        "e5081c80ab427dc370346f4a0e31aa2bad8d9798c38061db9ae55a4e8df454fd28119894344e71b78770cc931d61f480ecbb0b89d6eb69690161e49a715fcd55",
+    );
+    let byte_input = |witness: u32| FunctionInput { witness: Witness(witness), num_bits: 8 };
+
+    // 256 would be read as 0 if the recovery id were truncated to a byte.
+    for recovery_id in [2_u128, 256] {
+        let blackbox = BlackBoxFuncCall::EcdsaSecp256k1Recover {
+            signature: (1..=64).map(byte_input).collect(),
+            recovery_id: byte_input(65),
+            hashed_message: (66..=97).map(byte_input).collect(),
+            public_key_x: (98..=129).map(Witness).collect(),
+            public_key_y: (130..=161).map(Witness).collect(),
+        };
+        let mut witness_assignments: BTreeMap<Witness, FieldElement> = signature
+            .iter()
+            .chain(&hashed_message)
+            .zip((1..=64).chain(66..=97))
+            .map(|(&byte, witness)| (Witness(witness), FieldElement::from(byte as u128)))
+            .collect();
+        witness_assignments.insert(Witness(65), FieldElement::from(recovery_id));
+
+        let backend = MockSolver::new();
+        let mut acvm = ACVM::new(
+            &backend,
+            vec![Opcode::BlackBoxFuncCall(blackbox)],
+            witness_assignments.into(),
+        );
+        assert!(matches!(
+            acvm.solve(),
+            ACVMStatus::Failure(OpcodeResolutionError::BlackBoxFunctionFailed(
+                BlackBoxFunc::EcdsaSecp256k1Recover,
+                _
+            ))
+        ));
+    }
+}
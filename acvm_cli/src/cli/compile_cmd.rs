@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use acvm::{
+    acir::{circuit::Opcode, BlackBoxFunc},
+    compiler::compile,
+    Language,
+};
+use clap::{Args, ValueEnum};
+
+use crate::{
+    errors::CliError,
+    fs::{read_circuit, write_circuit},
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LanguageArg {
+    R1cs,
+    Plonk,
+}
+
+/// Transforms a circuit into a form which can be handled by a backend using the chosen language
+#[derive(Debug, Clone, Args)]
+pub(crate) struct CompileCommand {
+    /// Path to the serialized circuit
+    #[arg(long, short)]
+    circuit: PathBuf,
+
+    /// Path at which to write the compiled circuit
+    #[arg(long, short)]
+    output: PathBuf,
+
+    /// The NP complete language targeted by the backend
+    #[arg(long, value_enum, default_value_t = LanguageArg::Plonk)]
+    language: LanguageArg,
+
+    /// The width of the backend's arithmetic gates when targeting PLONK
+    #[arg(long, default_value_t = 3)]
+    width: usize,
+
+    /// Black box functions which the backend does not support, e.g. `--unsupported sha256,keccak256`
+    #[arg(long, value_delimiter = ',', value_parser = parse_black_box_func)]
+    unsupported: Vec<BlackBoxFunc>,
+
+    /// Whether the backend lacks support for memory opcodes
+    #[arg(long)]
+    no_memory: bool,
+}
+
+fn parse_black_box_func(name: &str) -> Result<BlackBoxFunc, String> {
+    BlackBoxFunc::lookup(name).ok_or_else(|| format!("unknown black box function `{name}`"))
+}
+
+pub(crate) fn run(args: CompileCommand) -> Result<(), CliError> {
+    let circuit = read_circuit(&args.circuit)?;
+
+    let language = match args.language {
+        LanguageArg::R1cs => Language::R1CS,
+        LanguageArg::Plonk if args.width < 3 => return Err(CliError::InvalidWidth),
+        LanguageArg::Plonk => Language::PLONKCSat { width: args.width },
+    };
+    let is_opcode_supported = |opcode: &Opcode| match opcode {
+        Opcode::BlackBoxFuncCall(func) => !args.unsupported.contains(&func.get_black_box_func()),
+        Opcode::MemoryOp { .. } | Opcode::MemoryInit { .. } => !args.no_memory,
+        _ => true,
+    };
+
+    let num_opcodes = circuit.opcodes.len();
+    let (compiled_circuit, _) = compile(circuit, language, is_opcode_supported)?;
+
+    write_circuit(&args.output, &compiled_circuit)?;
+    println!(
+        "Compiled circuit with {num_opcodes} opcodes into {} opcodes, written to {}",
+        compiled_circuit.opcodes.len(),
+        args.output.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use acvm::{
+    acir::{
+        circuit::{Circuit, OpcodeLocation},
+        native_types::WitnessMap,
+    },
+    pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM},
+};
+use clap::Args;
+
+use super::ForeignCallArgs;
+use crate::{
+    errors::CliError,
+    foreign_calls::ForeignCallResolver,
+    fs::{read_circuit, read_witness_map, write_witness_map},
+    solver::black_box_solver,
+};
+
+/// Executes a circuit to calculate its solved witness
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExecuteCommand {
+    /// Path to the serialized circuit
+    #[arg(long, short)]
+    circuit: PathBuf,
+
+    /// Path to the serialized initial witness
+    #[arg(long, short)]
+    witness: PathBuf,
+
+    /// Path at which to write the solved witness
+    #[arg(long, short)]
+    output: PathBuf,
+
+    #[clap(flatten)]
+    foreign_call_args: ForeignCallArgs,
+}
+
+pub(crate) fn run(args: ExecuteCommand) -> Result<(), CliError> {
+    let circuit = read_circuit(&args.circuit)?;
+    let initial_witness = read_witness_map(&args.witness)?;
+    let resolver = args.foreign_call_args.resolver()?;
+
+    let solved_witness = execute_circuit(&circuit, initial_witness, &resolver)?;
+
+    write_witness_map(&args.output, solved_witness)?;
+    println!("Solved witness written to {}", args.output.display());
+    Ok(())
+}
+
+/// Executes `circuit` until all opcodes are solved, resolving any foreign calls using `resolver`.
+pub(crate) fn execute_circuit(
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    resolver: &ForeignCallResolver,
+) -> Result<WitnessMap, CliError> {
+    let blackbox_solver = black_box_solver();
+    let mut acvm = ACVM::new(&blackbox_solver, circuit.opcodes.clone(), initial_witness);
+
+    loop {
+        match acvm.solve() {
+            ACVMStatus::Solved => break,
+            ACVMStatus::InProgress => {
+                unreachable!("Execution should not stop while in `InProgress` state.")
+            }
+            ACVMStatus::Failure(error) => return Err(execution_error(circuit, error)),
+            ACVMStatus::RequiresForeignCall(foreign_call) => {
+                let result = resolver.resolve(&foreign_call)?;
+                acvm.resolve_pending_foreign_call(result);
+            }
+        }
+    }
+
+    Ok(acvm.finalize())
+}
+
+/// Converts an [`OpcodeResolutionError`] into a [`CliError`], attaching the circuit's assert message if one exists.
+fn execution_error(circuit: &Circuit, error: OpcodeResolutionError) -> CliError {
+    let opcode_location = match &error {
+        OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+            ..
+        }
+        | OpcodeResolutionError::IndexOutOfBounds {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+            ..
+        } => Some(*opcode_location),
+        OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
+            call_stack.last().copied()
+        }
+        _ => None,
+    };
+
+    let opcode = opcode_location.map_or_else(|| "unknown".to_owned(), |loc| loc.to_string());
+    match opcode_location.and_then(|loc: OpcodeLocation| circuit.get_assert_message(loc)) {
+        Some(message) => CliError::AssertionFailed { opcode, message: message.to_owned() },
+        None => CliError::ExecutionFailed { opcode, error },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::{
+            brillig::{Opcode as BrilligOpcode, RegisterIndex, RegisterOrMemory},
+            circuit::{
+                brillig::{Brillig, BrilligInputs, BrilligOutputs},
+                Circuit, Opcode, OpcodeLocation,
+            },
+            native_types::{Expression, Witness, WitnessMap},
+        },
+        FieldElement,
+    };
+
+    use super::execute_circuit;
+    use crate::{errors::CliError, foreign_calls::ForeignCallResolver};
+
+    /// Returns a circuit which asks the foreign call `double` for twice its input and then checks the result.
+    fn doubling_circuit() -> Circuit {
+        let brillig = Opcode::Brillig(Brillig {
+            inputs: vec![BrilligInputs::Single(Witness(1).into())],
+            outputs: vec![BrilligOutputs::Simple(Witness(2))],
+            foreign_call_results: vec![],
+            bytecode: vec![BrilligOpcode::ForeignCall {
+                function: "double".to_owned(),
+                destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+                inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+            }],
+            predicate: None,
+        });
+        let check = Opcode::Arithmetic(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::from(2u128), Witness(1)),
+                (-FieldElement::one(), Witness(2)),
+            ],
+            q_c: FieldElement::zero(),
+        });
+
+        Circuit {
+            current_witness_index: 2,
+            opcodes: vec![brillig, check],
+            assert_messages: vec![(OpcodeLocation::Acir(1), "not doubled".to_owned())],
+            ..Circuit::default()
+        }
+    }
+
+    fn mocked(json: &str) -> ForeignCallResolver {
+        ForeignCallResolver::Mocked(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn executes_circuit_with_mocked_foreign_calls() {
+        let resolver = mocked(r#"[{ "function": "double", "outputs": ["0x06"] }]"#);
+        let initial_witness = WitnessMap::from(
+            [(Witness(1), FieldElement::from(3u128))]
+                .into_iter()
+                .collect::<std::collections::BTreeMap<_, _>>(),
+        );
+
+        let solved_witness =
+            execute_circuit(&doubling_circuit(), initial_witness, &resolver).unwrap();
+        assert_eq!(solved_witness[&Witness(2)], FieldElement::from(6u128));
+    }
+
+    #[test]
+    fn reports_assert_message_on_failure() {
+        let resolver = mocked(r#"[{ "function": "double", "outputs": ["0x07"] }]"#);
+        let initial_witness = WitnessMap::from(
+            [(Witness(1), FieldElement::from(3u128))]
+                .into_iter()
+                .collect::<std::collections::BTreeMap<_, _>>(),
+        );
+
+        let error = execute_circuit(&doubling_circuit(), initial_witness, &resolver).unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::AssertionFailed { opcode, message } if opcode == "1" && message == "not doubled"
+        ));
+    }
+}
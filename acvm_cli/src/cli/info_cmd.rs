@@ -0,0 +1,133 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use acvm::acir::circuit::{Circuit, Opcode};
+use clap::Args;
+use serde::Serialize;
+
+use crate::{errors::CliError, fs::read_circuit};
+
+/// Prints information about a circuit along with statistics on the opcodes it contains
+#[derive(Debug, Clone, Args)]
+pub(crate) struct InfoCommand {
+    /// Path to the serialized circuit
+    #[arg(long, short)]
+    circuit: PathBuf,
+
+    /// Output the information as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+struct CircuitInfo {
+    current_witness_index: u32,
+    private_parameters: usize,
+    public_parameters: usize,
+    return_values: usize,
+    assert_messages: usize,
+    total_opcodes: usize,
+    /// Number of opcodes of each kind, keyed by [`Opcode::name`].
+    opcodes: BTreeMap<String, usize>,
+    /// Number of quadratic terms across all arithmetic opcodes.
+    mul_terms: usize,
+    /// Number of linear terms across all arithmetic opcodes.
+    linear_terms: usize,
+    /// Number of Brillig opcodes across all Brillig bytecode in the circuit.
+    brillig_opcodes: usize,
+}
+
+impl CircuitInfo {
+    fn new(circuit: &Circuit) -> Self {
+        let mut info = CircuitInfo {
+            current_witness_index: circuit.current_witness_index,
+            private_parameters: circuit.private_parameters.len(),
+            public_parameters: circuit.public_parameters.0.len(),
+            return_values: circuit.return_values.0.len(),
+            assert_messages: circuit.assert_messages.len(),
+            total_opcodes: circuit.opcodes.len(),
+            ..CircuitInfo::default()
+        };
+
+        for opcode in &circuit.opcodes {
+            *info.opcodes.entry(opcode.name().to_owned()).or_default() += 1;
+            match opcode {
+                Opcode::Arithmetic(expr) => {
+                    info.mul_terms += expr.mul_terms.len();
+                    info.linear_terms += expr.linear_combinations.len();
+                }
+                Opcode::Brillig(brillig) => info.brillig_opcodes += brillig.bytecode.len(),
+                _ => (),
+            }
+        }
+        info
+    }
+}
+
+impl std::fmt::Display for CircuitInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "current witness index : {}", self.current_witness_index)?;
+        writeln!(f, "private parameters    : {}", self.private_parameters)?;
+        writeln!(f, "public parameters     : {}", self.public_parameters)?;
+        writeln!(f, "return values         : {}", self.return_values)?;
+        writeln!(f, "assert messages       : {}", self.assert_messages)?;
+        writeln!(f, "total opcodes         : {}", self.total_opcodes)?;
+        for (name, count) in &self.opcodes {
+            writeln!(f, "  {name:<30} {count}")?;
+        }
+        writeln!(f, "arithmetic mul terms   : {}", self.mul_terms)?;
+        writeln!(f, "arithmetic linear terms: {}", self.linear_terms)?;
+        writeln!(f, "brillig opcodes       : {}", self.brillig_opcodes)
+    }
+}
+
+pub(crate) fn run(args: InfoCommand) -> Result<(), CliError> {
+    let circuit = read_circuit(&args.circuit)?;
+    let info = CircuitInfo::new(&circuit);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&info).expect("info is always serializable"));
+    } else {
+        print!("{info}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::{
+            circuit::{
+                opcodes::{BlackBoxFuncCall, FunctionInput},
+                Circuit, Opcode,
+            },
+            native_types::{Expression, Witness},
+        },
+        FieldElement,
+    };
+
+    use super::CircuitInfo;
+
+    #[test]
+    fn counts_opcodes_by_name() {
+        let range = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: Witness(1), num_bits: 8 },
+        });
+        let arithmetic = Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: vec![(FieldElement::one(), Witness(3))],
+            q_c: FieldElement::zero(),
+        });
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![range.clone(), arithmetic, range],
+            ..Circuit::default()
+        };
+
+        let info = CircuitInfo::new(&circuit);
+        assert_eq!(info.total_opcodes, 3);
+        assert_eq!(info.opcodes["range"], 2);
+        assert_eq!(info.opcodes["arithmetic"], 1);
+        assert_eq!(info.mul_terms, 1);
+        assert_eq!(info.linear_terms, 1);
+    }
+}
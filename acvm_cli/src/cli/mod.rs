@@ -0,0 +1,70 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::{errors::CliError, foreign_calls::ForeignCallResolver};
+
+mod compile_cmd;
+mod execute_cmd;
+mod info_cmd;
+mod print_cmd;
+mod verify_cmd;
+
+const ACVM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Parser, Debug)]
+#[command(name = "acvm-cli", author, version = ACVM_VERSION, about, long_about = None)]
+struct AcvmCli {
+    #[command(subcommand)]
+    command: AcvmCommand,
+}
+
+#[non_exhaustive]
+#[derive(Subcommand, Clone, Debug)]
+enum AcvmCommand {
+    Execute(execute_cmd::ExecuteCommand),
+    Compile(compile_cmd::CompileCommand),
+    Info(info_cmd::InfoCommand),
+    Verify(verify_cmd::VerifyCommand),
+    Print(print_cmd::PrintCommand),
+}
+
+/// Arguments which control how foreign calls are resolved during execution.
+#[derive(Args, Clone, Debug, Default)]
+pub(crate) struct ForeignCallArgs {
+    /// JSON file containing mocked results for foreign calls
+    #[arg(long, conflicts_with = "oracle_command")]
+    foreign_calls: Option<PathBuf>,
+
+    /// Program to run for each foreign call, which receives the call as JSON on stdin
+    #[arg(long)]
+    oracle_command: Option<String>,
+
+    /// Argument to pass to the oracle command, which may be repeated
+    #[arg(long = "oracle-arg", requires = "oracle_command", allow_hyphen_values = true)]
+    oracle_args: Vec<String>,
+}
+
+impl ForeignCallArgs {
+    pub(crate) fn resolver(&self) -> Result<ForeignCallResolver, CliError> {
+        match (&self.foreign_calls, &self.oracle_command) {
+            (Some(path), _) => ForeignCallResolver::from_mock_file(path),
+            (None, Some(program)) => Ok(ForeignCallResolver::Command {
+                program: program.clone(),
+                args: self.oracle_args.clone(),
+            }),
+            (None, None) => Ok(ForeignCallResolver::Disabled),
+        }
+    }
+}
+
+pub(crate) fn start_cli() -> Result<(), CliError> {
+    let AcvmCli { command } = AcvmCli::parse();
+
+    match command {
+        AcvmCommand::Execute(args) => execute_cmd::run(args),
+        AcvmCommand::Compile(args) => compile_cmd::run(args),
+        AcvmCommand::Info(args) => info_cmd::run(args),
+        AcvmCommand::Verify(args) => verify_cmd::run(args),
+        AcvmCommand::Print(args) => print_cmd::run(args),
+    }
+}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{errors::CliError, fs::read_circuit};
+
+/// Prints a human readable representation of a circuit
+#[derive(Debug, Clone, Args)]
+pub(crate) struct PrintCommand {
+    /// Path to the serialized circuit
+    #[arg(long, short)]
+    circuit: PathBuf,
+}
+
+pub(crate) fn run(args: PrintCommand) -> Result<(), CliError> {
+    let circuit = read_circuit(&args.circuit)?;
+    print!("{circuit}");
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use super::{execute_cmd::execute_circuit, ForeignCallArgs};
+use crate::{
+    errors::CliError,
+    fs::{read_circuit, read_witness_map},
+};
+
+/// Checks that a witness satisfies every constraint in a circuit
+#[derive(Debug, Clone, Args)]
+pub(crate) struct VerifyCommand {
+    /// Path to the serialized circuit
+    #[arg(long, short)]
+    circuit: PathBuf,
+
+    /// Path to the serialized witness to be checked
+    #[arg(long, short)]
+    witness: PathBuf,
+
+    #[clap(flatten)]
+    foreign_call_args: ForeignCallArgs,
+}
+
+pub(crate) fn run(args: VerifyCommand) -> Result<(), CliError> {
+    let circuit = read_circuit(&args.circuit)?;
+    let witness = read_witness_map(&args.witness)?;
+    let resolver = args.foreign_call_args.resolver()?;
+
+    // Executing the circuit with a full witness results in every opcode being checked against the existing
+    // assignments. Any constraint which is not satisfied by the witness will cause execution to fail.
+    let witness_len = witness.len();
+    let solved_witness = execute_circuit(&circuit, witness, &resolver)?;
+
+    let newly_assigned = solved_witness.len() - witness_len;
+    if newly_assigned != 0 {
+        return Err(CliError::IncompleteWitness(newly_assigned));
+    }
+
+    println!("Witness satisfies circuit");
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+
+use acvm::{
+    acir::native_types::WitnessMapError, compiler::CompileError, pwg::OpcodeResolutionError,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub(crate) enum FilesystemError {
+    #[error("Error: {} does not exist", .0.display())]
+    PathNotFound(PathBuf),
+
+    #[error("Error: could not read {}: {1}", .0.display())]
+    ReadFailed(PathBuf, std::io::Error),
+
+    #[error("Error: could not write {}: {1}", .0.display())]
+    WriteFailed(PathBuf, std::io::Error),
+
+    #[error("Error: {} is not a valid serialized circuit: {1}", .0.display())]
+    InvalidCircuit(PathBuf, std::io::Error),
+
+    #[error("Error: {} is not a valid serialized witness map: {1}", .0.display())]
+    InvalidWitnessMap(PathBuf, WitnessMapError),
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum ForeignCallError {
+    #[error("Error: {} is not a valid foreign call file: {1}", .0.display())]
+    InvalidMockFile(PathBuf, serde_json::Error),
+
+    #[error("Error: could not parse field element {0:?}")]
+    InvalidFieldElement(String),
+
+    #[error("Error: no mocked result found for foreign call `{0}`")]
+    NoMatchingMock(String),
+
+    #[error("Error: failed to run foreign call command `{0}`: {1}")]
+    CommandFailed(String, std::io::Error),
+
+    #[error("Error: foreign call command `{0}` exited with {1}")]
+    CommandExited(String, std::process::ExitStatus),
+
+    #[error("Error: foreign call command `{0}` returned invalid output: {1}")]
+    InvalidCommandOutput(String, serde_json::Error),
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum CliError {
+    #[error(transparent)]
+    FilesystemError(#[from] FilesystemError),
+
+    #[error(transparent)]
+    ForeignCallError(#[from] ForeignCallError),
+
+    #[error("Error: failed to compile circuit: {0}")]
+    CompileError(#[from] CompileError),
+
+    #[error("Error: circuit execution failed at opcode {opcode}: {error}")]
+    ExecutionFailed { opcode: String, error: OpcodeResolutionError },
+
+    #[error("Error: assertion failed at opcode {opcode}: {message}")]
+    AssertionFailed { opcode: String, message: String },
+
+    #[error("Error: witness is incomplete, {0} witnesses were assigned during verification")]
+    IncompleteWitness(usize),
+
+    #[error("Error: the width of a PLONK circuit must be at least 3")]
+    InvalidWidth,
+}
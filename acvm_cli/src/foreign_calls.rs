@@ -0,0 +1,285 @@
+//! Foreign calls made by Brillig opcodes are resolved outside of the ACVM.
+//!
+//! The CLI can answer them either from a JSON file of mocked results or by delegating to a local subprocess.
+//!
+//! A mock file is a JSON array of entries of the form
+//! `{ "function": "get_number", "inputs": [["0x01"]], "outputs": ["0x02", ["0x03", "0x04"]] }`
+//! where `inputs` is optional. An entry without `inputs` will match every call to `function`.
+//!
+//! A foreign call command receives a JSON object `{ "function": ..., "inputs": [[...], ...] }` on stdin
+//! and must write a JSON array of outputs, in the same format as the `outputs` of a mock, to stdout.
+
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use acvm::{
+    acir::brillig::{ForeignCallOutput, ForeignCallResult, Value},
+    pwg::ForeignCallWaitInfo,
+    FieldElement,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{FilesystemError, ForeignCallError};
+
+/// A single output of a foreign call as it is written in JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonForeignCallOutput {
+    Single(String),
+    Array(Vec<String>),
+}
+
+impl JsonForeignCallOutput {
+    fn into_output(self) -> Result<ForeignCallOutput, ForeignCallError> {
+        match self {
+            JsonForeignCallOutput::Single(value) => {
+                Ok(ForeignCallOutput::Single(parse_value(&value)?))
+            }
+            JsonForeignCallOutput::Array(values) => {
+                let values =
+                    values.iter().map(|value| parse_value(value)).collect::<Result<_, _>>()?;
+                Ok(ForeignCallOutput::Array(values))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ForeignCallMock {
+    function: String,
+    #[serde(default)]
+    inputs: Option<Vec<Vec<String>>>,
+    outputs: Vec<JsonForeignCallOutput>,
+}
+
+impl ForeignCallMock {
+    fn matches(&self, foreign_call: &ForeignCallWaitInfo) -> Result<bool, ForeignCallError> {
+        if self.function != foreign_call.function {
+            return Ok(false);
+        }
+        let Some(expected_inputs) = &self.inputs else {
+            return Ok(true);
+        };
+        if expected_inputs.len() != foreign_call.inputs.len() {
+            return Ok(false);
+        }
+        for (expected, actual) in expected_inputs.iter().zip(&foreign_call.inputs) {
+            let expected =
+                expected.iter().map(|value| parse_value(value)).collect::<Result<Vec<_>, _>>()?;
+            if expected != actual.values() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// The JSON request written to the stdin of a foreign call command.
+#[derive(Serialize)]
+struct ForeignCallRequest<'a> {
+    function: &'a str,
+    inputs: Vec<Vec<String>>,
+}
+
+fn parse_value(value: &str) -> Result<Value, ForeignCallError> {
+    // Hex strings are decoded byte by byte, so odd-length strings such as `0x1` are padded with a leading zero.
+    let padded = match value.strip_prefix("0x") {
+        Some(digits) if digits.len() % 2 == 1 => format!("0x0{digits}"),
+        _ => value.to_owned(),
+    };
+    FieldElement::try_from_str(&padded)
+        .map(Value::from)
+        .ok_or_else(|| ForeignCallError::InvalidFieldElement(value.to_owned()))
+}
+
+fn encode_value(value: &Value) -> String {
+    format!("0x{}", value.to_field().to_hex())
+}
+
+fn decode_outputs(
+    outputs: Vec<JsonForeignCallOutput>,
+) -> Result<ForeignCallResult, ForeignCallError> {
+    let values =
+        outputs.into_iter().map(JsonForeignCallOutput::into_output).collect::<Result<_, _>>()?;
+    Ok(ForeignCallResult { values })
+}
+
+/// Determines how the CLI answers foreign calls encountered during execution.
+#[derive(Debug, Clone, Default)]
+pub(crate) enum ForeignCallResolver {
+    /// Any foreign call results in an error.
+    #[default]
+    Disabled,
+    /// Foreign calls are answered from a list of mocked results.
+    Mocked(Vec<ForeignCallMock>),
+    /// Foreign calls are forwarded to a subprocess.
+    Command { program: String, args: Vec<String> },
+}
+
+impl ForeignCallResolver {
+    pub(crate) fn from_mock_file(path: &Path) -> Result<Self, crate::errors::CliError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| FilesystemError::ReadFailed(path.to_path_buf(), err))?;
+        let mocks = serde_json::from_str(&contents)
+            .map_err(|err| ForeignCallError::InvalidMockFile(path.to_path_buf(), err))?;
+        Ok(ForeignCallResolver::Mocked(mocks))
+    }
+
+    pub(crate) fn resolve(
+        &self,
+        foreign_call: &ForeignCallWaitInfo,
+    ) -> Result<ForeignCallResult, ForeignCallError> {
+        match self {
+            ForeignCallResolver::Disabled => {
+                Err(ForeignCallError::NoMatchingMock(foreign_call.function.clone()))
+            }
+            ForeignCallResolver::Mocked(mocks) => {
+                for mock in mocks {
+                    if mock.matches(foreign_call)? {
+                        return decode_outputs(mock.outputs.clone());
+                    }
+                }
+                Err(ForeignCallError::NoMatchingMock(foreign_call.function.clone()))
+            }
+            ForeignCallResolver::Command { program, args } => {
+                Self::resolve_with_command(program, args, foreign_call)
+            }
+        }
+    }
+
+    fn resolve_with_command(
+        program: &str,
+        args: &[String],
+        foreign_call: &ForeignCallWaitInfo,
+    ) -> Result<ForeignCallResult, ForeignCallError> {
+        let request = ForeignCallRequest {
+            function: &foreign_call.function,
+            inputs: foreign_call
+                .inputs
+                .iter()
+                .map(|input| input.values().iter().map(encode_value).collect())
+                .collect(),
+        };
+        let request =
+            serde_json::to_vec(&request).expect("foreign call requests are always serializable");
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| ForeignCallError::CommandFailed(program.to_owned(), err))?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(&request)
+            .map_err(|err| ForeignCallError::CommandFailed(program.to_owned(), err))?;
+
+        let output = child
+            .wait_with_output()
+            .map_err(|err| ForeignCallError::CommandFailed(program.to_owned(), err))?;
+        if !output.status.success() {
+            return Err(ForeignCallError::CommandExited(program.to_owned(), output.status));
+        }
+
+        let outputs: Vec<JsonForeignCallOutput> = serde_json::from_slice(&output.stdout)
+            .map_err(|err| ForeignCallError::InvalidCommandOutput(program.to_owned(), err))?;
+        decode_outputs(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::brillig::{ForeignCallOutput, ForeignCallParam, Value},
+        pwg::ForeignCallWaitInfo,
+    };
+
+    use super::{parse_value, ForeignCallResolver};
+
+    fn mocked(json: &str) -> ForeignCallResolver {
+        ForeignCallResolver::Mocked(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn resolves_mock_with_matching_inputs() {
+        let resolver = mocked(
+            r#"[
+                { "function": "square", "inputs": [["0x02"]], "outputs": ["0x04"] },
+                { "function": "square", "inputs": [["3"]], "outputs": ["9"] },
+                { "function": "range", "outputs": [["0x00", "0x01", "0x02"]] }
+            ]"#,
+        );
+
+        let call = ForeignCallWaitInfo {
+            function: "square".to_owned(),
+            inputs: vec![ForeignCallParam::Single(Value::from(3u128))],
+            input_types: vec![None],
+            destination_types: vec![None],
+        };
+        let result = resolver.resolve(&call).unwrap();
+        assert_eq!(result.values, vec![ForeignCallOutput::Single(Value::from(9u128))]);
+
+        let call = ForeignCallWaitInfo {
+            function: "range".to_owned(),
+            inputs: vec![],
+            input_types: vec![],
+            destination_types: vec![None],
+        };
+        let result = resolver.resolve(&call).unwrap();
+        assert_eq!(
+            result.values,
+            vec![ForeignCallOutput::Array(vec![
+                Value::from(0u128),
+                Value::from(1u128),
+                Value::from(2u128)
+            ])]
+        );
+    }
+
+    #[test]
+    fn errors_on_unknown_foreign_call() {
+        let resolver =
+            mocked(r#"[{ "function": "square", "inputs": [["0x02"]], "outputs": ["0x04"] }]"#);
+
+        let call = ForeignCallWaitInfo {
+            function: "square".to_owned(),
+            inputs: vec![ForeignCallParam::Single(Value::from(5u128))],
+            input_types: vec![None],
+            destination_types: vec![None],
+        };
+        assert!(resolver.resolve(&call).is_err());
+    }
+
+    #[test]
+    fn parses_odd_length_hex_values() {
+        assert_eq!(parse_value("0x1").unwrap(), Value::from(1u128));
+        assert_eq!(parse_value("0x100").unwrap(), Value::from(256u128));
+        assert_eq!(parse_value("0x0100").unwrap(), Value::from(256u128));
+        assert!(parse_value("0xg").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn passes_arguments_to_command_unsplit() {
+        // The script is a single argument even though it contains spaces.
+        let resolver = ForeignCallResolver::Command {
+            program: "sh".to_owned(),
+            args: vec!["-c".to_owned(), r#"cat > /dev/null; echo '["0x1"]'"#.to_owned()],
+        };
+
+        let call = ForeignCallWaitInfo {
+            function: "one".to_owned(),
+            inputs: vec![],
+            input_types: vec![],
+            destination_types: vec![None],
+        };
+        let result = resolver.resolve(&call).unwrap();
+        assert_eq!(result.values, vec![ForeignCallOutput::Single(Value::from(1u128))]);
+    }
+}
@@ -0,0 +1,85 @@
+use std::{fs::File, path::Path};
+
+use acvm::acir::{
+    circuit::Circuit,
+    native_types::{WitnessMap, WitnessMapReader, WitnessMapWriter},
+};
+
+use crate::errors::FilesystemError;
+
+fn read_file(path: &Path) -> Result<Vec<u8>, FilesystemError> {
+    if !path.exists() {
+        return Err(FilesystemError::PathNotFound(path.to_path_buf()));
+    }
+    std::fs::read(path).map_err(|err| FilesystemError::ReadFailed(path.to_path_buf(), err))
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), FilesystemError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| FilesystemError::WriteFailed(path.to_path_buf(), err))?;
+    }
+    std::fs::write(path, bytes).map_err(|err| FilesystemError::WriteFailed(path.to_path_buf(), err))
+}
+
+/// Reads a [`Circuit`] which has been serialized using [`Circuit::write`].
+pub(crate) fn read_circuit(path: &Path) -> Result<Circuit, FilesystemError> {
+    let bytes = read_file(path)?;
+    Circuit::read(&*bytes).map_err(|err| FilesystemError::InvalidCircuit(path.to_path_buf(), err))
+}
+
+pub(crate) fn write_circuit(path: &Path, circuit: &Circuit) -> Result<(), FilesystemError> {
+    let mut bytes = Vec::new();
+    circuit
+        .write(&mut bytes)
+        .map_err(|err| FilesystemError::WriteFailed(path.to_path_buf(), err))?;
+    write_file(path, &bytes)
+}
+
+/// Reads a [`WitnessMap`] from `path`.
+///
+/// Files with a `.json` or `.toml` extension are read as human-readable witness maps,
+/// anything else is expected to be in the compressed binary format.
+pub(crate) fn read_witness_map(path: &Path) -> Result<WitnessMap, FilesystemError> {
+    let invalid_witness_map = |err| FilesystemError::InvalidWitnessMap(path.to_path_buf(), err);
+    match extension(path) {
+        Some("json") => {
+            if !path.exists() {
+                return Err(FilesystemError::PathNotFound(path.to_path_buf()));
+            }
+            let file = File::open(path)
+                .map_err(|err| FilesystemError::ReadFailed(path.to_path_buf(), err))?;
+            WitnessMapReader::new(file).read_witness_map().map_err(invalid_witness_map)
+        }
+        Some("toml") => {
+            let bytes = read_file(path)?;
+            WitnessMap::from_toml(&String::from_utf8_lossy(&bytes)).map_err(invalid_witness_map)
+        }
+        _ => {
+            let bytes = read_file(path)?;
+            WitnessMap::try_from(bytes.as_slice()).map_err(invalid_witness_map)
+        }
+    }
+}
+
+/// Writes `witness_map` to `path`, choosing the format from the file extension as in [`read_witness_map`].
+pub(crate) fn write_witness_map(
+    path: &Path,
+    witness_map: WitnessMap,
+) -> Result<(), FilesystemError> {
+    let invalid_witness_map = |err| FilesystemError::InvalidWitnessMap(path.to_path_buf(), err);
+    let bytes: Vec<u8> = match extension(path) {
+        Some("json") => {
+            let mut writer = WitnessMapWriter::new(Vec::new()).map_err(invalid_witness_map)?;
+            writer.write_witness_map(&witness_map).map_err(invalid_witness_map)?;
+            writer.finish().map_err(invalid_witness_map)?
+        }
+        Some("toml") => witness_map.to_toml().map_err(invalid_witness_map)?.into_bytes(),
+        _ => witness_map.try_into().map_err(invalid_witness_map)?,
+    };
+    write_file(path, &bytes)
+}
+
+fn extension(path: &Path) -> Option<&str> {
+    path.extension().and_then(|extension| extension.to_str())
+}
@@ -0,0 +1,17 @@
+#![warn(unused_crate_dependencies, unused_extern_crates)]
+#![warn(unreachable_pub)]
+
+//! A command line tool for executing, compiling and inspecting serialized ACIR circuits.
+
+mod cli;
+mod errors;
+mod foreign_calls;
+mod fs;
+mod solver;
+
+fn main() {
+    if let Err(error) = cli::start_cli() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+}
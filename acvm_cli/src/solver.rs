@@ -0,0 +1,48 @@
+//! The black box functions which do not have a Rust implementation are solved using Barretenberg.
+//! Building without the `barretenberg` feature avoids the wasm runtime at the cost of reporting these
+//! functions as unsupported.
+
+#[cfg(feature = "barretenberg")]
+#[allow(deprecated)]
+pub(crate) fn black_box_solver() -> barretenberg_blackbox_solver::BarretenbergSolver {
+    barretenberg_blackbox_solver::BarretenbergSolver::new()
+}
+
+#[cfg(not(feature = "barretenberg"))]
+pub(crate) fn black_box_solver() -> UnsupportedBlackBoxSolver {
+    UnsupportedBlackBoxSolver
+}
+
+#[cfg(not(feature = "barretenberg"))]
+pub(crate) struct UnsupportedBlackBoxSolver;
+
+#[cfg(not(feature = "barretenberg"))]
+impl acvm::BlackBoxFunctionSolver for UnsupportedBlackBoxSolver {
+    fn schnorr_verify(
+        &self,
+        _public_key_x: &acvm::FieldElement,
+        _public_key_y: &acvm::FieldElement,
+        _signature: &[u8],
+        _message: &[u8],
+    ) -> Result<bool, acvm::BlackBoxResolutionError> {
+        Err(acvm::BlackBoxResolutionError::Unsupported(acvm::acir::BlackBoxFunc::SchnorrVerify))
+    }
+
+    fn pedersen(
+        &self,
+        _inputs: &[acvm::FieldElement],
+        _domain_separator: u32,
+    ) -> Result<(acvm::FieldElement, acvm::FieldElement), acvm::BlackBoxResolutionError> {
+        Err(acvm::BlackBoxResolutionError::Unsupported(acvm::acir::BlackBoxFunc::Pedersen))
+    }
+
+    fn fixed_base_scalar_mul(
+        &self,
+        _low: &acvm::FieldElement,
+        _high: &acvm::FieldElement,
+    ) -> Result<(acvm::FieldElement, acvm::FieldElement), acvm::BlackBoxResolutionError> {
+        Err(acvm::BlackBoxResolutionError::Unsupported(
+            acvm::acir::BlackBoxFunc::FixedBaseScalarMul,
+        ))
+    }
+}
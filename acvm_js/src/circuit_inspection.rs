@@ -0,0 +1,140 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::acir::{
+    circuit::{Circuit, Opcode},
+    native_types::Witness,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::Error;
+use serde::Serialize;
+use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+
+#[wasm_bindgen(typescript_custom_section)]
+const CIRCUIT: &'static str = r#"
+/**
+* An opcode in the form produced by serializing it to JSON, e.g. `{ Arithmetic: { mul_terms, linear_combinations, q_c } }`.
+* Field elements are represented as hex strings without a `0x` prefix.
+*/
+export type Opcode = { [kind: string]: unknown };
+
+export type AssertMessage = { location: string; message: string };
+
+export type Circuit = {
+  currentWitnessIndex: number;
+  opcodes: Opcode[];
+  privateParameters: number[];
+  publicParameters: number[];
+  returnValues: number[];
+  assertMessages: AssertMessage[];
+};
+
+/**
+* @typedef {Object} OpcodeStatistics - A summary of the contents of a circuit.
+* @property {number} totalOpcodes - The number of opcodes in the circuit.
+* @property {Record<string, number>} opcodes - The number of opcodes of each kind, keyed by the opcode's name.
+* @property {number} currentWitnessIndex - The highest witness index used in the circuit.
+* @property {number} privateParameters - The number of private parameters to the circuit.
+* @property {number} publicInputs - The number of public parameters and return values of the circuit.
+*/
+export type OpcodeStatistics = {
+  totalOpcodes: number;
+  opcodes: Record<string, number>;
+  currentWitnessIndex: number;
+  privateParameters: number;
+  publicInputs: number;
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Circuit")]
+    pub type JsCircuit;
+
+    #[wasm_bindgen(typescript_type = "OpcodeStatistics")]
+    pub type JsOpcodeStatistics;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CircuitModel<'a> {
+    current_witness_index: u32,
+    opcodes: &'a [Opcode],
+    private_parameters: &'a BTreeSet<Witness>,
+    public_parameters: &'a BTreeSet<Witness>,
+    return_values: &'a BTreeSet<Witness>,
+    assert_messages: Vec<AssertMessage<'a>>,
+}
+
+#[derive(Serialize)]
+struct AssertMessage<'a> {
+    location: String,
+    message: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OpcodeStatistics<'a> {
+    total_opcodes: usize,
+    opcodes: BTreeMap<&'a str, usize>,
+    current_witness_index: u32,
+    private_parameters: usize,
+    public_inputs: usize,
+}
+
+pub(crate) fn read_circuit(circuit: &[u8]) -> Result<Circuit, Error> {
+    Circuit::read(circuit)
+        .map_err(|err| Error::new(&format!("Failed to deserialize circuit: {err}")))
+}
+
+fn to_js_value(value: &impl Serialize) -> Result<JsValue, Error> {
+    <JsValue as JsValueSerdeExt>::from_serde(value).map_err(|err| Error::new(&err.to_string()))
+}
+
+/// Deserializes a circuit into a javascript object.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @returns {Circuit} The contents of the circuit.
+#[wasm_bindgen(js_name = decodeCircuit, skip_jsdoc)]
+pub fn decode_circuit(circuit: Vec<u8>) -> Result<JsCircuit, Error> {
+    console_error_panic_hook::set_once();
+    let circuit = read_circuit(&circuit)?;
+
+    let model = CircuitModel {
+        current_witness_index: circuit.current_witness_index,
+        opcodes: &circuit.opcodes,
+        private_parameters: &circuit.private_parameters,
+        public_parameters: &circuit.public_parameters.0,
+        return_values: &circuit.return_values.0,
+        assert_messages: circuit
+            .assert_messages
+            .iter()
+            .map(|(location, message)| AssertMessage { location: location.to_string(), message })
+            .collect(),
+    };
+
+    Ok(to_js_value(&model)?.into())
+}
+
+/// Summarizes the opcodes and witnesses which make up a circuit.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @returns {OpcodeStatistics} A summary of the contents of the circuit.
+#[wasm_bindgen(js_name = getOpcodeStatistics, skip_jsdoc)]
+pub fn get_opcode_statistics(circuit: Vec<u8>) -> Result<JsOpcodeStatistics, Error> {
+    console_error_panic_hook::set_once();
+    let circuit = read_circuit(&circuit)?;
+
+    let mut opcodes = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        *opcodes.entry(opcode.name()).or_default() += 1;
+    }
+    let statistics = OpcodeStatistics {
+        total_opcodes: circuit.opcodes.len(),
+        opcodes,
+        current_witness_index: circuit.current_witness_index,
+        private_parameters: circuit.private_parameters.len(),
+        public_inputs: circuit.public_inputs().0.len(),
+    };
+
+    Ok(to_js_value(&statistics)?.into())
+}
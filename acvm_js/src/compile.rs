@@ -0,0 +1,142 @@
+use std::cell::RefCell;
+
+use acvm::{
+    acir::circuit::{Opcode, OpcodeLocation},
+    compiler::{self, AcirTransformationMap},
+    Language,
+};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{Array, Error, JsString};
+use serde::Deserialize;
+use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+
+use crate::circuit_inspection::read_circuit;
+
+#[wasm_bindgen(typescript_custom_section)]
+const COMPILE: &'static str = r#"
+/**
+* The constraint system targeted by the compiler.
+*/
+export type Language = { kind: "R1CS" } | { kind: "PLONKCSat"; width: number };
+
+/**
+* A callback which returns whether the backend natively supports an opcode.
+* Opcodes which are not supported are replaced by fallback implementations where possible.
+* @callback IsOpcodeSupported
+* @param {Opcode} opcode - The opcode in the form returned by `decodeCircuit`.
+* @returns {boolean} Whether the opcode is supported.
+*/
+export type IsOpcodeSupported = (opcode: Opcode) => boolean;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Language")]
+    pub type JsLanguage;
+
+    #[wasm_bindgen(extends = js_sys::Function, typescript_type = "IsOpcodeSupported")]
+    pub type IsOpcodeSupported;
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind")]
+enum LanguageModel {
+    R1CS,
+    PLONKCSat { width: usize },
+}
+
+impl From<LanguageModel> for Language {
+    fn from(language: LanguageModel) -> Self {
+        match language {
+            LanguageModel::R1CS => Language::R1CS,
+            LanguageModel::PLONKCSat { width } => Language::PLONKCSat { width },
+        }
+    }
+}
+
+/// A circuit which has been compiled for a backend,
+/// along with the mapping from the locations of opcodes in the original circuit to their new locations.
+#[wasm_bindgen]
+pub struct CompiledCircuit {
+    circuit: Vec<u8>,
+    transformation_map: AcirTransformationMap,
+}
+
+#[wasm_bindgen]
+impl CompiledCircuit {
+    /// A serialized representation of the compiled circuit.
+    #[wasm_bindgen(getter)]
+    pub fn circuit(&self) -> Vec<u8> {
+        self.circuit.clone()
+    }
+
+    /// Returns the locations in the compiled circuit of the opcodes which were produced from an opcode of the original circuit.
+    ///
+    /// @param {string} old_location - The location of an opcode in the original circuit, e.g. `"2"` or `"2.5"` for an opcode within a Brillig opcode.
+    /// @returns {string[]} The locations of the corresponding opcodes in the compiled circuit.
+    #[wasm_bindgen(js_name = newLocations, skip_jsdoc)]
+    pub fn new_locations(&self, old_location: String) -> Result<Array, Error> {
+        let old_location: OpcodeLocation =
+            old_location.parse().map_err(|err| Error::new(&format!("{err}")))?;
+
+        Ok(self
+            .transformation_map
+            .new_locations(old_location)
+            .map(|location| JsString::from(location.to_string()))
+            .collect())
+    }
+}
+
+/// Optimizes a circuit for a backend, replacing any opcodes which the backend does not support.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {Language} language - The constraint system targeted by the backend.
+/// @param {IsOpcodeSupported} is_opcode_supported - A callback which returns whether the backend supports an opcode.
+/// @returns {CompiledCircuit} The compiled circuit along with the mapping from old to new opcode locations.
+#[wasm_bindgen(js_name = compileCircuit, skip_jsdoc)]
+pub fn compile_circuit(
+    circuit: Vec<u8>,
+    language: JsLanguage,
+    is_opcode_supported: IsOpcodeSupported,
+) -> Result<CompiledCircuit, Error> {
+    console_error_panic_hook::set_once();
+    let circuit = read_circuit(&circuit)?;
+    let language: LanguageModel = JsValue::from(language)
+        .into_serde()
+        .map_err(|err| Error::new(&format!("Invalid language: {err}")))?;
+
+    // The compiler expects an infallible callback so we record the first error raised by the javascript callback
+    // and report it once compilation has finished.
+    let callback_error = RefCell::new(None);
+    let is_supported = |opcode: &Opcode| -> bool {
+        let result = <JsValue as JsValueSerdeExt>::from_serde(opcode)
+            .map_err(|err| Error::new(&err.to_string()))
+            .and_then(|opcode| {
+                is_opcode_supported.call1(&JsValue::null(), &opcode).map_err(|err| {
+                    let error = Error::new("Error calling `is_opcode_supported`");
+                    error.set_cause(&err);
+                    error
+                })
+            });
+        match result {
+            Ok(supported) => supported.is_truthy(),
+            Err(err) => {
+                callback_error.borrow_mut().get_or_insert(err);
+                true
+            }
+        }
+    };
+
+    let result = compiler::compile(circuit, language.into(), is_supported);
+    if let Some(err) = callback_error.into_inner() {
+        return Err(err);
+    }
+    let (circuit, transformation_map) = result.map_err(|err| Error::new(&err.to_string()))?;
+
+    let mut serialized_circuit = Vec::new();
+    circuit
+        .write(&mut serialized_circuit)
+        .map_err(|err| Error::new(&format!("Failed to serialize circuit: {err}")))?;
+
+    Ok(CompiledCircuit { circuit: serialized_circuit, transformation_map })
+}
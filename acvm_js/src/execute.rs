@@ -77,34 +77,7 @@ pub async fn execute_circuit_with_black_box_solver(
                 unreachable!("Execution should not stop while in `InProgress` state.")
             }
             ACVMStatus::Failure(error) => {
-                let (assert_message, call_stack) = match &error {
-                    OpcodeResolutionError::UnsatisfiedConstrain {
-                        opcode_location: ErrorLocation::Resolved(opcode_location),
-                    }
-                    | OpcodeResolutionError::IndexOutOfBounds {
-                        opcode_location: ErrorLocation::Resolved(opcode_location),
-                        ..
-                    } => (
-                        get_assert_message(&circuit.assert_messages, opcode_location),
-                        Some(vec![*opcode_location]),
-                    ),
-                    OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
-                        let failing_opcode =
-                            call_stack.last().expect("Brillig error call stacks cannot be empty");
-                        (
-                            get_assert_message(&circuit.assert_messages, failing_opcode),
-                            Some(call_stack.clone()),
-                        )
-                    }
-                    _ => (None, None),
-                };
-
-                let error_string = match &assert_message {
-                    Some(assert_message) => format!("Assertion failed: {}", assert_message),
-                    None => error.to_string(),
-                };
-
-                return Err(JsExecutionError::new(error_string.into(), call_stack).into());
+                return Err(execution_error(&error, &circuit.assert_messages).into());
             }
             ACVMStatus::RequiresForeignCall(foreign_call) => {
                 let result = resolve_brillig(&foreign_call_handler, &foreign_call).await?;
@@ -118,6 +91,42 @@ pub async fn execute_circuit_with_black_box_solver(
     Ok(witness_map.into())
 }
 
+/// Converts an error encountered while executing a circuit into a [`JsExecutionError`],
+/// attaching the assertion message associated with the failing opcode if one exists.
+pub(crate) fn execution_error(
+    error: &OpcodeResolutionError,
+    assert_messages: &[(OpcodeLocation, String)],
+) -> JsExecutionError {
+    let (assert_message, call_stack) = match error {
+        OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+            ..
+        }
+        | OpcodeResolutionError::IndexOutOfBounds {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+            ..
+        } => (get_assert_message(assert_messages, opcode_location), Some(vec![*opcode_location])),
+        OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
+            let failing_opcode =
+                call_stack.last().expect("Brillig error call stacks cannot be empty");
+            (get_assert_message(assert_messages, failing_opcode), Some(call_stack.clone()))
+        }
+        _ => (None, None),
+    };
+
+    let error_string = match &assert_message {
+        Some(assert_message) => format!("Assertion failed: {}", assert_message),
+        None => error.to_string(),
+    };
+
+    let constraint_failure = match error {
+        OpcodeResolutionError::UnsatisfiedConstrain { reason, .. } => Some(&**reason),
+        _ => None,
+    };
+
+    JsExecutionError::new(error_string, call_stack, constraint_failure)
+}
+
 // Searches the slice for `opcode_location`.
 // This is functionality equivalent to .get on a map.
 fn get_assert_message(
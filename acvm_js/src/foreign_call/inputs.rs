@@ -1,17 +1,57 @@
-use acvm::brillig_vm::brillig::Value;
+use acvm::brillig_vm::brillig::{abi::AbiType, ForeignCallParam, Value};
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::{JsString, Object, Reflect};
+use wasm_bindgen::JsValue;
 
 use crate::js_witness_map::field_element_to_js_string;
 
-pub(super) fn encode_foreign_call_inputs(foreign_call_inputs: &[Vec<Value>]) -> js_sys::Array {
+fn encode_values(values: &[Value]) -> js_sys::Array {
+    let array = js_sys::Array::default();
+    for value in values {
+        let hex_js_string = field_element_to_js_string(&value.to_field());
+        array.push(&hex_js_string);
+    }
+    array
+}
+
+pub(crate) fn encode_foreign_call_inputs(
+    foreign_call_inputs: &[ForeignCallParam],
+) -> js_sys::Array {
     let inputs = js_sys::Array::default();
     for input in foreign_call_inputs {
-        let input_array = js_sys::Array::default();
-        for value in input {
-            let hex_js_string = field_element_to_js_string(&value.to_field());
-            input_array.push(&hex_js_string);
-        }
-        inputs.push(&input_array);
+        inputs.push(&encode_values(input.values()));
     }
 
     inputs
 }
+
+/// Encodes each input along with its shape and, if known, its type.
+pub(crate) fn encode_foreign_call_params(
+    foreign_call_inputs: &[ForeignCallParam],
+    input_types: &[Option<AbiType>],
+) -> js_sys::Array {
+    let params = js_sys::Array::default();
+    for (index, input) in foreign_call_inputs.iter().enumerate() {
+        let param = Object::new();
+        let set = |property: &str, value: &JsValue| {
+            Reflect::set(&param, &JsValue::from(property), value)
+                .expect("Newly created objects should be writable");
+        };
+
+        let kind = match input {
+            ForeignCallParam::Single(_) => "single",
+            ForeignCallParam::Array(_) => "array",
+            ForeignCallParam::Vector(_) => "vector",
+        };
+        set("kind", &JsString::from(kind));
+        set("values", &encode_values(input.values()));
+        if let Some(Some(typ)) = input_types.get(index) {
+            let typ = <JsValue as JsValueSerdeExt>::from_serde(typ)
+                .expect("ABI types should always be serializable");
+            set("type", &typ);
+        }
+        params.push(&param);
+    }
+
+    params
+}
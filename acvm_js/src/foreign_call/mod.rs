@@ -6,19 +6,47 @@ use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 mod inputs;
 mod outputs;
 
+pub(crate) use inputs::{encode_foreign_call_inputs, encode_foreign_call_params};
+pub(crate) use outputs::decode_foreign_call_result;
+
 #[wasm_bindgen(typescript_custom_section)]
 const FOREIGN_CALL_HANDLER: &'static str = r#"
 export type ForeignCallInput = string[]
 export type ForeignCallOutput = string | string[]
 
+/**
+* The type of a foreign call parameter, as declared by the Brillig bytecode.
+*/
+export type AbiType =
+    | "Field"
+    | "Boolean"
+    | { Unsigned: { bit_size: number } }
+    | { Signed: { bit_size: number } }
+    | { String: { length: number } }
+    | { Array: { length: number; element: AbiType } }
+    | { Struct: { fields: [string, AbiType][] } };
+
+/**
+* @typedef {Object} ForeignCallParam - A foreign call input along with the shape of the memory it was read from.
+* @property {string} kind - `"single"` for a register, `"array"` for a fixed length array or `"vector"` for a variable length vector.
+* @property {string[]} values - The hex encoded values of the input.
+* @property {AbiType} [type] - The type of the input, if it is known.
+*/
+export type ForeignCallParam = {
+    kind: "single" | "array" | "vector";
+    values: string[];
+    type?: AbiType;
+};
+
 /**
 * A callback which performs an foreign call and returns the response.
 * @callback ForeignCallHandler
 * @param {string} name - The identifier for the type of foreign call being performed.
 * @param {string[][]} inputs - An array of hex encoded inputs to the foreign call.
+* @param {ForeignCallParam[]} params - The same inputs along with their shapes and types.
 * @returns {Promise<string[]>} outputs - An array of hex encoded outputs containing the results of the foreign call.
 */
-export type ForeignCallHandler = (name: string, inputs: ForeignCallInput[]) => Promise<ForeignCallOutput[]>;
+export type ForeignCallHandler = (name: string, inputs: ForeignCallInput[], params: ForeignCallParam[]) => Promise<ForeignCallOutput[]>;
 "#;
 
 #[wasm_bindgen]
@@ -34,9 +62,13 @@ pub(super) async fn resolve_brillig(
     // Prepare to call
     let name = JsString::from(foreign_call_wait_info.function.clone());
     let inputs = inputs::encode_foreign_call_inputs(&foreign_call_wait_info.inputs);
+    let params = inputs::encode_foreign_call_params(
+        &foreign_call_wait_info.inputs,
+        &foreign_call_wait_info.input_types,
+    );
 
     // Perform foreign call
-    let outputs = perform_foreign_call(foreign_call_callback, name, inputs).await?;
+    let outputs = perform_foreign_call(foreign_call_callback, name, inputs, params).await?;
 
     // The Brillig VM checks that the number of return values from
     // the foreign call is valid so we don't need to do it here.
@@ -47,11 +79,12 @@ async fn perform_foreign_call(
     foreign_call_handler: &ForeignCallHandler,
     name: JsString,
     inputs: js_sys::Array,
+    params: js_sys::Array,
 ) -> Result<js_sys::Array, Error> {
     // Call and await
     let this = JsValue::null();
     let ret_js_val = foreign_call_handler
-        .call2(&this, &name, &inputs)
+        .call3(&this, &name, &inputs, &params)
         .map_err(|err| wrap_js_error("Error calling `foreign_call_callback`", &err))?;
     let ret_js_prom: js_sys::Promise = ret_js_val.into();
     let ret_future: wasm_bindgen_futures::JsFuture = ret_js_prom.into();
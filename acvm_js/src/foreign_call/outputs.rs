@@ -20,7 +20,7 @@ fn decode_foreign_call_output(output: JsValue) -> Result<ForeignCallOutput, Stri
     }
 }
 
-pub(super) fn decode_foreign_call_result(
+pub(crate) fn decode_foreign_call_result(
     js_array: js_sys::Array,
 ) -> Result<ForeignCallResult, String> {
     let mut values: Vec<ForeignCallOutput> = Vec::with_capacity(js_array.length() as usize);
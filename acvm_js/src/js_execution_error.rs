@@ -1,11 +1,26 @@
-use acvm::acir::circuit::OpcodeLocation;
-use js_sys::{Array, Error, JsString, Reflect};
+use acvm::{acir::circuit::OpcodeLocation, pwg::ConstraintFailure};
+use js_sys::{Array, Error, JsString, Object, Reflect};
 use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
 
+use crate::{js_witness_map::field_element_to_js_string, JsWitnessMap};
+
 #[wasm_bindgen(typescript_custom_section)]
 const EXECUTION_ERROR: &'static str = r#"
+export type ConstraintFailure =
+    | { kind: "arithmetic"; expression: string; witnessValues: WitnessMap }
+    | { kind: "range"; witness: number; value: string; numBits: number }
+    | { kind: "radixDecomposition"; value: string; radix: number; numLimbs: number }
+    | {
+          kind: "conflictingAssignment";
+          witness: number;
+          oldValue: string;
+          newValue: string;
+          witnessValues: WitnessMap;
+      };
+
 export type ExecutionError = Error & {
     callStack?: string[];
+    constraintFailure?: ConstraintFailure;
 };
 "#;
 
@@ -23,9 +38,14 @@ extern "C" {
 }
 
 impl JsExecutionError {
-    /// Creates a new execution error with the given call stack.
+    /// Creates a new execution error with the given call stack and, if the error was caused by
+    /// an unsatisfied constraint, the details of that constraint.
     /// Call stacks won't be optional in the future, after removing ErrorLocation in ACVM.
-    pub fn new(message: String, call_stack: Option<Vec<OpcodeLocation>>) -> Self {
+    pub fn new(
+        message: String,
+        call_stack: Option<Vec<OpcodeLocation>>,
+        constraint_failure: Option<&ConstraintFailure>,
+    ) -> Self {
         let mut error = JsExecutionError::constructor(JsString::from(message));
         let js_call_stack = match call_stack {
             Some(call_stack) => {
@@ -39,6 +59,10 @@ impl JsExecutionError {
         };
 
         error.set_property("callStack", js_call_stack);
+        error.set_property(
+            "constraintFailure",
+            constraint_failure.map_or(JsValue::UNDEFINED, constraint_failure_to_js),
+        );
 
         error
     }
@@ -50,3 +74,45 @@ impl JsExecutionError {
         );
     }
 }
+
+fn constraint_failure_to_js(constraint_failure: &ConstraintFailure) -> JsValue {
+    let object = Object::new();
+    let set = |property: &str, value: JsValue| {
+        Reflect::set(&object, &JsValue::from(property), &value)
+            .expect("Newly created objects should be writable");
+    };
+
+    match constraint_failure {
+        ConstraintFailure::Arithmetic { expression, witness_values } => {
+            set("kind", "arithmetic".into());
+            set("expression", expression.to_string().into());
+            set("witnessValues", JsWitnessMap::from(witness_values.clone()).into());
+        }
+        ConstraintFailure::Range { witness, value, num_bits } => {
+            set("kind", "range".into());
+            set("witness", witness.witness_index().into());
+            set("value", field_element_to_js_string(value).into());
+            set("numBits", (*num_bits).into());
+        }
+        ConstraintFailure::RadixDecomposition { value, radix, num_limbs } => {
+            set("kind", "radixDecomposition".into());
+            set("value", field_element_to_js_string(value).into());
+            set("radix", (*radix).into());
+            set("numLimbs", (*num_limbs as u32).into());
+        }
+        ConstraintFailure::ConflictingAssignment {
+            witness,
+            old_value,
+            new_value,
+            witness_values,
+        } => {
+            set("kind", "conflictingAssignment".into());
+            set("witness", witness.witness_index().into());
+            set("oldValue", field_element_to_js_string(old_value).into());
+            set("newValue", field_element_to_js_string(new_value).into());
+            set("witnessValues", JsWitnessMap::from(witness_values.clone()).into());
+        }
+    }
+
+    object.into()
+}
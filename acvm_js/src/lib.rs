@@ -8,6 +8,8 @@
 cfg_if::cfg_if! {
     if #[cfg(target_arch = "wasm32")] {
         mod build_info;
+        mod circuit_inspection;
+        mod compile;
         mod compression;
         mod execute;
         mod foreign_call;
@@ -15,13 +17,17 @@ cfg_if::cfg_if! {
         mod logging;
         mod public_witness;
         mod js_execution_error;
+        mod wasm_acvm;
 
         pub use build_info::build_info;
+        pub use circuit_inspection::{decode_circuit, get_opcode_statistics, JsCircuit, JsOpcodeStatistics};
+        pub use compile::{compile_circuit, CompiledCircuit, IsOpcodeSupported, JsLanguage};
         pub use compression::{compress_witness, decompress_witness};
         pub use execute::{execute_circuit, execute_circuit_with_black_box_solver, create_black_box_solver};
         pub use js_witness_map::JsWitnessMap;
         pub use logging::{init_log_level, LogLevel};
         pub use public_witness::{get_public_parameters_witness, get_public_witness, get_return_witness};
         pub use js_execution_error::JsExecutionError;
+        pub use wasm_acvm::{create_acvm, JsACVMStatus, WasmACVM};
     }
 }
@@ -0,0 +1,182 @@
+use std::cell::Cell;
+
+use acvm::{
+    acir::circuit::OpcodeLocation,
+    pwg::{ACVMStatus, ACVM},
+};
+#[allow(deprecated)]
+use barretenberg_blackbox_solver::BarretenbergSolver;
+
+use js_sys::{Error, JsString, Object, Reflect};
+use wasm_bindgen::{
+    prelude::{wasm_bindgen, JsValue},
+    JsCast,
+};
+
+use crate::{
+    circuit_inspection::read_circuit,
+    execute::execution_error,
+    foreign_call::{
+        decode_foreign_call_result, encode_foreign_call_inputs, encode_foreign_call_params,
+    },
+    JsWitnessMap,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ACVM_STATUS: &'static str = r#"
+export type ACVMStatus =
+    | { kind: "solved" }
+    | { kind: "inProgress" }
+    | { kind: "failure"; error: ExecutionError }
+    | {
+          kind: "requiresForeignCall";
+          name: string;
+          inputs: ForeignCallInput[];
+          params: ForeignCallParam[];
+      };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, js_name = "ACVMStatus", typescript_type = "ACVMStatus")]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type JsACVMStatus;
+}
+
+thread_local! {
+    /// The black box solver shared by every [`WasmACVM`].
+    ///
+    /// As an [`ACVM`] borrows its solver, the solver is leaked so that it outlives every [`WasmACVM`] created.
+    /// Only a single solver is ever created as wasm is single threaded.
+    #[allow(deprecated)]
+    static SHARED_SOLVER: Cell<Option<&'static BarretenbergSolver>> = Cell::new(None);
+}
+
+#[allow(deprecated)]
+async fn shared_solver() -> &'static BarretenbergSolver {
+    if let Some(solver) = SHARED_SOLVER.with(Cell::get) {
+        return solver;
+    }
+
+    let solver = BarretenbergSolver::initialize().await;
+    SHARED_SOLVER.with(|shared_solver| match shared_solver.get() {
+        // Another `WasmACVM` initialized the shared solver while we were waiting on ours.
+        Some(solver) => solver,
+        None => {
+            let solver: &'static BarretenbergSolver = Box::leak(Box::new(solver));
+            shared_solver.set(Some(solver));
+            solver
+        }
+    })
+}
+
+/// An ACVM which executes an ACIR circuit one opcode at a time,
+/// allowing the state of execution to be inspected between steps.
+#[wasm_bindgen]
+#[allow(deprecated)]
+pub struct WasmACVM {
+    acvm: ACVM<'static, BarretenbergSolver>,
+    assert_messages: Vec<(OpcodeLocation, String)>,
+}
+
+/// Creates an ACVM which executes an ACIR circuit step by step.
+///
+/// @param {Uint8Array} circuit - A serialized representation of an ACIR circuit
+/// @param {WitnessMap} initial_witness - The initial witness map defining all of the inputs to `circuit`..
+/// @returns {WasmACVM} An ACVM which is ready to execute the first opcode of `circuit`.
+#[wasm_bindgen(js_name = createACVM, skip_jsdoc)]
+pub async fn create_acvm(
+    circuit: Vec<u8>,
+    initial_witness: JsWitnessMap,
+) -> Result<WasmACVM, Error> {
+    console_error_panic_hook::set_once();
+    let circuit = read_circuit(&circuit)?;
+
+    let acvm = ACVM::new(shared_solver().await, circuit.opcodes, initial_witness.into());
+    Ok(WasmACVM { acvm, assert_messages: circuit.assert_messages })
+}
+
+#[wasm_bindgen]
+impl WasmACVM {
+    /// Executes the next opcode of the circuit.
+    ///
+    /// Execution does not progress while the ACVM is waiting on a foreign call or has finished executing.
+    ///
+    /// @returns {ACVMStatus} The status of the ACVM after executing the opcode.
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn step(&mut self) -> JsACVMStatus {
+        if self.acvm.get_status() == &ACVMStatus::InProgress {
+            self.acvm.solve_opcode();
+        }
+        self.status()
+    }
+
+    /// Executes the circuit until it has been solved, fails or requires a foreign call to be resolved.
+    ///
+    /// @returns {ACVMStatus} The status of the ACVM once execution halts.
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn solve(&mut self) -> JsACVMStatus {
+        self.acvm.solve();
+        self.status()
+    }
+
+    /// Returns the current status of the ACVM, including the details of any pending foreign call.
+    ///
+    /// @returns {ACVMStatus} The current status of the ACVM.
+    #[wasm_bindgen(skip_jsdoc)]
+    pub fn status(&self) -> JsACVMStatus {
+        let object = Object::new();
+        let set = |property: &str, value: JsValue| {
+            Reflect::set(&object, &JsValue::from(property), &value)
+                .expect("Newly created objects should be writable");
+        };
+
+        match self.acvm.get_status() {
+            ACVMStatus::Solved => set("kind", "solved".into()),
+            ACVMStatus::InProgress => set("kind", "inProgress".into()),
+            ACVMStatus::Failure(error) => {
+                set("kind", "failure".into());
+                set("error", execution_error(error, &self.assert_messages).into());
+            }
+            ACVMStatus::RequiresForeignCall(foreign_call) => {
+                set("kind", "requiresForeignCall".into());
+                set("name", JsString::from(foreign_call.function.clone()).into());
+                set("inputs", encode_foreign_call_inputs(&foreign_call.inputs).into());
+                set(
+                    "params",
+                    encode_foreign_call_params(&foreign_call.inputs, &foreign_call.input_types)
+                        .into(),
+                );
+            }
+        }
+
+        object.unchecked_into()
+    }
+
+    /// Resolves the pending foreign call, allowing execution to continue.
+    ///
+    /// @param {ForeignCallOutput[]} outputs - An array of hex encoded outputs containing the results of the foreign call.
+    #[wasm_bindgen(js_name = resolveForeignCall, skip_jsdoc)]
+    pub fn resolve_foreign_call(&mut self, outputs: js_sys::Array) -> Result<(), Error> {
+        if self.acvm.get_pending_foreign_call().is_none() {
+            return Err(Error::new("ACVM is not waiting on a foreign call"));
+        }
+        let result = decode_foreign_call_result(outputs).map_err(|message| Error::new(&message))?;
+        self.acvm.resolve_pending_foreign_call(result);
+        Ok(())
+    }
+
+    /// Returns the witnesses which have been solved so far.
+    ///
+    /// @returns {WitnessMap} The current witness map of the ACVM.
+    #[wasm_bindgen(js_name = witnessMap, skip_jsdoc)]
+    pub fn witness_map(&self) -> JsWitnessMap {
+        self.acvm.witness_map().clone().into()
+    }
+
+    /// Returns the index of the next opcode to be executed.
+    #[wasm_bindgen(js_name = instructionPointer)]
+    pub fn instruction_pointer(&self) -> usize {
+        self.acvm.instruction_pointer()
+    }
+}
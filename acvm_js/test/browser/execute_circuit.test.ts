@@ -7,6 +7,7 @@ import initACVM, {
   WitnessMap,
   initLogLevel,
   ForeignCallHandler,
+  ForeignCallParam,
 } from "../../../result/";
 
 beforeEach(async () => {
@@ -83,18 +84,22 @@ it("successfully processes complex brillig foreign call opcodes", async () => {
     oracleResponse,
     oracleCallName,
     oracleCallInputs,
+    oracleCallParams,
   } = await import("../shared/complex_foreign_call");
 
   let observedName = "";
   let observedInputs: string[][] = [];
+  let observedParams: ForeignCallParam[] = [];
   const foreignCallHandler: ForeignCallHandler = async (
     name: string,
-    inputs: string[][]
+    inputs: string[][],
+    params: ForeignCallParam[]
   ) => {
     // Throwing inside the oracle callback causes a timeout so we log the observed values
     // and defer the check against expected values until after the execution is complete.
     observedName = name;
     observedInputs = inputs;
+    observedParams = params;
 
     return oracleResponse;
   };
@@ -108,6 +113,7 @@ it("successfully processes complex brillig foreign call opcodes", async () => {
   // Check that expected values were passed to oracle callback.
   expect(observedName).to.be.eq(oracleCallName);
   expect(observedInputs).to.be.deep.eq(oracleCallInputs);
+  expect(observedParams).to.be.deep.eq(oracleCallParams);
 
   // If incorrect value is written into circuit then execution should halt due to unsatisfied constraint in
   // arithmetic opcode. Nevertheless, check that returned value was inserted correctly.
@@ -0,0 +1,79 @@
+import { expect } from "@esm-bundle/chai";
+import initACVM, {
+  createACVM,
+  ACVMStatus,
+  WasmACVM,
+  WitnessMap,
+  initLogLevel,
+} from "../../../result/";
+
+beforeEach(async () => {
+  await initACVM();
+
+  initLogLevel("INFO");
+});
+
+it("executes a circuit one opcode at a time", async () => {
+  const { bytecode, initialWitnessMap, resultWitness, expectedResult } =
+    await import("../shared/addition");
+
+  const acvm: WasmACVM = await createACVM(bytecode, initialWitnessMap);
+  expect(acvm.status()).to.be.deep.eq({ kind: "inProgress" });
+  expect(acvm.instructionPointer()).to.be.eq(0);
+
+  let steps = 0;
+  let status: ACVMStatus = acvm.status();
+  while (status.kind === "inProgress") {
+    status = acvm.step();
+    steps++;
+    // Each step executes a single opcode.
+    expect(acvm.instructionPointer()).to.be.eq(steps);
+  }
+  expect(status).to.be.deep.eq({ kind: "solved" });
+
+  // Stepping a solved ACVM has no effect.
+  expect(acvm.step()).to.be.deep.eq({ kind: "solved" });
+  expect(acvm.instructionPointer()).to.be.eq(steps);
+
+  const solvedWitness: WitnessMap = acvm.witnessMap();
+  initialWitnessMap.forEach((value, key) => {
+    expect(solvedWitness.get(key) as string).to.be.eq(value);
+  });
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+});
+
+it("pauses execution on foreign calls until they are resolved", async () => {
+  const {
+    bytecode,
+    initialWitnessMap,
+    expectedWitnessMap,
+    oracleResponse,
+    oracleCallName,
+    oracleCallInputs,
+    oracleCallParams,
+  } = await import("../shared/foreign_call");
+
+  const acvm: WasmACVM = await createACVM(bytecode, initialWitnessMap);
+
+  expect(() => acvm.resolveForeignCall(oracleResponse)).to.throw(
+    "ACVM is not waiting on a foreign call"
+  );
+
+  const status: ACVMStatus = acvm.solve();
+  expect(status).to.be.deep.eq({
+    kind: "requiresForeignCall",
+    name: oracleCallName,
+    inputs: oracleCallInputs,
+    params: oracleCallParams,
+  });
+  expect(acvm.status()).to.be.deep.eq(status);
+
+  // Execution does not progress until the foreign call is resolved.
+  const instructionPointer = acvm.instructionPointer();
+  expect(acvm.step()).to.be.deep.eq(status);
+  expect(acvm.instructionPointer()).to.be.eq(instructionPointer);
+
+  acvm.resolveForeignCall(oracleResponse);
+  expect(acvm.solve()).to.be.deep.eq({ kind: "solved" });
+  expect(acvm.witnessMap()).to.be.deep.eq(expectedWitnessMap);
+});
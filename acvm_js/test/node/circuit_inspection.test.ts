@@ -0,0 +1,78 @@
+import { expect } from "chai";
+import {
+  compileCircuit,
+  decodeCircuit,
+  getOpcodeStatistics,
+  Circuit,
+  CompiledCircuit,
+  Opcode,
+  OpcodeStatistics,
+} from "../../../result/";
+
+it("decodes a circuit into a javascript object", async () => {
+  const { bytecode } = await import("../shared/addition");
+
+  const circuit: Circuit = decodeCircuit(bytecode);
+
+  expect(circuit.currentWitnessIndex).to.be.eq(4);
+  expect(circuit.privateParameters).to.be.deep.eq([1, 2]);
+  expect(circuit.publicParameters).to.be.deep.eq([]);
+  expect(circuit.returnValues).to.be.deep.eq([3]);
+  expect(circuit.assertMessages).to.be.deep.eq([]);
+
+  expect(circuit.opcodes).to.have.length(1);
+  expect(Object.keys(circuit.opcodes[0])).to.be.deep.eq(["Arithmetic"]);
+});
+
+it("summarizes the opcodes of a circuit", async () => {
+  const { bytecode } = await import("../shared/memory_op");
+
+  const statistics: OpcodeStatistics = getOpcodeStatistics(bytecode);
+  const decoded: Circuit = decodeCircuit(bytecode);
+
+  expect(statistics.totalOpcodes).to.be.eq(decoded.opcodes.length);
+  const counted = Object.values(statistics.opcodes).reduce(
+    (total, count) => total + count,
+    0
+  );
+  expect(counted).to.be.eq(statistics.totalOpcodes);
+  expect(statistics.currentWitnessIndex).to.be.eq(
+    decoded.currentWitnessIndex
+  );
+  expect(statistics.privateParameters).to.be.eq(
+    decoded.privateParameters.length
+  );
+});
+
+it("compiles a circuit for a backend", async () => {
+  // The backend is only asked about black box functions and memory opcodes.
+  const { bytecode } = await import("../shared/fixed_base_scalar_mul");
+
+  const observedOpcodes: Opcode[] = [];
+  const compiled: CompiledCircuit = compileCircuit(
+    bytecode,
+    { kind: "PLONKCSat", width: 3 },
+    (opcode: Opcode) => {
+      observedOpcodes.push(opcode);
+      return true;
+    }
+  );
+
+  expect(observedOpcodes).to.be.deep.eq(decodeCircuit(bytecode).opcodes);
+  expect(decodeCircuit(compiled.circuit).opcodes).to.be.deep.eq(
+    observedOpcodes
+  );
+  expect(compiled.newLocations("0")).to.be.deep.eq(["0"]);
+  expect(compiled.newLocations("1")).to.be.deep.eq([]);
+  expect(() => compiled.newLocations("not a location")).to.throw();
+});
+
+it("reports errors raised by the opcode support callback", async () => {
+  const { bytecode } = await import("../shared/fixed_base_scalar_mul");
+
+  expect(() =>
+    compileCircuit(bytecode, { kind: "R1CS" }, () => {
+      throw Error("unexpected opcode");
+    })
+  ).to.throw("Error calling `is_opcode_supported`");
+});
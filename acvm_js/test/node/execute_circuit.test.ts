@@ -6,6 +6,7 @@ import {
   WasmBlackBoxFunctionSolver,
   WitnessMap,
   ForeignCallHandler,
+  ForeignCallParam,
 } from "../../../result/";
 
 it("successfully executes circuit and extracts return value", async () => {
@@ -76,18 +77,22 @@ it("successfully processes complex brillig foreign call opcodes", async () => {
     oracleResponse,
     oracleCallName,
     oracleCallInputs,
+    oracleCallParams,
   } = await import("../shared/complex_foreign_call");
 
   let observedName = "";
   let observedInputs: string[][] = [];
+  let observedParams: ForeignCallParam[] = [];
   const foreignCallHandler: ForeignCallHandler = async (
     name: string,
-    inputs: string[][]
+    inputs: string[][],
+    params: ForeignCallParam[]
   ) => {
     // Throwing inside the oracle callback causes a timeout so we log the observed values
     // and defer the check against expected values until after the execution is complete.
     observedName = name;
     observedInputs = inputs;
+    observedParams = params;
 
     return oracleResponse;
   };
@@ -101,6 +106,7 @@ it("successfully processes complex brillig foreign call opcodes", async () => {
   // Check that expected values were passed to oracle callback.
   expect(observedName).to.be.eq(oracleCallName);
   expect(observedInputs).to.be.deep.eq(oracleCallInputs);
+  expect(observedParams).to.be.deep.eq(oracleCallParams);
 
   // If incorrect value is written into circuit then execution should halt due to unsatisfied constraint in
   // arithmetic opcode. Nevertheless, check that returned value was inserted correctly.
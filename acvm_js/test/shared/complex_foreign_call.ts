@@ -26,6 +26,22 @@ export const oracleCallInputs = [
   ],
   ["0x0000000000000000000000000000000000000000000000000000000000000006"],
 ];
+export const oracleCallParams = [
+  {
+    kind: "array",
+    values: [
+      "0x0000000000000000000000000000000000000000000000000000000000000001",
+      "0x0000000000000000000000000000000000000000000000000000000000000002",
+      "0x0000000000000000000000000000000000000000000000000000000000000003",
+    ],
+  },
+  {
+    kind: "single",
+    values: [
+      "0x0000000000000000000000000000000000000000000000000000000000000006",
+    ],
+  },
+];
 
 export const oracleResponse = [
   [
@@ -16,6 +16,14 @@ export const oracleCallName = "invert";
 export const oracleCallInputs = [
   ["0x0000000000000000000000000000000000000000000000000000000000000005"],
 ];
+export const oracleCallParams = [
+  {
+    kind: "single",
+    values: [
+      "0x0000000000000000000000000000000000000000000000000000000000000005",
+    ],
+  },
+];
 
 export const oracleResponse = [
   "0x135b52945a13d9aa49b9b57c33cd568ba9ae5ce9ca4a2d06e7f3fbd4c6666667",
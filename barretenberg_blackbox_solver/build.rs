@@ -1,15 +1,20 @@
+#[cfg(feature = "embedded-wasm")]
 use std::{
     fs::File,
     io::{Cursor, Read},
     path::{Path, PathBuf},
 };
 
+#[cfg(feature = "embedded-wasm")]
 const BARRETENBERG_ARCHIVE: &str = "BARRETENBERG_ARCHIVE";
+#[cfg(feature = "embedded-wasm")]
 const BARRETENBERG_BIN_DIR: &str = "BARRETENBERG_BIN_DIR";
 
+#[cfg(feature = "embedded-wasm")]
 const BARRETENBERG_ARCHIVE_FALLBACK: &str = "https://github.com/AztecProtocol/barretenberg/releases/download/barretenberg-v0.5.0/acvm_backend.wasm.tar.gz";
 // const ARCHIVE_SHA256: &str = "1xpycikqlvsjcryi3hkbc4mwmmdz7zshw6f76vyf1qssq53asyfx";
 
+#[cfg(feature = "embedded-wasm")]
 fn unpack_wasm(archive_path: &Path, target_dir: &Path) -> Result<(), String> {
     if archive_path.exists() && archive_path.is_file() {
         let archive = File::open(archive_path).map_err(|_| "Could not read archive")?;
@@ -21,6 +26,7 @@ fn unpack_wasm(archive_path: &Path, target_dir: &Path) -> Result<(), String> {
     }
 }
 
+#[cfg(feature = "embedded-wasm")]
 fn unpack_archive<T: Read>(archive: T, target_dir: &Path) {
     use flate2::read::GzDecoder;
     use tar::Archive;
@@ -32,6 +38,7 @@ fn unpack_archive<T: Read>(archive: T, target_dir: &Path) {
 }
 
 /// Try to download the specified URL into a buffer which is returned.
+#[cfg(feature = "embedded-wasm")]
 fn download_binary_from_url(url: &str) -> Result<Cursor<Vec<u8>>, String> {
     let response = reqwest::blocking::get(url).map_err(|error| error.to_string())?;
 
@@ -39,6 +46,7 @@ fn download_binary_from_url(url: &str) -> Result<Cursor<Vec<u8>>, String> {
     Ok(Cursor::new(bytes.to_vec()))
 }
 
+#[cfg(feature = "embedded-wasm")]
 fn main() -> Result<(), String> {
     let out_dir = std::env::var("OUT_DIR").unwrap();
 
@@ -59,3 +67,7 @@ fn main() -> Result<(), String> {
         }
     }
 }
+
+/// The wasm module is only downloaded when it is to be embedded into the crate.
+#[cfg(not(feature = "embedded-wasm"))]
+fn main() {}
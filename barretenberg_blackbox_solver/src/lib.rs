@@ -1,17 +1,25 @@
 use acir::{BlackBoxFunc, FieldElement};
 use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 
+#[cfg(not(target_arch = "wasm32"))]
+mod pool;
 mod wasm;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use pool::PooledBarretenbergSolver;
+pub use wasm::WasmLoadError;
+
 use wasm::Barretenberg;
 
 use self::wasm::{Pedersen, ScalarMul, SchnorrSig};
 
+#[cfg(feature = "embedded-wasm")]
 #[deprecated = "The `BarretenbergSolver` is a temporary solution and will be removed in future."]
 pub struct BarretenbergSolver {
     blackbox_vendor: Barretenberg,
 }
 
+#[cfg(feature = "embedded-wasm")]
 #[allow(deprecated)]
 impl BarretenbergSolver {
     #[cfg(target_arch = "wasm32")]
@@ -27,7 +35,7 @@ impl BarretenbergSolver {
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "embedded-wasm"))]
 #[allow(deprecated)]
 impl Default for BarretenbergSolver {
     fn default() -> Self {
@@ -35,8 +43,37 @@ impl Default for BarretenbergSolver {
     }
 }
 
+#[cfg(feature = "embedded-wasm")]
 #[allow(deprecated)]
 impl BlackBoxFunctionSolver for BarretenbergSolver {
+    fn schnorr_verify(
+        &self,
+        public_key_x: &FieldElement,
+        public_key_y: &FieldElement,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        self.blackbox_vendor.schnorr_verify(public_key_x, public_key_y, signature, message)
+    }
+
+    fn pedersen(
+        &self,
+        inputs: &[FieldElement],
+        domain_separator: u32,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        self.blackbox_vendor.pedersen(inputs, domain_separator)
+    }
+
+    fn fixed_base_scalar_mul(
+        &self,
+        low: &FieldElement,
+        high: &FieldElement,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        self.blackbox_vendor.fixed_base_scalar_mul(low, high)
+    }
+}
+
+impl BlackBoxFunctionSolver for Barretenberg {
     fn schnorr_verify(
         &self,
         public_key_x: &FieldElement,
@@ -51,8 +88,7 @@ impl BlackBoxFunctionSolver for BarretenbergSolver {
         let sig_s: [u8; 32] = signature[0..32].try_into().unwrap();
         let sig_e: [u8; 32] = signature[32..64].try_into().unwrap();
 
-        #[allow(deprecated)]
-        self.blackbox_vendor.verify_signature(pub_key, sig_s, sig_e, message).map_err(|err| {
+        self.verify_signature(pub_key, sig_s, sig_e, message).map_err(|err| {
             BlackBoxResolutionError::Failed(BlackBoxFunc::SchnorrVerify, err.to_string())
         })
     }
@@ -62,9 +98,7 @@ impl BlackBoxFunctionSolver for BarretenbergSolver {
         inputs: &[FieldElement],
         domain_separator: u32,
     ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
-        #[allow(deprecated)]
-        self.blackbox_vendor
-            .encrypt(inputs.to_vec(), domain_separator)
+        self.encrypt(inputs.to_vec(), domain_separator)
             .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Pedersen, err.to_string()))
     }
 
@@ -73,8 +107,7 @@ impl BlackBoxFunctionSolver for BarretenbergSolver {
         low: &FieldElement,
         high: &FieldElement,
     ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
-        #[allow(deprecated)]
-        self.blackbox_vendor.fixed_base(low, high).map_err(|err| {
+        self.fixed_base(low, high).map_err(|err| {
             BlackBoxResolutionError::Failed(BlackBoxFunc::FixedBaseScalarMul, err.to_string())
         })
     }
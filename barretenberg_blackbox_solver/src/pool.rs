@@ -0,0 +1,167 @@
+use std::{
+    path::Path,
+    sync::{Condvar, Mutex, MutexGuard},
+};
+
+use acir::FieldElement;
+use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
+
+use crate::wasm::{Barretenberg, BarretenbergModule, WasmLoadError};
+
+/// A [`BlackBoxFunctionSolver`] backed by a fixed size pool of Barretenberg wasm instances.
+///
+/// Each call checks out an instance for its sole use, blocking if every instance is busy, so a single solver
+/// can be shared between threads. The wasm module is compiled once and then instantiated `size` times.
+#[derive(Debug)]
+pub struct PooledBarretenbergSolver {
+    instances: Mutex<Vec<Barretenberg>>,
+    available: Condvar,
+    size: usize,
+}
+
+impl PooledBarretenbergSolver {
+    /// Creates a pool of `size` instances of the Barretenberg wasm module embedded at build time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    #[cfg(feature = "embedded-wasm")]
+    pub fn new(size: usize) -> Result<Self, WasmLoadError> {
+        Self::from_module(&BarretenbergModule::embedded()?, size)
+    }
+
+    /// Creates a pool of `size` instances of the Barretenberg wasm module found at `path`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn from_path(path: impl AsRef<Path>, size: usize) -> Result<Self, WasmLoadError> {
+        Self::from_module(&BarretenbergModule::from_path(path.as_ref())?, size)
+    }
+
+    /// Creates a pool of `size` instances of the Barretenberg wasm module contained in `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn from_bytes(bytes: &[u8], size: usize) -> Result<Self, WasmLoadError> {
+        Self::from_module(&BarretenbergModule::from_bytes(bytes)?, size)
+    }
+
+    fn from_module(module: &BarretenbergModule, size: usize) -> Result<Self, WasmLoadError> {
+        assert!(size > 0, "a Barretenberg pool must contain at least one instance");
+
+        let instances = (0..size).map(|_| module.instantiate()).collect::<Result<_, _>>()?;
+        Ok(PooledBarretenbergSolver {
+            instances: Mutex::new(instances),
+            available: Condvar::new(),
+            size,
+        })
+    }
+
+    /// Returns the number of wasm instances in the pool.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Barretenberg>> {
+        // The lock is never held while an instance is in use so a poisoned lock can be ignored.
+        self.instances.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Runs `f` against an idle instance, waiting for one to become available if necessary.
+    fn with_instance<T>(&self, f: impl FnOnce(&Barretenberg) -> T) -> T {
+        let mut instances = self.lock();
+        let instance = loop {
+            match instances.pop() {
+                Some(instance) => break instance,
+                None => {
+                    instances = self
+                        .available
+                        .wait(instances)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                }
+            }
+        };
+        drop(instances);
+
+        let checkout = Checkout { pool: self, instance: Some(instance) };
+        f(checkout.instance.as_ref().expect("instance is only taken on drop"))
+    }
+}
+
+/// Returns a checked out instance to its pool when dropped, even if the call using it panics.
+struct Checkout<'a> {
+    pool: &'a PooledBarretenbergSolver,
+    instance: Option<Barretenberg>,
+}
+
+impl Drop for Checkout<'_> {
+    fn drop(&mut self) {
+        if let Some(instance) = self.instance.take() {
+            self.pool.lock().push(instance);
+            self.pool.available.notify_one();
+        }
+    }
+}
+
+impl BlackBoxFunctionSolver for PooledBarretenbergSolver {
+    fn schnorr_verify(
+        &self,
+        public_key_x: &FieldElement,
+        public_key_y: &FieldElement,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        self.with_instance(|barretenberg| {
+            barretenberg.schnorr_verify(public_key_x, public_key_y, signature, message)
+        })
+    }
+
+    fn pedersen(
+        &self,
+        inputs: &[FieldElement],
+        domain_separator: u32,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        self.with_instance(|barretenberg| barretenberg.pedersen(inputs, domain_separator))
+    }
+
+    fn fixed_base_scalar_mul(
+        &self,
+        low: &FieldElement,
+        high: &FieldElement,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        self.with_instance(|barretenberg| barretenberg.fixed_base_scalar_mul(low, high))
+    }
+}
+
+#[cfg(all(test, feature = "embedded-wasm"))]
+mod tests {
+    use acir::FieldElement;
+    use acvm_blackbox_solver::BlackBoxFunctionSolver;
+
+    use super::PooledBarretenbergSolver;
+
+    #[test]
+    fn pool_is_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PooledBarretenbergSolver>();
+
+        let solver = PooledBarretenbergSolver::new(2).unwrap();
+        let expected = solver.fixed_base_scalar_mul(&FieldElement::one(), &FieldElement::zero());
+        assert!(expected.is_ok());
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        solver.fixed_base_scalar_mul(&FieldElement::one(), &FieldElement::zero())
+                    })
+                })
+                .collect();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), expected);
+            }
+        });
+    }
+}
@@ -45,6 +45,17 @@ pub(crate) enum FeatureError {
     #[error("Value expected to be 0 or 1 representing a boolean")]
     InvalidBool,
 }
+/// An error encountered while loading the Barretenberg wasm module.
+#[derive(Debug, thiserror::Error)]
+pub enum WasmLoadError {
+    #[error("Could not read Barretenberg wasm module from {}", path.display())]
+    ReadFailed { path: PathBuf, source: std::io::Error },
+    #[error("Could not compile Barretenberg wasm module")]
+    CompileFailed(#[from] wasmer::CompileError),
+    #[error("Could not instantiate Barretenberg wasm module")]
+    InstantiationFailed(#[source] Box<wasmer::InstantiationError>),
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
 pub(crate) struct BackendError(#[from] Error);
@@ -62,7 +73,7 @@ pub(crate) struct Barretenberg {
     instance: wasmer::Instance,
 }
 
-use std::cell::RefCell;
+use std::{cell::RefCell, path::PathBuf};
 
 use wasmer::{
     imports, Function, FunctionEnv, FunctionEnvMut, Imports, Instance, Memory, MemoryType, Store,
@@ -77,26 +88,71 @@ use wasmer::{
 /// when calling functions on the wasm, however it's important to not overrun this scratch space as otherwise
 /// the written data will begin to corrupt the stack.
 ///
-/// Using this scratch space isn't particularly safe if we have multiple threads interacting with the wasm however,
-/// each thread could write to the same pointer address simultaneously.
+/// Each [`Barretenberg`] has its own memory and so its own scratch space. This is safe as long as an instance is
+/// only used by a single thread at a time, which [`PooledBarretenbergSolver`][crate::PooledBarretenbergSolver] ensures.
 pub(super) const WASM_SCRATCH_BYTES: usize = 1024;
 
 /// Embed the Barretenberg WASM file
+#[cfg(feature = "embedded-wasm")]
 #[derive(rust_embed::RustEmbed)]
 #[folder = "$BARRETENBERG_BIN_DIR"]
 #[include = "acvm_backend.wasm"]
 struct Wasm;
 
-impl Barretenberg {
-    #[cfg(not(target_arch = "wasm32"))]
-    pub(crate) fn new() -> Barretenberg {
-        let (instance, memory, store) = instance_load();
+/// A compiled Barretenberg wasm module from which any number of [`Barretenberg`] instances can be created.
+///
+/// Instances share the engine used to compile the module so that it is only compiled once.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub(crate) struct BarretenbergModule {
+    engine: wasmer::Engine,
+    module: wasmer::Module,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BarretenbergModule {
+    /// Compiles the wasm module which was embedded at build time.
+    #[cfg(feature = "embedded-wasm")]
+    pub(crate) fn embedded() -> Result<Self, WasmLoadError> {
+        Self::from_bytes(&Wasm::get("acvm_backend.wasm").unwrap().data)
+    }
+
+    /// Reads and compiles the wasm module found at `path`.
+    pub(crate) fn from_path(path: &std::path::Path) -> Result<Self, WasmLoadError> {
+        let bytes = std::fs::read(path)
+            .map_err(|source| WasmLoadError::ReadFailed { path: path.to_path_buf(), source })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Compiles the wasm module contained in `bytes`.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, WasmLoadError> {
+        let store = Store::default();
+        let module = wasmer::Module::new(&store, bytes)?;
+        Ok(BarretenbergModule { engine: store.engine().clone(), module })
+    }
+
+    /// Creates a new instance of the module with its own store and memory.
+    pub(crate) fn instantiate(&self) -> Result<Barretenberg, WasmLoadError> {
+        let (memory, mut store, custom_imports) =
+            init_memory_and_state(Store::new(self.engine.clone()));
+        let instance = Instance::new(&mut store, &self.module, &custom_imports)
+            .map_err(|err| WasmLoadError::InstantiationFailed(Box::new(err)))?;
+
         let barretenberg = Barretenberg { memory, instance, store: RefCell::new(store) };
         barretenberg.call_wasi_initialize();
-        barretenberg
+        Ok(barretenberg)
+    }
+}
+
+impl Barretenberg {
+    #[cfg(all(not(target_arch = "wasm32"), feature = "embedded-wasm"))]
+    pub(crate) fn new() -> Barretenberg {
+        BarretenbergModule::embedded()
+            .and_then(|module| module.instantiate())
+            .expect("expected embedded Barretenberg wasm module to be loaded")
     }
 
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(all(target_arch = "wasm32", feature = "embedded-wasm"))]
     pub(crate) async fn initialize() -> Barretenberg {
         let (instance, memory, store) = instance_load().await;
         let barretenberg = Barretenberg { memory, instance, store: RefCell::new(store) };
@@ -258,9 +314,7 @@ impl Barretenberg {
     }
 }
 
-fn init_memory_and_state() -> (Memory, Store, Imports) {
-    let mut store = Store::default();
-
+fn init_memory_and_state(mut store: Store) -> (Memory, Store, Imports) {
     let mem_type = MemoryType::new(18, Some(65536), false);
     let memory = Memory::new(&mut store, mem_type).unwrap();
 
@@ -287,23 +341,12 @@ fn init_memory_and_state() -> (Memory, Store, Imports) {
     (memory, store, custom_imports)
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-fn instance_load() -> (Instance, Memory, Store) {
-    use wasmer::Module;
-
-    let (memory, mut store, custom_imports) = init_memory_and_state();
-
-    let module = Module::new(&store, Wasm::get("acvm_backend.wasm").unwrap().data).unwrap();
-
-    (Instance::new(&mut store, &module, &custom_imports).unwrap(), memory, store)
-}
-
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", feature = "embedded-wasm"))]
 async fn instance_load() -> (Instance, Memory, Store) {
     use js_sys::WebAssembly::{self};
     use wasmer::AsJs;
 
-    let (memory, mut store, custom_imports) = init_memory_and_state();
+    let (memory, mut store, custom_imports) = init_memory_and_state(Store::default());
 
     let wasm_binary = Wasm::get("acvm_backend.wasm").unwrap().data;
 
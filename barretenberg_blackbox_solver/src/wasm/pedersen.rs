@@ -35,6 +35,7 @@ impl Pedersen for Barretenberg {
     }
 }
 
+#[cfg(feature = "embedded-wasm")]
 #[test]
 fn pedersen_hash_to_point() -> Result<(), Error> {
     let barretenberg = Barretenberg::new();
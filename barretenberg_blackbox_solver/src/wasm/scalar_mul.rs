@@ -65,7 +65,7 @@ impl ScalarMul for Barretenberg {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "embedded-wasm"))]
 mod test {
     use super::*;
     #[test]
@@ -0,0 +1,410 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::Mutex,
+};
+
+use acir::{AcirField, BlackBoxFunc, FieldElement};
+
+use crate::{BlackBoxCall, BlackBoxFunctionSolver, BlackBoxResolutionError};
+
+/// The number of calls to each black box function which were answered from the cache of a [`CachingSolver`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStatistics {
+    /// The number of calls which were answered from the cache.
+    pub hits: u64,
+    /// The number of calls which were passed on to the wrapped solver.
+    pub misses: u64,
+}
+
+impl CacheStatistics {
+    /// Returns the proportion of calls which were answered from the cache, or zero if no calls have been made.
+    pub fn hit_rate(&self) -> f64 {
+        let calls = self.hits + self.misses;
+        if calls == 0 {
+            0.0
+        } else {
+            self.hits as f64 / calls as f64
+        }
+    }
+}
+
+impl std::ops::AddAssign for CacheStatistics {
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+    }
+}
+
+/// A bounded map which evicts its least recently used entry once full.
+#[derive(Debug)]
+struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>,
+    /// The keys of `entries` ordered by when they were last used.
+    recency: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        LruCache { capacity, entries: HashMap::new(), recency: BTreeMap::new(), tick: 0 }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let (value, last_used) = self.entries.get_mut(key)?;
+        let key = self.recency.remove(last_used).expect("every entry has a recency");
+        self.tick += 1;
+        *last_used = self.tick;
+        self.recency.insert(self.tick, key);
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (value, self.tick)) {
+            // The key was inserted concurrently by another caller.
+            self.recency.remove(&last_used);
+        } else if self.entries.len() > self.capacity {
+            let (_, evicted) = self.recency.pop_first().expect("cache is not empty");
+            self.entries.remove(&evicted);
+        }
+        self.recency.insert(self.tick, key);
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CachedOutput<F> {
+    Boolean(bool),
+    Point(F, F),
+}
+
+#[derive(Debug)]
+struct CacheState<F> {
+    outputs: LruCache<BlackBoxCall<F>, CachedOutput<F>>,
+    statistics: HashMap<BlackBoxFunc, CacheStatistics>,
+}
+
+/// A [`BlackBoxFunctionSolver`] which remembers the results of the most recent calls to the solver it wraps.
+///
+/// Circuits often repeat calls to expensive black box functions with identical inputs, e.g. when recomputing Merkle paths.
+/// Such calls are answered from a bounded cache holding the results of the `capacity` most recently used calls,
+/// rather than being passed on to the wrapped solver. Failed calls are never cached.
+///
+/// As it implements [`BlackBoxFunctionSolver`] itself, a `CachingSolver` can be used by both the ACVM and the Brillig VM.
+#[derive(Debug)]
+pub struct CachingSolver<B, F = FieldElement> {
+    solver: B,
+    state: Mutex<CacheState<F>>,
+}
+
+impl<B: BlackBoxFunctionSolver<F>, F: AcirField> CachingSolver<B, F> {
+    /// Wraps `solver` in a cache which holds the results of up to `capacity` calls.
+    pub fn new(solver: B, capacity: usize) -> Self {
+        let state = CacheState { outputs: LruCache::new(capacity), statistics: HashMap::default() };
+        CachingSolver { solver, state: Mutex::new(state) }
+    }
+
+    /// Returns the wrapped solver.
+    pub fn inner(&self) -> &B {
+        &self.solver
+    }
+
+    /// Consumes the cache, returning the wrapped solver.
+    pub fn into_inner(self) -> B {
+        self.solver
+    }
+
+    /// Returns the number of calls whose results are currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().outputs.len()
+    }
+
+    /// Returns `true` if no results are currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cache statistics for calls to `function`.
+    pub fn statistics(&self, function: BlackBoxFunc) -> CacheStatistics {
+        self.lock().statistics.get(&function).copied().unwrap_or_default()
+    }
+
+    /// Returns the cache statistics across calls to all black box functions.
+    pub fn total_statistics(&self) -> CacheStatistics {
+        let mut total = CacheStatistics::default();
+        for statistics in self.lock().statistics.values() {
+            total += *statistics;
+        }
+        total
+    }
+
+    /// Removes all cached results and resets the cache statistics.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.outputs.clear();
+        state.statistics.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState<F>> {
+        // The cache is left in a consistent state even if a thread panics while holding the lock.
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Returns the cached output of the call described by `key`, or computes it with `solve` and caches it.
+    fn cached(
+        &self,
+        key: BlackBoxCall<F>,
+        solve: impl FnOnce(&B) -> Result<CachedOutput<F>, BlackBoxResolutionError>,
+    ) -> Result<CachedOutput<F>, BlackBoxResolutionError> {
+        let function = key.function();
+        {
+            let mut state = self.lock();
+            let cached = state.outputs.get(&key);
+            let statistics = state.statistics.entry(function).or_default();
+            if let Some(output) = cached {
+                statistics.hits += 1;
+                return Ok(output);
+            }
+            statistics.misses += 1;
+        }
+
+        // The lock is released while solving so that calls from other threads are not blocked.
+        let output = solve(&self.solver)?;
+        self.lock().outputs.insert(key, output.clone());
+        Ok(output)
+    }
+
+    /// As [`Self::cached`], for functions which return a point on the embedded curve.
+    fn cached_point(
+        &self,
+        key: BlackBoxCall<F>,
+        solve: impl FnOnce(&B) -> Result<(F, F), BlackBoxResolutionError>,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        let function = key.function();
+        let output =
+            self.cached(key, |solver| solve(solver).map(|(x, y)| CachedOutput::Point(x, y)))?;
+        match output {
+            CachedOutput::Point(x, y) => Ok((x, y)),
+            CachedOutput::Boolean(_) => unreachable!("{function} always returns a point"),
+        }
+    }
+}
+
+impl<B: BlackBoxFunctionSolver<F>, F: AcirField> BlackBoxFunctionSolver<F> for CachingSolver<B, F> {
+    fn schnorr_verify(
+        &self,
+        public_key_x: &F,
+        public_key_y: &F,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        let key = BlackBoxCall::SchnorrVerify {
+            public_key_x: *public_key_x,
+            public_key_y: *public_key_y,
+            signature: signature.to_vec(),
+            message: message.to_vec(),
+        };
+        let output = self.cached(key, |solver| {
+            solver
+                .schnorr_verify(public_key_x, public_key_y, signature, message)
+                .map(CachedOutput::Boolean)
+        })?;
+        match output {
+            CachedOutput::Boolean(valid) => Ok(valid),
+            CachedOutput::Point(..) => unreachable!("schnorr_verify always returns a boolean"),
+        }
+    }
+
+    fn pedersen(
+        &self,
+        inputs: &[F],
+        domain_separator: u32,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        let key = BlackBoxCall::Pedersen { inputs: inputs.to_vec(), domain_separator };
+        self.cached_point(key, |solver| solver.pedersen(inputs, domain_separator))
+    }
+
+    fn fixed_base_scalar_mul(&self, low: &F, high: &F) -> Result<(F, F), BlackBoxResolutionError> {
+        let key = BlackBoxCall::FixedBaseScalarMul { low: *low, high: *high };
+        self.cached_point(key, |solver| solver.fixed_base_scalar_mul(low, high))
+    }
+
+    fn embedded_curve_add(
+        &self,
+        input1_x: &F,
+        input1_y: &F,
+        input2_x: &F,
+        input2_y: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        let key = BlackBoxCall::EmbeddedCurveAdd {
+            input1_x: *input1_x,
+            input1_y: *input1_y,
+            input2_x: *input2_x,
+            input2_y: *input2_y,
+        };
+        self.cached_point(key, |solver| {
+            solver.embedded_curve_add(input1_x, input1_y, input2_x, input2_y)
+        })
+    }
+
+    fn variable_base_scalar_mul(
+        &self,
+        point_x: &F,
+        point_y: &F,
+        scalar_low: &F,
+        scalar_high: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        let key = BlackBoxCall::VariableBaseScalarMul {
+            point_x: *point_x,
+            point_y: *point_y,
+            scalar_low: *scalar_low,
+            scalar_high: *scalar_high,
+        };
+        self.cached_point(key, |solver| {
+            solver.variable_base_scalar_mul(point_x, point_y, scalar_low, scalar_high)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use acir::{BlackBoxFunc, FieldElement};
+
+    use super::{CacheStatistics, CachingSolver};
+    use crate::{BlackBoxFunctionSolver, BlackBoxResolutionError};
+
+    /// Counts the calls made to it, returning the sum of its inputs from each function.
+    #[derive(Default)]
+    struct CountingSolver {
+        calls: Cell<usize>,
+    }
+
+    impl BlackBoxFunctionSolver for CountingSolver {
+        fn schnorr_verify(
+            &self,
+            _public_key_x: &FieldElement,
+            _public_key_y: &FieldElement,
+            signature: &[u8],
+            _message: &[u8],
+        ) -> Result<bool, BlackBoxResolutionError> {
+            self.calls.set(self.calls.get() + 1);
+            if signature.is_empty() {
+                return Err(BlackBoxResolutionError::Failed(
+                    BlackBoxFunc::SchnorrVerify,
+                    "empty signature".to_owned(),
+                ));
+            }
+            Ok(true)
+        }
+        fn pedersen(
+            &self,
+            inputs: &[FieldElement],
+            domain_separator: u32,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            self.calls.set(self.calls.get() + 1);
+            let sum = inputs.iter().fold(FieldElement::zero(), |sum, input| sum + *input);
+            Ok((sum, FieldElement::from(domain_separator as u128)))
+        }
+        fn fixed_base_scalar_mul(
+            &self,
+            low: &FieldElement,
+            high: &FieldElement,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok((*low, *high))
+        }
+    }
+
+    #[test]
+    fn repeated_calls_are_answered_from_the_cache() {
+        let solver = CachingSolver::new(CountingSolver::default(), 8);
+        let inputs = [FieldElement::from(1u128), FieldElement::from(2u128)];
+
+        let first = solver.pedersen(&inputs, 0).unwrap();
+        assert_eq!(solver.pedersen(&inputs, 0).unwrap(), first);
+        // Calls with different inputs are not answered from the cache.
+        assert_ne!(solver.pedersen(&inputs, 1).unwrap(), first);
+        assert_eq!(
+            solver.fixed_base_scalar_mul(&inputs[0], &inputs[1]).unwrap(),
+            (inputs[0], inputs[1])
+        );
+
+        assert_eq!(solver.inner().calls.get(), 3);
+        assert_eq!(solver.len(), 3);
+        assert_eq!(
+            solver.statistics(BlackBoxFunc::Pedersen),
+            CacheStatistics { hits: 1, misses: 2 }
+        );
+        assert_eq!(
+            solver.statistics(BlackBoxFunc::FixedBaseScalarMul),
+            CacheStatistics { hits: 0, misses: 1 }
+        );
+        assert_eq!(solver.total_statistics().hit_rate(), 0.25);
+
+        solver.clear();
+        assert!(solver.is_empty());
+        assert_eq!(solver.total_statistics(), CacheStatistics::default());
+    }
+
+    #[test]
+    fn least_recently_used_results_are_evicted() {
+        let solver = CachingSolver::new(CountingSolver::default(), 2);
+        let [a, b, c] = [1u128, 2, 3].map(FieldElement::from);
+
+        solver.fixed_base_scalar_mul(&a, &a).unwrap();
+        solver.fixed_base_scalar_mul(&b, &b).unwrap();
+        // Using `a` makes `b` the least recently used result, which is evicted when `c` is cached.
+        solver.fixed_base_scalar_mul(&a, &a).unwrap();
+        solver.fixed_base_scalar_mul(&c, &c).unwrap();
+        assert_eq!(solver.len(), 2);
+        assert_eq!(solver.inner().calls.get(), 3);
+
+        solver.fixed_base_scalar_mul(&a, &a).unwrap();
+        assert_eq!(solver.inner().calls.get(), 3);
+        solver.fixed_base_scalar_mul(&b, &b).unwrap();
+        assert_eq!(solver.inner().calls.get(), 4);
+    }
+
+    #[test]
+    fn failed_calls_are_not_cached() {
+        let solver = CachingSolver::new(CountingSolver::default(), 2);
+        let key = FieldElement::one();
+
+        assert!(solver.schnorr_verify(&key, &key, &[], &[]).is_err());
+        assert!(solver.schnorr_verify(&key, &key, &[], &[]).is_err());
+        assert!(solver.schnorr_verify(&key, &key, &[1], &[]).unwrap());
+        assert!(solver.schnorr_verify(&key, &key, &[1], &[]).unwrap());
+
+        assert_eq!(solver.inner().calls.get(), 3);
+        assert_eq!(
+            solver.statistics(BlackBoxFunc::SchnorrVerify),
+            CacheStatistics { hits: 1, misses: 3 }
+        );
+    }
+
+    #[test]
+    fn zero_capacity_disables_caching() {
+        let solver = CachingSolver::new(CountingSolver::default(), 0);
+        let one = FieldElement::one();
+
+        solver.fixed_base_scalar_mul(&one, &one).unwrap();
+        solver.fixed_base_scalar_mul(&one, &one).unwrap();
+        assert_eq!(solver.inner().calls.get(), 2);
+        assert!(solver.is_empty());
+    }
+}
@@ -0,0 +1,24 @@
+use acir::{BlackBoxFunc, FieldElement};
+
+/// The arguments of a single call to a [`BlackBoxFunctionSolver`][crate::BlackBoxFunctionSolver].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BlackBoxCall<F = FieldElement> {
+    SchnorrVerify { public_key_x: F, public_key_y: F, signature: Vec<u8>, message: Vec<u8> },
+    Pedersen { inputs: Vec<F>, domain_separator: u32 },
+    FixedBaseScalarMul { low: F, high: F },
+    EmbeddedCurveAdd { input1_x: F, input1_y: F, input2_x: F, input2_y: F },
+    VariableBaseScalarMul { point_x: F, point_y: F, scalar_low: F, scalar_high: F },
+}
+
+impl<F> BlackBoxCall<F> {
+    /// Returns the black box function which was called.
+    pub fn function(&self) -> BlackBoxFunc {
+        match self {
+            BlackBoxCall::SchnorrVerify { .. } => BlackBoxFunc::SchnorrVerify,
+            BlackBoxCall::Pedersen { .. } => BlackBoxFunc::Pedersen,
+            BlackBoxCall::FixedBaseScalarMul { .. } => BlackBoxFunc::FixedBaseScalarMul,
+            BlackBoxCall::EmbeddedCurveAdd { .. } => BlackBoxFunc::EmbeddedCurveAdd,
+            BlackBoxCall::VariableBaseScalarMul { .. } => BlackBoxFunc::VariableBaseScalarMul,
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Reference implementations of EdDSA signature verification.
+//!
+//! - Ed25519 signatures are verified as specified in [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032).
+//! - BabyJubJub signatures are verified as by circomlib's `EdDSAPoseidonVerifier`, hashing with the circom
+//!   instantiation of Poseidon. [BabyJubJub][babyjubjub] is a twisted Edwards curve whose base field is the
+//!   BN254 scalar field, so its points are represented by pairs of field elements.
+//!
+//! [babyjubjub]: https://eips.ethereum.org/EIPS/eip-2494
+
+use acir::{AcirField, BlackBoxFunc};
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use light_poseidon::{Poseidon, PoseidonHasher};
+
+use crate::BlackBoxResolutionError;
+
+pub fn eddsa_ed25519_verify(
+    public_key: &[u8; 32],
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<bool, BlackBoxResolutionError> {
+    // A public key which does not decode to a curve point cannot have produced a valid signature.
+    let Ok(verifying_key) = VerifyingKey::from_bytes(public_key) else {
+        return Ok(false);
+    };
+    let signature = Signature::from_bytes(signature);
+    Ok(verifying_key.verify(message, &signature).is_ok())
+}
+
+/// Verifies a signature `(R8, S)` by the public key `A` over the single field element `message`.
+///
+/// Fails if `F` is not the BN254 scalar field, which is the base field of BabyJubJub.
+pub fn eddsa_babyjubjub_verify<F: AcirField>(
+    public_key_x: &F,
+    public_key_y: &F,
+    signature_s: &F,
+    signature_r8_x: &F,
+    signature_r8_y: &F,
+    message: &F,
+) -> Result<bool, BlackBoxResolutionError> {
+    if F::modulus().to_bytes_be() != Fr::MODULUS.to_bytes_be() {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::EddsaBabyJubJub,
+            "BabyJubJub is only defined over the BN254 scalar field".to_string(),
+        ));
+    }
+
+    let public_key = (*public_key_x, *public_key_y);
+    let r8 = (*signature_r8_x, *signature_r8_y);
+    if !is_on_curve(public_key) || !is_on_curve(r8) || public_key_x.is_zero() {
+        return Ok(false);
+    }
+    let sub_order = F::from_hex(SUB_ORDER).expect("should be a valid field element");
+    if signature_s.to_be_bytes() >= sub_order.to_be_bytes() {
+        return Ok(false);
+    }
+
+    let hash = poseidon_hash(&[r8.0, r8.1, public_key.0, public_key.1, *message])
+        .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::EddsaBabyJubJub, err))?;
+
+    // S * B8 == R8 + 8 * hash * A
+    let base = (
+        F::from_hex(BASE8_X).expect("should be a valid field element"),
+        F::from_hex(BASE8_Y).expect("should be a valid field element"),
+    );
+    let lhs = scalar_mul(base, signature_s);
+    let mut hash_times_public_key = scalar_mul(public_key, &hash);
+    for _ in 0..3 {
+        hash_times_public_key = add(hash_times_public_key, hash_times_public_key);
+    }
+    let rhs = add(r8, hash_times_public_key);
+
+    Ok(lhs == rhs)
+}
+
+/// The order of the prime order subgroup of BabyJubJub.
+const SUB_ORDER: &str = "0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1";
+/// The generator of the prime order subgroup used by circomlib, `Base8`.
+const BASE8_X: &str = "0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051";
+const BASE8_Y: &str = "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b";
+
+/// The coefficients of the curve `a * x^2 + y^2 = 1 + d * x^2 * y^2`.
+fn coefficients<F: AcirField>() -> (F, F) {
+    (F::from(168700_u128), F::from(168696_u128))
+}
+
+fn is_on_curve<F: AcirField>((x, y): (F, F)) -> bool {
+    let (a, d) = coefficients::<F>();
+    let (x_squared, y_squared) = (x * x, y * y);
+    a * x_squared + y_squared == F::one() + d * x_squared * y_squared
+}
+
+/// Adds two points using the twisted Edwards addition law, which is complete on BabyJubJub.
+fn add<F: AcirField>((x1, y1): (F, F), (x2, y2): (F, F)) -> (F, F) {
+    let (a, d) = coefficients::<F>();
+    let t = d * x1 * x2 * y1 * y2;
+    let x3 = (x1 * y2 + y1 * x2) / (F::one() + t);
+    let y3 = (y1 * y2 - a * x1 * x2) / (F::one() - t);
+    (x3, y3)
+}
+
+/// Multiplies `point` by the integer representation of `scalar` using double-and-add.
+fn scalar_mul<F: AcirField>(point: (F, F), scalar: &F) -> (F, F) {
+    let mut result = (F::zero(), F::one());
+    for byte in scalar.to_be_bytes() {
+        for bit in (0..8).rev() {
+            result = add(result, result);
+            if (byte >> bit) & 1 == 1 {
+                result = add(result, point);
+            }
+        }
+    }
+    result
+}
+
+fn poseidon_hash<F: AcirField>(inputs: &[F]) -> Result<F, String> {
+    let inputs: Vec<Fr> =
+        inputs.iter().map(|input| Fr::from_be_bytes_mod_order(&input.to_be_bytes())).collect();
+    let mut hasher = Poseidon::<Fr>::new_circom(inputs.len()).map_err(|err| err.to_string())?;
+    let hash = hasher.hash(&inputs).map_err(|err| err.to_string())?;
+    Ok(F::from_be_bytes_reduce(&hash.into_bigint().to_bytes_be()))
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::FieldElement;
+
+    use super::{eddsa_babyjubjub_verify, eddsa_ed25519_verify};
+
+    fn from_hex<const N: usize>(hex: &str) -> [u8; N] {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect::<Vec<u8>>()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn verifies_rfc_8032_test_vectors() {
+        // TEST 1 and TEST 2 from RFC 8032, section 7.1.
+        let public_key =
+            from_hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        let signature = from_hex(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        );
+        assert!(eddsa_ed25519_verify(&public_key, &signature, &[]).unwrap());
+
+        let public_key =
+            from_hex("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c");
+        let signature = from_hex(
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        );
+        assert!(eddsa_ed25519_verify(&public_key, &signature, &[0x72]).unwrap());
+        assert!(!eddsa_ed25519_verify(&public_key, &signature, &[0x73]).unwrap());
+    }
+
+    /// A signature over the message `1234` by the secret scalar
+    /// `0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcde` with the nonce
+    /// `0x0fedcba9876543210fedcba9876543210fedcba9876543210fedcba98765432`.
+    fn babyjubjub_signature() -> [FieldElement; 5] {
+        [
+            "0x0420363509042103f03c2529541a4c1d5767542c91886c07d99853c4636336f1",
+            "0x249a9d2e91187b047ccbe22d3839a7c24aa2de53b338ccedaed3ce8c7bfa8499",
+            "0x037bdc2905b3fda5c447dd831c60d2c0d9c7b844fbd2944431ba99256364929e",
+            "0x2eaa0aa4353db8ec74974fe45355edb35f9b76ac069d2e648809f1ffb0c781e2",
+            "0x168ad114fcbfd5261fcff77eca6755b96fe9b064deaeb3cabcd2ba0e07bc4283",
+        ]
+        .map(|hex| FieldElement::from_hex(hex).unwrap())
+    }
+
+    #[test]
+    fn verifies_babyjubjub_signature() {
+        let [public_key_x, public_key_y, s, r8_x, r8_y] = babyjubjub_signature();
+        let message = FieldElement::from(1234_u128);
+
+        let valid =
+            eddsa_babyjubjub_verify(&public_key_x, &public_key_y, &s, &r8_x, &r8_y, &message);
+        assert!(valid.unwrap());
+
+        let wrong_message = FieldElement::from(1235_u128);
+        let valid =
+            eddsa_babyjubjub_verify(&public_key_x, &public_key_y, &s, &r8_x, &r8_y, &wrong_message);
+        assert!(!valid.unwrap());
+    }
+
+    #[test]
+    fn rejects_malleated_babyjubjub_signature() {
+        let [public_key_x, public_key_y, s, r8_x, r8_y] = babyjubjub_signature();
+        let message = FieldElement::from(1234_u128);
+
+        // Adding the subgroup order to `S` leaves the equation satisfied but must still be rejected.
+        let sub_order = FieldElement::from_hex(super::SUB_ORDER).unwrap();
+        let valid = eddsa_babyjubjub_verify(
+            &public_key_x,
+            &public_key_y,
+            &(s + sub_order),
+            &r8_x,
+            &r8_y,
+            &message,
+        );
+        assert!(!valid.unwrap());
+    }
+}
@@ -0,0 +1,134 @@
+//! Reference implementations of arithmetic over the embedded curve, the curve whose base field is the field on
+//! which ACIR is defined. For BN254 this is [Grumpkin][grumpkin], `y^2 = x^3 - 17`.
+//!
+//! Only short Weierstrass curves with `a = 0` are supported. The group law does not depend upon `b`
+//! so the functions here work for any such curve, provided that the inputs lie on it.
+//! The point at infinity is represented by `(0, 0)` which never lies on a curve with `a = 0` and `b != 0`.
+//!
+//! [grumpkin]: https://hackmd.io/@aztec-network/ByzgNxBfd#2-Grumpkin---A-curve-on-top-of-BN-254-for-SNARK-efficient-group-operations
+
+use acir::{AcirField, BlackBoxFunc};
+
+use crate::BlackBoxResolutionError;
+
+/// Returns whether `(x, y)` is the representation of the point at infinity.
+fn is_infinity<F: AcirField>(x: &F, y: &F) -> bool {
+    x.is_zero() && y.is_zero()
+}
+
+fn double<F: AcirField>(x: F, y: F) -> (F, F) {
+    if y.is_zero() {
+        // Either the point at infinity or a point of order two.
+        return (F::zero(), F::zero());
+    }
+    let lambda = (F::from(3_u128) * x * x) / (F::from(2_u128) * y);
+    let x3 = lambda * lambda - x - x;
+    let y3 = lambda * (x - x3) - y;
+    (x3, y3)
+}
+
+fn add<F: AcirField>(x1: F, y1: F, x2: F, y2: F) -> (F, F) {
+    if is_infinity(&x1, &y1) {
+        return (x2, y2);
+    }
+    if is_infinity(&x2, &y2) {
+        return (x1, y1);
+    }
+    if x1 == x2 {
+        return if y1 == y2 { double(x1, y1) } else { (F::zero(), F::zero()) };
+    }
+    let lambda = (y2 - y1) / (x2 - x1);
+    let x3 = lambda * lambda - x1 - x2;
+    let y3 = lambda * (x1 - x3) - y1;
+    (x3, y3)
+}
+
+/// Adds the points `(input1_x, input1_y)` and `(input2_x, input2_y)` on the embedded curve.
+pub fn embedded_curve_add<F: AcirField>(
+    input1_x: &F,
+    input1_y: &F,
+    input2_x: &F,
+    input2_y: &F,
+) -> Result<(F, F), BlackBoxResolutionError> {
+    Ok(add(*input1_x, *input1_y, *input2_x, *input2_y))
+}
+
+/// Multiplies the point `(point_x, point_y)` on the embedded curve by the scalar `scalar_low + scalar_high * 2^128`.
+///
+/// Fails if either limb of the scalar does not fit in 128 bits.
+pub fn variable_base_scalar_mul<F: AcirField>(
+    point_x: &F,
+    point_y: &F,
+    scalar_low: &F,
+    scalar_high: &F,
+) -> Result<(F, F), BlackBoxResolutionError> {
+    let (Some(low), Some(high)) = (scalar_low.try_into_u128(), scalar_high.try_into_u128()) else {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::VariableBaseScalarMul,
+            "scalar limbs must each fit in 128 bits".to_string(),
+        ));
+    };
+
+    // Double-and-add, starting from the most significant bit of the scalar.
+    let mut result = (F::zero(), F::zero());
+    for limb in [high, low] {
+        for bit in (0..128).rev() {
+            result = double(result.0, result.1);
+            if (limb >> bit) & 1 == 1 {
+                result = add(result.0, result.1, *point_x, *point_y);
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::FieldElement;
+
+    use super::{embedded_curve_add, variable_base_scalar_mul};
+
+    /// The generator of Grumpkin.
+    fn generator() -> (FieldElement, FieldElement) {
+        let y = FieldElement::from_hex(
+            "0x0000000000000002cf135e7506a45d632d270d45f1181294833fc48d823f272c",
+        )
+        .unwrap();
+        (FieldElement::one(), y)
+    }
+
+    fn is_on_grumpkin(x: FieldElement, y: FieldElement) -> bool {
+        y * y == x * x * x - FieldElement::from(17_u128)
+    }
+
+    #[test]
+    fn addition_matches_scalar_multiplication() {
+        let (gx, gy) = generator();
+        assert!(is_on_grumpkin(gx, gy));
+
+        let double = embedded_curve_add(&gx, &gy, &gx, &gy).unwrap();
+        let triple = embedded_curve_add(&double.0, &double.1, &gx, &gy).unwrap();
+        assert!(is_on_grumpkin(triple.0, triple.1));
+
+        let three = FieldElement::from(3_u128);
+        let zero = FieldElement::zero();
+        assert_eq!(variable_base_scalar_mul(&gx, &gy, &three, &zero).unwrap(), triple);
+    }
+
+    #[test]
+    fn handles_the_point_at_infinity() {
+        let (gx, gy) = generator();
+        let zero = FieldElement::zero();
+
+        assert_eq!(variable_base_scalar_mul(&gx, &gy, &zero, &zero).unwrap(), (zero, zero));
+        assert_eq!(embedded_curve_add(&gx, &gy, &zero, &zero).unwrap(), (gx, gy));
+        assert_eq!(embedded_curve_add(&gx, &gy, &gx, &-gy).unwrap(), (zero, zero));
+    }
+
+    #[test]
+    fn rejects_oversized_scalar_limbs() {
+        let (gx, gy) = generator();
+        let too_large = FieldElement::from(u128::MAX) + FieldElement::one();
+        assert!(variable_base_scalar_mul(&gx, &gy, &too_large, &FieldElement::zero()).is_err());
+    }
+}
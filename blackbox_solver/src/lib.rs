@@ -5,13 +5,25 @@
 //! For functions that are backend-dependent, it provides a Trait [BlackBoxFunctionSolver] that must be implemented by the backend.
 //! For functions that have a reference implementation, such as [keccak256], this crate exports the reference implementation directly.
 
-use acir::{BlackBoxFunc, FieldElement};
+use acir::{AcirField, BlackBoxFunc, FieldElement};
 use blake2::digest::generic_array::GenericArray;
 use blake2::{Blake2s256, Digest};
-use sha2::Sha256;
+use sha2::{Sha256, Sha512};
 use sha3::Keccak256;
 use thiserror::Error;
 
+mod caching;
+mod call;
+mod eddsa;
+mod embedded_curve;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use caching::{CacheStatistics, CachingSolver};
+pub use call::BlackBoxCall;
+pub use eddsa::{eddsa_babyjubjub_verify, eddsa_ed25519_verify};
+pub use embedded_curve::{embedded_curve_add, variable_base_scalar_mul};
+
 #[derive(Clone, PartialEq, Eq, Debug, Error)]
 pub enum BlackBoxResolutionError {
     #[error("unsupported blackbox function: {0}")]
@@ -24,24 +36,52 @@ pub enum BlackBoxResolutionError {
 /// doesn't have a canonical Rust implementation.
 ///
 /// Returns an [`BlackBoxResolutionError`] if the backend does not support the given [`acir::BlackBoxFunc`].
-pub trait BlackBoxFunctionSolver {
+pub trait BlackBoxFunctionSolver<F = FieldElement> {
     fn schnorr_verify(
         &self,
-        public_key_x: &FieldElement,
-        public_key_y: &FieldElement,
+        public_key_x: &F,
+        public_key_y: &F,
         signature: &[u8],
         message: &[u8],
     ) -> Result<bool, BlackBoxResolutionError>;
     fn pedersen(
         &self,
-        inputs: &[FieldElement],
+        inputs: &[F],
         domain_separator: u32,
-    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError>;
-    fn fixed_base_scalar_mul(
+    ) -> Result<(F, F), BlackBoxResolutionError>;
+    fn fixed_base_scalar_mul(&self, low: &F, high: &F) -> Result<(F, F), BlackBoxResolutionError>;
+
+    /// Adds two points on the embedded curve.
+    ///
+    /// Defaults to the reference implementation, [`embedded_curve_add`].
+    fn embedded_curve_add(
+        &self,
+        input1_x: &F,
+        input1_y: &F,
+        input2_x: &F,
+        input2_y: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError>
+    where
+        F: AcirField,
+    {
+        embedded_curve_add(input1_x, input1_y, input2_x, input2_y)
+    }
+
+    /// Multiplies a point on the embedded curve by the scalar `scalar_low + scalar_high * 2^128`.
+    ///
+    /// Defaults to the reference implementation, [`variable_base_scalar_mul`].
+    fn variable_base_scalar_mul(
         &self,
-        low: &FieldElement,
-        high: &FieldElement,
-    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError>;
+        point_x: &F,
+        point_y: &F,
+        scalar_low: &F,
+        scalar_high: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError>
+    where
+        F: AcirField,
+    {
+        variable_base_scalar_mul(point_x, point_y, scalar_low, scalar_high)
+    }
 }
 
 pub fn sha256(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
@@ -49,18 +89,38 @@ pub fn sha256(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::SHA256, err))
 }
 
+pub fn sha512(inputs: &[u8]) -> Result<[u8; 64], BlackBoxResolutionError> {
+    generic_hash_512::<Sha512>(inputs)
+        .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::SHA512, err))
+}
+
 pub fn blake2s(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
     generic_hash_256::<Blake2s256>(inputs)
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Blake2s, err))
 }
 
+pub fn blake3(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
+    Ok(blake3::hash(inputs).into())
+}
+
 pub fn keccak256(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
     generic_hash_256::<Keccak256>(inputs)
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Keccak256, err))
 }
 
-pub fn hash_to_field_128_security(inputs: &[u8]) -> Result<FieldElement, BlackBoxResolutionError> {
-    generic_hash_to_field::<Blake2s256>(inputs)
+/// Hashes `inputs` with Blake2s and reduces the digest into an element of the field `F`.
+///
+/// Fails for fields of fewer than 128 bits, as these cannot hold an output with 128 bits of security.
+pub fn hash_to_field_128_security<F: AcirField>(
+    inputs: &[u8],
+) -> Result<F, BlackBoxResolutionError> {
+    if F::max_num_bits() < 128 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::HashToField128Security,
+            format!("field of {} bits is too small for 128 bits of security", F::max_num_bits()),
+        ));
+    }
+    generic_hash_to_field::<Blake2s256, F>(inputs)
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::HashToField128Security, err))
 }
 
@@ -82,6 +142,19 @@ pub fn ecdsa_secp256r1_verify(
     Ok(verify_secp256r1_ecdsa_signature(hashed_msg, public_key_x, public_key_y, signature))
 }
 
+/// Recovers the public key which produced the secp256k1 ECDSA `signature` over `hashed_msg`, returning the
+/// big-endian bytes of its coordinates.
+///
+/// Only the recovery ids `0` and `1` are supported, which select the nonce point with an even or odd y-coordinate.
+pub fn ecdsa_secp256k1_recover(
+    hashed_msg: &[u8],
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<([u8; 32], [u8; 32]), BlackBoxResolutionError> {
+    recover_secp256k1_public_key(hashed_msg, signature, recovery_id)
+        .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::EcdsaSecp256k1Recover, err))
+}
+
 /// Does a generic hash of the inputs returning the resulting 32 bytes separately.
 fn generic_hash_256<D: Digest>(message: &[u8]) -> Result<[u8; 32], String> {
     let output_bytes: [u8; 32] =
@@ -90,12 +163,20 @@ fn generic_hash_256<D: Digest>(message: &[u8]) -> Result<[u8; 32], String> {
     Ok(output_bytes)
 }
 
+/// Does a generic hash of the inputs returning the resulting 64 bytes separately.
+fn generic_hash_512<D: Digest>(message: &[u8]) -> Result<[u8; 64], String> {
+    let output_bytes: [u8; 64] =
+        D::digest(message).as_slice().try_into().map_err(|_| "digest should be 512 bits")?;
+
+    Ok(output_bytes)
+}
+
 /// Does a generic hash of the entire inputs converting the resulting hash into a single output field.
-fn generic_hash_to_field<D: Digest>(message: &[u8]) -> Result<FieldElement, String> {
+fn generic_hash_to_field<D: Digest, F: AcirField>(message: &[u8]) -> Result<F, String> {
     let output_bytes: [u8; 32] =
         D::digest(message).as_slice().try_into().map_err(|_| "digest should be 256 bits")?;
 
-    Ok(FieldElement::from_be_bytes_reduce(&output_bytes))
+    Ok(F::from_be_bytes_reduce(&output_bytes))
 }
 
 fn verify_secp256k1_ecdsa_signature(
@@ -153,6 +234,33 @@ fn verify_secp256k1_ecdsa_signature(
     }
 }
 
+fn recover_secp256k1_public_key(
+    hashed_msg: &[u8],
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<([u8; 32], [u8; 32]), String> {
+    use k256::ecdsa::{recoverable, Signature};
+    use k256::elliptic_curve::sec1::{Coordinates, ToEncodedPoint};
+
+    let hashed_msg: [u8; 32] =
+        hashed_msg.try_into().map_err(|_| "hashed message should be 32 bytes".to_string())?;
+    let signature = Signature::try_from(signature.as_slice())
+        .map_err(|_| "signature components should be non-zero and less than the group order")?;
+    let recovery_id = recoverable::Id::new(recovery_id)
+        .map_err(|_| format!("unsupported recovery id {recovery_id}"))?;
+
+    let public_key = recoverable::Signature::new(&signature, recovery_id)
+        .and_then(|signature| {
+            signature.recover_verifying_key_from_digest_bytes(GenericArray::from_slice(&hashed_msg))
+        })
+        .map_err(|_| "no public key can be recovered from the signature".to_string())?;
+
+    match public_key.to_encoded_point(false).coordinates() {
+        Coordinates::Uncompressed { x, y } => Ok(((*x).into(), (*y).into())),
+        _ => unreachable!("Point is uncompressed"),
+    }
+}
+
 fn verify_secp256r1_ecdsa_signature(
     hashed_msg: &[u8],
     public_key_x_bytes: &[u8; 32],
@@ -211,10 +319,13 @@ fn verify_secp256r1_ecdsa_signature(
 
 #[cfg(test)]
 mod test {
-    use super::{verify_secp256k1_ecdsa_signature, verify_secp256r1_ecdsa_signature};
+    use super::{
+        recover_secp256k1_public_key, verify_secp256k1_ecdsa_signature,
+        verify_secp256r1_ecdsa_signature,
+    };
 
-    #[test]
-    fn verifies_valid_k1_signature_with_low_s_value() {
+    /// Returns a hashed message, the coordinates of a public key and a low S signature by that key over the message.
+    fn secp256k1_test_vector() -> ([u8; 32], [u8; 32], [u8; 32], [u8; 64]) {
         // 0x3a73f4123a5cd2121f21cd7e8d358835476949d035d9c2da6806b4633ac8c1e2,
         let hashed_message: [u8; 32] = [
             0x3a, 0x73, 0xf4, 0x12, 0x3a, 0x5c, 0xd2, 0x12, 0x1f, 0x21, 0xcd, 0x7e, 0x8d, 0x35,
@@ -245,12 +356,34 @@ mod test {
             0x01, 0x61, 0xe4, 0x9a, 0x71, 0x5f, 0xcd, 0x55,
         ];
 
+        (hashed_message, pub_key_x, pub_key_y, signature)
+    }
+
+    #[test]
+    fn verifies_valid_k1_signature_with_low_s_value() {
+        let (hashed_message, pub_key_x, pub_key_y, signature) = secp256k1_test_vector();
+
         let valid =
             verify_secp256k1_ecdsa_signature(&hashed_message, &pub_key_x, &pub_key_y, &signature);
 
         assert!(valid)
     }
 
+    #[test]
+    fn recovers_k1_public_key() {
+        let (hashed_message, pub_key_x, pub_key_y, signature) = secp256k1_test_vector();
+
+        let recovered = recover_secp256k1_public_key(&hashed_message, &signature, 0).unwrap();
+        assert_eq!(recovered, (pub_key_x, pub_key_y));
+
+        // The other recovery id selects the negated nonce point, which yields a different key.
+        let recovered = recover_secp256k1_public_key(&hashed_message, &signature, 1).unwrap();
+        assert_ne!(recovered, (pub_key_x, pub_key_y));
+
+        assert!(recover_secp256k1_public_key(&hashed_message, &signature, 2).is_err());
+        assert!(recover_secp256k1_public_key(&hashed_message, &[0; 64], 0).is_err());
+    }
+
     #[test]
     fn verifies_valid_r1_signature_with_low_s_value() {
         // 0x54705ba3baafdbdfba8c5f9a70f7a89bee98d906b53e31074da7baecdc0da9ad
@@ -0,0 +1,389 @@
+//! [`BlackBoxFunctionSolver`] implementations for use in tests, allowing black box functions to be exercised without a backend.
+//!
+//! - [`MockSolver`] answers each function with a programmable result or error.
+//! - [`RecordingSolver`] wraps another solver and logs every call made to it, so tests can assert on black box usage.
+
+use std::sync::Mutex;
+
+use acir::{AcirField, BlackBoxFunc, FieldElement};
+
+use crate::{BlackBoxCall, BlackBoxFunctionSolver, BlackBoxResolutionError};
+
+type SchnorrVerifyHandler<F> =
+    Box<dyn Fn(&F, &F, &[u8], &[u8]) -> Result<bool, BlackBoxResolutionError> + Send + Sync>;
+type PedersenHandler<F> =
+    Box<dyn Fn(&[F], u32) -> Result<(F, F), BlackBoxResolutionError> + Send + Sync>;
+type FixedBaseScalarMulHandler<F> =
+    Box<dyn Fn(&F, &F) -> Result<(F, F), BlackBoxResolutionError> + Send + Sync>;
+type CurveOperationHandler<F> =
+    Box<dyn Fn(&F, &F, &F, &F) -> Result<(F, F), BlackBoxResolutionError> + Send + Sync>;
+
+/// A [`BlackBoxFunctionSolver`] whose results are programmed by the test using it.
+///
+/// Functions without a programmed result fail with [`BlackBoxResolutionError::Unsupported`].
+///
+/// ```
+/// # use acir::{AcirField, FieldElement};
+/// # use acvm_blackbox_solver::{testing::MockSolver, BlackBoxFunctionSolver};
+/// let solver = MockSolver::new().on_fixed_base_scalar_mul(|low, high| Ok((*low, *high)));
+///
+/// let one = FieldElement::one();
+/// assert_eq!(solver.fixed_base_scalar_mul(&one, &one), Ok((one, one)));
+/// assert!(solver.pedersen(&[one], 0).is_err());
+/// ```
+pub struct MockSolver<F = FieldElement> {
+    schnorr_verify: Option<SchnorrVerifyHandler<F>>,
+    pedersen: Option<PedersenHandler<F>>,
+    fixed_base_scalar_mul: Option<FixedBaseScalarMulHandler<F>>,
+    embedded_curve_add: Option<CurveOperationHandler<F>>,
+    variable_base_scalar_mul: Option<CurveOperationHandler<F>>,
+}
+
+impl<F> Default for MockSolver<F> {
+    fn default() -> Self {
+        MockSolver {
+            schnorr_verify: None,
+            pedersen: None,
+            fixed_base_scalar_mul: None,
+            embedded_curve_add: None,
+            variable_base_scalar_mul: None,
+        }
+    }
+}
+
+impl<F> std::fmt::Debug for MockSolver<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockSolver")
+            .field("schnorr_verify", &self.schnorr_verify.is_some())
+            .field("pedersen", &self.pedersen.is_some())
+            .field("fixed_base_scalar_mul", &self.fixed_base_scalar_mul.is_some())
+            .field("embedded_curve_add", &self.embedded_curve_add.is_some())
+            .field("variable_base_scalar_mul", &self.variable_base_scalar_mul.is_some())
+            .finish()
+    }
+}
+
+impl<F: AcirField> MockSolver<F> {
+    /// Creates a solver for which every function is unsupported.
+    ///
+    /// This is a `const fn` so that tests can share a single solver in a `static`.
+    pub const fn new() -> Self {
+        MockSolver {
+            schnorr_verify: None,
+            pedersen: None,
+            fixed_base_scalar_mul: None,
+            embedded_curve_add: None,
+            variable_base_scalar_mul: None,
+        }
+    }
+
+    /// Answers calls to `schnorr_verify` with `handler`.
+    pub fn on_schnorr_verify(
+        mut self,
+        handler: impl Fn(&F, &F, &[u8], &[u8]) -> Result<bool, BlackBoxResolutionError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.schnorr_verify = Some(Box::new(handler));
+        self
+    }
+
+    /// Answers calls to `pedersen` with `handler`.
+    pub fn on_pedersen(
+        mut self,
+        handler: impl Fn(&[F], u32) -> Result<(F, F), BlackBoxResolutionError> + Send + Sync + 'static,
+    ) -> Self {
+        self.pedersen = Some(Box::new(handler));
+        self
+    }
+
+    /// Answers calls to `fixed_base_scalar_mul` with `handler`.
+    pub fn on_fixed_base_scalar_mul(
+        mut self,
+        handler: impl Fn(&F, &F) -> Result<(F, F), BlackBoxResolutionError> + Send + Sync + 'static,
+    ) -> Self {
+        self.fixed_base_scalar_mul = Some(Box::new(handler));
+        self
+    }
+
+    /// Answers calls to `embedded_curve_add` with `handler`.
+    pub fn on_embedded_curve_add(
+        mut self,
+        handler: impl Fn(&F, &F, &F, &F) -> Result<(F, F), BlackBoxResolutionError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.embedded_curve_add = Some(Box::new(handler));
+        self
+    }
+
+    /// Answers calls to `variable_base_scalar_mul` with `handler`.
+    pub fn on_variable_base_scalar_mul(
+        mut self,
+        handler: impl Fn(&F, &F, &F, &F) -> Result<(F, F), BlackBoxResolutionError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.variable_base_scalar_mul = Some(Box::new(handler));
+        self
+    }
+
+    /// Fails every call to `function` with [`BlackBoxResolutionError::Failed`] and the given `reason`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `function` is not solved by a [`BlackBoxFunctionSolver`].
+    pub fn failing(self, function: BlackBoxFunc, reason: &str) -> Self {
+        let reason = reason.to_owned();
+        let error = move || BlackBoxResolutionError::Failed(function, reason.clone());
+        match function {
+            BlackBoxFunc::SchnorrVerify => self.on_schnorr_verify(move |_, _, _, _| Err(error())),
+            BlackBoxFunc::Pedersen => self.on_pedersen(move |_, _| Err(error())),
+            BlackBoxFunc::FixedBaseScalarMul => {
+                self.on_fixed_base_scalar_mul(move |_, _| Err(error()))
+            }
+            BlackBoxFunc::EmbeddedCurveAdd => {
+                self.on_embedded_curve_add(move |_, _, _, _| Err(error()))
+            }
+            BlackBoxFunc::VariableBaseScalarMul => {
+                self.on_variable_base_scalar_mul(move |_, _, _, _| Err(error()))
+            }
+            _ => panic!("{function} is not solved by a `BlackBoxFunctionSolver`"),
+        }
+    }
+}
+
+impl<F: AcirField> BlackBoxFunctionSolver<F> for MockSolver<F> {
+    fn schnorr_verify(
+        &self,
+        public_key_x: &F,
+        public_key_y: &F,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        match &self.schnorr_verify {
+            Some(handler) => handler(public_key_x, public_key_y, signature, message),
+            None => Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::SchnorrVerify)),
+        }
+    }
+
+    fn pedersen(
+        &self,
+        inputs: &[F],
+        domain_separator: u32,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        match &self.pedersen {
+            Some(handler) => handler(inputs, domain_separator),
+            None => Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::Pedersen)),
+        }
+    }
+
+    fn fixed_base_scalar_mul(&self, low: &F, high: &F) -> Result<(F, F), BlackBoxResolutionError> {
+        match &self.fixed_base_scalar_mul {
+            Some(handler) => handler(low, high),
+            None => Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::FixedBaseScalarMul)),
+        }
+    }
+
+    fn embedded_curve_add(
+        &self,
+        input1_x: &F,
+        input1_y: &F,
+        input2_x: &F,
+        input2_y: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        match &self.embedded_curve_add {
+            Some(handler) => handler(input1_x, input1_y, input2_x, input2_y),
+            None => Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::EmbeddedCurveAdd)),
+        }
+    }
+
+    fn variable_base_scalar_mul(
+        &self,
+        point_x: &F,
+        point_y: &F,
+        scalar_low: &F,
+        scalar_high: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        match &self.variable_base_scalar_mul {
+            Some(handler) => handler(point_x, point_y, scalar_low, scalar_high),
+            None => Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::VariableBaseScalarMul)),
+        }
+    }
+}
+
+/// A [`BlackBoxFunctionSolver`] which records the arguments of every call before passing it on to the solver it wraps.
+///
+/// Calls are recorded whether or not they succeed.
+#[derive(Debug)]
+pub struct RecordingSolver<B, F = FieldElement> {
+    solver: B,
+    calls: Mutex<Vec<BlackBoxCall<F>>>,
+}
+
+impl<B: BlackBoxFunctionSolver<F>, F: AcirField> RecordingSolver<B, F> {
+    /// Wraps `solver`, recording every call made to it.
+    pub fn new(solver: B) -> Self {
+        RecordingSolver { solver, calls: Mutex::new(Vec::new()) }
+    }
+
+    /// Returns the wrapped solver.
+    pub fn inner(&self) -> &B {
+        &self.solver
+    }
+
+    /// Consumes the recorder, returning the wrapped solver.
+    pub fn into_inner(self) -> B {
+        self.solver
+    }
+
+    /// Returns every call made so far, in the order in which they were made.
+    pub fn calls(&self) -> Vec<BlackBoxCall<F>> {
+        self.lock().clone()
+    }
+
+    /// Returns the calls made so far to `function`, in the order in which they were made.
+    pub fn calls_to(&self, function: BlackBoxFunc) -> Vec<BlackBoxCall<F>> {
+        self.lock().iter().filter(|call| call.function() == function).cloned().collect()
+    }
+
+    /// Forgets all calls made so far.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<BlackBoxCall<F>>> {
+        // Recording a call cannot leave the log in an inconsistent state so a poisoned lock can be ignored.
+        self.calls.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn record(&self, call: BlackBoxCall<F>) {
+        self.lock().push(call);
+    }
+}
+
+impl<B: BlackBoxFunctionSolver<F>, F: AcirField> BlackBoxFunctionSolver<F>
+    for RecordingSolver<B, F>
+{
+    fn schnorr_verify(
+        &self,
+        public_key_x: &F,
+        public_key_y: &F,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        self.record(BlackBoxCall::SchnorrVerify {
+            public_key_x: *public_key_x,
+            public_key_y: *public_key_y,
+            signature: signature.to_vec(),
+            message: message.to_vec(),
+        });
+        self.solver.schnorr_verify(public_key_x, public_key_y, signature, message)
+    }
+
+    fn pedersen(
+        &self,
+        inputs: &[F],
+        domain_separator: u32,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        self.record(BlackBoxCall::Pedersen { inputs: inputs.to_vec(), domain_separator });
+        self.solver.pedersen(inputs, domain_separator)
+    }
+
+    fn fixed_base_scalar_mul(&self, low: &F, high: &F) -> Result<(F, F), BlackBoxResolutionError> {
+        self.record(BlackBoxCall::FixedBaseScalarMul { low: *low, high: *high });
+        self.solver.fixed_base_scalar_mul(low, high)
+    }
+
+    fn embedded_curve_add(
+        &self,
+        input1_x: &F,
+        input1_y: &F,
+        input2_x: &F,
+        input2_y: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        self.record(BlackBoxCall::EmbeddedCurveAdd {
+            input1_x: *input1_x,
+            input1_y: *input1_y,
+            input2_x: *input2_x,
+            input2_y: *input2_y,
+        });
+        self.solver.embedded_curve_add(input1_x, input1_y, input2_x, input2_y)
+    }
+
+    fn variable_base_scalar_mul(
+        &self,
+        point_x: &F,
+        point_y: &F,
+        scalar_low: &F,
+        scalar_high: &F,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        self.record(BlackBoxCall::VariableBaseScalarMul {
+            point_x: *point_x,
+            point_y: *point_y,
+            scalar_low: *scalar_low,
+            scalar_high: *scalar_high,
+        });
+        self.solver.variable_base_scalar_mul(point_x, point_y, scalar_low, scalar_high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{BlackBoxFunc, FieldElement};
+
+    use super::{MockSolver, RecordingSolver};
+    use crate::{BlackBoxCall, BlackBoxFunctionSolver, BlackBoxResolutionError};
+
+    #[test]
+    fn mock_returns_programmed_results() {
+        let solver = MockSolver::new()
+            .on_pedersen(|inputs, domain_separator| {
+                Ok((inputs[0], FieldElement::from(domain_separator as u128)))
+            })
+            .failing(BlackBoxFunc::SchnorrVerify, "invalid signature");
+
+        let one = FieldElement::one();
+        assert_eq!(solver.pedersen(&[one], 7), Ok((one, FieldElement::from(7u128))));
+        assert_eq!(
+            solver.schnorr_verify(&one, &one, &[], &[]),
+            Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::SchnorrVerify,
+                "invalid signature".to_owned()
+            ))
+        );
+        assert_eq!(
+            solver.fixed_base_scalar_mul(&one, &one),
+            Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::FixedBaseScalarMul))
+        );
+    }
+
+    #[test]
+    fn recorder_logs_every_call() {
+        let solver = RecordingSolver::new(
+            MockSolver::new().on_fixed_base_scalar_mul(|low, high| Ok((*low, *high))),
+        );
+
+        let [one, two] = [1u128, 2].map(FieldElement::from);
+        assert!(solver.fixed_base_scalar_mul(&one, &two).is_ok());
+        assert!(solver.pedersen(&[two], 3).is_err());
+        assert!(solver.fixed_base_scalar_mul(&two, &one).is_ok());
+
+        assert_eq!(
+            solver.calls(),
+            vec![
+                BlackBoxCall::FixedBaseScalarMul { low: one, high: two },
+                BlackBoxCall::Pedersen { inputs: vec![two], domain_separator: 3 },
+                BlackBoxCall::FixedBaseScalarMul { low: two, high: one },
+            ]
+        );
+        assert_eq!(solver.calls_to(BlackBoxFunc::FixedBaseScalarMul).len(), 2);
+        assert!(solver.calls_to(BlackBoxFunc::SchnorrVerify).is_empty());
+
+        solver.clear();
+        assert!(solver.calls().is_empty());
+    }
+}
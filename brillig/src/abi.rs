@@ -0,0 +1,381 @@
+//! Encoding of typed values passed to and from [foreign calls][crate::Opcode::ForeignCall].
+//!
+//! Foreign call parameters are flat lists of field elements, into which values are flattened as follows:
+//! - fields, booleans and integers occupy a single element, with negative integers stored in two's complement form.
+//! - strings occupy one element per byte of their UTF-8 encoding.
+//! - arrays and structs occupy the concatenation of their elements or fields, in order.
+
+use acir_field::{AcirField, FieldElement};
+use serde::{Deserialize, Serialize};
+
+use crate::{ForeignCallParam, Value};
+
+/// Describes the type of a [foreign call][crate::Opcode::ForeignCall] parameter,
+/// allowing callers to [decode][AbiValue::decode] its inputs and [encode][AbiValue::encode] its outputs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AbiType {
+    Field,
+    Boolean,
+    Unsigned { bit_size: u32 },
+    Signed { bit_size: u32 },
+    String { length: usize },
+    Array { length: usize, element: Box<AbiType> },
+    Struct { fields: Vec<(String, AbiType)> },
+}
+
+impl AbiType {
+    /// Returns the number of field elements which a value of this type is flattened into.
+    pub fn num_values(&self) -> usize {
+        match self {
+            AbiType::Field
+            | AbiType::Boolean
+            | AbiType::Unsigned { .. }
+            | AbiType::Signed { .. } => 1,
+            AbiType::String { length } => *length,
+            AbiType::Array { length, element } => length * element.num_values(),
+            AbiType::Struct { fields } => fields.iter().map(|(_, typ)| typ.num_values()).sum(),
+        }
+    }
+
+    /// Returns `true` if values of this type are held in a single register rather than in memory.
+    pub fn is_scalar(&self) -> bool {
+        matches!(
+            self,
+            AbiType::Field | AbiType::Boolean | AbiType::Unsigned { .. } | AbiType::Signed { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for AbiType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbiType::Field => write!(f, "Field"),
+            AbiType::Boolean => write!(f, "bool"),
+            AbiType::Unsigned { bit_size } => write!(f, "u{bit_size}"),
+            AbiType::Signed { bit_size } => write!(f, "i{bit_size}"),
+            AbiType::String { length } => write!(f, "str<{length}>"),
+            AbiType::Array { length, element } => write!(f, "[{element}; {length}]"),
+            AbiType::Struct { fields } => {
+                let fields: Vec<_> =
+                    fields.iter().map(|(name, typ)| format!("{name}: {typ}")).collect();
+                write!(f, "{{ {} }}", fields.join(", "))
+            }
+        }
+    }
+}
+
+/// A value of an [`AbiType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue<F = FieldElement> {
+    Field(F),
+    Boolean(bool),
+    Unsigned(u128),
+    Signed(i128),
+    String(String),
+    Array(Vec<AbiValue<F>>),
+    Struct(Vec<(String, AbiValue<F>)>),
+}
+
+/// The error returned when a value cannot be encoded as, or decoded from, a foreign call parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiError {
+    /// The value is not of the expected type.
+    TypeMismatch { expected: AbiType },
+    /// The number of elements in the value differs from the number expected by its type.
+    LengthMismatch { typ: AbiType, expected: usize, found: usize },
+    /// The value does not fit in its type.
+    OutOfRange { typ: AbiType, value: String },
+    /// Integers must have a bit size between 1 and 128 which fits in the field.
+    UnsupportedBitSize(u32),
+    /// A decoded string was not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for AbiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbiError::TypeMismatch { expected } => {
+                write!(f, "expected a value of type {expected}")
+            }
+            AbiError::LengthMismatch { typ, expected, found } => {
+                write!(
+                    f,
+                    "expected {expected} elements for a value of type {typ} but found {found}"
+                )
+            }
+            AbiError::OutOfRange { typ, value } => {
+                write!(f, "value {value} does not fit in type {typ}")
+            }
+            AbiError::UnsupportedBitSize(bit_size) => {
+                write!(f, "integers of {bit_size} bits are not supported")
+            }
+            AbiError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for AbiError {}
+
+impl<F: AcirField> AbiValue<F> {
+    /// Encodes the value as a foreign call parameter of type `typ`.
+    ///
+    /// Scalar values are encoded as [single][ForeignCallParam::Single] parameters and all other values as [arrays][ForeignCallParam::Array].
+    pub fn encode(&self, typ: &AbiType) -> Result<ForeignCallParam<F>, AbiError> {
+        let mut values = Vec::with_capacity(typ.num_values());
+        self.flatten(typ, &mut values)?;
+
+        if typ.is_scalar() {
+            Ok(ForeignCallParam::Single(values[0]))
+        } else {
+            Ok(ForeignCallParam::Array(values))
+        }
+    }
+
+    /// Decodes a foreign call parameter of type `typ`, regardless of the shape of the parameter.
+    pub fn decode(param: &ForeignCallParam<F>, typ: &AbiType) -> Result<Self, AbiError> {
+        let values = param.values();
+        if values.len() != typ.num_values() {
+            return Err(AbiError::LengthMismatch {
+                typ: typ.clone(),
+                expected: typ.num_values(),
+                found: values.len(),
+            });
+        }
+        Self::unflatten(typ, &mut values.iter())
+    }
+
+    fn flatten(&self, typ: &AbiType, values: &mut Vec<Value<F>>) -> Result<(), AbiError> {
+        let type_mismatch = || AbiError::TypeMismatch { expected: typ.clone() };
+        let out_of_range = |value: String| AbiError::OutOfRange { typ: typ.clone(), value };
+        let length_mismatch =
+            |expected, found| AbiError::LengthMismatch { typ: typ.clone(), expected, found };
+
+        match (self, typ) {
+            (AbiValue::Field(value), AbiType::Field) => values.push(Value::from(*value)),
+            (AbiValue::Boolean(value), AbiType::Boolean) => {
+                values.push(Value::from(F::from(*value)))
+            }
+            (AbiValue::Unsigned(value), AbiType::Unsigned { bit_size }) => {
+                let mask = integer_mask::<F>(*bit_size)?;
+                if value & !mask != 0 {
+                    return Err(out_of_range(value.to_string()));
+                }
+                values.push(Value::from(F::from(*value)));
+            }
+            (AbiValue::Signed(value), AbiType::Signed { bit_size }) => {
+                let mask = integer_mask::<F>(*bit_size)?;
+                // The value fits if sign extending its lowest `bit_size` bits recovers it.
+                if sign_extend(*value as u128 & mask, *bit_size) != *value {
+                    return Err(out_of_range(value.to_string()));
+                }
+                values.push(Value::from(F::from(*value as u128 & mask)));
+            }
+            (AbiValue::String(value), AbiType::String { length }) => {
+                if value.len() != *length {
+                    return Err(length_mismatch(*length, value.len()));
+                }
+                values.extend(value.bytes().map(|byte| Value::from(F::from(byte as u128))));
+            }
+            (AbiValue::Array(elements), AbiType::Array { length, element }) => {
+                if elements.len() != *length {
+                    return Err(length_mismatch(*length, elements.len()));
+                }
+                for value in elements {
+                    value.flatten(element, values)?;
+                }
+            }
+            (AbiValue::Struct(fields), AbiType::Struct { fields: field_types }) => {
+                if fields.len() != field_types.len() {
+                    return Err(length_mismatch(field_types.len(), fields.len()));
+                }
+                for ((name, value), (expected_name, typ)) in fields.iter().zip(field_types) {
+                    if name != expected_name {
+                        return Err(type_mismatch());
+                    }
+                    value.flatten(typ, values)?;
+                }
+            }
+            _ => return Err(type_mismatch()),
+        }
+        Ok(())
+    }
+
+    fn unflatten<'a>(
+        typ: &AbiType,
+        values: &mut impl Iterator<Item = &'a Value<F>>,
+    ) -> Result<Self, AbiError> {
+        let out_of_range =
+            |value: F| AbiError::OutOfRange { typ: typ.clone(), value: value.to_string() };
+        let mut next =
+            || values.next().expect("number of values is checked before decoding").to_field();
+
+        let value = match typ {
+            AbiType::Field => AbiValue::Field(next()),
+            AbiType::Boolean => match next() {
+                value if value.is_zero() => AbiValue::Boolean(false),
+                value if value.is_one() => AbiValue::Boolean(true),
+                value => return Err(out_of_range(value)),
+            },
+            AbiType::Unsigned { bit_size } => {
+                integer_mask::<F>(*bit_size)?;
+                let value = next();
+                if value.num_bits() > *bit_size {
+                    return Err(out_of_range(value));
+                }
+                AbiValue::Unsigned(value.to_u128())
+            }
+            AbiType::Signed { bit_size } => {
+                integer_mask::<F>(*bit_size)?;
+                let value = next();
+                if value.num_bits() > *bit_size {
+                    return Err(out_of_range(value));
+                }
+                AbiValue::Signed(sign_extend(value.to_u128(), *bit_size))
+            }
+            AbiType::String { length } => {
+                let bytes = (0..*length)
+                    .map(|_| {
+                        let value = next();
+                        if value.num_bits() > 8 {
+                            return Err(out_of_range(value));
+                        }
+                        Ok(value.to_u128() as u8)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                AbiValue::String(String::from_utf8(bytes).map_err(|_| AbiError::InvalidUtf8)?)
+            }
+            AbiType::Array { length, element } => AbiValue::Array(
+                (0..*length).map(|_| Self::unflatten(element, values)).collect::<Result<_, _>>()?,
+            ),
+            AbiType::Struct { fields } => AbiValue::Struct(
+                fields
+                    .iter()
+                    .map(|(name, typ)| Ok((name.clone(), Self::unflatten(typ, values)?)))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        Ok(value)
+    }
+}
+
+/// Returns a mask of the lowest `bit_size` bits, if integers of `bit_size` bits are supported.
+fn integer_mask<F: AcirField>(bit_size: u32) -> Result<u128, AbiError> {
+    if bit_size == 0 || bit_size > 128 || bit_size >= F::max_num_bits() {
+        return Err(AbiError::UnsupportedBitSize(bit_size));
+    }
+    Ok(u128::MAX >> (128 - bit_size))
+}
+
+/// Interprets the lowest `bit_size` bits of `value` as a two's complement integer.
+fn sign_extend(value: u128, bit_size: u32) -> i128 {
+    let shift = 128 - bit_size;
+    ((value << shift) as i128) >> shift
+}
+
+#[cfg(test)]
+mod tests {
+    use acir_field::FieldElement;
+
+    use super::{AbiError, AbiType, AbiValue};
+    use crate::{ForeignCallParam, Value};
+
+    fn values(values: &[u128]) -> Vec<Value> {
+        values.iter().map(|value| Value::from(*value)).collect()
+    }
+
+    #[test]
+    fn scalars_are_encoded_as_single_values() {
+        let encoded = AbiValue::<FieldElement>::Boolean(true).encode(&AbiType::Boolean);
+        assert_eq!(encoded, Ok(ForeignCallParam::Single(Value::from(1_u128))));
+
+        let typ = AbiType::Signed { bit_size: 8 };
+        let encoded = AbiValue::<FieldElement>::Signed(-1).encode(&typ).unwrap();
+        assert_eq!(encoded, ForeignCallParam::Single(Value::from(255_u128)));
+        assert_eq!(AbiValue::decode(&encoded, &typ), Ok(AbiValue::Signed(-1)));
+
+        let typ = AbiType::Signed { bit_size: 128 };
+        let encoded = AbiValue::<FieldElement>::Signed(i128::MIN).encode(&typ).unwrap();
+        assert_eq!(AbiValue::decode(&encoded, &typ), Ok(AbiValue::Signed(i128::MIN)));
+    }
+
+    #[test]
+    fn integers_must_fit_in_their_type() {
+        let typ = AbiType::Unsigned { bit_size: 8 };
+        assert_eq!(
+            AbiValue::<FieldElement>::Unsigned(256).encode(&typ),
+            Err(AbiError::OutOfRange { typ: typ.clone(), value: "256".to_owned() })
+        );
+        assert!(AbiValue::<FieldElement>::decode(
+            &ForeignCallParam::Single(Value::from(256_u128)),
+            &typ
+        )
+        .is_err());
+
+        let typ = AbiType::Signed { bit_size: 8 };
+        assert!(AbiValue::<FieldElement>::Signed(127).encode(&typ).is_ok());
+        assert!(AbiValue::<FieldElement>::Signed(128).encode(&typ).is_err());
+        assert!(AbiValue::<FieldElement>::Signed(-128).encode(&typ).is_ok());
+        assert!(AbiValue::<FieldElement>::Signed(-129).encode(&typ).is_err());
+
+        assert_eq!(
+            AbiValue::<FieldElement>::Unsigned(0).encode(&AbiType::Unsigned { bit_size: 0 }),
+            Err(AbiError::UnsupportedBitSize(0))
+        );
+    }
+
+    #[test]
+    fn structs_are_flattened_in_field_order() {
+        // struct { name: str<2>, point: [i8; 2], valid: bool, hash: Field }
+        let typ = AbiType::Struct {
+            fields: vec![
+                ("name".to_owned(), AbiType::String { length: 2 }),
+                (
+                    "point".to_owned(),
+                    AbiType::Array {
+                        length: 2,
+                        element: Box::new(AbiType::Signed { bit_size: 8 }),
+                    },
+                ),
+                ("valid".to_owned(), AbiType::Boolean),
+                ("hash".to_owned(), AbiType::Field),
+            ],
+        };
+        let value = AbiValue::Struct(vec![
+            ("name".to_owned(), AbiValue::String("hi".to_owned())),
+            ("point".to_owned(), AbiValue::Array(vec![AbiValue::Signed(3), AbiValue::Signed(-2)])),
+            ("valid".to_owned(), AbiValue::Boolean(false)),
+            ("hash".to_owned(), AbiValue::Field(-FieldElement::one())),
+        ]);
+        assert_eq!(typ.num_values(), 6);
+        assert_eq!(typ.to_string(), "{ name: str<2>, point: [i8; 2], valid: bool, hash: Field }");
+
+        let encoded = value.encode(&typ).unwrap();
+        let mut expected = values(&[b'h' as u128, b'i' as u128, 3, 254, 0]);
+        expected.push(Value::from(-FieldElement::one()));
+        assert_eq!(encoded, ForeignCallParam::Array(expected.clone()));
+
+        // Values are decoded regardless of the shape of the parameter.
+        assert_eq!(AbiValue::decode(&ForeignCallParam::Vector(expected), &typ), Ok(value));
+    }
+
+    #[test]
+    fn values_must_match_their_type() {
+        let typ = AbiType::Array { length: 2, element: Box::new(AbiType::Field) };
+        assert_eq!(
+            AbiValue::<FieldElement>::Boolean(true).encode(&typ),
+            Err(AbiError::TypeMismatch { expected: typ.clone() })
+        );
+        assert_eq!(
+            AbiValue::<FieldElement>::decode(&ForeignCallParam::Array(values(&[1, 2, 3])), &typ),
+            Err(AbiError::LengthMismatch { typ, expected: 2, found: 3 })
+        );
+
+        let typ = AbiType::String { length: 1 };
+        assert_eq!(
+            AbiValue::<FieldElement>::decode(&ForeignCallParam::Array(values(&[0xff])), &typ),
+            Err(AbiError::InvalidUtf8)
+        );
+        assert!(AbiValue::<FieldElement>::decode(&ForeignCallParam::Array(values(&[0x100])), &typ)
+            .is_err());
+    }
+}
@@ -0,0 +1,550 @@
+use crate::{BinaryFieldOp, BinaryIntOp};
+use acir_field::AcirField;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, ToPrimitive, Zero};
+
+/// Evaluate a binary operation on two FieldElements and return the result as a FieldElement.
+pub fn evaluate_binary_field_op<F: AcirField>(op: &BinaryFieldOp, a: F, b: F) -> F {
+    match op {
+        // Perform addition, subtraction, multiplication, and division based on the BinaryOp variant.
+        BinaryFieldOp::Add => a + b,
+        BinaryFieldOp::Sub => a - b,
+        BinaryFieldOp::Mul => a * b,
+        BinaryFieldOp::Div => a / b,
+        BinaryFieldOp::Equals => (a == b).into(),
+    }
+}
+
+/// Evaluate a binary operation on two unsigned big integers with a given bit size and return the result as a big integer.
+pub fn evaluate_binary_bigint_op(
+    op: &BinaryIntOp,
+    a: BigUint,
+    b: BigUint,
+    bit_size: u32,
+) -> BigUint {
+    let bit_modulo = &(BigUint::one() << bit_size);
+    match op {
+        // Perform addition, subtraction, and multiplication, applying a modulo operation to keep the result within the bit size.
+        BinaryIntOp::Add => (a + b) % bit_modulo,
+        BinaryIntOp::Sub => (bit_modulo + a - b) % bit_modulo,
+        BinaryIntOp::Mul => (a * b) % bit_modulo,
+        // Perform unsigned division using the modulo operation on a and b.
+        BinaryIntOp::UnsignedDiv => (a % bit_modulo) / (b % bit_modulo),
+        // Perform signed division by first converting a and b to signed integers and then back to unsigned after the operation.
+        BinaryIntOp::SignedDiv => {
+            let signed_div = to_big_signed(a, bit_size) / to_big_signed(b, bit_size);
+            to_big_unsigned(signed_div, bit_size)
+        }
+        // Perform a == operation, returning 0 or 1
+        BinaryIntOp::Equals => {
+            if (a % bit_modulo) == (b % bit_modulo) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            }
+        }
+        // Perform a < operation, returning 0 or 1
+        BinaryIntOp::LessThan => {
+            if (a % bit_modulo) < (b % bit_modulo) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            }
+        }
+        // Perform a <= operation, returning 0 or 1
+        BinaryIntOp::LessThanEquals => {
+            if (a % bit_modulo) <= (b % bit_modulo) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            }
+        }
+        // Perform bitwise AND, OR, XOR, left shift, and right shift operations, applying a modulo operation to keep the result within the bit size.
+        BinaryIntOp::And => (a & b) % bit_modulo,
+        BinaryIntOp::Or => (a | b) % bit_modulo,
+        BinaryIntOp::Xor => (a ^ b) % bit_modulo,
+        BinaryIntOp::Shl => {
+            assert!(bit_size <= 128, "unsupported bit size for right shift");
+            let b = b.to_u128().unwrap();
+            (a << b) % bit_modulo
+        }
+        BinaryIntOp::Shr => {
+            assert!(bit_size <= 128, "unsupported bit size for right shift");
+            let b = b.to_u128().unwrap();
+            (a >> b) % bit_modulo
+        }
+        // Perform signed < and <= operations on the two's complement interpretations of a and b, returning 0 or 1
+        BinaryIntOp::SignedLessThan => {
+            if to_big_signed(a % bit_modulo, bit_size) < to_big_signed(b % bit_modulo, bit_size) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            }
+        }
+        BinaryIntOp::SignedLessThanEquals => {
+            if to_big_signed(a % bit_modulo, bit_size) <= to_big_signed(b % bit_modulo, bit_size) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            }
+        }
+        BinaryIntOp::UnsignedRem => (a % bit_modulo) % (b % bit_modulo),
+        // Perform a truncated signed remainder, so that a non-zero result has the same sign as a.
+        BinaryIntOp::SignedRem => {
+            let signed_rem =
+                to_big_signed(a % bit_modulo, bit_size) % to_big_signed(b % bit_modulo, bit_size);
+            to_big_unsigned(signed_rem, bit_size)
+        }
+        // Shifting a signed integer right rounds towards negative infinity, which fills the vacated bits with the sign bit.
+        // Shifts by at least `bit_size` bits leave only copies of the sign bit.
+        BinaryIntOp::SignedShr => {
+            let shift = b.to_u32().map_or(bit_size, |b| b.min(bit_size));
+            let signed_shr = to_big_signed(a % bit_modulo, bit_size) >> shift;
+            to_big_unsigned(signed_shr, bit_size)
+        }
+    }
+}
+
+/// Evaluate a bitwise NOT on an unsigned big integer with a given bit size and return the result as a big integer.
+pub fn evaluate_bigint_not(a: BigUint, bit_size: u32) -> BigUint {
+    let bit_modulo = BigUint::one() << bit_size;
+    (&bit_modulo - BigUint::one()) - (a % bit_modulo)
+}
+
+/// Convert an unsigned big integer into a `bit_size` bit integer and return the result as a big integer.
+///
+/// If `sign_extend_from` is set then `a` is interpreted as a two's complement integer of that many bits,
+/// so that it keeps its signed value when widened.
+pub fn evaluate_bigint_cast(a: BigUint, bit_size: u32, sign_extend_from: Option<u32>) -> BigUint {
+    match sign_extend_from {
+        Some(source_bit_size) if source_bit_size < bit_size => {
+            let source_modulo = BigUint::one() << source_bit_size;
+            to_big_unsigned(to_big_signed(a % source_modulo, source_bit_size), bit_size)
+        }
+        _ => a % (BigUint::one() << bit_size),
+    }
+}
+
+/// Evaluate a binary operation on two integers of at most 128 bits using native arithmetic.
+///
+/// This computes the same result as [`evaluate_binary_bigint_op`] without allocating, but requires that
+/// `bit_size <= 128` and that both operands are less than `2^bit_size`.
+pub fn evaluate_binary_u128_op(op: &BinaryIntOp, a: u128, b: u128, bit_size: u32) -> u128 {
+    debug_assert!(bit_size <= 128 && a <= u128_mask(bit_size) && b <= u128_mask(bit_size));
+    let mask = u128_mask(bit_size);
+    match op {
+        BinaryIntOp::Add => a.wrapping_add(b) & mask,
+        BinaryIntOp::Sub => a.wrapping_sub(b) & mask,
+        BinaryIntOp::Mul => a.wrapping_mul(b) & mask,
+        BinaryIntOp::UnsignedDiv => a / b,
+        BinaryIntOp::SignedDiv => {
+            (to_i128(a, bit_size).wrapping_div(to_i128(b, bit_size)) as u128) & mask
+        }
+        BinaryIntOp::Equals => u128::from(a == b),
+        BinaryIntOp::LessThan => u128::from(a < b),
+        BinaryIntOp::LessThanEquals => u128::from(a <= b),
+        BinaryIntOp::And => a & b,
+        BinaryIntOp::Or => a | b,
+        BinaryIntOp::Xor => a ^ b,
+        // Shifting by at least `bit_size` bits leaves no bits of `a` within the result.
+        BinaryIntOp::Shl => u32::try_from(b)
+            .ok()
+            .and_then(|shift| a.checked_shl(shift))
+            .map_or(0, |shifted| shifted & mask),
+        BinaryIntOp::Shr => {
+            u32::try_from(b).ok().and_then(|shift| a.checked_shr(shift)).unwrap_or(0)
+        }
+        BinaryIntOp::SignedLessThan => u128::from(to_i128(a, bit_size) < to_i128(b, bit_size)),
+        BinaryIntOp::SignedLessThanEquals => {
+            u128::from(to_i128(a, bit_size) <= to_i128(b, bit_size))
+        }
+        BinaryIntOp::UnsignedRem => a % b,
+        BinaryIntOp::SignedRem => {
+            (to_i128(a, bit_size).wrapping_rem(to_i128(b, bit_size)) as u128) & mask
+        }
+        // Shifts by 127 bits already leave only copies of the sign bit.
+        BinaryIntOp::SignedShr => {
+            let shift = u32::try_from(b).map_or(127, |b| b.min(127));
+            ((to_i128(a, bit_size) >> shift) as u128) & mask
+        }
+    }
+}
+
+/// Evaluate a bitwise NOT on an integer of at most 128 bits using native arithmetic.
+///
+/// This computes the same result as [`evaluate_bigint_not`], but requires that `bit_size <= 128` and `a < 2^bit_size`.
+pub fn evaluate_u128_not(a: u128, bit_size: u32) -> u128 {
+    !a & u128_mask(bit_size)
+}
+
+/// Returns the largest `bit_size` bit integer, where `bit_size <= 128`.
+fn u128_mask(bit_size: u32) -> u128 {
+    u128::MAX.checked_shr(128 - bit_size).unwrap_or(0)
+}
+
+/// Interprets `a` as a `bit_size` bit two's complement integer, where `bit_size <= 128`.
+fn to_i128(a: u128, bit_size: u32) -> i128 {
+    // The only zero bit integer is zero.
+    if bit_size == 0 {
+        return 0;
+    }
+    let unused_bits = 128 - bit_size;
+    ((a << unused_bits) as i128) >> unused_bits
+}
+
+/// Interprets `a` as a `bit_size` bit two's complement integer.
+fn to_big_signed(a: BigUint, bit_size: u32) -> BigInt {
+    // The only zero bit integer is zero.
+    if bit_size == 0 {
+        return BigInt::zero();
+    }
+    let pow_2 = BigUint::from(2_u32).pow(bit_size - 1);
+    if a < pow_2 {
+        BigInt::from(a)
+    } else {
+        BigInt::from(a) - 2 * BigInt::from(pow_2)
+    }
+}
+
+fn to_big_unsigned(a: BigInt, bit_size: u32) -> BigUint {
+    if a >= BigInt::zero() {
+        BigUint::from_bytes_le(&a.to_bytes_le().1)
+    } else {
+        BigUint::from(2_u32).pow(bit_size) - BigUint::from_bytes_le(&a.to_bytes_le().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestParams {
+        a: u128,
+        b: u128,
+        result: u128,
+    }
+
+    fn evaluate_u128(op: &BinaryIntOp, a: u128, b: u128, bit_size: u32) -> u128 {
+        // Convert to big integers
+        let lhs_big = BigUint::from(a);
+        let rhs_big = BigUint::from(b);
+        let result_value = evaluate_binary_bigint_op(op, lhs_big, rhs_big, bit_size);
+        // Convert back to u128
+        result_value.to_u128().unwrap()
+    }
+
+    fn to_signed(a: u128, bit_size: u32) -> i128 {
+        assert!(bit_size < 128);
+        let pow_2 = 2_u128.pow(bit_size - 1);
+        if a < pow_2 {
+            a as i128
+        } else {
+            (a.wrapping_sub(2 * pow_2)) as i128
+        }
+    }
+
+    fn to_unsigned(a: i128, bit_size: u32) -> u128 {
+        if a >= 0 {
+            a as u128
+        } else {
+            (a + 2_i128.pow(bit_size)) as u128
+        }
+    }
+
+    fn to_negative(a: u128, bit_size: u32) -> u128 {
+        assert!(a > 0);
+        let two_pow = 2_u128.pow(bit_size);
+        two_pow - a
+    }
+
+    fn evaluate_int_ops(test_params: Vec<TestParams>, op: BinaryIntOp, bit_size: u32) {
+        for test in test_params {
+            assert_eq!(evaluate_u128(&op, test.a, test.b, bit_size), test.result);
+        }
+    }
+
+    #[test]
+    fn add_test() {
+        let bit_size = 4;
+
+        let test_ops = vec![
+            TestParams { a: 5, b: 10, result: 15 },
+            TestParams { a: 10, b: 10, result: 4 },
+            TestParams { a: 5, b: to_negative(3, bit_size), result: 2 },
+            TestParams { a: to_negative(3, bit_size), b: 1, result: to_negative(2, bit_size) },
+            TestParams { a: 5, b: to_negative(6, bit_size), result: to_negative(1, bit_size) },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::Add, bit_size);
+    }
+
+    #[test]
+    fn sub_test() {
+        let bit_size = 4;
+
+        let test_ops = vec![
+            TestParams { a: 5, b: 3, result: 2 },
+            TestParams { a: 5, b: 10, result: to_negative(5, bit_size) },
+            TestParams { a: 5, b: to_negative(3, bit_size), result: 8 },
+            TestParams { a: to_negative(3, bit_size), b: 2, result: to_negative(5, bit_size) },
+            TestParams { a: 14, b: to_negative(3, bit_size), result: 1 },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::Sub, bit_size);
+    }
+
+    #[test]
+    fn mul_test() {
+        let bit_size = 4;
+
+        let test_ops = vec![
+            TestParams { a: 5, b: 3, result: 15 },
+            TestParams { a: 5, b: 10, result: 2 },
+            TestParams { a: to_negative(1, bit_size), b: to_negative(5, bit_size), result: 5 },
+            TestParams { a: to_negative(1, bit_size), b: 5, result: to_negative(5, bit_size) },
+            TestParams {
+                a: to_negative(2, bit_size),
+                b: 7,
+                // negative 14 wraps to a 2
+                result: to_negative(14, bit_size),
+            },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::Mul, bit_size);
+
+        let bit_size = 127;
+        let a = 2_u128.pow(bit_size) - 1;
+        let b = 3;
+
+        // ( 2**(n-1) - 1 ) * 3 = 2*2**(n-1) - 2 + (2**(n-1) - 1) => wraps to (2**(n-1) - 1) - 2
+        assert_eq!(evaluate_u128(&BinaryIntOp::Mul, a, b, bit_size), a - 2);
+    }
+
+    #[test]
+    fn div_test() {
+        let bit_size = 4;
+
+        let test_ops =
+            vec![TestParams { a: 5, b: 3, result: 1 }, TestParams { a: 5, b: 10, result: 0 }];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::UnsignedDiv, bit_size);
+    }
+
+    #[test]
+    fn to_signed_roundtrip() {
+        let bit_size = 32;
+        let minus_one = 2_u128.pow(bit_size) - 1;
+        assert_eq!(to_unsigned(to_signed(minus_one, bit_size), bit_size), minus_one);
+    }
+
+    #[test]
+    fn signed_div_test() {
+        let bit_size = 32;
+
+        let test_ops = vec![
+            TestParams { a: 5, b: to_negative(10, bit_size), result: 0 },
+            TestParams { a: 5, b: to_negative(1, bit_size), result: to_negative(5, bit_size) },
+            TestParams { a: to_negative(5, bit_size), b: to_negative(1, bit_size), result: 5 },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::SignedDiv, bit_size);
+    }
+
+    #[test]
+    fn signed_comparison_test() {
+        let bit_size = 4;
+
+        let test_ops = vec![
+            TestParams { a: 5, b: 3, result: 0 },
+            TestParams { a: 3, b: 5, result: 1 },
+            TestParams { a: to_negative(1, bit_size), b: 1, result: 1 },
+            TestParams { a: 1, b: to_negative(1, bit_size), result: 0 },
+            TestParams { a: to_negative(8, bit_size), b: 7, result: 1 },
+            TestParams { a: to_negative(2, bit_size), b: to_negative(2, bit_size), result: 0 },
+        ];
+        evaluate_int_ops(test_ops, BinaryIntOp::SignedLessThan, bit_size);
+
+        let test_ops = vec![
+            TestParams { a: 5, b: 3, result: 0 },
+            TestParams { a: to_negative(1, bit_size), b: 1, result: 1 },
+            TestParams { a: 7, b: to_negative(8, bit_size), result: 0 },
+            TestParams { a: to_negative(2, bit_size), b: to_negative(2, bit_size), result: 1 },
+        ];
+        evaluate_int_ops(test_ops, BinaryIntOp::SignedLessThanEquals, bit_size);
+
+        // A single bit integer holds either 0 or -1.
+        evaluate_int_ops(
+            vec![TestParams { a: 1, b: 0, result: 1 }, TestParams { a: 0, b: 1, result: 0 }],
+            BinaryIntOp::SignedLessThan,
+            1,
+        );
+    }
+
+    #[test]
+    fn rem_test() {
+        let bit_size = 4;
+
+        let test_ops = vec![
+            TestParams { a: 5, b: 3, result: 2 },
+            TestParams { a: 3, b: 5, result: 3 },
+            TestParams { a: to_negative(1, bit_size), b: 4, result: 3 },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::UnsignedRem, bit_size);
+    }
+
+    #[test]
+    fn signed_rem_test() {
+        let bit_size = 32;
+
+        let test_ops = vec![
+            TestParams { a: 7, b: 3, result: 1 },
+            TestParams { a: 7, b: to_negative(3, bit_size), result: 1 },
+            TestParams { a: to_negative(7, bit_size), b: 3, result: to_negative(1, bit_size) },
+            TestParams {
+                a: to_negative(7, bit_size),
+                b: to_negative(3, bit_size),
+                result: to_negative(1, bit_size),
+            },
+            // i32::MIN % -1 does not overflow.
+            TestParams { a: 2_u128.pow(31), b: to_negative(1, bit_size), result: 0 },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::SignedRem, bit_size);
+    }
+
+    #[test]
+    fn signed_shr_test() {
+        let bit_size = 8;
+
+        let test_ops = vec![
+            TestParams { a: 64, b: 2, result: 16 },
+            TestParams { a: to_negative(64, bit_size), b: 2, result: to_negative(16, bit_size) },
+            // Rounds towards negative infinity.
+            TestParams { a: to_negative(5, bit_size), b: 1, result: to_negative(3, bit_size) },
+            TestParams { a: to_negative(1, bit_size), b: 7, result: to_negative(1, bit_size) },
+            TestParams { a: to_negative(1, bit_size), b: 100, result: to_negative(1, bit_size) },
+            TestParams { a: 127, b: 100, result: 0 },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::SignedShr, bit_size);
+
+        // -2^253 >> 250 = -8 for integers wider than 128 bits.
+        let a = BigUint::one() << 253;
+        assert_eq!(
+            evaluate_binary_bigint_op(&BinaryIntOp::SignedShr, a, BigUint::from(250_u32), 254),
+            (BigUint::one() << 254) - BigUint::from(8_u32)
+        );
+    }
+
+    #[test]
+    fn not_test() {
+        assert_eq!(evaluate_bigint_not(BigUint::from(0b1010_u32), 4), BigUint::from(0b0101_u32));
+        assert_eq!(evaluate_bigint_not(BigUint::zero(), 128), BigUint::from(u128::MAX));
+        assert_eq!(evaluate_bigint_not(BigUint::one(), 1), BigUint::zero());
+        assert_eq!(evaluate_bigint_not(BigUint::from(5_u32), 0), BigUint::zero());
+    }
+
+    #[test]
+    fn cast_test() {
+        // Truncation keeps the lowest bits.
+        assert_eq!(
+            evaluate_bigint_cast(BigUint::from(0x1234_u32), 8, None),
+            BigUint::from(0x34_u32)
+        );
+        assert_eq!(
+            evaluate_bigint_cast(BigUint::from(0xff_u32), 4, Some(8)),
+            BigUint::from(0xf_u32)
+        );
+        // Unsigned values are zero-extended.
+        assert_eq!(
+            evaluate_bigint_cast(BigUint::from(0xff_u32), 16, None),
+            BigUint::from(0xff_u32)
+        );
+        // Signed values are sign-extended.
+        assert_eq!(
+            evaluate_bigint_cast(BigUint::from(0xff_u32), 16, Some(8)),
+            BigUint::from(0xffff_u32)
+        );
+        assert_eq!(
+            evaluate_bigint_cast(BigUint::from(0x7f_u32), 16, Some(8)),
+            BigUint::from(0x7f_u32)
+        );
+        assert_eq!(evaluate_bigint_cast(BigUint::one(), 128, Some(1)), BigUint::from(u128::MAX));
+    }
+
+    #[test]
+    fn native_evaluation_matches_bigint_evaluation() {
+        let ops = [
+            BinaryIntOp::Add,
+            BinaryIntOp::Sub,
+            BinaryIntOp::Mul,
+            BinaryIntOp::UnsignedDiv,
+            BinaryIntOp::SignedDiv,
+            BinaryIntOp::Equals,
+            BinaryIntOp::LessThan,
+            BinaryIntOp::LessThanEquals,
+            BinaryIntOp::And,
+            BinaryIntOp::Or,
+            BinaryIntOp::Xor,
+            BinaryIntOp::Shl,
+            BinaryIntOp::Shr,
+            BinaryIntOp::SignedLessThan,
+            BinaryIntOp::SignedLessThanEquals,
+            BinaryIntOp::UnsignedRem,
+            BinaryIntOp::SignedRem,
+            BinaryIntOp::SignedShr,
+        ];
+        for bit_size in [0, 1, 7, 8, 32, 64, 127, 128] {
+            let mask = u128_mask(bit_size);
+            let half = mask >> 1;
+            let operands: Vec<u128> = [
+                0,
+                1,
+                2,
+                3,
+                5,
+                bit_size as u128,
+                half,
+                half + 1,
+                mask.wrapping_sub(1),
+                mask,
+                0x9e37_79b9_7f4a_7c15,
+            ]
+            .into_iter()
+            .map(|operand| operand & mask)
+            .collect();
+
+            for op in &ops {
+                for &a in &operands {
+                    for &b in &operands {
+                        let divides = matches!(
+                            op,
+                            BinaryIntOp::UnsignedDiv
+                                | BinaryIntOp::SignedDiv
+                                | BinaryIntOp::UnsignedRem
+                                | BinaryIntOp::SignedRem
+                        );
+                        let shifts = matches!(op, BinaryIntOp::Shl | BinaryIntOp::Shr);
+                        // Skip division by zero, which panics, and shifts too large to evaluate as big integers.
+                        if (divides && b == 0) || (shifts && b > 256) {
+                            continue;
+                        }
+
+                        assert_eq!(
+                            evaluate_binary_u128_op(op, a, b, bit_size),
+                            evaluate_u128(op, a, b, bit_size),
+                            "{op:?} on {a} and {b} with bit size {bit_size}"
+                        );
+                    }
+                }
+                for &a in &operands {
+                    assert_eq!(
+                        BigUint::from(evaluate_u128_not(a, bit_size)),
+                        evaluate_bigint_not(BigUint::from(a), bit_size)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -45,4 +45,49 @@ pub enum BlackBoxOp {
     Pedersen { inputs: HeapVector, domain_separator: RegisterIndex, output: HeapArray },
     /// Performs scalar multiplication over the embedded curve.
     FixedBaseScalarMul { low: RegisterIndex, high: RegisterIndex, result: HeapArray },
+    /// Adds two points on the embedded curve.
+    EmbeddedCurveAdd {
+        input1_x: RegisterIndex,
+        input1_y: RegisterIndex,
+        input2_x: RegisterIndex,
+        input2_y: RegisterIndex,
+        result: HeapArray,
+    },
+    /// Performs scalar multiplication of an arbitrary point on the embedded curve.
+    VariableBaseScalarMul {
+        point_x: RegisterIndex,
+        point_y: RegisterIndex,
+        scalar_low: RegisterIndex,
+        scalar_high: RegisterIndex,
+        result: HeapArray,
+    },
+    /// Calculates the SHA512 hash of the inputs.
+    Sha512 { message: HeapVector, output: HeapArray },
+    /// Calculates the Blake3 hash of the inputs.
+    Blake3 { message: HeapVector, output: HeapArray },
+    /// Verifies an EdDSA signature over the Ed25519 curve.
+    EddsaEd25519 {
+        public_key: HeapArray,
+        signature: HeapArray,
+        message: HeapVector,
+        result: RegisterIndex,
+    },
+    /// Verifies an EdDSA signature over the BabyJubJub curve, as produced by circomlib.
+    EddsaBabyJubJub {
+        public_key_x: RegisterIndex,
+        public_key_y: RegisterIndex,
+        signature_s: RegisterIndex,
+        signature_r8_x: RegisterIndex,
+        signature_r8_y: RegisterIndex,
+        message: RegisterIndex,
+        result: RegisterIndex,
+    },
+    /// Recovers the public key which produced an ECDSA signature over the secp256k1 curve.
+    EcdsaSecp256k1Recover {
+        hashed_msg: HeapVector,
+        signature: HeapArray,
+        recovery_id: RegisterIndex,
+        public_key_x: HeapArray,
+        public_key_y: HeapArray,
+    },
 }
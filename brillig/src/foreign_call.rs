@@ -1,34 +1,65 @@
 use crate::value::Value;
+use acir_field::FieldElement;
 use serde::{Deserialize, Serialize};
 
-/// Single output of a [foreign call][crate::Opcode::ForeignCall].
+/// Single input or output of a [foreign call][crate::Opcode::ForeignCall].
+///
+/// The variant records the [`RegisterOrMemory`][crate::RegisterOrMemory] which the parameter is read from or written to,
+/// so that callers can distinguish between single values, fixed length arrays and variable length vectors.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
-pub enum ForeignCallOutput {
-    Single(Value),
-    Array(Vec<Value>),
+pub enum ForeignCallParam<F = FieldElement> {
+    /// A value held in a [register][crate::RegisterOrMemory::RegisterIndex].
+    Single(Value<F>),
+    /// The contents of a [fixed length array][crate::RegisterOrMemory::HeapArray].
+    ///
+    /// Outputs of this form may also be written to a [vector][crate::RegisterOrMemory::HeapVector].
+    Array(Vec<Value<F>>),
+    /// The contents of a [variable length vector][crate::RegisterOrMemory::HeapVector].
+    Vector(Vec<Value<F>>),
+}
+
+/// Single output of a [foreign call][crate::Opcode::ForeignCall].
+pub type ForeignCallOutput<F = FieldElement> = ForeignCallParam<F>;
+
+impl<F> ForeignCallParam<F> {
+    /// Returns the values held by the parameter, regardless of its shape.
+    pub fn values(&self) -> &[Value<F>] {
+        match self {
+            ForeignCallParam::Single(value) => std::slice::from_ref(value),
+            ForeignCallParam::Array(values) | ForeignCallParam::Vector(values) => values,
+        }
+    }
+
+    /// Returns the values held by the parameter, regardless of its shape.
+    pub fn into_values(self) -> Vec<Value<F>> {
+        match self {
+            ForeignCallParam::Single(value) => vec![value],
+            ForeignCallParam::Array(values) | ForeignCallParam::Vector(values) => values,
+        }
+    }
 }
 
 /// Represents the full output of a [foreign call][crate::Opcode::ForeignCall].
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
-pub struct ForeignCallResult {
+pub struct ForeignCallResult<F = FieldElement> {
     /// Resolved output values of the foreign call.
-    pub values: Vec<ForeignCallOutput>,
+    pub values: Vec<ForeignCallParam<F>>,
 }
 
-impl From<Value> for ForeignCallResult {
-    fn from(value: Value) -> Self {
-        ForeignCallResult { values: vec![ForeignCallOutput::Single(value)] }
+impl<F> From<Value<F>> for ForeignCallResult<F> {
+    fn from(value: Value<F>) -> Self {
+        ForeignCallResult { values: vec![ForeignCallParam::Single(value)] }
     }
 }
 
-impl From<Vec<Value>> for ForeignCallResult {
-    fn from(values: Vec<Value>) -> Self {
-        ForeignCallResult { values: vec![ForeignCallOutput::Array(values)] }
+impl<F> From<Vec<Value<F>>> for ForeignCallResult<F> {
+    fn from(values: Vec<Value<F>>) -> Self {
+        ForeignCallResult { values: vec![ForeignCallParam::Array(values)] }
     }
 }
 
-impl From<Vec<ForeignCallOutput>> for ForeignCallResult {
-    fn from(values: Vec<ForeignCallOutput>) -> Self {
+impl<F> From<Vec<ForeignCallParam<F>>> for ForeignCallResult<F> {
+    fn from(values: Vec<ForeignCallParam<F>>) -> Self {
         ForeignCallResult { values }
     }
 }
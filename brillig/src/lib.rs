@@ -8,13 +8,20 @@
 //! [acvm]: https://crates.io/crates/acvm
 //! [brillig_vm]: https://crates.io/crates/brillig_vm
 
+pub mod abi;
+mod arithmetic;
 mod black_box;
 mod foreign_call;
 mod opcodes;
+pub mod optimizer;
 mod value;
 
+pub use arithmetic::{
+    evaluate_bigint_cast, evaluate_bigint_not, evaluate_binary_bigint_op, evaluate_binary_field_op,
+    evaluate_binary_u128_op, evaluate_u128_not,
+};
 pub use black_box::BlackBoxOp;
-pub use foreign_call::{ForeignCallOutput, ForeignCallResult};
+pub use foreign_call::{ForeignCallOutput, ForeignCallParam, ForeignCallResult};
 pub use opcodes::{
     BinaryFieldOp, BinaryIntOp, HeapArray, HeapVector, RegisterIndex, RegisterOrMemory,
 };
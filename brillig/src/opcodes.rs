@@ -1,4 +1,5 @@
-use crate::{black_box::BlackBoxOp, Value};
+use crate::{abi::AbiType, black_box::BlackBoxOp, Value};
+use acir_field::FieldElement;
 use serde::{Deserialize, Serialize};
 
 pub type Label = usize;
@@ -57,7 +58,7 @@ pub enum RegisterOrMemory {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Opcode {
+pub enum Opcode<F = FieldElement> {
     /// Takes the fields in registers `lhs` and `rhs`
     /// Performs the specified binary operation
     /// and stores the value in the `result` register.  
@@ -98,7 +99,7 @@ pub enum Opcode {
     },
     Const {
         destination: RegisterIndex,
-        value: Value,
+        value: Value<F>,
     },
     Return,
     /// Used to get data from an outside source.
@@ -131,9 +132,70 @@ pub enum Opcode {
     Trap,
     /// Stop execution
     Stop,
+    /// Takes the `bit_size` size integer in register `source`,
+    /// flips each of its bits
+    /// and stores the value in the `destination` register.
+    Not {
+        destination: RegisterIndex,
+        source: RegisterIndex,
+        bit_size: u32,
+    },
+    /// Converts the value in register `source` into a `bit_size` size integer
+    /// and stores it in the `destination` register.
+    ///
+    /// Values wider than `bit_size` are truncated to their lowest `bit_size` bits.
+    /// Narrower values are zero-extended unless `sign_extend_from` is set.
+    Cast {
+        destination: RegisterIndex,
+        source: RegisterIndex,
+        bit_size: u32,
+        /// If set, `source` holds a two's complement integer of this many bits which is sign-extended
+        /// rather than zero-extended.
+        sign_extend_from: Option<u32>,
+    },
+    /// Calls the function at `location` within a new frame of registers, so that the callee cannot overwrite
+    /// the registers of its caller.
+    ///
+    /// The values of the `arguments` registers are copied into registers `0..arguments.len()` of the new frame,
+    /// whose other registers start out as zero. Upon [`Opcode::Return`], registers `0..results.len()` of the
+    /// callee's frame are copied into the `results` registers of the caller, whose other registers are restored
+    /// to their values from before the call. Memory is shared between all frames.
+    FrameCall {
+        location: Label,
+        arguments: Vec<RegisterIndex>,
+        results: Vec<RegisterIndex>,
+    },
+    /// Allocates a block of as many memory slots as the value in register `size` on the heap
+    /// and stores a pointer to its first slot in the `destination` register.
+    ///
+    /// The slots of the block are uninitialized until they are written to.
+    Alloc {
+        destination: RegisterIndex,
+        size: RegisterIndex,
+    },
+    /// Frees the block of memory, previously returned by [`Opcode::Alloc`], pointed to by the value in register
+    /// `pointer`, so that its slots may be reused by later allocations.
+    Free {
+        pointer: RegisterIndex,
+    },
+    /// A [foreign call][Opcode::ForeignCall] which also describes the [types][AbiType] of its parameters,
+    /// so that the caller can decode them into structured values.
+    TypedForeignCall {
+        function: String,
+        destinations: Vec<RegisterOrMemory>,
+        inputs: Vec<RegisterOrMemory>,
+        /// Optional types of the values written to each of the `destinations`.
+        ///
+        /// Destinations without a corresponding entry are untyped.
+        destination_types: Vec<Option<AbiType>>,
+        /// Optional types of the values read from each of the `inputs`.
+        ///
+        /// Inputs without a corresponding entry are untyped.
+        input_types: Vec<Option<AbiType>>,
+    },
 }
 
-impl Opcode {
+impl<F> Opcode<F> {
     pub fn name(&self) -> &'static str {
         match self {
             Opcode::BinaryFieldOp { .. } => "binary_field_op",
@@ -151,6 +213,12 @@ impl Opcode {
             Opcode::BlackBox(_) => "black_box",
             Opcode::Trap => "trap",
             Opcode::Stop => "stop",
+            Opcode::Not { .. } => "not",
+            Opcode::Cast { .. } => "cast",
+            Opcode::FrameCall { .. } => "frame_call",
+            Opcode::Alloc { .. } => "alloc",
+            Opcode::Free { .. } => "free",
+            Opcode::TypedForeignCall { .. } => "typed_foreign_call",
         }
     }
 }
@@ -167,6 +235,9 @@ pub enum BinaryFieldOp {
 }
 
 /// Binary fixed-length integer expressions
+///
+/// Operations prefixed with `Signed` interpret their operands as `bit_size` bit two's complement integers.
+/// All other operations interpret their operands as unsigned integers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BinaryIntOp {
     Add,
@@ -190,4 +261,14 @@ pub enum BinaryIntOp {
     Shl,
     /// (>>) Shift right
     Shr,
+    /// (<) Signed less than
+    SignedLessThan,
+    /// (<=) Signed less or equal
+    SignedLessThanEquals,
+    /// (%) Unsigned remainder
+    UnsignedRem,
+    /// (%) Signed remainder, taking the sign of the dividend
+    SignedRem,
+    /// (>>) Arithmetic shift right, filling vacated bits with the sign bit
+    SignedShr,
 }
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use acir_field::AcirField;
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use super::{block_leaders, register_accesses};
+use crate::{
+    evaluate_binary_bigint_op, evaluate_binary_field_op, BinaryIntOp, Opcode, RegisterIndex, Value,
+};
+
+/// Replaces binary operations whose operands are both known constants with a [`Opcode::Const`] holding the result.
+///
+/// Register values are only tracked within a basic block, so an operand is known if it was last written to
+/// by a [`Opcode::Const`] (or a [`Opcode::Mov`] from a known register) earlier in the same block.
+///
+/// Operations which would fail at runtime, such as division by zero, are left in place.
+pub fn fold_constants<F: AcirField>(
+    mut bytecode: Vec<Opcode<F>>,
+    opcode_positions: Vec<usize>,
+) -> (Vec<Opcode<F>>, Vec<usize>) {
+    let leaders = block_leaders(&bytecode);
+    let mut known_values: HashMap<RegisterIndex, Value<F>> = HashMap::new();
+
+    for (index, opcode) in bytecode.iter_mut().enumerate() {
+        if leaders.contains(&index) {
+            known_values.clear();
+        }
+
+        let folded_value = match opcode {
+            Opcode::BinaryFieldOp { op, lhs, rhs, .. } => {
+                match (known_values.get(lhs), known_values.get(rhs)) {
+                    (Some(lhs), Some(rhs)) => {
+                        Some(evaluate_binary_field_op(op, lhs.to_field(), rhs.to_field()).into())
+                    }
+                    _ => None,
+                }
+            }
+            Opcode::BinaryIntOp { op, bit_size, lhs, rhs, .. } => {
+                match (known_values.get(lhs), known_values.get(rhs)) {
+                    (Some(lhs), Some(rhs)) => fold_binary_int_op(*op, *bit_size, *lhs, *rhs),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        match (&*opcode, folded_value) {
+            (
+                Opcode::BinaryFieldOp { destination, .. } | Opcode::BinaryIntOp { destination, .. },
+                Some(value),
+            ) => {
+                let destination = *destination;
+                known_values.insert(destination, value);
+                *opcode = Opcode::Const { destination, value };
+            }
+            (Opcode::Const { destination, value }, _) => {
+                known_values.insert(*destination, *value);
+            }
+            (Opcode::Mov { destination, source }, _) => match known_values.get(source).copied() {
+                Some(value) => {
+                    known_values.insert(*destination, value);
+                }
+                None => {
+                    known_values.remove(destination);
+                }
+            },
+            (opcode, _) => {
+                for register in register_accesses(opcode).1 {
+                    known_values.remove(&register);
+                }
+            }
+        }
+    }
+
+    (bytecode, opcode_positions)
+}
+
+/// Evaluates a binary integer operation on constant operands, returning `None` if it cannot be evaluated
+/// without failing.
+fn fold_binary_int_op<F: AcirField>(
+    op: BinaryIntOp,
+    bit_size: u32,
+    lhs: Value<F>,
+    rhs: Value<F>,
+) -> Option<Value<F>> {
+    // The VM refuses to execute operations on integers wider than the field.
+    if bit_size > F::max_num_bits() {
+        return None;
+    }
+
+    let bit_modulo = BigUint::from(1_u32) << bit_size;
+    let lhs = BigUint::from_bytes_be(&lhs.to_field().to_be_bytes());
+    let rhs = BigUint::from_bytes_be(&rhs.to_field().to_be_bytes());
+    // Operands which do not fit within `bit_size` are malformed so we leave their handling to the VM.
+    if lhs >= bit_modulo || rhs >= bit_modulo {
+        return None;
+    }
+
+    let can_evaluate = match op {
+        BinaryIntOp::UnsignedDiv
+        | BinaryIntOp::SignedDiv
+        | BinaryIntOp::UnsignedRem
+        | BinaryIntOp::SignedRem => !rhs.is_zero(),
+        BinaryIntOp::Shl | BinaryIntOp::Shr => bit_size <= 128 && rhs < BigUint::from(bit_size),
+        _ => true,
+    };
+    if !can_evaluate {
+        return None;
+    }
+
+    let result = evaluate_binary_bigint_op(&op, lhs, rhs, bit_size);
+    Some(F::from_be_bytes_reduce(&result.to_bytes_be()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use acir_field::FieldElement;
+
+    use super::fold_constants;
+    use crate::{BinaryFieldOp, BinaryIntOp, Opcode, RegisterIndex, Value};
+
+    fn constant(register: usize, value: u128) -> Opcode {
+        Opcode::Const { destination: RegisterIndex::from(register), value: Value::from(value) }
+    }
+
+    fn int_op(op: BinaryIntOp, destination: usize, lhs: usize, rhs: usize) -> Opcode {
+        Opcode::BinaryIntOp {
+            destination: RegisterIndex::from(destination),
+            op,
+            bit_size: 8,
+            lhs: RegisterIndex::from(lhs),
+            rhs: RegisterIndex::from(rhs),
+        }
+    }
+
+    #[test]
+    fn folds_operations_on_constants() {
+        let bytecode = vec![
+            constant(0, 200),
+            constant(1, 100),
+            int_op(BinaryIntOp::Add, 2, 0, 1),
+            // Register 2 is now known so this can also be folded.
+            int_op(BinaryIntOp::Mul, 3, 2, 1),
+            Opcode::BinaryFieldOp {
+                destination: RegisterIndex::from(4),
+                op: BinaryFieldOp::Sub,
+                lhs: RegisterIndex::from(1),
+                rhs: RegisterIndex::from(0),
+            },
+        ];
+
+        let (optimized, positions) = fold_constants(bytecode, vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(
+            optimized,
+            vec![
+                constant(0, 200),
+                constant(1, 100),
+                constant(2, 44),
+                constant(3, 48),
+                Opcode::Const {
+                    destination: RegisterIndex::from(4),
+                    value: Value::from(-FieldElement::from(100_u128)),
+                },
+            ]
+        );
+        assert_eq!(positions, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn does_not_fold_across_blocks_or_overwritten_registers() {
+        let bytecode = vec![
+            constant(0, 1),
+            constant(1, 2),
+            Opcode::Load {
+                destination: RegisterIndex::from(1),
+                source_pointer: RegisterIndex::from(0),
+            },
+            // Register 1 has been overwritten by a value loaded from memory.
+            int_op(BinaryIntOp::Add, 2, 0, 1),
+            Opcode::JumpIf { condition: RegisterIndex::from(2), location: 5 },
+            // Register values are not tracked into a new block.
+            int_op(BinaryIntOp::Add, 2, 0, 0),
+        ];
+
+        let (optimized, _) = fold_constants(bytecode.clone(), vec![0, 1, 2, 3, 4, 5]);
+
+        assert_eq!(optimized, bytecode);
+    }
+
+    #[test]
+    fn does_not_fold_failing_operations() {
+        let bytecode = vec![
+            constant(0, 1),
+            constant(1, 0),
+            int_op(BinaryIntOp::UnsignedDiv, 2, 0, 1),
+            int_op(BinaryIntOp::SignedRem, 2, 0, 1),
+        ];
+
+        let (optimized, _) = fold_constants(bytecode.clone(), vec![0, 1, 2, 3]);
+
+        assert_eq!(optimized, bytecode);
+    }
+}
@@ -0,0 +1,94 @@
+use super::{live_registers, retain_opcodes};
+use crate::{Opcode, RegisterIndex};
+
+/// Removes [`Opcode::Mov`] instructions whose destination is never read before being overwritten,
+/// as well as those which move a register onto itself.
+///
+/// `output_registers` are the registers whose values are read once execution has finished.
+pub fn remove_dead_movs<F: Clone>(
+    mut bytecode: Vec<Opcode<F>>,
+    mut opcode_positions: Vec<usize>,
+    output_registers: &[RegisterIndex],
+) -> (Vec<Opcode<F>>, Vec<usize>) {
+    // Removing a `Mov` may leave the `Mov` which wrote to its source dead, so repeat until nothing changes.
+    loop {
+        let (_, live_registers) = live_registers(&bytecode, output_registers);
+        let keep: Vec<bool> = bytecode
+            .iter()
+            .zip(&live_registers)
+            .map(|(opcode, live)| match opcode {
+                Opcode::Mov { destination, source } => {
+                    destination != source && live.contains(destination)
+                }
+                _ => true,
+            })
+            .collect();
+
+        if keep.iter().all(|keep| *keep) {
+            return (bytecode, opcode_positions);
+        }
+        (bytecode, opcode_positions) = retain_opcodes(bytecode, opcode_positions, &keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_dead_movs;
+    use crate::{Opcode, RegisterIndex};
+
+    fn mov(destination: usize, source: usize) -> Opcode {
+        Opcode::Mov {
+            destination: RegisterIndex::from(destination),
+            source: RegisterIndex::from(source),
+        }
+    }
+
+    #[test]
+    fn removes_movs_to_registers_which_are_never_read() {
+        let bytecode = vec![
+            mov(1, 0),
+            // Register 1 is overwritten before being read.
+            mov(1, 2),
+            mov(3, 3),
+            Opcode::Jump { location: 5 },
+            mov(0, 1),
+            Opcode::Stop,
+        ];
+
+        let (optimized, positions) = remove_dead_movs(
+            bytecode,
+            vec![0, 1, 2, 3, 4, 5],
+            &[RegisterIndex::from(0), RegisterIndex::from(1)],
+        );
+
+        assert_eq!(
+            optimized,
+            vec![mov(1, 2), Opcode::Jump { location: 3 }, mov(0, 1), Opcode::Stop]
+        );
+        assert_eq!(positions, vec![1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn removes_chains_of_dead_movs() {
+        let bytecode = vec![mov(1, 0), mov(2, 1), Opcode::Stop];
+
+        let (optimized, positions) = remove_dead_movs(bytecode, vec![0, 1, 2], &[]);
+
+        assert_eq!(optimized, vec![Opcode::Stop]);
+        assert_eq!(positions, vec![2]);
+    }
+
+    #[test]
+    fn keeps_movs_read_by_later_iterations_of_a_loop() {
+        let bytecode = vec![
+            mov(1, 0),
+            Opcode::JumpIf { condition: RegisterIndex::from(1), location: 3 },
+            Opcode::Jump { location: 0 },
+            Opcode::Stop,
+        ];
+
+        let (optimized, _) = remove_dead_movs(bytecode.clone(), vec![0, 1, 2, 3], &[]);
+
+        assert_eq!(optimized, bytecode);
+    }
+}
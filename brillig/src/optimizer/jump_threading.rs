@@ -0,0 +1,108 @@
+use super::{jump_target_mut, retain_opcodes};
+use crate::Opcode;
+
+/// Redirects jumps and calls whose target is an unconditional [`Opcode::Jump`] to that jump's final destination,
+/// and removes unconditional jumps to the following opcode.
+pub fn thread_jumps<F>(
+    mut bytecode: Vec<Opcode<F>>,
+    mut opcode_positions: Vec<usize>,
+) -> (Vec<Opcode<F>>, Vec<usize>) {
+    let final_destinations: Vec<usize> =
+        (0..bytecode.len()).map(|label| final_destination(&bytecode, label)).collect();
+
+    for opcode in &mut bytecode {
+        if let Some(location) = jump_target_mut(opcode) {
+            if let Some(destination) = final_destinations.get(*location) {
+                *location = *destination;
+            }
+        }
+    }
+
+    // Removing a jump may leave a preceding jump pointing at the opcode which follows it, so repeat until nothing changes.
+    loop {
+        let keep: Vec<bool> = bytecode
+            .iter()
+            .enumerate()
+            .map(|(index, opcode)| {
+                !matches!(opcode, Opcode::Jump { location } if *location == index + 1)
+            })
+            .collect();
+
+        if keep.iter().all(|keep| *keep) {
+            return (bytecode, opcode_positions);
+        }
+        (bytecode, opcode_positions) = retain_opcodes(bytecode, opcode_positions, &keep);
+    }
+}
+
+/// Follows the chain of unconditional jumps starting at `label`, returning the label at which it ends.
+fn final_destination<F>(bytecode: &[Opcode<F>], mut label: usize) -> usize {
+    let mut visited = Vec::new();
+    while let Some(Opcode::Jump { location }) = bytecode.get(label) {
+        // A cycle of jumps never terminates so we leave it in place.
+        if visited.contains(&label) {
+            break;
+        }
+        visited.push(label);
+        label = *location;
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::thread_jumps;
+    use crate::{Opcode, RegisterIndex};
+
+    #[test]
+    fn threads_jumps_through_unconditional_jumps() {
+        let condition = RegisterIndex::from(0);
+        let bytecode: Vec<Opcode> = vec![
+            Opcode::JumpIf { condition, location: 3 },
+            Opcode::Call { location: 4 },
+            Opcode::Stop,
+            Opcode::Jump { location: 4 },
+            Opcode::Jump { location: 6 },
+            Opcode::Trap,
+            Opcode::Return,
+        ];
+
+        let (optimized, positions) = thread_jumps(bytecode, vec![0, 1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(
+            optimized,
+            vec![
+                Opcode::JumpIf { condition, location: 6 },
+                Opcode::Call { location: 6 },
+                Opcode::Stop,
+                Opcode::Jump { location: 6 },
+                Opcode::Jump { location: 6 },
+                Opcode::Trap,
+                Opcode::Return,
+            ]
+        );
+        assert_eq!(positions, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn removes_jumps_to_the_next_opcode() {
+        let bytecode: Vec<Opcode> =
+            vec![Opcode::Jump { location: 2 }, Opcode::Jump { location: 2 }, Opcode::Stop];
+
+        let (optimized, positions) = thread_jumps(bytecode, vec![0, 1, 2]);
+
+        assert_eq!(optimized, vec![Opcode::Stop]);
+        assert_eq!(positions, vec![2]);
+    }
+
+    #[test]
+    fn leaves_cycles_of_jumps_in_place() {
+        let bytecode: Vec<Opcode> =
+            vec![Opcode::Jump { location: 1 }, Opcode::Jump { location: 0 }];
+
+        let (optimized, positions) = thread_jumps(bytecode, vec![0, 1]);
+
+        assert_eq!(optimized, vec![Opcode::Jump { location: 0 }]);
+        assert_eq!(positions, vec![1]);
+    }
+}
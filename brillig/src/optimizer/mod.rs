@@ -0,0 +1,426 @@
+//! Optimization passes over Brillig bytecode.
+//!
+//! Each pass takes the bytecode alongside a vector mapping each of its opcodes to the index of the opcode in the
+//! originally emitted bytecode from which it derives. The pass returns the optimized bytecode along with the updated
+//! mapping, allowing consumers to keep debug information such as [`OpcodeLocation::Brillig`] pointing at the
+//! original opcodes.
+//!
+//! [`OpcodeLocation::Brillig`]: https://docs.rs/acir/latest/acir/circuit/enum.OpcodeLocation.html
+
+use std::collections::BTreeSet;
+
+use acir_field::AcirField;
+
+use crate::{BlackBoxOp, HeapArray, HeapVector, Opcode, RegisterIndex, RegisterOrMemory};
+
+mod constant_folding;
+mod dead_mov;
+mod jump_threading;
+mod register_coalescing;
+mod unreachable_code;
+
+pub use constant_folding::fold_constants;
+pub use dead_mov::remove_dead_movs;
+pub use jump_threading::thread_jumps;
+pub use register_coalescing::coalesce_registers;
+pub use unreachable_code::remove_unreachable_code;
+
+/// Applies all optimization passes to `bytecode`.
+///
+/// `output_registers` are the registers whose values are read once execution has finished.
+/// Returns the optimized bytecode along with, for each of its opcodes, the index of the opcode in `bytecode`
+/// from which it derives.
+pub fn optimize<F: AcirField>(
+    bytecode: Vec<Opcode<F>>,
+    output_registers: &[RegisterIndex],
+) -> (Vec<Opcode<F>>, Vec<usize>) {
+    let opcode_positions = (0..bytecode.len()).collect();
+
+    let (bytecode, opcode_positions) = fold_constants(bytecode, opcode_positions);
+    let (bytecode, opcode_positions) = thread_jumps(bytecode, opcode_positions);
+    let (bytecode, opcode_positions) = remove_unreachable_code(bytecode, opcode_positions);
+    // Removing unreachable code may leave jumps to the opcode which follows them.
+    let (bytecode, opcode_positions) = thread_jumps(bytecode, opcode_positions);
+    let (bytecode, opcode_positions) =
+        remove_dead_movs(bytecode, opcode_positions, output_registers);
+    coalesce_registers(bytecode, opcode_positions, output_registers)
+}
+
+/// Whether an opcode reads from or writes to a register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read,
+    Write,
+}
+
+fn visit_array(array: &mut HeapArray, visit: &mut impl FnMut(&mut RegisterIndex, Access)) {
+    visit(&mut array.pointer, Access::Read);
+}
+
+fn visit_vector(vector: &mut HeapVector, visit: &mut impl FnMut(&mut RegisterIndex, Access)) {
+    visit(&mut vector.pointer, Access::Read);
+    visit(&mut vector.size, Access::Read);
+}
+
+/// Calls `visit` on each register operand of `opcode`.
+fn visit_registers<F>(opcode: &mut Opcode<F>, mut visit: impl FnMut(&mut RegisterIndex, Access)) {
+    match opcode {
+        Opcode::BinaryFieldOp { destination, lhs, rhs, .. }
+        | Opcode::BinaryIntOp { destination, lhs, rhs, .. } => {
+            visit(lhs, Access::Read);
+            visit(rhs, Access::Read);
+            visit(destination, Access::Write);
+        }
+        Opcode::Not { destination, source, .. }
+        | Opcode::Cast { destination, source, .. }
+        | Opcode::Mov { destination, source } => {
+            visit(source, Access::Read);
+            visit(destination, Access::Write);
+        }
+        Opcode::Load { destination, source_pointer } => {
+            visit(source_pointer, Access::Read);
+            visit(destination, Access::Write);
+        }
+        Opcode::Store { destination_pointer, source } => {
+            visit(destination_pointer, Access::Read);
+            visit(source, Access::Read);
+        }
+        Opcode::Const { destination, .. } => visit(destination, Access::Write),
+        Opcode::Alloc { destination, size } => {
+            visit(size, Access::Read);
+            visit(destination, Access::Write);
+        }
+        Opcode::Free { pointer } => visit(pointer, Access::Read),
+        Opcode::FrameCall { arguments, results, .. } => {
+            for argument in arguments {
+                visit(argument, Access::Read);
+            }
+            for result in results {
+                visit(result, Access::Write);
+            }
+        }
+        Opcode::JumpIf { condition, .. } | Opcode::JumpIfNot { condition, .. } => {
+            visit(condition, Access::Read);
+        }
+        Opcode::ForeignCall { destinations, inputs, .. }
+        | Opcode::TypedForeignCall { destinations, inputs, .. } => {
+            for input in inputs {
+                match input {
+                    RegisterOrMemory::RegisterIndex(register) => visit(register, Access::Read),
+                    RegisterOrMemory::HeapArray(array) => visit_array(array, &mut visit),
+                    RegisterOrMemory::HeapVector(vector) => visit_vector(vector, &mut visit),
+                }
+            }
+            for destination in destinations {
+                match destination {
+                    RegisterOrMemory::RegisterIndex(register) => visit(register, Access::Write),
+                    RegisterOrMemory::HeapArray(array) => visit_array(array, &mut visit),
+                    RegisterOrMemory::HeapVector(vector) => {
+                        visit(&mut vector.pointer, Access::Read);
+                        visit(&mut vector.size, Access::Write);
+                    }
+                }
+            }
+        }
+        Opcode::BlackBox(black_box_op) => match black_box_op {
+            BlackBoxOp::Sha256 { message, output }
+            | BlackBoxOp::Sha512 { message, output }
+            | BlackBoxOp::Blake2s { message, output }
+            | BlackBoxOp::Blake3 { message, output }
+            | BlackBoxOp::Keccak256 { message, output } => {
+                visit_vector(message, &mut visit);
+                visit_array(output, &mut visit);
+            }
+            BlackBoxOp::HashToField128Security { message, output } => {
+                visit_vector(message, &mut visit);
+                visit(output, Access::Write);
+            }
+            BlackBoxOp::EcdsaSecp256k1 {
+                hashed_msg,
+                public_key_x,
+                public_key_y,
+                signature,
+                result,
+            }
+            | BlackBoxOp::EcdsaSecp256r1 {
+                hashed_msg,
+                public_key_x,
+                public_key_y,
+                signature,
+                result,
+            } => {
+                visit_vector(hashed_msg, &mut visit);
+                visit_array(public_key_x, &mut visit);
+                visit_array(public_key_y, &mut visit);
+                visit_array(signature, &mut visit);
+                visit(result, Access::Write);
+            }
+            BlackBoxOp::EcdsaSecp256k1Recover {
+                hashed_msg,
+                signature,
+                recovery_id,
+                public_key_x,
+                public_key_y,
+            } => {
+                visit_vector(hashed_msg, &mut visit);
+                visit_array(signature, &mut visit);
+                visit(recovery_id, Access::Read);
+                visit_array(public_key_x, &mut visit);
+                visit_array(public_key_y, &mut visit);
+            }
+            BlackBoxOp::SchnorrVerify {
+                public_key_x,
+                public_key_y,
+                message,
+                signature,
+                result,
+            } => {
+                visit(public_key_x, Access::Read);
+                visit(public_key_y, Access::Read);
+                visit_vector(message, &mut visit);
+                visit_vector(signature, &mut visit);
+                visit(result, Access::Write);
+            }
+            BlackBoxOp::EddsaEd25519 { public_key, signature, message, result } => {
+                visit_array(public_key, &mut visit);
+                visit_array(signature, &mut visit);
+                visit_vector(message, &mut visit);
+                visit(result, Access::Write);
+            }
+            BlackBoxOp::EddsaBabyJubJub {
+                public_key_x,
+                public_key_y,
+                signature_s,
+                signature_r8_x,
+                signature_r8_y,
+                message,
+                result,
+            } => {
+                visit(public_key_x, Access::Read);
+                visit(public_key_y, Access::Read);
+                visit(signature_s, Access::Read);
+                visit(signature_r8_x, Access::Read);
+                visit(signature_r8_y, Access::Read);
+                visit(message, Access::Read);
+                visit(result, Access::Write);
+            }
+            BlackBoxOp::Pedersen { inputs, domain_separator, output } => {
+                visit_vector(inputs, &mut visit);
+                visit(domain_separator, Access::Read);
+                visit_array(output, &mut visit);
+            }
+            BlackBoxOp::FixedBaseScalarMul { low, high, result } => {
+                visit(low, Access::Read);
+                visit(high, Access::Read);
+                visit_array(result, &mut visit);
+            }
+            BlackBoxOp::EmbeddedCurveAdd { input1_x, input1_y, input2_x, input2_y, result } => {
+                visit(input1_x, Access::Read);
+                visit(input1_y, Access::Read);
+                visit(input2_x, Access::Read);
+                visit(input2_y, Access::Read);
+                visit_array(result, &mut visit);
+            }
+            BlackBoxOp::VariableBaseScalarMul {
+                point_x,
+                point_y,
+                scalar_low,
+                scalar_high,
+                result,
+            } => {
+                visit(point_x, Access::Read);
+                visit(point_y, Access::Read);
+                visit(scalar_low, Access::Read);
+                visit(scalar_high, Access::Read);
+                visit_array(result, &mut visit);
+            }
+        },
+        Opcode::Jump { .. }
+        | Opcode::Call { .. }
+        | Opcode::Return
+        | Opcode::Trap
+        | Opcode::Stop => {}
+    }
+}
+
+/// Returns the registers read by `opcode` and those which it may write to.
+fn register_accesses<F: Clone>(opcode: &Opcode<F>) -> (Vec<RegisterIndex>, Vec<RegisterIndex>) {
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    visit_registers(&mut opcode.clone(), |register, access| match access {
+        Access::Read => reads.push(*register),
+        Access::Write => writes.push(*register),
+    });
+    (reads, writes)
+}
+
+/// Returns the label which `opcode` may jump to, if any.
+fn jump_target<F>(opcode: &Opcode<F>) -> Option<usize> {
+    match opcode {
+        Opcode::Jump { location }
+        | Opcode::JumpIf { location, .. }
+        | Opcode::JumpIfNot { location, .. }
+        | Opcode::Call { location }
+        | Opcode::FrameCall { location, .. } => Some(*location),
+        _ => None,
+    }
+}
+
+fn jump_target_mut<F>(opcode: &mut Opcode<F>) -> Option<&mut usize> {
+    match opcode {
+        Opcode::Jump { location }
+        | Opcode::JumpIf { location, .. }
+        | Opcode::JumpIfNot { location, .. }
+        | Opcode::Call { location }
+        | Opcode::FrameCall { location, .. } => Some(location),
+        _ => None,
+    }
+}
+
+/// Returns the indices of the opcodes which begin a basic block.
+fn block_leaders<F>(bytecode: &[Opcode<F>]) -> BTreeSet<usize> {
+    let mut leaders = BTreeSet::from([0]);
+    for (index, opcode) in bytecode.iter().enumerate() {
+        if let Some(location) = jump_target(opcode) {
+            leaders.insert(location);
+        }
+        if matches!(
+            opcode,
+            Opcode::Jump { .. }
+                | Opcode::JumpIf { .. }
+                | Opcode::JumpIfNot { .. }
+                | Opcode::Call { .. }
+                | Opcode::FrameCall { .. }
+                | Opcode::Return
+                | Opcode::Trap
+                | Opcode::Stop
+        ) {
+            leaders.insert(index + 1);
+        }
+    }
+    leaders
+}
+
+/// Computes the sets of registers whose values may be read before and after each opcode of `bytecode` executes.
+///
+/// `output_registers` are considered to be read once execution finishes.
+fn live_registers<F: Clone>(
+    bytecode: &[Opcode<F>],
+    output_registers: &[RegisterIndex],
+) -> (Vec<BTreeSet<RegisterIndex>>, Vec<BTreeSet<RegisterIndex>>) {
+    let accesses: Vec<_> = bytecode.iter().map(register_accesses).collect();
+    // A return may resume execution after any call.
+    let return_sites: Vec<usize> = bytecode
+        .iter()
+        .enumerate()
+        .filter(|(_, opcode)| matches!(opcode, Opcode::Call { .. } | Opcode::FrameCall { .. }))
+        .map(|(index, _)| index + 1)
+        .collect();
+    // A return from a new frame of registers reads the callee's results.
+    let frame_results = frame_registers(bytecode);
+    let exit: BTreeSet<RegisterIndex> = output_registers.iter().copied().collect();
+
+    let mut live_in: Vec<BTreeSet<RegisterIndex>> = vec![BTreeSet::new(); bytecode.len()];
+    let mut live_out = live_in.clone();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in (0..bytecode.len()).rev() {
+            let successors = match &bytecode[index] {
+                Opcode::Jump { location } | Opcode::Call { location } => vec![*location],
+                // The caller's registers are kept aside during a call within a new frame,
+                // so they remain live until execution resumes after it.
+                Opcode::JumpIf { location, .. }
+                | Opcode::JumpIfNot { location, .. }
+                | Opcode::FrameCall { location, .. } => vec![*location, index + 1],
+                Opcode::Return => return_sites.clone(),
+                Opcode::Trap => Vec::new(),
+                Opcode::Stop => vec![bytecode.len()],
+                _ => vec![index + 1],
+            };
+            let mut out = BTreeSet::new();
+            for successor in successors {
+                match live_in.get(successor) {
+                    Some(live) => out.extend(live.iter().copied()),
+                    // Execution finishes upon running past the end of the bytecode.
+                    None => out.extend(exit.iter().copied()),
+                }
+            }
+
+            let (reads, writes) = &accesses[index];
+            let mut live = out.clone();
+            // Foreign calls only write to their destinations if the caller provides enough outputs,
+            // so only other opcodes are guaranteed to overwrite the registers which they write to.
+            if !matches!(
+                bytecode[index],
+                Opcode::ForeignCall { .. } | Opcode::TypedForeignCall { .. }
+            ) {
+                for register in writes {
+                    live.remove(register);
+                }
+            }
+            live.extend(reads.iter().copied());
+            if matches!(bytecode[index], Opcode::Return) {
+                live.extend(frame_results.iter().copied());
+            }
+
+            if live != live_in[index] || out != live_out[index] {
+                live_in[index] = live;
+                live_out[index] = out;
+                changed = true;
+            }
+        }
+    }
+    (live_in, live_out)
+}
+
+/// Returns the registers through which [`Opcode::FrameCall`]s pass arguments into and results out of a new frame.
+///
+/// These are identified by their position within the callee's frame, so must keep their indices.
+fn frame_registers<F>(bytecode: &[Opcode<F>]) -> BTreeSet<RegisterIndex> {
+    let frame_size = bytecode
+        .iter()
+        .map(|opcode| match opcode {
+            Opcode::FrameCall { arguments, results, .. } => arguments.len().max(results.len()),
+            _ => 0,
+        })
+        .max()
+        .unwrap_or_default();
+    (0..frame_size).map(RegisterIndex::from).collect()
+}
+
+/// Removes the opcodes at the indices for which `keep` is false.
+///
+/// Jumps to a removed opcode are redirected to the next opcode which is kept.
+fn retain_opcodes<F>(
+    bytecode: Vec<Opcode<F>>,
+    opcode_positions: Vec<usize>,
+    keep: &[bool],
+) -> (Vec<Opcode<F>>, Vec<usize>) {
+    // `new_indices[i]` is the new index of the first opcode kept at or after index `i`.
+    let mut new_indices = Vec::with_capacity(keep.len() + 1);
+    let mut kept = 0;
+    for keep_opcode in keep {
+        new_indices.push(kept);
+        if *keep_opcode {
+            kept += 1;
+        }
+    }
+    new_indices.push(kept);
+
+    let mut new_bytecode = Vec::with_capacity(kept);
+    let mut new_positions = Vec::with_capacity(kept);
+    for ((mut opcode, position), keep_opcode) in
+        bytecode.into_iter().zip(opcode_positions).zip(keep)
+    {
+        if !keep_opcode {
+            continue;
+        }
+        if let Some(location) = jump_target_mut(&mut opcode) {
+            *location = new_indices[(*location).min(keep.len())];
+        }
+        new_bytecode.push(opcode);
+        new_positions.push(position);
+    }
+    (new_bytecode, new_positions)
+}
@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{frame_registers, live_registers, register_accesses, retain_opcodes, visit_registers};
+use crate::{Opcode, RegisterIndex};
+
+/// Merges the source and destination registers of [`Opcode::Mov`] instructions whenever the two registers
+/// never hold different values which are both needed, removing the moves which this makes redundant.
+///
+/// Registers which are read before being written to (i.e. the VM's inputs), `output_registers`, whose values are
+/// read once execution has finished, and the registers holding the arguments and results of functions called
+/// within a new frame keep their indices.
+pub fn coalesce_registers<F: Clone>(
+    mut bytecode: Vec<Opcode<F>>,
+    opcode_positions: Vec<usize>,
+    output_registers: &[RegisterIndex],
+) -> (Vec<Opcode<F>>, Vec<usize>) {
+    let (live_in, live_out) = live_registers(&bytecode, output_registers);
+    let mut interference = InterferenceGraph::default();
+
+    // Every register is defined upon entry, either as an input or as zero, so all registers which are live there
+    // hold values which must be kept apart.
+    let mut fixed_registers: BTreeSet<RegisterIndex> = output_registers.iter().copied().collect();
+    fixed_registers.extend(frame_registers(&bytecode));
+    if let Some(live_on_entry) = live_in.first() {
+        for register in live_on_entry {
+            for other in live_on_entry {
+                interference.add_edge(*register, *other);
+            }
+        }
+        fixed_registers.extend(live_on_entry);
+    }
+
+    // A register which is written to interferes with every other register that is live afterwards,
+    // unless it is simply receiving a copy of that register.
+    for (opcode, live) in bytecode.iter().zip(&live_out) {
+        let copied_register = match opcode {
+            Opcode::Mov { source, .. } => Some(*source),
+            _ => None,
+        };
+        for written in register_accesses(opcode).1 {
+            for register in live {
+                if Some(*register) != copied_register {
+                    interference.add_edge(written, *register);
+                }
+            }
+        }
+    }
+
+    let mut representatives = Representatives::default();
+    for opcode in &bytecode {
+        let Opcode::Mov { destination, source } = opcode else {
+            continue;
+        };
+        let destination = representatives.find(*destination);
+        let source = representatives.find(*source);
+        if destination == source || interference.interferes(destination, source) {
+            continue;
+        }
+
+        let (kept, replaced) =
+            match (fixed_registers.contains(&destination), fixed_registers.contains(&source)) {
+                (true, true) => continue,
+                (true, false) => (destination, source),
+                (false, _) => (source, destination),
+            };
+        interference.merge(kept, replaced);
+        representatives.union(kept, replaced);
+    }
+
+    for opcode in &mut bytecode {
+        visit_registers(opcode, |register, _| *register = representatives.find(*register));
+    }
+    let keep: Vec<bool> = bytecode
+        .iter()
+        .map(|opcode| !matches!(opcode, Opcode::Mov { destination, source } if destination == source))
+        .collect();
+    retain_opcodes(bytecode, opcode_positions, &keep)
+}
+
+/// Records which pairs of registers may not share an index.
+#[derive(Default)]
+struct InterferenceGraph {
+    edges: BTreeMap<RegisterIndex, BTreeSet<RegisterIndex>>,
+}
+
+impl InterferenceGraph {
+    fn add_edge(&mut self, a: RegisterIndex, b: RegisterIndex) {
+        if a != b {
+            self.edges.entry(a).or_default().insert(b);
+            self.edges.entry(b).or_default().insert(a);
+        }
+    }
+
+    fn interferes(&self, a: RegisterIndex, b: RegisterIndex) -> bool {
+        self.edges.get(&a).map_or(false, |neighbours| neighbours.contains(&b))
+    }
+
+    /// Moves all edges of `replaced` onto `kept`.
+    fn merge(&mut self, kept: RegisterIndex, replaced: RegisterIndex) {
+        for neighbour in self.edges.remove(&replaced).unwrap_or_default() {
+            if let Some(edges) = self.edges.get_mut(&neighbour) {
+                edges.remove(&replaced);
+            }
+            self.add_edge(kept, neighbour);
+        }
+    }
+}
+
+/// Tracks the register which each register has been merged into.
+#[derive(Default)]
+struct Representatives {
+    parents: BTreeMap<RegisterIndex, RegisterIndex>,
+}
+
+impl Representatives {
+    fn find(&self, mut register: RegisterIndex) -> RegisterIndex {
+        while let Some(parent) = self.parents.get(&register) {
+            register = *parent;
+        }
+        register
+    }
+
+    fn union(&mut self, kept: RegisterIndex, replaced: RegisterIndex) {
+        self.parents.insert(replaced, kept);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::coalesce_registers;
+    use crate::{BinaryIntOp, Opcode, RegisterIndex};
+
+    fn mov(destination: usize, source: usize) -> Opcode {
+        Opcode::Mov {
+            destination: RegisterIndex::from(destination),
+            source: RegisterIndex::from(source),
+        }
+    }
+
+    fn add(destination: usize, lhs: usize, rhs: usize) -> Opcode {
+        Opcode::BinaryIntOp {
+            destination: RegisterIndex::from(destination),
+            op: BinaryIntOp::Add,
+            bit_size: 32,
+            lhs: RegisterIndex::from(lhs),
+            rhs: RegisterIndex::from(rhs),
+        }
+    }
+
+    #[test]
+    fn merges_registers_connected_by_moves() {
+        // Registers 0 and 1 are inputs and register 0 is the output.
+        let bytecode = vec![add(2, 0, 1), mov(3, 2), add(4, 3, 1), mov(0, 4), Opcode::Stop];
+
+        let (optimized, positions) =
+            coalesce_registers(bytecode, vec![0, 1, 2, 3, 4], &[RegisterIndex::from(0)]);
+
+        assert_eq!(optimized, vec![add(2, 0, 1), add(0, 2, 1), Opcode::Stop]);
+        assert_eq!(positions, vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn keeps_registers_holding_different_values_apart() {
+        // Register 1 is modified while register 2 still holds its old value.
+        let bytecode = vec![mov(2, 1), add(1, 1, 1), add(0, 1, 2), Opcode::Stop];
+
+        let (optimized, _) =
+            coalesce_registers(bytecode.clone(), vec![0, 1, 2, 3], &[RegisterIndex::from(0)]);
+
+        assert_eq!(optimized, bytecode);
+    }
+
+    #[test]
+    fn keeps_inputs_and_outputs_apart() {
+        let bytecode = vec![mov(0, 1), Opcode::Stop];
+
+        let (optimized, _) = coalesce_registers(
+            bytecode.clone(),
+            vec![0, 1],
+            &[RegisterIndex::from(0), RegisterIndex::from(1)],
+        );
+
+        assert_eq!(optimized, bytecode);
+    }
+}
@@ -0,0 +1,71 @@
+use super::retain_opcodes;
+use crate::Opcode;
+
+/// Removes opcodes which cannot be reached from the start of the bytecode.
+///
+/// Calls are assumed to return, so the opcode following a reachable [`Opcode::Call`] or [`Opcode::FrameCall`]
+/// is reachable.
+pub fn remove_unreachable_code<F>(
+    bytecode: Vec<Opcode<F>>,
+    opcode_positions: Vec<usize>,
+) -> (Vec<Opcode<F>>, Vec<usize>) {
+    let mut reachable = vec![false; bytecode.len()];
+    let mut to_visit = vec![0];
+    while let Some(index) = to_visit.pop() {
+        // Labels past the end of the bytecode finish execution.
+        if index >= bytecode.len() || reachable[index] {
+            continue;
+        }
+        reachable[index] = true;
+
+        match &bytecode[index] {
+            Opcode::Jump { location } => to_visit.push(*location),
+            Opcode::JumpIf { location, .. }
+            | Opcode::JumpIfNot { location, .. }
+            | Opcode::Call { location }
+            | Opcode::FrameCall { location, .. } => {
+                to_visit.push(*location);
+                to_visit.push(index + 1);
+            }
+            Opcode::Return | Opcode::Trap | Opcode::Stop => {}
+            _ => to_visit.push(index + 1),
+        }
+    }
+
+    retain_opcodes(bytecode, opcode_positions, &reachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_unreachable_code;
+    use crate::{Opcode, RegisterIndex, Value};
+
+    #[test]
+    fn removes_unreachable_opcodes() {
+        let condition = RegisterIndex::from(0);
+        let bytecode: Vec<Opcode> = vec![
+            Opcode::JumpIfNot { condition, location: 3 },
+            Opcode::Call { location: 5 },
+            Opcode::Stop,
+            Opcode::Trap,
+            // Unreachable as it follows a trap and is never jumped to.
+            Opcode::Const { destination: condition, value: Value::from(1_u128) },
+            Opcode::Return,
+            Opcode::Stop,
+        ];
+
+        let (optimized, positions) = remove_unreachable_code(bytecode, vec![0, 1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(
+            optimized,
+            vec![
+                Opcode::JumpIfNot { condition, location: 3 },
+                Opcode::Call { location: 4 },
+                Opcode::Stop,
+                Opcode::Trap,
+                Opcode::Return,
+            ]
+        );
+        assert_eq!(positions, vec![0, 1, 2, 3, 5]);
+    }
+}
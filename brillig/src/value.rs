@@ -1,4 +1,4 @@
-use acir_field::FieldElement;
+use acir_field::{generic_ark, AcirField, FieldElement};
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
@@ -10,20 +10,30 @@ pub enum Typ {
     Signed { bit_size: u32 },
 }
 
+impl std::fmt::Display for Typ {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Typ::Field => write!(f, "Field"),
+            Typ::Unsigned { bit_size } => write!(f, "u{bit_size}"),
+            Typ::Signed { bit_size } => write!(f, "i{bit_size}"),
+        }
+    }
+}
+
 /// `Value` represents the base descriptor for a value in the VM.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Value {
-    inner: FieldElement,
+pub struct Value<F = FieldElement> {
+    inner: F,
 }
 
-impl Value {
+impl<F: AcirField> Value<F> {
     /// Returns `true` if the `Value` represents `zero`
     pub fn is_zero(&self) -> bool {
         self.inner.is_zero()
     }
 
     /// Converts `Value` into a `FieldElement`.
-    pub fn to_field(&self) -> FieldElement {
+    pub fn to_field(&self) -> F {
         self.inner
     }
 
@@ -42,60 +52,62 @@ impl Value {
     }
 }
 
-impl From<usize> for Value {
-    fn from(value: usize) -> Self {
-        Value { inner: FieldElement::from(value as u128) }
+impl<F: AcirField> From<F> for Value<F> {
+    fn from(value: F) -> Self {
+        Value { inner: value }
     }
 }
 
-impl From<u128> for Value {
-    fn from(value: u128) -> Self {
-        Value { inner: FieldElement::from(value) }
+// Conversions from integers are implemented for each field individually as a blanket implementation would overlap with
+// the conversion from the field itself.
+impl<P: generic_ark::PrimeField> From<usize> for Value<generic_ark::FieldElement<P>> {
+    fn from(value: usize) -> Self {
+        Value { inner: generic_ark::FieldElement::from(value as u128) }
     }
 }
 
-impl From<FieldElement> for Value {
-    fn from(value: FieldElement) -> Self {
-        Value { inner: value }
+impl<P: generic_ark::PrimeField> From<u128> for Value<generic_ark::FieldElement<P>> {
+    fn from(value: u128) -> Self {
+        Value { inner: generic_ark::FieldElement::from(value) }
     }
 }
 
-impl From<bool> for Value {
+impl<P: generic_ark::PrimeField> From<bool> for Value<generic_ark::FieldElement<P>> {
     fn from(value: bool) -> Self {
-        Value { inner: FieldElement::from(value) }
+        Value { inner: generic_ark::FieldElement::from(value) }
     }
 }
 
-impl Add for Value {
-    type Output = Value;
+impl<F: AcirField> Add for Value<F> {
+    type Output = Value<F>;
 
     fn add(self, rhs: Self) -> Self::Output {
         Value { inner: self.inner + rhs.inner }
     }
 }
-impl Sub for Value {
-    type Output = Value;
+impl<F: AcirField> Sub for Value<F> {
+    type Output = Value<F>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         Value { inner: self.inner - rhs.inner }
     }
 }
-impl Mul for Value {
-    type Output = Value;
+impl<F: AcirField> Mul for Value<F> {
+    type Output = Value<F>;
 
     fn mul(self, rhs: Self) -> Self::Output {
         Value { inner: self.inner * rhs.inner }
     }
 }
-impl Div for Value {
-    type Output = Value;
+impl<F: AcirField> Div for Value<F> {
+    type Output = Value<F>;
 
     fn div(self, rhs: Self) -> Self::Output {
         Value { inner: self.inner / rhs.inner }
     }
 }
-impl Neg for Value {
-    type Output = Value;
+impl<F: AcirField> Neg for Value<F> {
+    type Output = Value<F>;
 
     fn neg(self) -> Self::Output {
         Value { inner: -self.inner }
@@ -0,0 +1,152 @@
+//! Benchmarks of the Brillig interpreter on integer-heavy loops.
+//!
+//! Run with `cargo bench -p brillig_vm`. Loops over integers of at most 128 bits are executed on native integers
+//! whereas the 254-bit variants exercise the arbitrary precision fallback, providing a baseline for comparison.
+
+use std::time::{Duration, Instant};
+
+use acir::brillig::{BinaryIntOp, Opcode, RegisterIndex, Value};
+use acvm_blackbox_solver::testing::MockSolver;
+use brillig_vm::{DecodedBytecode, Registers, VMStatus, VM};
+
+const ITERATIONS: u128 = 10_000;
+const SAMPLES: u32 = 20;
+
+fn r(index: usize) -> RegisterIndex {
+    RegisterIndex::from(index)
+}
+
+fn constant(destination: usize, value: u128) -> Opcode {
+    Opcode::Const { destination: r(destination), value: Value::from(value) }
+}
+
+fn int_op(op: BinaryIntOp, bit_size: u32, destination: usize, lhs: usize, rhs: usize) -> Opcode {
+    Opcode::BinaryIntOp { destination: r(destination), op, bit_size, lhs: r(lhs), rhs: r(rhs) }
+}
+
+/// Computes `acc = (acc * 3 + i) ^ i` for each `i` below the value in register 0.
+fn counting_loop(bit_size: u32) -> Vec<Opcode> {
+    vec![
+        constant(1, 0),
+        constant(2, 0),
+        constant(3, 1),
+        constant(4, 3),
+        int_op(BinaryIntOp::LessThan, bit_size, 5, 1, 0),
+        Opcode::JumpIfNot { condition: r(5), location: 11 },
+        int_op(BinaryIntOp::Mul, bit_size, 2, 2, 4),
+        int_op(BinaryIntOp::Add, bit_size, 2, 2, 1),
+        int_op(BinaryIntOp::Xor, bit_size, 2, 2, 1),
+        int_op(BinaryIntOp::Add, bit_size, 1, 1, 3),
+        Opcode::Jump { location: 4 },
+        Opcode::Stop,
+    ]
+}
+
+/// Repeatedly applies the mixing functions of a SHA-256 round, as emitted for hash helpers, for as many rounds as
+/// the value in register 0.
+fn sha256_rounds(bit_size: u32) -> Vec<Opcode> {
+    let u32_op = |op, destination, lhs, rhs| int_op(op, bit_size, destination, lhs, rhs);
+    // Computes `rotr(e, n)` into register `destination` using registers holding `n` and `32 - n`.
+    let rotate = |destination: usize, shift: usize, complement: usize| {
+        [
+            u32_op(BinaryIntOp::Shr, 20, 5, shift),
+            u32_op(BinaryIntOp::Shl, 21, 5, complement),
+            u32_op(BinaryIntOp::Or, destination, 20, 21),
+        ]
+    };
+
+    let mut bytecode = vec![
+        constant(1, 0),
+        constant(2, 1),
+        // e, f, g, h
+        constant(5, 0x510e_527f),
+        constant(6, 0x9b05_688c),
+        constant(7, 0x1f83_d9ab),
+        constant(8, 0x5be0_cd19),
+        // Rotation amounts
+        constant(10, 6),
+        constant(11, 26),
+        constant(12, 11),
+        constant(13, 21),
+        constant(14, 25),
+        constant(15, 7),
+    ];
+    let loop_start = bytecode.len();
+    bytecode.extend([
+        u32_op(BinaryIntOp::LessThan, 3, 1, 0),
+        Opcode::JumpIfNot { condition: r(3), location: 0 },
+    ]);
+    bytecode.extend(rotate(22, 10, 11));
+    bytecode.extend(rotate(23, 12, 13));
+    bytecode.extend(rotate(24, 14, 15));
+    bytecode.extend([
+        // S1 = rotr(e, 6) ^ rotr(e, 11) ^ rotr(e, 25)
+        u32_op(BinaryIntOp::Xor, 22, 22, 23),
+        u32_op(BinaryIntOp::Xor, 22, 22, 24),
+        // ch = (e & f) ^ (!e & g)
+        u32_op(BinaryIntOp::And, 25, 5, 6),
+        Opcode::Not { destination: r(26), source: r(5), bit_size },
+        u32_op(BinaryIntOp::And, 26, 26, 7),
+        u32_op(BinaryIntOp::Xor, 25, 25, 26),
+        // h = h + S1 + ch, then rotate the working variables
+        u32_op(BinaryIntOp::Add, 8, 8, 22),
+        u32_op(BinaryIntOp::Add, 8, 8, 25),
+        Opcode::Mov { destination: r(27), source: r(8) },
+        Opcode::Mov { destination: r(8), source: r(7) },
+        Opcode::Mov { destination: r(7), source: r(6) },
+        Opcode::Mov { destination: r(6), source: r(5) },
+        Opcode::Mov { destination: r(5), source: r(27) },
+        u32_op(BinaryIntOp::Add, 1, 1, 2),
+        Opcode::Jump { location: loop_start },
+    ]);
+    let loop_end = bytecode.len();
+    bytecode.push(Opcode::Stop);
+    bytecode[loop_start + 1] = Opcode::JumpIfNot { condition: r(3), location: loop_end };
+    bytecode
+}
+
+fn execute(bytecode: impl Into<DecodedBytecode>, iterations: u128) {
+    let solver = MockSolver::new();
+    let mut vm =
+        VM::new(Registers::load(vec![Value::from(iterations)]), vec![], bytecode, vec![], &solver);
+    assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+}
+
+/// Reports the mean time taken by `run` over a number of samples.
+fn bench(name: &str, mut run: impl FnMut()) {
+    run();
+    let mut total = Duration::ZERO;
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+        run();
+        total += start.elapsed();
+    }
+    println!("{name:<32} {:>12.3?}", total / SAMPLES);
+}
+
+fn main() {
+    for bit_size in [32, 64, 128, 254] {
+        let bytecode = counting_loop(bit_size);
+        bench(&format!("counting loop (u{bit_size})"), || execute(bytecode.clone(), ITERATIONS));
+    }
+    // Shifts are only supported on integers of up to 128 bits.
+    for bit_size in [32, 64, 128] {
+        let bytecode = sha256_rounds(bit_size);
+        bench(&format!("sha256 rounds (u{bit_size})"), || execute(bytecode.clone(), ITERATIONS));
+    }
+
+    // Brillig calls emitted by the ACVM are often short, in which case preparing the bytecode for execution
+    // makes up a large part of their cost.
+    let bytecode = sha256_rounds(32);
+    let decoded = DecodedBytecode::new(bytecode.clone());
+    bench("short calls (decoded per call)", || {
+        for _ in 0..ITERATIONS {
+            execute(bytecode.clone(), 1);
+        }
+    });
+    bench("short calls (shared decoding)", || {
+        for _ in 0..ITERATIONS {
+            execute(decoded.clone(), 1);
+        }
+    });
+}
@@ -1,31 +1,60 @@
 use acir::brillig::{BlackBoxOp, HeapArray, HeapVector, Value};
-use acir::{BlackBoxFunc, FieldElement};
+use acir::{AcirField, BlackBoxFunc};
 use acvm_blackbox_solver::{
-    blake2s, ecdsa_secp256k1_verify, ecdsa_secp256r1_verify, hash_to_field_128_security, keccak256,
-    sha256, BlackBoxFunctionSolver, BlackBoxResolutionError,
+    blake2s, blake3, ecdsa_secp256k1_recover, ecdsa_secp256k1_verify, ecdsa_secp256r1_verify,
+    eddsa_babyjubjub_verify, eddsa_ed25519_verify, hash_to_field_128_security, keccak256, sha256,
+    sha512, BlackBoxFunctionSolver, BlackBoxResolutionError,
 };
 
-use crate::{Memory, Registers};
+use crate::{Memory, MemoryError, Registers};
 
-fn read_heap_vector<'a>(
-    memory: &'a Memory,
-    registers: &Registers,
+/// An error encountered while evaluating a [`BlackBoxOp`].
+#[derive(Debug)]
+pub(crate) enum BlackBoxError {
+    Resolution(BlackBoxResolutionError),
+    Memory(MemoryError),
+}
+
+impl std::fmt::Display for BlackBoxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlackBoxError::Resolution(error) => error.fmt(f),
+            BlackBoxError::Memory(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<BlackBoxResolutionError> for BlackBoxError {
+    fn from(error: BlackBoxResolutionError) -> Self {
+        BlackBoxError::Resolution(error)
+    }
+}
+
+impl From<MemoryError> for BlackBoxError {
+    fn from(error: MemoryError) -> Self {
+        BlackBoxError::Memory(error)
+    }
+}
+
+fn read_heap_vector<'a, F: AcirField>(
+    memory: &'a Memory<F>,
+    registers: &Registers<F>,
     vector: &HeapVector,
-) -> &'a [Value] {
+) -> Result<&'a [Value<F>], MemoryError> {
     memory
         .read_slice(registers.get(vector.pointer).to_usize(), registers.get(vector.size).to_usize())
 }
 
-fn read_heap_array<'a>(
-    memory: &'a Memory,
-    registers: &Registers,
+fn read_heap_array<'a, F: AcirField>(
+    memory: &'a Memory<F>,
+    registers: &Registers<F>,
     array: &HeapArray,
-) -> &'a [Value] {
+) -> Result<&'a [Value<F>], MemoryError> {
     memory.read_slice(registers.get(array.pointer).to_usize(), array.size)
 }
 
 /// Extracts the last byte of every value
-fn to_u8_vec(inputs: &[Value]) -> Vec<u8> {
+fn to_u8_vec<F: AcirField>(inputs: &[Value<F>]) -> Vec<u8> {
     let mut result = Vec::with_capacity(inputs.len());
     for input in inputs {
         let field_bytes = input.to_field().to_be_bytes();
@@ -35,39 +64,51 @@ fn to_u8_vec(inputs: &[Value]) -> Vec<u8> {
     result
 }
 
-fn to_value_vec(input: &[u8]) -> Vec<Value> {
-    input.iter().map(|x| Value::from(*x as usize)).collect()
+fn to_value_vec<F: AcirField>(input: &[u8]) -> Vec<Value<F>> {
+    input.iter().map(|x| F::from(*x as u128).into()).collect()
 }
 
-pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
+pub(crate) fn evaluate_black_box<F: AcirField, Solver: BlackBoxFunctionSolver<F>>(
     op: &BlackBoxOp,
     solver: &Solver,
-    registers: &mut Registers,
-    memory: &mut Memory,
-) -> Result<(), BlackBoxResolutionError> {
+    registers: &mut Registers<F>,
+    memory: &mut Memory<F>,
+) -> Result<(), BlackBoxError> {
     match op {
         BlackBoxOp::Sha256 { message, output } => {
-            let message = to_u8_vec(read_heap_vector(memory, registers, message));
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
             let bytes = sha256(message.as_slice())?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes));
+            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes))?;
+            Ok(())
+        }
+        BlackBoxOp::Sha512 { message, output } => {
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let bytes = sha512(message.as_slice())?;
+            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes))?;
             Ok(())
         }
         BlackBoxOp::Blake2s { message, output } => {
-            let message = to_u8_vec(read_heap_vector(memory, registers, message));
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
             let bytes = blake2s(message.as_slice())?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes));
+            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes))?;
+            Ok(())
+        }
+        BlackBoxOp::Blake3 { message, output } => {
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let bytes = blake3(message.as_slice())?;
+            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes))?;
             Ok(())
         }
         BlackBoxOp::Keccak256 { message, output } => {
-            let message = to_u8_vec(read_heap_vector(memory, registers, message));
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
             let bytes = keccak256(message.as_slice())?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes));
+            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes))?;
             Ok(())
         }
         BlackBoxOp::HashToField128Security { message, output } => {
-            let field = hash_to_field_128_security(&to_u8_vec(read_heap_vector(
+            let field: F = hash_to_field_128_security(&to_u8_vec(read_heap_vector(
                 memory, registers, message,
-            )))?;
+            )?))?;
             registers.set(*output, field.into());
             Ok(())
         }
@@ -95,7 +136,7 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
                 memory,
                 registers,
                 public_key_x,
-            ))
+            )?)
             .try_into()
             .map_err(|_| {
                 BlackBoxResolutionError::Failed(bb_func, "Invalid public key x length".to_string())
@@ -104,18 +145,18 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
                 memory,
                 registers,
                 public_key_y,
-            ))
+            )?)
             .try_into()
             .map_err(|_| {
                 BlackBoxResolutionError::Failed(bb_func, "Invalid public key y length".to_string())
             })?;
-            let signature: [u8; 64] = to_u8_vec(read_heap_array(memory, registers, signature))
+            let signature: [u8; 64] = to_u8_vec(read_heap_array(memory, registers, signature)?)
                 .try_into()
                 .map_err(|_| {
                     BlackBoxResolutionError::Failed(bb_func, "Invalid signature length".to_string())
                 })?;
 
-            let hashed_msg = to_u8_vec(read_heap_vector(memory, registers, hashed_msg));
+            let hashed_msg = to_u8_vec(read_heap_vector(memory, registers, hashed_msg)?);
 
             let result = match op {
                 BlackBoxOp::EcdsaSecp256k1 { .. } => {
@@ -127,29 +168,139 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
                 _ => unreachable!(),
             };
 
-            registers.set(*result_register, result.into());
+            registers.set(*result_register, F::from(result).into());
+            Ok(())
+        }
+        BlackBoxOp::EcdsaSecp256k1Recover {
+            hashed_msg,
+            signature,
+            recovery_id,
+            public_key_x,
+            public_key_y,
+        } => {
+            let signature: [u8; 64] = to_u8_vec(read_heap_array(memory, registers, signature)?)
+                .try_into()
+                .map_err(|_| {
+                    BlackBoxResolutionError::Failed(
+                        BlackBoxFunc::EcdsaSecp256k1Recover,
+                        "Invalid signature length".to_string(),
+                    )
+                })?;
+            let recovery_id = match registers.get(*recovery_id).to_field() {
+                recovery_id if recovery_id.is_zero() => 0,
+                recovery_id if recovery_id.is_one() => 1,
+                recovery_id => {
+                    return Err(BlackBoxResolutionError::Failed(
+                        BlackBoxFunc::EcdsaSecp256k1Recover,
+                        format!("recovery id must be 0 or 1 but received {}", recovery_id.to_hex()),
+                    )
+                    .into())
+                }
+            };
+            let hashed_msg = to_u8_vec(read_heap_vector(memory, registers, hashed_msg)?);
+
+            let (x, y) = ecdsa_secp256k1_recover(&hashed_msg, &signature, recovery_id)?;
+            memory
+                .write_slice(registers.get(public_key_x.pointer).to_usize(), &to_value_vec(&x))?;
+            memory
+                .write_slice(registers.get(public_key_y.pointer).to_usize(), &to_value_vec(&y))?;
+            Ok(())
+        }
+        BlackBoxOp::EddsaEd25519 { public_key, signature, message, result } => {
+            let public_key: [u8; 32] = to_u8_vec(read_heap_array(memory, registers, public_key)?)
+                .try_into()
+                .map_err(|_| {
+                    BlackBoxResolutionError::Failed(
+                        BlackBoxFunc::EddsaEd25519,
+                        "Invalid public key length".to_string(),
+                    )
+                })?;
+            let signature: [u8; 64] = to_u8_vec(read_heap_array(memory, registers, signature)?)
+                .try_into()
+                .map_err(|_| {
+                    BlackBoxResolutionError::Failed(
+                        BlackBoxFunc::EddsaEd25519,
+                        "Invalid signature length".to_string(),
+                    )
+                })?;
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let verified = eddsa_ed25519_verify(&public_key, &signature, &message)?;
+            registers.set(*result, F::from(verified).into());
+            Ok(())
+        }
+        BlackBoxOp::EddsaBabyJubJub {
+            public_key_x,
+            public_key_y,
+            signature_s,
+            signature_r8_x,
+            signature_r8_y,
+            message,
+            result,
+        } => {
+            let verified = eddsa_babyjubjub_verify(
+                &registers.get(*public_key_x).to_field(),
+                &registers.get(*public_key_y).to_field(),
+                &registers.get(*signature_s).to_field(),
+                &registers.get(*signature_r8_x).to_field(),
+                &registers.get(*signature_r8_y).to_field(),
+                &registers.get(*message).to_field(),
+            )?;
+            registers.set(*result, F::from(verified).into());
             Ok(())
         }
         BlackBoxOp::SchnorrVerify { public_key_x, public_key_y, message, signature, result } => {
             let public_key_x = registers.get(*public_key_x).to_field();
             let public_key_y = registers.get(*public_key_y).to_field();
-            let message: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, message));
-            let signature: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, signature));
+            let message: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let signature: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, signature)?);
             let verified =
                 solver.schnorr_verify(&public_key_x, &public_key_y, &signature, &message)?;
-            registers.set(*result, verified.into());
+            registers.set(*result, F::from(verified).into());
             Ok(())
         }
         BlackBoxOp::FixedBaseScalarMul { low, high, result } => {
             let low = registers.get(*low).to_field();
             let high = registers.get(*high).to_field();
+            if !low.fits_in_u128() || !high.fits_in_u128() {
+                return Err(BlackBoxResolutionError::Failed(
+                    BlackBoxFunc::FixedBaseScalarMul,
+                    "scalar limbs must each fit in 128 bits".to_string(),
+                )
+                .into());
+            }
             let (x, y) = solver.fixed_base_scalar_mul(&low, &high)?;
-            memory.write_slice(registers.get(result.pointer).to_usize(), &[x.into(), y.into()]);
+            memory.write_slice(registers.get(result.pointer).to_usize(), &[x.into(), y.into()])?;
+            Ok(())
+        }
+        BlackBoxOp::EmbeddedCurveAdd { input1_x, input1_y, input2_x, input2_y, result } => {
+            let input1_x = registers.get(*input1_x).to_field();
+            let input1_y = registers.get(*input1_y).to_field();
+            let input2_x = registers.get(*input2_x).to_field();
+            let input2_y = registers.get(*input2_y).to_field();
+            let (x, y) = solver.embedded_curve_add(&input1_x, &input1_y, &input2_x, &input2_y)?;
+            memory.write_slice(registers.get(result.pointer).to_usize(), &[x.into(), y.into()])?;
+            Ok(())
+        }
+        BlackBoxOp::VariableBaseScalarMul { point_x, point_y, scalar_low, scalar_high, result } => {
+            let point_x = registers.get(*point_x).to_field();
+            let point_y = registers.get(*point_y).to_field();
+            let scalar_low = registers.get(*scalar_low).to_field();
+            let scalar_high = registers.get(*scalar_high).to_field();
+            if !scalar_low.fits_in_u128() || !scalar_high.fits_in_u128() {
+                return Err(BlackBoxResolutionError::Failed(
+                    BlackBoxFunc::VariableBaseScalarMul,
+                    "scalar limbs must each fit in 128 bits".to_string(),
+                )
+                .into());
+            }
+            let (x, y) =
+                solver.variable_base_scalar_mul(&point_x, &point_y, &scalar_low, &scalar_high)?;
+            memory.write_slice(registers.get(result.pointer).to_usize(), &[x.into(), y.into()])?;
             Ok(())
         }
         BlackBoxOp::Pedersen { inputs, domain_separator, output } => {
-            let inputs: Vec<FieldElement> =
-                read_heap_vector(memory, registers, inputs).iter().map(|x| x.to_field()).collect();
+            let inputs: Vec<F> =
+                read_heap_vector(memory, registers, inputs)?.iter().map(|x| x.to_field()).collect();
             let domain_separator: u32 =
                 registers.get(*domain_separator).to_u128().try_into().map_err(|_| {
                     BlackBoxResolutionError::Failed(
@@ -158,7 +309,7 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
                     )
                 })?;
             let (x, y) = solver.pedersen(&inputs, domain_separator)?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &[x.into(), y.into()]);
+            memory.write_slice(registers.get(output.pointer).to_usize(), &[x.into(), y.into()])?;
             Ok(())
         }
     }
@@ -166,11 +317,13 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
 
 #[cfg(test)]
 mod test {
-    use acir::brillig::BlackBoxOp;
+    use acir::{brillig::BlackBoxOp, BlackBoxFunc};
+    use acvm_blackbox_solver::{testing::MockSolver, BlackBoxResolutionError};
 
     use crate::{
-        black_box::{evaluate_black_box, to_u8_vec, to_value_vec},
-        DummyBlackBoxSolver, HeapArray, HeapVector, Memory, Registers, Value,
+        black_box::{evaluate_black_box, to_u8_vec, to_value_vec, BlackBoxError},
+        tests::SOLVER,
+        HeapArray, HeapVector, Memory, Registers, Value,
     };
 
     #[test]
@@ -178,10 +331,10 @@ mod test {
         let message: Vec<u8> = b"hello world".to_vec();
         let message_length = message.len();
 
-        let mut memory = Memory::from(vec![]);
+        let mut memory: Memory = Memory::from(vec![]);
         let message_pointer = 0;
         let result_pointer = message_pointer + message_length;
-        memory.write_slice(message_pointer, to_value_vec(&message).as_slice());
+        memory.write_slice(message_pointer, to_value_vec(&message).as_slice()).unwrap();
 
         let mut registers = Registers {
             inner: vec![
@@ -196,9 +349,9 @@ mod test {
             output: HeapArray { pointer: 2.into(), size: 32 },
         };
 
-        evaluate_black_box(&op, &DummyBlackBoxSolver, &mut registers, &mut memory).unwrap();
+        evaluate_black_box(&op, &SOLVER, &mut registers, &mut memory).unwrap();
 
-        let result = memory.read_slice(result_pointer, 32);
+        let result = memory.read_slice(result_pointer, 32).unwrap();
 
         assert_eq!(
             to_u8_vec(result),
@@ -208,4 +361,67 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn embedded_curve_add_is_solved_by_the_solver() {
+        let (x, y) = (Value::from(1u128), Value::from(2u128));
+        let mut memory: Memory = Memory::from(vec![]);
+        let result_pointer = 0;
+        let mut registers = Registers { inner: vec![x, y, x, y, Value::from(result_pointer)] };
+
+        let op = BlackBoxOp::EmbeddedCurveAdd {
+            input1_x: 0.into(),
+            input1_y: 1.into(),
+            input2_x: 2.into(),
+            input2_y: 3.into(),
+            result: HeapArray { pointer: 4.into(), size: 2 },
+        };
+
+        let solver =
+            MockSolver::new().on_embedded_curve_add(acvm_blackbox_solver::embedded_curve_add);
+        evaluate_black_box(&op, &solver, &mut registers, &mut memory).unwrap();
+
+        let (expected_x, expected_y) = acvm_blackbox_solver::embedded_curve_add(
+            &x.to_field(),
+            &y.to_field(),
+            &x.to_field(),
+            &y.to_field(),
+        )
+        .unwrap();
+        assert_eq!(
+            memory.read_slice(result_pointer, 2).unwrap(),
+            &[Value::from(expected_x), Value::from(expected_y)]
+        );
+    }
+
+    #[test]
+    fn ecdsa_secp256k1_recover_rejects_non_boolean_recovery_id() {
+        let mut memory: Memory = Memory::from(vec![Value::from(1u128); 96]);
+        // 256 would be read as 0 if the recovery id were truncated to a byte.
+        let mut registers = Registers {
+            inner: vec![
+                Value::from(0u128),
+                Value::from(32u128),
+                Value::from(256u128),
+                Value::from(96u128),
+                Value::from(128u128),
+            ],
+        };
+
+        let op = BlackBoxOp::EcdsaSecp256k1Recover {
+            hashed_msg: HeapVector { pointer: 0.into(), size: 1.into() },
+            signature: HeapArray { pointer: 1.into(), size: 64 },
+            recovery_id: 2.into(),
+            public_key_x: HeapArray { pointer: 3.into(), size: 32 },
+            public_key_y: HeapArray { pointer: 4.into(), size: 32 },
+        };
+
+        assert!(matches!(
+            evaluate_black_box(&op, &SOLVER, &mut registers, &mut memory),
+            Err(BlackBoxError::Resolution(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::EcdsaSecp256k1Recover,
+                _
+            )))
+        ));
+    }
 }
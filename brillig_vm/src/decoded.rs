@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use acir::brillig::{BinaryFieldOp, BinaryIntOp, Opcode, RegisterIndex, Value};
+use acir::{AcirField, FieldElement};
+
+/// Brillig bytecode which has been decoded into the form executed by the [VM][crate::VM].
+///
+/// Decoding resolves ahead of execution how each opcode is executed, such as whether an integer operation can be
+/// evaluated on native integers. The decoded bytecode is reference counted so that it can be shared between
+/// any number of VMs without being copied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedBytecode<F = FieldElement> {
+    opcodes: Arc<[Opcode<F>]>,
+    instructions: Arc<[Instruction<F>]>,
+}
+
+impl<F: AcirField> DecodedBytecode<F> {
+    /// Decodes `bytecode` for execution.
+    pub fn new(bytecode: Vec<Opcode<F>>) -> Self {
+        let instructions = bytecode.iter().map(Instruction::decode).collect();
+        Self { opcodes: bytecode.into(), instructions }
+    }
+}
+
+impl<F> DecodedBytecode<F> {
+    /// Returns the opcodes from which this bytecode was decoded.
+    pub fn opcodes(&self) -> &[Opcode<F>] {
+        &self.opcodes
+    }
+
+    /// Returns the number of opcodes in the bytecode.
+    pub fn len(&self) -> usize {
+        self.opcodes.len()
+    }
+
+    /// Returns `true` if the bytecode contains no opcodes.
+    pub fn is_empty(&self) -> bool {
+        self.opcodes.is_empty()
+    }
+
+    pub(crate) fn instruction(&self, program_counter: usize) -> &Instruction<F> {
+        &self.instructions[program_counter]
+    }
+}
+
+impl<F: AcirField> From<Vec<Opcode<F>>> for DecodedBytecode<F> {
+    fn from(bytecode: Vec<Opcode<F>>) -> Self {
+        Self::new(bytecode)
+    }
+}
+
+/// The executable form of a single [`Opcode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Instruction<F> {
+    BinaryFieldOp {
+        op: BinaryFieldOp,
+        lhs: RegisterIndex,
+        rhs: RegisterIndex,
+        destination: RegisterIndex,
+    },
+    /// A [`Opcode::BinaryIntOp`] on integers of at most 128 bits, which is evaluated on native integers
+    /// whenever its operands are less than `2^bit_size`.
+    SmallBinaryIntOp {
+        op: BinaryIntOp,
+        bit_size: u32,
+        lhs: RegisterIndex,
+        rhs: RegisterIndex,
+        destination: RegisterIndex,
+    },
+    /// A [`Opcode::Not`] on an integer of at most 128 bits, which is evaluated on native integers
+    /// whenever its operand is less than `2^bit_size`.
+    SmallNot {
+        bit_size: u32,
+        source: RegisterIndex,
+        destination: RegisterIndex,
+    },
+    Jump {
+        location: usize,
+    },
+    JumpIf {
+        condition: RegisterIndex,
+        location: usize,
+    },
+    JumpIfNot {
+        condition: RegisterIndex,
+        location: usize,
+    },
+    Call {
+        location: usize,
+    },
+    Return,
+    Mov {
+        destination: RegisterIndex,
+        source: RegisterIndex,
+    },
+    Const {
+        destination: RegisterIndex,
+        value: Value<F>,
+    },
+    Load {
+        destination: RegisterIndex,
+        source_pointer: RegisterIndex,
+    },
+    Store {
+        destination_pointer: RegisterIndex,
+        source: RegisterIndex,
+    },
+    Stop,
+    /// Any other opcode, which is executed by interpreting the original [`Opcode`].
+    Interpret,
+}
+
+impl<F: AcirField> Instruction<F> {
+    fn decode(opcode: &Opcode<F>) -> Self {
+        match *opcode {
+            Opcode::BinaryFieldOp { destination, op, lhs, rhs } => {
+                Instruction::BinaryFieldOp { op, lhs, rhs, destination }
+            }
+            Opcode::BinaryIntOp { destination, op, bit_size, lhs, rhs }
+                if fits_native_integer::<F>(bit_size) =>
+            {
+                Instruction::SmallBinaryIntOp { op, bit_size, lhs, rhs, destination }
+            }
+            Opcode::Not { destination, source, bit_size } if fits_native_integer::<F>(bit_size) => {
+                Instruction::SmallNot { bit_size, source, destination }
+            }
+            Opcode::Jump { location } => Instruction::Jump { location },
+            Opcode::JumpIf { condition, location } => Instruction::JumpIf { condition, location },
+            Opcode::JumpIfNot { condition, location } => {
+                Instruction::JumpIfNot { condition, location }
+            }
+            Opcode::Call { location } => Instruction::Call { location },
+            Opcode::Return => Instruction::Return,
+            Opcode::Mov { destination, source } => Instruction::Mov { destination, source },
+            Opcode::Const { destination, value } => Instruction::Const { destination, value },
+            Opcode::Load { destination, source_pointer } => {
+                Instruction::Load { destination, source_pointer }
+            }
+            Opcode::Store { destination_pointer, source } => {
+                Instruction::Store { destination_pointer, source }
+            }
+            Opcode::Stop => Instruction::Stop,
+            Opcode::BinaryIntOp { .. }
+            | Opcode::Not { .. }
+            | Opcode::Cast { .. }
+            | Opcode::ForeignCall { .. }
+            | Opcode::TypedForeignCall { .. }
+            | Opcode::BlackBox(_)
+            | Opcode::FrameCall { .. }
+            | Opcode::Alloc { .. }
+            | Opcode::Free { .. }
+            | Opcode::Trap => Instruction::Interpret,
+        }
+    }
+}
+
+/// Integers of more than 128 bits, or wider than the field, are left to the arbitrary precision implementation
+/// which also reports the latter as an error.
+fn fits_native_integer<F: AcirField>(bit_size: u32) -> bool {
+    bit_size <= 128 && bit_size <= F::max_num_bits()
+}
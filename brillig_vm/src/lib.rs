@@ -10,31 +10,39 @@
 //! [acvm]: https://crates.io/crates/acvm
 
 use acir::brillig::{
-    BinaryFieldOp, BinaryIntOp, ForeignCallOutput, ForeignCallResult, HeapArray, HeapVector,
-    Opcode, RegisterIndex, RegisterOrMemory, Value,
+    abi::AbiType, BinaryFieldOp, BinaryIntOp, ForeignCallOutput, ForeignCallParam,
+    ForeignCallResult, HeapArray, HeapVector, Opcode, RegisterIndex, RegisterOrMemory, Typ, Value,
 };
-use acir::FieldElement;
+use acir::{AcirField, FieldElement};
 // Re-export `brillig`.
 pub use acir::brillig;
 
-mod arithmetic;
 mod black_box;
+mod decoded;
 mod memory;
 mod registers;
+mod types;
 
-use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
-use arithmetic::{evaluate_binary_bigint_op, evaluate_binary_field_op};
+use acir::brillig::{
+    evaluate_bigint_cast, evaluate_bigint_not, evaluate_binary_bigint_op, evaluate_binary_field_op,
+    evaluate_binary_u128_op, evaluate_u128_not,
+};
+use acvm_blackbox_solver::BlackBoxFunctionSolver;
 use black_box::evaluate_black_box;
+use decoded::Instruction;
+
+pub use decoded::DecodedBytecode;
 
-pub use memory::Memory;
+pub use memory::{Memory, MemoryError};
 use num_bigint::BigUint;
 pub use registers::Registers;
+use types::RegisterTypes;
 
 /// The error call stack contains the opcode indexes of the call stack at the time of failure, plus the index of the opcode that failed.
 pub type ErrorCallStack = Vec<usize>;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum VMStatus {
+pub enum VMStatus<F = FieldElement> {
     Finished,
     InProgress,
     Failure {
@@ -51,16 +59,38 @@ pub enum VMStatus {
         /// Interpreted by simulator context
         function: String,
         /// Input values
-        /// Each input is a list of values as an input can be either a single value or a memory pointer
-        inputs: Vec<Vec<Value>>,
+        /// Each input keeps the shape of the [RegisterOrMemory] it was read from
+        inputs: Vec<ForeignCallParam<F>>,
+        /// The type of each input, if known
+        input_types: Vec<Option<AbiType>>,
+        /// The type of each of the expected outputs, if known
+        destination_types: Vec<Option<AbiType>>,
     },
 }
 
+/// A call which has yet to return.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct CallFrame<F> {
+    /// The index of the opcode which made the call
+    call_site: usize,
+    /// The state of the caller, if the call is executing within a new frame of registers
+    caller: Option<CallerState<F>>,
+}
+
+/// The state of a caller which is restored upon returning from an [`Opcode::FrameCall`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct CallerState<F> {
+    registers: Registers<F>,
+    register_types: Option<RegisterTypes>,
+    /// The registers which receive the callee's results
+    results: Vec<RegisterIndex>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// VM encapsulates the state of the Brillig VM during execution.
-pub struct VM<'bb_solver, B: BlackBoxFunctionSolver> {
+pub struct VM<'bb_solver, B: BlackBoxFunctionSolver<F>, F = FieldElement> {
     /// Register storage
-    registers: Registers,
+    registers: Registers<F>,
     /// Instruction pointer
     program_counter: usize,
     /// A counter maintained throughout a Brillig process that determines
@@ -68,26 +98,31 @@ pub struct VM<'bb_solver, B: BlackBoxFunctionSolver> {
     foreign_call_counter: usize,
     /// Represents the outputs of all foreign calls during a Brillig process
     /// List is appended onto by the caller upon reaching a [VMStatus::ForeignCallWait]
-    foreign_call_results: Vec<ForeignCallResult>,
+    foreign_call_results: Vec<ForeignCallResult<F>>,
     /// Executable opcodes
-    bytecode: Vec<Opcode>,
+    bytecode: DecodedBytecode<F>,
     /// Status of the VM
-    status: VMStatus,
+    status: VMStatus<F>,
     /// Memory of the VM
-    memory: Memory,
+    memory: Memory<F>,
     /// Call stack
-    call_stack: Vec<Value>,
+    call_stack: Vec<CallFrame<F>>,
     /// The solver for blackbox functions
     black_box_solver: &'bb_solver B,
+    /// The types of the values held in each register, if the VM is type-checking its bytecode
+    register_types: Option<RegisterTypes>,
 }
 
-impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
+impl<'bb_solver, B: BlackBoxFunctionSolver<F>, F: AcirField> VM<'bb_solver, B, F> {
     /// Constructs a new VM instance
+    ///
+    /// `bytecode` may either be a list of opcodes, which is decoded upon construction, or [`DecodedBytecode`]
+    /// which can be shared with other VMs executing the same bytecode.
     pub fn new(
-        inputs: Registers,
-        memory: Vec<Value>,
-        bytecode: Vec<Opcode>,
-        foreign_call_results: Vec<ForeignCallResult>,
+        inputs: Registers<F>,
+        memory: Vec<Value<F>>,
+        bytecode: impl Into<DecodedBytecode<F>>,
+        foreign_call_results: Vec<ForeignCallResult<F>>,
         black_box_solver: &'bb_solver B,
     ) -> Self {
         Self {
@@ -95,45 +130,62 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
             program_counter: 0,
             foreign_call_counter: 0,
             foreign_call_results,
-            bytecode,
+            bytecode: bytecode.into(),
             status: VMStatus::InProgress,
             memory: memory.into(),
             call_stack: Vec::new(),
             black_box_solver,
+            register_types: None,
         }
     }
 
+    /// Enables type-checked execution, in which the VM tracks the [type][Typ] of the value held in each register
+    /// and fails upon executing an opcode whose operands have unexpected types, e.g. adding a field to a `u32`.
+    ///
+    /// `register_types` holds the types of the input registers.
+    /// Registers written by opcodes which do not determine a type, such as [`Opcode::Const`], are untyped
+    /// and are accepted as an operand of any type.
+    pub fn with_type_checking(mut self, register_types: Vec<Typ>) -> Self {
+        self.register_types = Some(RegisterTypes::load(register_types));
+        self
+    }
+
     /// Updates the current status of the VM.
     /// Returns the given status.
-    fn status(&mut self, status: VMStatus) -> VMStatus {
+    fn status(&mut self, status: VMStatus<F>) -> VMStatus<F> {
         self.status = status.clone();
         status
     }
 
     /// Sets the current status of the VM to Finished (completed execution).
-    fn finish(&mut self) -> VMStatus {
+    fn finish(&mut self) -> VMStatus<F> {
         self.status(VMStatus::Finished)
     }
 
     /// Sets the status of the VM to `ForeignCallWait`.
     /// Indicating that the VM is now waiting for a foreign call to be resolved.
-    fn wait_for_foreign_call(&mut self, function: String, inputs: Vec<Vec<Value>>) -> VMStatus {
-        self.status(VMStatus::ForeignCallWait { function, inputs })
+    fn wait_for_foreign_call(
+        &mut self,
+        function: String,
+        inputs: Vec<ForeignCallParam<F>>,
+        input_types: Vec<Option<AbiType>>,
+        destination_types: Vec<Option<AbiType>>,
+    ) -> VMStatus<F> {
+        self.status(VMStatus::ForeignCallWait { function, inputs, input_types, destination_types })
     }
 
     /// Sets the current status of the VM to `fail`.
     /// Indicating that the VM encountered a `Trap` Opcode
     /// or an invalid state.
-    fn fail(&mut self, message: String) -> VMStatus {
-        let mut error_stack: Vec<_> =
-            self.call_stack.iter().map(|value| value.to_usize()).collect();
+    fn fail(&mut self, message: String) -> VMStatus<F> {
+        let mut error_stack: Vec<_> = self.call_stack.iter().map(|frame| frame.call_site).collect();
         error_stack.push(self.program_counter);
         self.status(VMStatus::Failure { call_stack: error_stack, message });
         self.status.clone()
     }
 
     /// Loop over the bytecode and update the program counter
-    pub fn process_opcodes(&mut self) -> VMStatus {
+    pub fn process_opcodes(&mut self) -> VMStatus<F> {
         while !matches!(
             self.process_opcode(),
             VMStatus::Finished | VMStatus::Failure { .. } | VMStatus::ForeignCallWait { .. }
@@ -142,155 +194,225 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
     }
 
     /// Returns all of the registers in the VM.
-    pub fn get_registers(&self) -> &Registers {
+    pub fn get_registers(&self) -> &Registers<F> {
         &self.registers
     }
 
-    pub fn get_memory(&self) -> &Vec<Value> {
+    pub fn get_memory(&self) -> &Vec<Value<F>> {
         self.memory.values()
     }
 
     /// Process a single opcode and modify the program counter.
-    pub fn process_opcode(&mut self) -> VMStatus {
-        let opcode = &self.bytecode[self.program_counter];
-        match opcode {
-            Opcode::BinaryFieldOp { op, lhs, rhs, destination: result } => {
-                self.process_binary_field_op(*op, *lhs, *rhs, *result);
+    pub fn process_opcode(&mut self) -> VMStatus<F> {
+        let Some(register_types) = &self.register_types else {
+            return self.execute_opcode();
+        };
+
+        match register_types.check_opcode(&self.bytecode.opcodes()[self.program_counter]) {
+            Ok(updates) => {
+                let status = self.execute_opcode();
+                // Registers are only written once the opcode has successfully executed.
+                if !matches!(status, VMStatus::Failure { .. } | VMStatus::ForeignCallWait { .. }) {
+                    if let Some(register_types) = &mut self.register_types {
+                        register_types.apply(updates);
+                    }
+                }
+                status
+            }
+            Err(error) => self.fail(error.to_string()),
+        }
+    }
+
+    /// Executes a single opcode and modifies the program counter.
+    fn execute_opcode(&mut self) -> VMStatus<F> {
+        match *self.bytecode.instruction(self.program_counter) {
+            Instruction::BinaryFieldOp { op, lhs, rhs, destination } => {
+                self.process_binary_field_op(op, lhs, rhs, destination);
                 self.increment_program_counter()
             }
-            Opcode::BinaryIntOp { op, bit_size, lhs, rhs, destination: result } => {
-                self.process_binary_int_op(*op, *bit_size, *lhs, *rhs, *result);
+            Instruction::SmallBinaryIntOp { op, bit_size, lhs, rhs, destination } => {
+                match self.process_small_binary_int_op(op, bit_size, lhs, rhs, destination) {
+                    Ok(()) => self.increment_program_counter(),
+                    Err(message) => self.fail(message),
+                }
+            }
+            Instruction::SmallNot { bit_size, source, destination } => {
+                self.process_small_not(bit_size, source, destination);
                 self.increment_program_counter()
             }
-            Opcode::Jump { location: destination } => self.set_program_counter(*destination),
-            Opcode::JumpIf { condition, location: destination } => {
+            Instruction::Jump { location } => self.set_program_counter(location),
+            Instruction::JumpIf { condition, location } => {
                 // Check if condition is true
                 // We use 0 to mean false and any other value to mean true
-                let condition_value = self.registers.get(*condition);
+                let condition_value = self.registers.get(condition);
                 if !condition_value.is_zero() {
-                    return self.set_program_counter(*destination);
+                    return self.set_program_counter(location);
                 }
                 self.increment_program_counter()
             }
-            Opcode::JumpIfNot { condition, location: destination } => {
-                let condition_value = self.registers.get(*condition);
+            Instruction::JumpIfNot { condition, location } => {
+                let condition_value = self.registers.get(condition);
                 if condition_value.is_zero() {
-                    return self.set_program_counter(*destination);
+                    return self.set_program_counter(location);
                 }
                 self.increment_program_counter()
             }
-            Opcode::Return => {
-                if let Some(register) = self.call_stack.pop() {
-                    self.set_program_counter(register.to_usize() + 1)
+            Instruction::Call { location } => {
+                // Push a return location
+                self.call_stack.push(CallFrame { call_site: self.program_counter, caller: None });
+                self.set_program_counter(location)
+            }
+            Instruction::Return => {
+                if let Some(CallFrame { call_site, caller }) = self.call_stack.pop() {
+                    if let Some(caller) = caller {
+                        self.return_to_caller(caller);
+                    }
+                    self.set_program_counter(call_site + 1)
                 } else {
                     self.fail("return opcode hit, but callstack already empty".to_string())
                 }
             }
-            Opcode::ForeignCall { function, destinations, inputs } => {
-                if self.foreign_call_counter >= self.foreign_call_results.len() {
-                    // When this opcode is called, it is possible that the results of a foreign call are
-                    // not yet known (not enough entries in `foreign_call_results`).
-                    // If that is the case, just resolve the inputs and pause the VM with a status
-                    // (VMStatus::ForeignCallWait) that communicates the foreign function name and
-                    // resolved inputs back to the caller. Once the caller pushes to `foreign_call_results`,
-                    // they can then make another call to the VM that starts at this opcode
-                    // but has the necessary results to proceed with execution.
-                    let resolved_inputs = inputs
-                        .iter()
-                        .map(|input| self.get_register_value_or_memory_values(*input))
-                        .collect::<Vec<_>>();
-                    return self.wait_for_foreign_call(function.clone(), resolved_inputs);
-                }
-
-                let values = &self.foreign_call_results[self.foreign_call_counter].values;
-
-                let mut invalid_foreign_call_result = false;
-                for (destination, output) in destinations.iter().zip(values) {
-                    match destination {
-                        RegisterOrMemory::RegisterIndex(value_index) => match output {
-                            ForeignCallOutput::Single(value) => {
-                                self.registers.set(*value_index, *value)
-                            }
-                            _ => unreachable!(
-                                "Function result size does not match brillig bytecode (expected 1 result)"
-                            ),
-                        },
-                        RegisterOrMemory::HeapArray(HeapArray { pointer: pointer_index, size }) => {
-                            match output {
-                                ForeignCallOutput::Array(values) => {
-                                    if values.len() != *size {
-                                        invalid_foreign_call_result = true;
-                                        break;
-                                    }
-                                    // Convert the destination pointer to a usize
-                                    let destination = self.registers.get(*pointer_index).to_usize();
-                                    // Write to our destination memory
-                                    self.memory.write_slice(destination, values);
-                                }
-                                _ => {
-                                    unreachable!("Function result size does not match brillig bytecode size")
-                                }
-                            }
-                        }
-                        RegisterOrMemory::HeapVector(HeapVector { pointer: pointer_index, size: size_index }) => {
-                            match output {
-                                ForeignCallOutput::Array(values) => {
-                                    // Set our size in the size register
-                                    self.registers.set(*size_index, Value::from(values.len()));
-                                    // Convert the destination pointer to a usize
-                                    let destination = self.registers.get(*pointer_index).to_usize();
-                                    // Write to our destination memory
-                                    self.memory.write_slice(destination, values);
-                                }
-                                _ => {
-                                    unreachable!("Function result size does not match brillig bytecode size")
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // These checks must come after resolving the foreign call outputs as `fail` uses a mutable reference
-                if destinations.len() != values.len() {
-                    self.fail(format!("{} output values were provided as a foreign call result for {} destination slots", values.len(), destinations.len()));
-                }
-                if invalid_foreign_call_result {
-                    self.fail("Function result size does not match brillig bytecode".to_owned());
-                }
-
-                self.foreign_call_counter += 1;
+            Instruction::Mov { destination, source } => {
+                let source_value = self.registers.get(source);
+                self.registers.set(destination, source_value);
                 self.increment_program_counter()
             }
-            Opcode::Mov { destination: destination_register, source: source_register } => {
-                let source_value = self.registers.get(*source_register);
-                self.registers.set(*destination_register, source_value);
+            Instruction::Const { destination, value } => {
+                self.registers.set(destination, value);
                 self.increment_program_counter()
             }
-            Opcode::Trap => self.fail("explicit trap hit in brillig".to_string()),
-            Opcode::Stop => self.finish(),
-            Opcode::Load { destination: destination_register, source_pointer } => {
+            Instruction::Load { destination, source_pointer } => {
                 // Convert our source_pointer to a usize
-                let source = self.registers.get(*source_pointer);
+                let source = self.registers.get(source_pointer);
                 // Use our usize source index to lookup the value in memory
-                let value = &self.memory.read(source.to_usize());
-                self.registers.set(*destination_register, *value);
-                self.increment_program_counter()
+                match self.memory.read(source.to_usize()) {
+                    Ok(value) => {
+                        self.registers.set(destination, value);
+                        self.increment_program_counter()
+                    }
+                    Err(error) => self.fail(error.to_string()),
+                }
             }
-            Opcode::Store { destination_pointer, source: source_register } => {
+            Instruction::Store { destination_pointer, source } => {
                 // Convert our destination_pointer to a usize
-                let destination = self.registers.get(*destination_pointer).to_usize();
+                let destination = self.registers.get(destination_pointer).to_usize();
                 // Use our usize destination index to set the value in memory
-                self.memory.write(destination, self.registers.get(*source_register));
+                match self.memory.write(destination, self.registers.get(source)) {
+                    Ok(()) => self.increment_program_counter(),
+                    Err(error) => self.fail(error.to_string()),
+                }
+            }
+            Instruction::Stop => self.finish(),
+            Instruction::Interpret => self.interpret_opcode(),
+        }
+    }
+
+    /// Executes the opcodes which are not decoded into a dedicated [`Instruction`] and modifies the program counter.
+    fn interpret_opcode(&mut self) -> VMStatus<F> {
+        let opcode = &self.bytecode.opcodes()[self.program_counter];
+        match opcode {
+            Opcode::BinaryIntOp { op, bit_size, lhs, rhs, destination: result } => {
+                // Integers wider than the field cannot be stored in a register without being reduced.
+                if *bit_size > F::max_num_bits() {
+                    return self.fail(format!(
+                        "integer bit size {bit_size} exceeds the {} bits of the field",
+                        F::max_num_bits()
+                    ));
+                }
+                match self.process_binary_int_op(*op, *bit_size, *lhs, *rhs, *result) {
+                    Ok(()) => self.increment_program_counter(),
+                    Err(message) => self.fail(message),
+                }
+            }
+            Opcode::Not { destination, source, bit_size } => {
+                if *bit_size > F::max_num_bits() {
+                    return self.fail(format!(
+                        "integer bit size {bit_size} exceeds the {} bits of the field",
+                        F::max_num_bits()
+                    ));
+                }
+                self.process_not(*bit_size, *source, *destination);
                 self.increment_program_counter()
             }
-            Opcode::Call { location } => {
-                // Push a return location
-                self.call_stack.push(Value::from(self.program_counter));
+            Opcode::Cast { destination, source, bit_size, sign_extend_from } => {
+                let widest_bit_size =
+                    sign_extend_from.map_or(*bit_size, |from| from.max(*bit_size));
+                if widest_bit_size > F::max_num_bits() {
+                    return self.fail(format!(
+                        "integer bit size {widest_bit_size} exceeds the {} bits of the field",
+                        F::max_num_bits()
+                    ));
+                }
+                self.process_cast(*bit_size, *sign_extend_from, *source, *destination);
+                self.increment_program_counter()
+            }
+            Opcode::ForeignCall { function, destinations, inputs } => {
+                let (function, destinations, inputs) =
+                    (function.clone(), destinations.clone(), inputs.clone());
+                self.process_foreign_call(function, &destinations, &inputs, &[], &[])
+            }
+            Opcode::TypedForeignCall {
+                function,
+                destinations,
+                inputs,
+                destination_types,
+                input_types,
+            } => {
+                let (function, destinations, inputs) =
+                    (function.clone(), destinations.clone(), inputs.clone());
+                let (destination_types, input_types) =
+                    (destination_types.clone(), input_types.clone());
+                self.process_foreign_call(
+                    function,
+                    &destinations,
+                    &inputs,
+                    &destination_types,
+                    &input_types,
+                )
+            }
+            Opcode::Trap => self.fail("explicit trap hit in brillig".to_string()),
+            Opcode::FrameCall { location, arguments, results } => {
+                let callee_registers = Registers::load(
+                    arguments.iter().map(|argument| self.registers.get(*argument)).collect(),
+                );
+                let callee_register_types =
+                    self.register_types.as_ref().map(|types| types.for_frame(arguments));
+
+                let caller = CallerState {
+                    registers: std::mem::replace(&mut self.registers, callee_registers),
+                    register_types: std::mem::replace(
+                        &mut self.register_types,
+                        callee_register_types,
+                    ),
+                    results: results.clone(),
+                };
+                self.call_stack
+                    .push(CallFrame { call_site: self.program_counter, caller: Some(caller) });
                 self.set_program_counter(*location)
             }
-            Opcode::Const { destination, value } => {
-                self.registers.set(*destination, *value);
-                self.increment_program_counter()
+            Opcode::Alloc { destination, size } => {
+                // Sizes which do not fit in a `usize` are rejected by the allocator like any other excessive size.
+                let size = self
+                    .registers
+                    .get(*size)
+                    .to_field()
+                    .try_to_u64()
+                    .and_then(|size| usize::try_from(size).ok())
+                    .unwrap_or(usize::MAX);
+                match self.memory.alloc(size) {
+                    Ok(pointer) => {
+                        self.registers.set(*destination, F::from(pointer as u128).into());
+                        self.increment_program_counter()
+                    }
+                    Err(error) => self.fail(error.to_string()),
+                }
+            }
+            Opcode::Free { pointer } => {
+                let pointer = self.registers.get(*pointer).to_usize();
+                match self.memory.free(pointer) {
+                    Ok(()) => self.increment_program_counter(),
+                    Err(error) => self.fail(error.to_string()),
+                }
             }
             Opcode::BlackBox(black_box_op) => {
                 match evaluate_black_box(
@@ -303,6 +425,17 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
                     Err(e) => self.fail(e.to_string()),
                 }
             }
+            Opcode::BinaryFieldOp { .. }
+            | Opcode::Jump { .. }
+            | Opcode::JumpIf { .. }
+            | Opcode::JumpIfNot { .. }
+            | Opcode::Call { .. }
+            | Opcode::Return
+            | Opcode::Mov { .. }
+            | Opcode::Const { .. }
+            | Opcode::Load { .. }
+            | Opcode::Store { .. }
+            | Opcode::Stop => unreachable!("{opcode:?} is decoded into a dedicated instruction"),
         }
     }
 
@@ -312,14 +445,14 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
     }
 
     /// Increments the program counter by 1.
-    fn increment_program_counter(&mut self) -> VMStatus {
+    fn increment_program_counter(&mut self) -> VMStatus<F> {
         self.set_program_counter(self.program_counter + 1)
     }
 
     /// Increments the program counter by `value`.
     /// If the program counter no longer points to an opcode
     /// in the bytecode, then the VMStatus reports halted.
-    fn set_program_counter(&mut self, value: usize) -> VMStatus {
+    fn set_program_counter(&mut self, value: usize) -> VMStatus<F> {
         assert!(self.program_counter < self.bytecode.len());
         self.program_counter = value;
         if self.program_counter >= self.bytecode.len() {
@@ -328,14 +461,154 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         self.status.clone()
     }
 
-    fn get_register_value_or_memory_values(&self, input: RegisterOrMemory) -> Vec<Value> {
+    /// Restores the registers of the caller of an [`Opcode::FrameCall`] which has returned,
+    /// copying the callee's results into them.
+    fn return_to_caller(&mut self, caller: CallerState<F>) {
+        let callee_registers = std::mem::replace(&mut self.registers, caller.registers);
+        let callee_register_types =
+            std::mem::replace(&mut self.register_types, caller.register_types);
+
+        for (index, result) in caller.results.into_iter().enumerate() {
+            let callee_register = RegisterIndex::from(index);
+            self.registers.set(result, callee_registers.get(callee_register));
+            if let (Some(types), Some(callee_types)) =
+                (&mut self.register_types, &callee_register_types)
+            {
+                types.apply(vec![(result, callee_types.get(callee_register))]);
+            }
+        }
+    }
+
+    /// Process a foreign call, either pausing the VM until its results are provided or writing them to `destinations`.
+    /// This method will modify the program counter once the results have been written.
+    fn process_foreign_call(
+        &mut self,
+        function: String,
+        destinations: &[RegisterOrMemory],
+        inputs: &[RegisterOrMemory],
+        destination_types: &[Option<AbiType>],
+        input_types: &[Option<AbiType>],
+    ) -> VMStatus<F> {
+        if self.foreign_call_counter >= self.foreign_call_results.len() {
+            // When this opcode is called, it is possible that the results of a foreign call are
+            // not yet known (not enough entries in `foreign_call_results`).
+            // If that is the case, just resolve the inputs and pause the VM with a status
+            // (VMStatus::ForeignCallWait) that communicates the foreign function name and
+            // resolved inputs back to the caller. Once the caller pushes to `foreign_call_results`,
+            // they can then make another call to the VM that starts at this opcode
+            // but has the necessary results to proceed with execution.
+            let resolved_inputs = inputs
+                .iter()
+                .map(|input| self.get_foreign_call_param(*input))
+                .collect::<Result<Vec<_>, _>>();
+            return match resolved_inputs {
+                Ok(resolved_inputs) => self.wait_for_foreign_call(
+                    function,
+                    resolved_inputs,
+                    pad_types(input_types, inputs.len()),
+                    pad_types(destination_types, destinations.len()),
+                ),
+                Err(error) => self.fail(error.to_string()),
+            };
+        }
+
+        let values = &self.foreign_call_results[self.foreign_call_counter].values;
+
+        let mut invalid_foreign_call_result = false;
+        let mut memory_error = None;
+        for (destination, output) in destinations.iter().zip(values) {
+            match destination {
+                RegisterOrMemory::RegisterIndex(value_index) => match output {
+                    ForeignCallOutput::Single(value) => self.registers.set(*value_index, *value),
+                    _ => unreachable!(
+                        "Function result size does not match brillig bytecode (expected 1 result)"
+                    ),
+                },
+                RegisterOrMemory::HeapArray(HeapArray { pointer: pointer_index, size }) => {
+                    match output {
+                        ForeignCallOutput::Array(values) => {
+                            if values.len() != *size {
+                                invalid_foreign_call_result = true;
+                                break;
+                            }
+                            // Convert the destination pointer to a usize
+                            let destination = self.registers.get(*pointer_index).to_usize();
+                            // Write to our destination memory
+                            if let Err(error) = self.memory.write_slice(destination, values) {
+                                memory_error = Some(error);
+                                break;
+                            }
+                        }
+                        ForeignCallOutput::Vector(_) => {
+                            invalid_foreign_call_result = true;
+                            break;
+                        }
+                        _ => {
+                            unreachable!(
+                                "Function result size does not match brillig bytecode size"
+                            )
+                        }
+                    }
+                }
+                RegisterOrMemory::HeapVector(HeapVector {
+                    pointer: pointer_index,
+                    size: size_index,
+                }) => {
+                    match output {
+                        // Arrays are accepted for vector destinations for compatibility with callers which do not distinguish them.
+                        ForeignCallOutput::Array(values) | ForeignCallOutput::Vector(values) => {
+                            // Set our size in the size register
+                            self.registers.set(*size_index, F::from(values.len() as u128).into());
+                            // Convert the destination pointer to a usize
+                            let destination = self.registers.get(*pointer_index).to_usize();
+                            // Write to our destination memory
+                            if let Err(error) = self.memory.write_slice(destination, values) {
+                                memory_error = Some(error);
+                                break;
+                            }
+                        }
+                        _ => {
+                            unreachable!(
+                                "Function result size does not match brillig bytecode size"
+                            )
+                        }
+                    }
+                }
+            }
+        }
+
+        // These checks must come after resolving the foreign call outputs as `fail` uses a mutable reference
+        if let Some(error) = memory_error {
+            return self.fail(error.to_string());
+        }
+        if destinations.len() != values.len() {
+            self.fail(format!(
+                "{} output values were provided as a foreign call result for {} destination slots",
+                values.len(),
+                destinations.len()
+            ));
+        }
+        if invalid_foreign_call_result {
+            self.fail("Function result size does not match brillig bytecode".to_owned());
+        }
+
+        self.foreign_call_counter += 1;
+        self.increment_program_counter()
+    }
+
+    fn get_foreign_call_param(
+        &self,
+        input: RegisterOrMemory,
+    ) -> Result<ForeignCallParam<F>, MemoryError> {
         match input {
             RegisterOrMemory::RegisterIndex(value_index) => {
-                vec![self.registers.get(value_index)]
+                Ok(ForeignCallParam::Single(self.registers.get(value_index)))
             }
             RegisterOrMemory::HeapArray(HeapArray { pointer: pointer_index, size }) => {
                 let start = self.registers.get(pointer_index);
-                self.memory.read_slice(start.to_usize(), size).to_vec()
+                self.memory
+                    .read_slice(start.to_usize(), size)
+                    .map(|values| ForeignCallParam::Array(values.to_vec()))
             }
             RegisterOrMemory::HeapVector(HeapVector {
                 pointer: pointer_index,
@@ -343,7 +616,9 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
             }) => {
                 let start = self.registers.get(pointer_index);
                 let size = self.registers.get(size_index);
-                self.memory.read_slice(start.to_usize(), size.to_usize()).to_vec()
+                self.memory
+                    .read_slice(start.to_usize(), size.to_usize())
+                    .map(|values| ForeignCallParam::Vector(values.to_vec()))
             }
         }
     }
@@ -366,7 +641,7 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         self.registers.set(result, result_value.into())
     }
 
-    /// Process a binary operation.
+    /// Process a binary operation, failing if it divides by zero.
     /// This method will not modify the program counter.
     fn process_binary_int_op(
         &mut self,
@@ -375,62 +650,127 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         lhs: RegisterIndex,
         rhs: RegisterIndex,
         result: RegisterIndex,
-    ) {
+    ) -> Result<(), String> {
         let lhs_value = self.registers.get(lhs);
         let rhs_value = self.registers.get(rhs);
 
         // Convert to big integers
         let lhs_big = BigUint::from_bytes_be(&lhs_value.to_field().to_be_bytes());
         let rhs_big = BigUint::from_bytes_be(&rhs_value.to_field().to_be_bytes());
+        // The divisor is truncated to `bit_size` bits before dividing.
+        if is_division(op) && &rhs_big % (BigUint::from(1_u8) << bit_size) == BigUint::from(0_u8) {
+            return Err(DIVISION_BY_ZERO.to_string());
+        }
         let result_value = evaluate_binary_bigint_op(&op, lhs_big, rhs_big, bit_size);
         // Convert back to field element
-        self.registers
-            .set(result, FieldElement::from_be_bytes_reduce(&result_value.to_bytes_be()).into());
+        self.registers.set(result, F::from_be_bytes_reduce(&result_value.to_bytes_be()).into());
+        Ok(())
     }
-}
 
-pub(crate) struct DummyBlackBoxSolver;
+    /// Process a binary operation on integers of at most 128 bits, using native integers if the operands fit.
+    /// This method will not modify the program counter.
+    fn process_small_binary_int_op(
+        &mut self,
+        op: BinaryIntOp,
+        bit_size: u32,
+        lhs: RegisterIndex,
+        rhs: RegisterIndex,
+        result: RegisterIndex,
+    ) -> Result<(), String> {
+        let lhs_value = self.registers.get(lhs);
+        let rhs_value = self.registers.get(rhs);
 
-impl BlackBoxFunctionSolver for DummyBlackBoxSolver {
-    fn schnorr_verify(
-        &self,
-        _public_key_x: &FieldElement,
-        _public_key_y: &FieldElement,
-        _signature: &[u8],
-        _message: &[u8],
-    ) -> Result<bool, BlackBoxResolutionError> {
-        Ok(true)
+        match (to_small_integer(lhs_value, bit_size), to_small_integer(rhs_value, bit_size)) {
+            (Some(_), Some(0)) if is_division(op) => Err(DIVISION_BY_ZERO.to_string()),
+            (Some(lhs_value), Some(rhs_value)) => {
+                let result_value = evaluate_binary_u128_op(&op, lhs_value, rhs_value, bit_size);
+                self.registers.set(result, F::from(result_value).into());
+                Ok(())
+            }
+            _ => self.process_binary_int_op(op, bit_size, lhs, rhs, result),
+        }
     }
-    fn pedersen(
-        &self,
-        _inputs: &[FieldElement],
-        _domain_separator: u32,
-    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
-        Ok((2_u128.into(), 3_u128.into()))
+
+    /// Process a bitwise NOT operation on an integer of at most 128 bits, using native integers if the operand fits.
+    /// This method will not modify the program counter.
+    fn process_small_not(&mut self, bit_size: u32, source: RegisterIndex, result: RegisterIndex) {
+        match to_small_integer(self.registers.get(source), bit_size) {
+            Some(source_value) => {
+                let result_value = evaluate_u128_not(source_value, bit_size);
+                self.registers.set(result, F::from(result_value).into());
+            }
+            None => self.process_not(bit_size, source, result),
+        }
     }
-    fn fixed_base_scalar_mul(
-        &self,
-        _low: &FieldElement,
-        _high: &FieldElement,
-    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
-        Ok((4_u128.into(), 5_u128.into()))
+
+    /// Process a bitwise NOT operation.
+    /// This method will not modify the program counter.
+    fn process_not(&mut self, bit_size: u32, source: RegisterIndex, result: RegisterIndex) {
+        let source_value = self.registers.get(source);
+
+        let source_big = BigUint::from_bytes_be(&source_value.to_field().to_be_bytes());
+        let result_value = evaluate_bigint_not(source_big, bit_size);
+        self.registers.set(result, F::from_be_bytes_reduce(&result_value.to_bytes_be()).into());
     }
+
+    /// Process a cast to a `bit_size` size integer.
+    /// This method will not modify the program counter.
+    fn process_cast(
+        &mut self,
+        bit_size: u32,
+        sign_extend_from: Option<u32>,
+        source: RegisterIndex,
+        result: RegisterIndex,
+    ) {
+        let source_value = self.registers.get(source);
+
+        let source_big = BigUint::from_bytes_be(&source_value.to_field().to_be_bytes());
+        let result_value = evaluate_bigint_cast(source_big, bit_size, sign_extend_from);
+        self.registers.set(result, F::from_be_bytes_reduce(&result_value.to_bytes_be()).into());
+    }
+}
+
+const DIVISION_BY_ZERO: &str = "attempted to divide by zero";
+
+/// Returns whether `op` divides its left operand by its right operand, which must therefore be non-zero.
+fn is_division(op: BinaryIntOp) -> bool {
+    matches!(
+        op,
+        BinaryIntOp::UnsignedDiv
+            | BinaryIntOp::SignedDiv
+            | BinaryIntOp::UnsignedRem
+            | BinaryIntOp::SignedRem
+    )
+}
+
+/// Returns `value` as a native integer if it is less than `2^bit_size`, where `bit_size <= 128`.
+fn to_small_integer<F: AcirField>(value: Value<F>, bit_size: u32) -> Option<u128> {
+    value.to_field().try_into_u128().filter(|value| bit_size == 128 || value >> bit_size == 0)
+}
+
+/// Returns the types of `len` foreign call parameters, treating any parameters without a type as untyped.
+fn pad_types(types: &[Option<AbiType>], len: usize) -> Vec<Option<AbiType>> {
+    (0..len).map(|index| types.get(index).cloned().flatten()).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use acvm_blackbox_solver::testing::MockSolver;
+
+    /// Solver for tests which do not call black box functions that require a backend.
+    pub(crate) static SOLVER: MockSolver = MockSolver::new();
 
     #[test]
     fn add_single_step_smoke() {
         // Load values into registers and initialize the registers that
         // will be used during bytecode processing
-        let input_registers =
+        let input_registers: Registers =
             Registers::load(vec![Value::from(1u128), Value::from(2u128), Value::from(0u128)]);
 
         // Add opcode to add the value in register `0` and `1`
         // and place the output in register `2`
-        let opcode = Opcode::BinaryIntOp {
+        let opcode: Opcode = Opcode::BinaryIntOp {
             op: BinaryIntOp::Add,
             bit_size: 2,
             lhs: RegisterIndex::from(0),
@@ -439,7 +779,7 @@ mod tests {
         };
 
         // Start VM
-        let mut vm = VM::new(input_registers, vec![], vec![opcode], vec![], &DummyBlackBoxSolver);
+        let mut vm = VM::new(input_registers, vec![], vec![opcode], vec![], &SOLVER);
 
         // Process a single VM opcode
         //
@@ -456,9 +796,36 @@ mod tests {
         assert_eq!(output_value, Value::from(3u128))
     }
 
+    #[test]
+    fn int_op_wider_than_field_fails() {
+        let bit_size = FieldElement::max_num_bits() + 1;
+        let opcode: Opcode = Opcode::BinaryIntOp {
+            op: BinaryIntOp::Add,
+            bit_size,
+            lhs: RegisterIndex::from(0),
+            rhs: RegisterIndex::from(1),
+            destination: RegisterIndex::from(2),
+        };
+        let input_registers: Registers =
+            Registers::load(vec![Value::from(1u128), Value::from(2u128)]);
+        let mut vm = VM::new(input_registers, vec![], vec![opcode], vec![], &SOLVER);
+
+        let status = vm.process_opcode();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                message: format!(
+                    "integer bit size {bit_size} exceeds the {} bits of the field",
+                    FieldElement::max_num_bits()
+                ),
+                call_stack: vec![0]
+            }
+        );
+    }
+
     #[test]
     fn jmpif_opcode() {
-        let mut registers = vec![];
+        let mut registers: Vec<Value> = vec![];
         let mut opcodes = vec![];
 
         let lhs = {
@@ -482,8 +849,7 @@ mod tests {
         opcodes.push(Opcode::Jump { location: 2 });
         opcodes.push(Opcode::JumpIf { condition: RegisterIndex::from(2), location: 3 });
 
-        let mut vm =
-            VM::new(Registers::load(registers), vec![], opcodes, vec![], &DummyBlackBoxSolver);
+        let mut vm = VM::new(Registers::load(registers), vec![], opcodes, vec![], &SOLVER);
 
         let status = vm.process_opcode();
         assert_eq!(status, VMStatus::InProgress);
@@ -500,7 +866,7 @@ mod tests {
 
     #[test]
     fn jmpifnot_opcode() {
-        let input_registers =
+        let input_registers: Registers =
             Registers::load(vec![Value::from(1u128), Value::from(2u128), Value::from(0u128)]);
 
         let trap_opcode = Opcode::Trap;
@@ -529,7 +895,7 @@ mod tests {
             vec![],
             vec![jump_opcode, trap_opcode, not_equal_cmp_opcode, jump_if_not_opcode, add_opcode],
             vec![],
-            &DummyBlackBoxSolver,
+            &SOLVER,
         );
 
         let status = vm.process_opcode();
@@ -561,14 +927,13 @@ mod tests {
 
     #[test]
     fn mov_opcode() {
-        let input_registers =
+        let input_registers: Registers =
             Registers::load(vec![Value::from(1u128), Value::from(2u128), Value::from(3u128)]);
 
         let mov_opcode =
             Opcode::Mov { destination: RegisterIndex::from(2), source: RegisterIndex::from(0) };
 
-        let mut vm =
-            VM::new(input_registers, vec![], vec![mov_opcode], vec![], &DummyBlackBoxSolver);
+        let mut vm = VM::new(input_registers, vec![], vec![mov_opcode], vec![], &SOLVER);
 
         let status = vm.process_opcode();
         assert_eq!(status, VMStatus::Finished);
@@ -585,7 +950,7 @@ mod tests {
     #[test]
     fn cmp_binary_ops() {
         let bit_size = 32;
-        let input_registers = Registers::load(vec![
+        let input_registers: Registers = Registers::load(vec![
             Value::from(2u128),
             Value::from(2u128),
             Value::from(0u128),
@@ -630,7 +995,7 @@ mod tests {
             vec![],
             vec![equal_opcode, not_equal_opcode, less_than_opcode, less_than_equal_opcode],
             vec![],
-            &DummyBlackBoxSolver,
+            &SOLVER,
         );
 
         let status = vm.process_opcode();
@@ -657,6 +1022,239 @@ mod tests {
         let lte_value = vm.registers.get(RegisterIndex::from(2));
         assert_eq!(lte_value, Value::from(true));
     }
+
+    #[test]
+    fn cast_opcode() {
+        let input_registers: Registers = Registers::load(vec![Value::from(0x1ff_u128)]);
+
+        let truncate_opcode = Opcode::Cast {
+            destination: RegisterIndex::from(1),
+            source: RegisterIndex::from(0),
+            bit_size: 8,
+            sign_extend_from: None,
+        };
+        let zero_extend_opcode = Opcode::Cast {
+            destination: RegisterIndex::from(2),
+            source: RegisterIndex::from(1),
+            bit_size: 16,
+            sign_extend_from: None,
+        };
+        let sign_extend_opcode = Opcode::Cast {
+            destination: RegisterIndex::from(3),
+            source: RegisterIndex::from(1),
+            bit_size: 16,
+            sign_extend_from: Some(8),
+        };
+
+        let mut vm = VM::new(
+            input_registers,
+            vec![],
+            vec![truncate_opcode, zero_extend_opcode, sign_extend_opcode],
+            vec![],
+            &SOLVER,
+        );
+
+        let status = vm.process_opcodes();
+        assert_eq!(status, VMStatus::Finished);
+        assert_eq!(vm.registers.get(RegisterIndex::from(1)), Value::from(0xff_u128));
+        assert_eq!(vm.registers.get(RegisterIndex::from(2)), Value::from(0xff_u128));
+        assert_eq!(vm.registers.get(RegisterIndex::from(3)), Value::from(0xffff_u128));
+    }
+
+    #[test]
+    fn cast_is_unaffected_by_type_checking() {
+        let input_registers: Registers =
+            Registers::load(vec![Value::from(0xfe_u128), Value::from(0xfe_u128)]);
+
+        let cast_opcodes = vec![
+            Opcode::Cast {
+                destination: RegisterIndex::from(2),
+                source: RegisterIndex::from(0),
+                bit_size: 16,
+                sign_extend_from: Some(8),
+            },
+            Opcode::Cast {
+                destination: RegisterIndex::from(3),
+                source: RegisterIndex::from(1),
+                bit_size: 16,
+                sign_extend_from: None,
+            },
+        ];
+
+        let mut unchecked_vm =
+            VM::new(input_registers.clone(), vec![], cast_opcodes.clone(), vec![], &SOLVER);
+        let mut checked_vm = VM::new(input_registers, vec![], cast_opcodes, vec![], &SOLVER)
+            .with_type_checking(vec![Typ::Signed { bit_size: 8 }, Typ::Unsigned { bit_size: 8 }]);
+
+        for vm in [&mut unchecked_vm, &mut checked_vm] {
+            let status = vm.process_opcodes();
+            assert_eq!(status, VMStatus::Finished);
+            assert_eq!(vm.registers.get(RegisterIndex::from(2)), Value::from(0xfffe_u128));
+            assert_eq!(vm.registers.get(RegisterIndex::from(3)), Value::from(0xfe_u128));
+        }
+    }
+
+    #[test]
+    fn type_checking_rejects_sign_extending_unsigned_integers() {
+        let input_registers: Registers = Registers::load(vec![Value::from(0xfe_u128)]);
+
+        let cast_opcode = Opcode::Cast {
+            destination: RegisterIndex::from(1),
+            source: RegisterIndex::from(0),
+            bit_size: 16,
+            sign_extend_from: Some(8),
+        };
+
+        let mut vm = VM::new(input_registers, vec![], vec![cast_opcode], vec![], &SOLVER)
+            .with_type_checking(vec![Typ::Unsigned { bit_size: 8 }]);
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                message: "type mismatch: expected register 0 to hold i8 but it holds u8"
+                    .to_string(),
+                call_stack: vec![0],
+            }
+        );
+    }
+
+    #[test]
+    fn type_checking_reports_mismatched_operands() {
+        let input_registers: Registers =
+            Registers::load(vec![Value::from(1u128), Value::from(2u128)]);
+
+        let add_opcode = Opcode::BinaryIntOp {
+            destination: RegisterIndex::from(2),
+            op: BinaryIntOp::Add,
+            bit_size: 32,
+            lhs: RegisterIndex::from(0),
+            rhs: RegisterIndex::from(1),
+        };
+
+        let mut vm =
+            VM::new(input_registers.clone(), vec![], vec![add_opcode.clone()], vec![], &SOLVER)
+                .with_type_checking(vec![Typ::Field, Typ::Unsigned { bit_size: 32 }]);
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                message: "type mismatch: expected register 0 to hold u32 or i32 but it holds Field"
+                    .to_string(),
+                call_stack: vec![0],
+            }
+        );
+
+        let mut vm = VM::new(input_registers, vec![], vec![add_opcode], vec![], &SOLVER)
+            .with_type_checking(vec![Typ::Signed { bit_size: 32 }, Typ::Unsigned { bit_size: 32 }]);
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                message: "type mismatch: binary operation on i32 and u32".to_string(),
+                call_stack: vec![0],
+            }
+        );
+    }
+
+    #[test]
+    fn type_checking_tracks_result_types() {
+        let input_registers: Registers = Registers::load(vec![Value::from(5u128)]);
+
+        let opcodes = vec![
+            // Constants are untyped and so may be used with the u32 in register 0.
+            Opcode::Const { destination: RegisterIndex::from(1), value: Value::from(3u128) },
+            Opcode::BinaryIntOp {
+                destination: RegisterIndex::from(2),
+                op: BinaryIntOp::Sub,
+                bit_size: 32,
+                lhs: RegisterIndex::from(0),
+                rhs: RegisterIndex::from(1),
+            },
+            // Register 2 now holds a u32 so may not be used in a field operation.
+            Opcode::BinaryFieldOp {
+                destination: RegisterIndex::from(3),
+                op: BinaryFieldOp::Add,
+                lhs: RegisterIndex::from(2),
+                rhs: RegisterIndex::from(1),
+            },
+        ];
+
+        let mut vm = VM::new(input_registers, vec![], opcodes, vec![], &SOLVER)
+            .with_type_checking(vec![Typ::Unsigned { bit_size: 32 }]);
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                message: "type mismatch: expected register 2 to hold Field but it holds u32"
+                    .to_string(),
+                call_stack: vec![2],
+            }
+        );
+        assert_eq!(vm.registers.get(RegisterIndex::from(2)), Value::from(2u128));
+    }
+
+    #[test]
+    fn not_opcode() {
+        let bit_size = 8;
+        let input_registers: Registers =
+            Registers::load(vec![Value::from(0b0110_1001_u128), Value::from(0u128)]);
+
+        let not_opcode = Opcode::Not {
+            destination: RegisterIndex::from(1),
+            source: RegisterIndex::from(0),
+            bit_size,
+        };
+
+        let mut vm = VM::new(input_registers, vec![], vec![not_opcode], vec![], &SOLVER);
+
+        let status = vm.process_opcode();
+        assert_eq!(status, VMStatus::Finished);
+
+        let output_value = vm.registers.get(RegisterIndex::from(1));
+        assert_eq!(output_value, Value::from(0b1001_0110_u128));
+    }
+    #[test]
+    fn int_ops_on_out_of_range_operands() {
+        // Operands which do not fit within the bit size are not reduced before being operated on,
+        // so these can not be evaluated on native integers.
+        let input_registers: Registers = Registers::load(vec![
+            Value::from(300u128),
+            Value::from(1u128),
+            Value::from(-FieldElement::one()),
+        ]);
+        let bytecode = vec![
+            Opcode::BinaryIntOp {
+                destination: RegisterIndex::from(3),
+                op: BinaryIntOp::Add,
+                bit_size: 8,
+                lhs: RegisterIndex::from(0),
+                rhs: RegisterIndex::from(1),
+            },
+            Opcode::BinaryIntOp {
+                destination: RegisterIndex::from(4),
+                op: BinaryIntOp::Xor,
+                bit_size: 128,
+                lhs: RegisterIndex::from(2),
+                rhs: RegisterIndex::from(1),
+            },
+            Opcode::Not {
+                destination: RegisterIndex::from(5),
+                source: RegisterIndex::from(0),
+                bit_size: 8,
+            },
+        ];
+
+        let mut vm = VM::new(input_registers, vec![], bytecode, vec![], &SOLVER);
+        assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+
+        let registers = vm.get_registers();
+        assert_eq!(registers.get(RegisterIndex::from(3)), Value::from(45u128));
+        // The lowest 128 bits of p - 1 are kept.
+        let expected_xor = (-FieldElement::one()).to_u128() ^ 1;
+        assert_eq!(registers.get(RegisterIndex::from(4)), Value::from(expected_xor));
+        assert_eq!(registers.get(RegisterIndex::from(5)), Value::from(211u128));
+    }
+
     #[test]
     fn store_opcode() {
         /// Brillig code for the following:
@@ -873,6 +1471,187 @@ mod tests {
         assert_eq!(memory, expected);
     }
 
+    /// Brillig code for the following recursive function, where each call executes within a new frame:
+    ///     fn sum(n: u32) -> u32 {
+    ///         if n == 0 {
+    ///             return 0;
+    ///         }
+    ///         n + sum(n - 1)
+    ///     }
+    fn brillig_recursive_sum() -> Vec<Opcode> {
+        let int_op = |op, destination: usize, lhs: usize, rhs: usize| Opcode::BinaryIntOp {
+            destination: RegisterIndex::from(destination),
+            op,
+            bit_size: 32,
+            lhs: RegisterIndex::from(lhs),
+            rhs: RegisterIndex::from(rhs),
+        };
+        vec![
+            Opcode::FrameCall {
+                location: 2,
+                arguments: vec![RegisterIndex::from(0)],
+                results: vec![RegisterIndex::from(1)],
+            },
+            Opcode::Stop,
+            // sum(n), with n held in register 0
+            Opcode::Const { destination: RegisterIndex::from(1), value: Value::from(0u128) },
+            int_op(BinaryIntOp::Equals, 2, 0, 1),
+            Opcode::JumpIfNot { condition: RegisterIndex::from(2), location: 6 },
+            // Register 0 already holds the result of zero.
+            Opcode::Return,
+            Opcode::Const { destination: RegisterIndex::from(3), value: Value::from(1u128) },
+            int_op(BinaryIntOp::Sub, 4, 0, 3),
+            Opcode::FrameCall {
+                location: 2,
+                arguments: vec![RegisterIndex::from(4)],
+                results: vec![RegisterIndex::from(5)],
+            },
+            // Register 0 still holds n as the recursive call could not overwrite it.
+            int_op(BinaryIntOp::Add, 0, 0, 5),
+            Opcode::Return,
+        ]
+    }
+
+    #[test]
+    fn frame_call_opcode() {
+        let input_registers = Registers::load(vec![Value::from(10u128)]);
+        let mut vm = VM::new(input_registers, vec![], brillig_recursive_sum(), vec![], &SOLVER);
+
+        assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+        assert_eq!(vm.call_stack, vec![]);
+        assert_eq!(
+            vm.get_registers().inner,
+            vec![Value::from(10u128), Value::from(55u128)],
+            "only the result register of the caller should be written to"
+        );
+    }
+
+    #[test]
+    fn frame_call_restores_register_types() {
+        let input_registers = Registers::load(vec![Value::from(4u128)]);
+        let mut vm = VM::new(input_registers, vec![], brillig_recursive_sum(), vec![], &SOLVER)
+            .with_type_checking(vec![Typ::Unsigned { bit_size: 32 }]);
+
+        assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+        let register_types = vm.register_types.as_ref().unwrap();
+        assert_eq!(
+            register_types.get(RegisterIndex::from(1)),
+            Some(Typ::Unsigned { bit_size: 32 })
+        );
+        assert_eq!(register_types.get(RegisterIndex::from(2)), None);
+    }
+
+    #[test]
+    fn alloc_and_free_opcodes() {
+        let r_size = RegisterIndex::from(0);
+        let r_pointer = RegisterIndex::from(1);
+        let r_value = RegisterIndex::from(2);
+        let r_second_pointer = RegisterIndex::from(3);
+        let bytecode: Vec<Opcode> = vec![
+            Opcode::Alloc { destination: r_pointer, size: r_size },
+            Opcode::Const { destination: r_value, value: Value::from(7u128) },
+            Opcode::Store { destination_pointer: r_pointer, source: r_value },
+            Opcode::Load { destination: r_value, source_pointer: r_pointer },
+            Opcode::Free { pointer: r_pointer },
+            // The freed block is reused.
+            Opcode::Alloc { destination: r_second_pointer, size: r_size },
+            Opcode::Free { pointer: r_second_pointer },
+            // Both pointers point to the same block, which has already been freed.
+            Opcode::Free { pointer: r_pointer },
+        ];
+        let input_registers = Registers::load(vec![Value::from(2u128)]);
+        // The heap starts after the input memory.
+        let memory = vec![Value::from(1u128)];
+        let mut vm = VM::new(input_registers, memory, bytecode, vec![], &SOLVER);
+
+        let status = vm.process_opcodes();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                message: "attempted to free address 1 which is not an allocated block".to_string(),
+                call_stack: vec![7],
+            }
+        );
+        let registers = vm.get_registers();
+        assert_eq!(registers.get(r_pointer), Value::from(1u128));
+        assert_eq!(registers.get(r_value), Value::from(7u128));
+        assert_eq!(registers.get(r_second_pointer), Value::from(1u128));
+    }
+
+    #[test]
+    fn division_by_zero_fails() {
+        // Both the native and the arbitrary precision paths must fail rather than panic.
+        for bit_size in [32, 254] {
+            for op in [
+                BinaryIntOp::UnsignedDiv,
+                BinaryIntOp::SignedDiv,
+                BinaryIntOp::UnsignedRem,
+                BinaryIntOp::SignedRem,
+            ] {
+                let opcode = Opcode::BinaryIntOp {
+                    op,
+                    bit_size,
+                    lhs: RegisterIndex::from(0),
+                    rhs: RegisterIndex::from(1),
+                    destination: RegisterIndex::from(2),
+                };
+                let input_registers = Registers::load(vec![Value::from(7u128), Value::from(0u128)]);
+                let mut vm = VM::new(input_registers, vec![], vec![opcode], vec![], &SOLVER);
+
+                assert_eq!(
+                    vm.process_opcodes(),
+                    VMStatus::Failure {
+                        message: "attempted to divide by zero".to_string(),
+                        call_stack: vec![0],
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn alloc_of_excessive_size_fails() {
+        let r_size = RegisterIndex::from(0);
+        let bytecode: Vec<Opcode> =
+            vec![Opcode::Alloc { destination: RegisterIndex::from(1), size: r_size }];
+        // The size does not fit in a `usize`.
+        let input_registers = Registers::load(vec![Value::from(-FieldElement::one())]);
+        let mut vm = VM::new(input_registers, vec![], bytecode, vec![], &SOLVER);
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                message: format!(
+                    "attempted to allocate {} slots which would exceed the maximum memory size of 16777216",
+                    usize::MAX
+                ),
+                call_stack: vec![0],
+            }
+        );
+    }
+
+    #[test]
+    fn load_of_uninitialized_memory_fails() {
+        let r_pointer = RegisterIndex::from(0);
+        let r_value = RegisterIndex::from(1);
+        let bytecode = vec![
+            Opcode::Alloc { destination: r_pointer, size: r_pointer },
+            Opcode::Call { location: 3 },
+            Opcode::Stop,
+            Opcode::Load { destination: r_value, source_pointer: r_pointer },
+            Opcode::Return,
+        ];
+        let mut vm = VM::new(empty_registers(), vec![], bytecode, vec![], &SOLVER);
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                message: "read of uninitialized memory at address 0".to_string(),
+                call_stack: vec![1, 3],
+            }
+        );
+    }
+
     fn empty_registers() -> Registers {
         Registers::load(vec![Value::from(0u128); 16])
     }
@@ -880,14 +1659,14 @@ mod tests {
     fn brillig_execute_and_get_vm(
         memory: Vec<Value>,
         opcodes: Vec<Opcode>,
-    ) -> VM<'static, DummyBlackBoxSolver> {
-        let mut vm = VM::new(empty_registers(), memory, opcodes, vec![], &DummyBlackBoxSolver);
+    ) -> VM<'static, MockSolver> {
+        let mut vm = VM::new(empty_registers(), memory, opcodes, vec![], &SOLVER);
         brillig_execute(&mut vm);
         assert_eq!(vm.call_stack, vec![]);
         vm
     }
 
-    fn brillig_execute(vm: &mut VM<DummyBlackBoxSolver>) {
+    fn brillig_execute(vm: &mut VM<MockSolver>) {
         loop {
             let status = vm.process_opcode();
             if matches!(status, VMStatus::Finished | VMStatus::ForeignCallWait { .. }) {
@@ -920,7 +1699,9 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "double".into(),
-                inputs: vec![vec![Value::from(5u128)]]
+                inputs: vec![ForeignCallParam::Single(Value::from(5u128))],
+                input_types: vec![None],
+                destination_types: vec![None],
             }
         );
 
@@ -981,7 +1762,9 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "matrix_2x2_transpose".into(),
-                inputs: vec![initial_matrix]
+                inputs: vec![ForeignCallParam::Array(initial_matrix)],
+                input_types: vec![None],
+                destination_types: vec![None],
             }
         );
 
@@ -995,7 +1778,7 @@ mod tests {
         assert_eq!(vm.status, VMStatus::Finished);
 
         // Check result in memory
-        let result_values = vm.memory.read_slice(0, 4).to_vec();
+        let result_values = vm.memory.read_slice(0, 4).unwrap().to_vec();
         assert_eq!(result_values, expected_result);
 
         // Ensure the foreign call counter has been incremented
@@ -1054,7 +1837,9 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "string_double".into(),
-                inputs: vec![input_string.clone()]
+                inputs: vec![ForeignCallParam::Vector(input_string.clone())],
+                input_types: vec![None],
+                destination_types: vec![None],
             }
         );
 
@@ -1070,7 +1855,8 @@ mod tests {
         assert_eq!(vm.status, VMStatus::Finished);
 
         // Check result in memory
-        let result_values = vm.memory.read_slice(input_string.len(), output_string.len()).to_vec();
+        let result_values =
+            vm.memory.read_slice(input_string.len(), output_string.len()).unwrap().to_vec();
         assert_eq!(result_values, output_string);
 
         // Ensure the foreign call counter has been incremented
@@ -1116,7 +1902,9 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "matrix_2x2_transpose".into(),
-                inputs: vec![initial_matrix.clone()]
+                inputs: vec![ForeignCallParam::Array(initial_matrix.clone())],
+                input_types: vec![None],
+                destination_types: vec![None],
             }
         );
 
@@ -1130,11 +1918,11 @@ mod tests {
         assert_eq!(vm.status, VMStatus::Finished);
 
         // Check initial memory still in place
-        let initial_values = vm.memory.read_slice(0, 4).to_vec();
+        let initial_values = vm.memory.read_slice(0, 4).unwrap().to_vec();
         assert_eq!(initial_values, initial_matrix);
 
         // Check result in memory
-        let result_values = vm.memory.read_slice(4, 4).to_vec();
+        let result_values = vm.memory.read_slice(4, 4).unwrap().to_vec();
         assert_eq!(result_values, expected_result);
 
         // Ensure the foreign call counter has been incremented
@@ -1201,7 +1989,9 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "matrix_2x2_transpose".into(),
-                inputs: vec![matrix_a, matrix_b]
+                inputs: vec![ForeignCallParam::Array(matrix_a), ForeignCallParam::Array(matrix_b)],
+                input_types: vec![None, None],
+                destination_types: vec![None],
             }
         );
 
@@ -1215,10 +2005,64 @@ mod tests {
         assert_eq!(vm.status, VMStatus::Finished);
 
         // Check result in memory
-        let result_values = vm.memory.read_slice(0, 4).to_vec();
+        let result_values = vm.memory.read_slice(0, 4).unwrap().to_vec();
         assert_eq!(result_values, expected_result);
 
         // Ensure the foreign call counter has been incremented
         assert_eq!(vm.foreign_call_counter, 1);
     }
+
+    #[test]
+    fn foreign_call_opcode_typed_parameters() {
+        let r_input = RegisterIndex::from(0);
+        let r_output = RegisterIndex::from(1);
+        let point =
+            AbiType::Array { length: 2, element: Box::new(AbiType::Signed { bit_size: 8 }) };
+
+        let program = vec![
+            Opcode::Const { destination: r_input, value: Value::from(7u128) },
+            Opcode::Const { destination: r_output, value: Value::from(0u128) },
+            Opcode::TypedForeignCall {
+                function: "get_point".into(),
+                destinations: vec![RegisterOrMemory::HeapArray(HeapArray {
+                    pointer: r_output,
+                    size: 2,
+                })],
+                inputs: vec![
+                    RegisterOrMemory::RegisterIndex(r_input),
+                    RegisterOrMemory::RegisterIndex(r_input),
+                ],
+                destination_types: vec![Some(point.clone())],
+                // The second input is untyped as it has no corresponding entry.
+                input_types: vec![Some(AbiType::Unsigned { bit_size: 32 })],
+            },
+            Opcode::Const { destination: r_input, value: Value::from(0u128) },
+        ];
+
+        let mut vm = brillig_execute_and_get_vm(vec![], program);
+        assert_eq!(
+            vm.status,
+            VMStatus::ForeignCallWait {
+                function: "get_point".into(),
+                inputs: vec![
+                    ForeignCallParam::Single(Value::from(7u128)),
+                    ForeignCallParam::Single(Value::from(7u128)),
+                ],
+                input_types: vec![Some(AbiType::Unsigned { bit_size: 32 }), None],
+                destination_types: vec![Some(point)],
+            }
+        );
+
+        // Vectors cannot be written to fixed length arrays.
+        vm.foreign_call_results.push(ForeignCallResult {
+            values: vec![ForeignCallOutput::Vector(vec![Value::from(1u128), Value::from(2u128)])],
+        });
+        assert_eq!(
+            vm.process_opcode(),
+            VMStatus::Failure {
+                message: "Function result size does not match brillig bytecode".to_owned(),
+                call_stack: vec![2]
+            }
+        );
+    }
 }
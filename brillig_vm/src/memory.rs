@@ -1,45 +1,244 @@
+use std::collections::BTreeMap;
+
+use acir::{AcirField, FieldElement};
+
 use crate::Value;
 
+/// The number of slots to which the heap may grow, so that excessive allocations fail rather than exhaust the host's memory.
+const MAX_MEMORY_SIZE: usize = 2_usize.pow(24);
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Memory {
+pub struct Memory<F = FieldElement> {
     // Memory is a vector of values.
-    // We grow the memory when values past the end are set, extending with 0s.
-    inner: Vec<Value>,
+    // We grow the memory when values past the end are set, extending with uninitialized 0s.
+    inner: Vec<Value<F>>,
+    /// Whether each slot of memory has been written to since it was last allocated.
+    initialized: Vec<bool>,
+    /// The sizes of the heap blocks which are currently allocated, keyed by their first slot.
+    allocations: BTreeMap<usize, usize>,
+    /// The sizes of the heap blocks which have been freed and may be reused, keyed by their first slot.
+    free_blocks: BTreeMap<usize, usize>,
+}
+
+/// An invalid access to the VM's [`Memory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryError {
+    /// A slot of memory was read before being written to.
+    UninitializedRead { address: usize },
+    /// A slot of memory belonging to a heap block which has been freed was accessed.
+    UseAfterFree { address: usize },
+    /// A pointer which does not point to an allocated heap block was freed.
+    InvalidFree { pointer: usize },
+    /// A heap block was allocated which would grow the memory past its maximum size.
+    AllocationTooLarge { size: usize },
+}
+
+impl std::fmt::Display for MemoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryError::UninitializedRead { address } => {
+                write!(f, "read of uninitialized memory at address {address}")
+            }
+            MemoryError::UseAfterFree { address } => {
+                write!(f, "access to freed memory at address {address}")
+            }
+            MemoryError::InvalidFree { pointer } => {
+                write!(f, "attempted to free address {pointer} which is not an allocated block")
+            }
+            MemoryError::AllocationTooLarge { size } => write!(
+                f,
+                "attempted to allocate {size} slots which would exceed the maximum memory size of {MAX_MEMORY_SIZE}"
+            ),
+        }
+    }
 }
 
-impl From<Vec<Value>> for Memory {
-    fn from(values: Vec<Value>) -> Self {
-        Memory { inner: values }
+impl<F> From<Vec<Value<F>>> for Memory<F> {
+    fn from(values: Vec<Value<F>>) -> Self {
+        let initialized = vec![true; values.len()];
+        Memory {
+            inner: values,
+            initialized,
+            allocations: BTreeMap::new(),
+            free_blocks: BTreeMap::new(),
+        }
     }
 }
 
-impl Memory {
+impl<F: AcirField> Memory<F> {
     /// Gets the value at pointer
-    pub fn read(&self, ptr: usize) -> Value {
-        self.inner[ptr]
+    pub fn read(&self, ptr: usize) -> Result<Value<F>, MemoryError> {
+        self.read_slice(ptr, 1).map(|values| values[0])
     }
 
-    pub fn read_slice(&self, ptr: usize, len: usize) -> &[Value] {
-        &self.inner[ptr..ptr + len]
+    pub fn read_slice(&self, ptr: usize, len: usize) -> Result<&[Value<F>], MemoryError> {
+        if len == 0 {
+            return Ok(&[]);
+        }
+        self.check_not_freed(ptr, len)?;
+        if let Some(address) =
+            (ptr..ptr + len).find(|address| !self.initialized.get(*address).unwrap_or(&false))
+        {
+            return Err(MemoryError::UninitializedRead { address });
+        }
+        Ok(&self.inner[ptr..ptr + len])
     }
 
     /// Sets the value at pointer `ptr` to `value`
-    pub fn write(&mut self, ptr: usize, value: Value) {
-        self.write_slice(ptr, &[value]);
+    pub fn write(&mut self, ptr: usize, value: Value<F>) -> Result<(), MemoryError> {
+        self.write_slice(ptr, &[value])
     }
 
     /// Sets the values after pointer `ptr` to `values`
-    pub fn write_slice(&mut self, ptr: usize, values: &[Value]) {
+    pub fn write_slice(&mut self, ptr: usize, values: &[Value<F>]) -> Result<(), MemoryError> {
+        self.check_not_freed(ptr, values.len())?;
         // Calculate new memory size
         let new_size = std::cmp::max(self.inner.len(), ptr + values.len());
         // Expand memory to new size with default values if needed
-        self.inner.resize(new_size, Value::from(0_usize));
+        self.resize(new_size);
 
         self.inner[ptr..ptr + values.len()].copy_from_slice(values);
+        self.initialized[ptr..ptr + values.len()].fill(true);
+        Ok(())
+    }
+
+    /// Allocates a block of `size` uninitialized slots on the heap, returning a pointer to its first slot.
+    ///
+    /// The first freed block which is large enough is reused, otherwise the block is placed after the end of memory.
+    /// Empty blocks are given a single slot so that every allocated block has a distinct pointer.
+    pub fn alloc(&mut self, size: usize) -> Result<usize, MemoryError> {
+        let size = size.max(1);
+        let reused_block = self
+            .free_blocks
+            .iter()
+            .find(|(_, free_size)| **free_size >= size)
+            .map(|(pointer, free_size)| (*pointer, *free_size));
+
+        let pointer = match reused_block {
+            Some((pointer, free_size)) => {
+                self.free_blocks.remove(&pointer);
+                if free_size > size {
+                    self.free_blocks.insert(pointer + size, free_size - size);
+                }
+                pointer
+            }
+            None => {
+                let pointer = self.inner.len();
+                match pointer.checked_add(size) {
+                    Some(end) if end <= MAX_MEMORY_SIZE => self.resize(end),
+                    _ => return Err(MemoryError::AllocationTooLarge { size }),
+                }
+                pointer
+            }
+        };
+        self.allocations.insert(pointer, size);
+        Ok(pointer)
+    }
+
+    /// Frees the heap block pointed to by `pointer` so that its slots may be reused by later allocations.
+    pub fn free(&mut self, pointer: usize) -> Result<(), MemoryError> {
+        let size = self.allocations.remove(&pointer).ok_or(MemoryError::InvalidFree { pointer })?;
+        self.initialized[pointer..pointer + size].fill(false);
+
+        // Merge the block with any adjacent free blocks.
+        let mut start = pointer;
+        let mut end = pointer + size;
+        if let Some(next_size) = self.free_blocks.remove(&end) {
+            end += next_size;
+        }
+        if let Some((previous, previous_size)) =
+            self.free_blocks.range(..start).next_back().map(|(p, s)| (*p, *s))
+        {
+            if previous + previous_size == start {
+                self.free_blocks.remove(&previous);
+                start = previous;
+            }
+        }
+        self.free_blocks.insert(start, end - start);
+        Ok(())
     }
 
     /// Returns the values of the memory
-    pub fn values(&self) -> &Vec<Value> {
+    pub fn values(&self) -> &Vec<Value<F>> {
         &self.inner
     }
+
+    /// Grows the memory to `new_size` slots, with the new slots being uninitialized.
+    fn resize(&mut self, new_size: usize) {
+        self.inner.resize(new_size, F::zero().into());
+        self.initialized.resize(new_size, false);
+    }
+
+    /// Checks that none of the `len` slots starting at `ptr` belong to a freed heap block.
+    fn check_not_freed(&self, ptr: usize, len: usize) -> Result<(), MemoryError> {
+        if len == 0 || self.free_blocks.is_empty() {
+            return Ok(());
+        }
+        // Only the last free block starting before the end of the range can overlap it, as free blocks are disjoint.
+        match self.free_blocks.range(..ptr + len).next_back() {
+            Some((block, size)) if block + size > ptr => {
+                Err(MemoryError::UseAfterFree { address: ptr.max(*block) })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::brillig::Value;
+
+    use super::{Memory, MemoryError, MAX_MEMORY_SIZE};
+
+    #[test]
+    fn reports_reads_of_uninitialized_memory() {
+        let mut memory: Memory = Memory::from(vec![Value::from(1u128)]);
+        memory.write(3, Value::from(2u128)).unwrap();
+
+        assert_eq!(memory.read(0), Ok(Value::from(1u128)));
+        assert_eq!(memory.read(3), Ok(Value::from(2u128)));
+        // The gap left by writing past the end of memory is uninitialized.
+        assert_eq!(memory.read_slice(0, 4), Err(MemoryError::UninitializedRead { address: 1 }));
+        assert_eq!(memory.read(4), Err(MemoryError::UninitializedRead { address: 4 }));
+    }
+
+    #[test]
+    fn reuses_freed_blocks() {
+        let mut memory: Memory = Memory::from(vec![Value::from(1u128); 2]);
+
+        let first = memory.alloc(3).unwrap();
+        let second = memory.alloc(2).unwrap();
+        assert_eq!((first, second), (2, 5));
+        assert_eq!(memory.read(first), Err(MemoryError::UninitializedRead { address: 2 }));
+
+        memory.write(second, Value::from(7u128)).unwrap();
+        memory.free(second).unwrap();
+        memory.free(first).unwrap();
+        assert_eq!(memory.read(second), Err(MemoryError::UseAfterFree { address: 5 }));
+        assert_eq!(
+            memory.write(first + 1, Value::from(7u128)),
+            Err(MemoryError::UseAfterFree { address: 3 })
+        );
+        assert_eq!(memory.free(second), Err(MemoryError::InvalidFree { pointer: 5 }));
+
+        // The freed blocks were merged, so a block spanning both can be allocated.
+        assert_eq!(memory.alloc(4), Ok(first));
+        assert_eq!(memory.alloc(1), Ok(6));
+        assert_eq!(memory.alloc(1), Ok(7));
+    }
+
+    #[test]
+    fn rejects_allocations_past_maximum_size() {
+        let mut memory: Memory = Memory::from(vec![Value::from(1u128)]);
+
+        assert_eq!(
+            memory.alloc(usize::MAX),
+            Err(MemoryError::AllocationTooLarge { size: usize::MAX })
+        );
+        // The heap starts after the existing slot of memory.
+        assert_eq!(
+            memory.alloc(MAX_MEMORY_SIZE),
+            Err(MemoryError::AllocationTooLarge { size: MAX_MEMORY_SIZE })
+        );
+    }
 }
@@ -0,0 +1,242 @@
+use acir::brillig::{BinaryIntOp, BlackBoxOp, Opcode, RegisterIndex, RegisterOrMemory, Typ};
+
+/// The type an opcode requires one of its operands to have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExpectedType {
+    Field,
+    Integer { bit_size: u32 },
+    Unsigned { bit_size: u32 },
+    Signed { bit_size: u32 },
+}
+
+impl ExpectedType {
+    fn accepts(&self, typ: Typ) -> bool {
+        match (self, typ) {
+            (ExpectedType::Field, Typ::Field) => true,
+            (ExpectedType::Integer { bit_size }, Typ::Unsigned { bit_size: found })
+            | (ExpectedType::Integer { bit_size }, Typ::Signed { bit_size: found })
+            | (ExpectedType::Unsigned { bit_size }, Typ::Unsigned { bit_size: found })
+            | (ExpectedType::Signed { bit_size }, Typ::Signed { bit_size: found }) => {
+                *bit_size == found
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ExpectedType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedType::Field => write!(f, "Field"),
+            ExpectedType::Integer { bit_size } => write!(f, "u{bit_size} or i{bit_size}"),
+            ExpectedType::Unsigned { bit_size } => write!(f, "u{bit_size}"),
+            ExpectedType::Signed { bit_size } => write!(f, "i{bit_size}"),
+        }
+    }
+}
+
+/// A type error detected while executing Brillig bytecode in type-checked mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TypeError {
+    /// An operand does not have the type required by the opcode.
+    UnexpectedType { register: RegisterIndex, expected: ExpectedType, found: Typ },
+    /// The two operands of a binary operation have different types.
+    OperandMismatch { lhs: Typ, rhs: Typ },
+}
+
+impl std::fmt::Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeError::UnexpectedType { register, expected, found } => write!(
+                f,
+                "type mismatch: expected register {} to hold {expected} but it holds {found}",
+                register.to_usize()
+            ),
+            TypeError::OperandMismatch { lhs, rhs } => {
+                write!(f, "type mismatch: binary operation on {lhs} and {rhs}")
+            }
+        }
+    }
+}
+
+/// Tracks the [type][Typ] of the value held in each register.
+///
+/// Registers whose type cannot be determined, such as those written by [`Opcode::Const`] or
+/// [`Opcode::Load`], are untyped and accepted by any opcode.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct RegisterTypes {
+    inner: Vec<Option<Typ>>,
+}
+
+impl RegisterTypes {
+    pub(crate) fn load(types: Vec<Typ>) -> Self {
+        Self { inner: types.into_iter().map(Some).collect() }
+    }
+
+    /// Returns the types of the registers of a new frame into which the values of the `arguments` registers are loaded.
+    pub(crate) fn for_frame(&self, arguments: &[RegisterIndex]) -> Self {
+        Self { inner: arguments.iter().map(|argument| self.get(*argument)).collect() }
+    }
+
+    /// Gets the type of the value in register `index`, if known.
+    pub(crate) fn get(&self, RegisterIndex(index): RegisterIndex) -> Option<Typ> {
+        self.inner.get(index).copied().flatten()
+    }
+
+    fn set(&mut self, RegisterIndex(index): RegisterIndex, typ: Option<Typ>) {
+        if index >= self.inner.len() {
+            self.inner.resize(index + 1, None);
+        }
+        self.inner[index] = typ;
+    }
+
+    /// Records the types of the registers written by an executed opcode, as returned by [`Self::check_opcode`].
+    pub(crate) fn apply(&mut self, updates: Vec<(RegisterIndex, Option<Typ>)>) {
+        for (register, typ) in updates {
+            self.set(register, typ);
+        }
+    }
+
+    /// Checks that the operands of `opcode` have the types it requires.
+    ///
+    /// Returns the types of the registers which `opcode` writes to, which should be [applied][Self::apply]
+    /// once it has executed.
+    pub(crate) fn check_opcode<F>(
+        &self,
+        opcode: &Opcode<F>,
+    ) -> Result<Vec<(RegisterIndex, Option<Typ>)>, TypeError> {
+        let updates = match opcode {
+            Opcode::BinaryFieldOp { destination, lhs, rhs, .. } => {
+                self.expect(*lhs, ExpectedType::Field)?;
+                self.expect(*rhs, ExpectedType::Field)?;
+                vec![(*destination, Some(Typ::Field))]
+            }
+            Opcode::BinaryIntOp { destination, op, bit_size, lhs, rhs } => {
+                let bit_size = *bit_size;
+                let expected = match op {
+                    BinaryIntOp::SignedDiv
+                    | BinaryIntOp::SignedLessThan
+                    | BinaryIntOp::SignedLessThanEquals
+                    | BinaryIntOp::SignedRem
+                    | BinaryIntOp::SignedShr => ExpectedType::Signed { bit_size },
+                    BinaryIntOp::UnsignedDiv
+                    | BinaryIntOp::UnsignedRem
+                    | BinaryIntOp::LessThan
+                    | BinaryIntOp::LessThanEquals => ExpectedType::Unsigned { bit_size },
+                    BinaryIntOp::Add
+                    | BinaryIntOp::Sub
+                    | BinaryIntOp::Mul
+                    | BinaryIntOp::Equals
+                    | BinaryIntOp::And
+                    | BinaryIntOp::Or
+                    | BinaryIntOp::Xor
+                    | BinaryIntOp::Shl
+                    | BinaryIntOp::Shr => ExpectedType::Integer { bit_size },
+                };
+                let lhs_typ = self.expect(*lhs, expected)?;
+                let rhs_typ = self.expect(*rhs, expected)?;
+                let operand_typ = match (lhs_typ, rhs_typ) {
+                    (Some(lhs), Some(rhs)) if lhs != rhs => {
+                        return Err(TypeError::OperandMismatch { lhs, rhs })
+                    }
+                    (Some(typ), _) | (None, Some(typ)) => typ,
+                    (None, None) if expected == ExpectedType::Signed { bit_size } => {
+                        Typ::Signed { bit_size }
+                    }
+                    (None, None) => Typ::Unsigned { bit_size },
+                };
+                let result_typ = match op {
+                    BinaryIntOp::Equals
+                    | BinaryIntOp::LessThan
+                    | BinaryIntOp::LessThanEquals
+                    | BinaryIntOp::SignedLessThan
+                    | BinaryIntOp::SignedLessThanEquals => Typ::Unsigned { bit_size: 1 },
+                    _ => operand_typ,
+                };
+                vec![(*destination, Some(result_typ))]
+            }
+            Opcode::Not { destination, source, bit_size } => {
+                let typ = self.expect(*source, ExpectedType::Integer { bit_size: *bit_size })?;
+                vec![(*destination, Some(typ.unwrap_or(Typ::Unsigned { bit_size: *bit_size })))]
+            }
+            Opcode::Cast { destination, source, bit_size, sign_extend_from } => {
+                let bit_size = *bit_size;
+                let typ = match sign_extend_from {
+                    Some(from_bit_size) => {
+                        self.expect(*source, ExpectedType::Signed { bit_size: *from_bit_size })?;
+                        Typ::Signed { bit_size }
+                    }
+                    // Truncating a signed integer leaves it signed, whereas zero-extending it does not.
+                    None => match self.get(*source) {
+                        Some(Typ::Signed { bit_size: from_bit_size }) if bit_size <= from_bit_size => {
+                            Typ::Signed { bit_size }
+                        }
+                        _ => Typ::Unsigned { bit_size },
+                    },
+                };
+                vec![(*destination, Some(typ))]
+            }
+            Opcode::Mov { destination, source } => vec![(*destination, self.get(*source))],
+            Opcode::Const { destination, .. }
+            | Opcode::Load { destination, .. }
+            | Opcode::Alloc { destination, .. } => vec![(*destination, None)],
+            Opcode::ForeignCall { destinations, .. }
+            | Opcode::TypedForeignCall { destinations, .. } => destinations
+                .iter()
+                .filter_map(|destination| match destination {
+                    RegisterOrMemory::RegisterIndex(register) => Some((*register, None)),
+                    RegisterOrMemory::HeapVector(vector) => Some((vector.size, None)),
+                    RegisterOrMemory::HeapArray(_) => None,
+                })
+                .collect(),
+            Opcode::BlackBox(black_box_op) => match black_box_op {
+                BlackBoxOp::HashToField128Security { output, .. } => {
+                    vec![(*output, Some(Typ::Field))]
+                }
+                BlackBoxOp::EcdsaSecp256k1 { result, .. }
+                | BlackBoxOp::EcdsaSecp256r1 { result, .. }
+                | BlackBoxOp::SchnorrVerify { result, .. }
+                | BlackBoxOp::EddsaEd25519 { result, .. }
+                | BlackBoxOp::EddsaBabyJubJub { result, .. } => {
+                    vec![(*result, Some(Typ::Unsigned { bit_size: 1 }))]
+                }
+                BlackBoxOp::Sha256 { .. }
+                | BlackBoxOp::Sha512 { .. }
+                | BlackBoxOp::Blake2s { .. }
+                | BlackBoxOp::Blake3 { .. }
+                | BlackBoxOp::Keccak256 { .. }
+                | BlackBoxOp::Pedersen { .. }
+                | BlackBoxOp::FixedBaseScalarMul { .. }
+                | BlackBoxOp::EmbeddedCurveAdd { .. }
+                | BlackBoxOp::VariableBaseScalarMul { .. }
+                | BlackBoxOp::EcdsaSecp256k1Recover { .. } => Vec::new(),
+            },
+            Opcode::JumpIfNot { .. }
+            | Opcode::JumpIf { .. }
+            | Opcode::Jump { .. }
+            | Opcode::Call { .. }
+            | Opcode::Return
+            // Calls within a new frame swap out the register types as they execute.
+            | Opcode::FrameCall { .. }
+            | Opcode::Free { .. }
+            | Opcode::Store { .. }
+            | Opcode::Trap
+            | Opcode::Stop => Vec::new(),
+        };
+        Ok(updates)
+    }
+
+    /// Checks that register `register` is either untyped or holds a value of the `expected` type.
+    fn expect(
+        &self,
+        register: RegisterIndex,
+        expected: ExpectedType,
+    ) -> Result<Option<Typ>, TypeError> {
+        match self.get(register) {
+            Some(found) if !expected.accepts(found) => {
+                Err(TypeError::UnexpectedType { register, expected, found })
+            }
+            typ => Ok(typ),
+        }
+    }
+}
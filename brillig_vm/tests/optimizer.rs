@@ -0,0 +1,102 @@
+use acir::brillig::{optimizer::optimize, BinaryIntOp, Opcode, RegisterIndex, Value};
+use acvm_blackbox_solver::testing::MockSolver;
+use brillig_vm::{Registers, VMStatus, VM};
+
+fn r(index: usize) -> RegisterIndex {
+    RegisterIndex::from(index)
+}
+
+fn int_op(op: BinaryIntOp, destination: usize, lhs: usize, rhs: usize) -> Opcode {
+    Opcode::BinaryIntOp { destination: r(destination), op, bit_size: 32, lhs: r(lhs), rhs: r(rhs) }
+}
+
+fn execute(bytecode: Vec<Opcode>, input: u128) -> Value {
+    let solver = MockSolver::new();
+    let mut vm =
+        VM::new(Registers::load(vec![Value::from(input)]), vec![], bytecode, vec![], &solver);
+    assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+    vm.get_registers().get(r(0))
+}
+
+#[test]
+fn optimized_bytecode_computes_the_same_outputs() {
+    // Computes `3 * (0 + 1 + ... + (n - 1))` where `n` is held in register 0.
+    let bytecode = vec![
+        Opcode::Const { destination: r(1), value: Value::from(0_u128) },
+        Opcode::Const { destination: r(2), value: Value::from(0_u128) },
+        Opcode::Const { destination: r(3), value: Value::from(1_u128) },
+        Opcode::Const { destination: r(4), value: Value::from(2_u128) },
+        int_op(BinaryIntOp::Add, 5, 3, 4),
+        Opcode::Mov { destination: r(6), source: r(5) },
+        Opcode::Jump { location: 7 },
+        // Loop header
+        int_op(BinaryIntOp::LessThan, 7, 2, 0),
+        Opcode::JumpIfNot { condition: r(7), location: 10 },
+        Opcode::Jump { location: 14 },
+        Opcode::Jump { location: 20 },
+        Opcode::Trap,
+        Opcode::Mov { destination: r(9), source: r(1) },
+        Opcode::Trap,
+        // Loop body
+        int_op(BinaryIntOp::Add, 8, 1, 2),
+        Opcode::Mov { destination: r(1), source: r(8) },
+        int_op(BinaryIntOp::Add, 2, 2, 3),
+        Opcode::Mov { destination: r(10), source: r(2) },
+        Opcode::Jump { location: 7 },
+        Opcode::Trap,
+        // Exit
+        int_op(BinaryIntOp::Mul, 11, 1, 6),
+        Opcode::Mov { destination: r(0), source: r(11) },
+        Opcode::Stop,
+    ];
+
+    let (optimized, positions) = optimize(bytecode.clone(), &[r(0)]);
+
+    assert_eq!(optimized.len(), 12);
+    assert_eq!(optimized.len(), positions.len());
+    for (opcode, position) in optimized.iter().zip(&positions) {
+        // Folded constants replace the operation from which they derive.
+        let original = &bytecode[*position];
+        assert!(
+            opcode.name() == original.name() || matches!(opcode, Opcode::Const { .. }),
+            "{opcode:?} does not derive from {original:?}"
+        );
+    }
+
+    for input in [0, 1, 5, 17] {
+        assert_eq!(execute(optimized.clone(), input), execute(bytecode.clone(), input));
+    }
+    assert_eq!(execute(optimized, 5), Value::from(30_u128));
+}
+
+#[test]
+fn optimized_frame_calls_compute_the_same_outputs() {
+    // Recursively computes `0 + 1 + ... + n` where `n` is held in register 0, calling each function within a new frame.
+    let bytecode = vec![
+        Opcode::Mov { destination: r(7), source: r(0) },
+        Opcode::FrameCall { location: 5, arguments: vec![r(7)], results: vec![r(8)] },
+        Opcode::Mov { destination: r(0), source: r(8) },
+        Opcode::Stop,
+        Opcode::Trap,
+        // sum(n), with n held in register 0
+        Opcode::Const { destination: r(1), value: Value::from(0_u128) },
+        int_op(BinaryIntOp::Equals, 2, 0, 1),
+        Opcode::JumpIfNot { condition: r(2), location: 9 },
+        Opcode::Return,
+        Opcode::Const { destination: r(3), value: Value::from(1_u128) },
+        int_op(BinaryIntOp::Sub, 4, 0, 3),
+        Opcode::Mov { destination: r(6), source: r(4) },
+        Opcode::FrameCall { location: 5, arguments: vec![r(6)], results: vec![r(5)] },
+        int_op(BinaryIntOp::Add, 9, 0, 5),
+        Opcode::Mov { destination: r(0), source: r(9) },
+        Opcode::Return,
+    ];
+
+    let (optimized, _) = optimize(bytecode.clone(), &[r(0)]);
+
+    assert!(optimized.len() < bytecode.len());
+    for input in [0, 1, 5, 10] {
+        assert_eq!(execute(optimized.clone(), input), execute(bytecode.clone(), input));
+    }
+    assert_eq!(execute(optimized, 10), Value::from(55_u128));
+}
@@ -301,8 +301,9 @@ fn blake2s_round(
     (new_opcodes, num_witness)
 }
 
+/// The mixing function `G`, which is shared by Blake2s and Blake3.
 #[allow(clippy::too_many_arguments)]
-fn g(
+pub(crate) fn g(
     state: &mut [UInt32],
     a: usize,
     b: usize,
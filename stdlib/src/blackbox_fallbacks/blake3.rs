@@ -0,0 +1,281 @@
+//! Blake3 fallback function.
+use super::{
+    blake2s::g,
+    utils::{byte_decomposition, round_to_nearest_byte},
+    UInt32,
+};
+use acir::{
+    circuit::Opcode,
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+const BLAKE3_BLOCK_LEN: usize = 64;
+const BLAKE3_CHUNK_LEN: usize = 1024;
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+const IV_VALUE: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+// Domain separation flags
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+
+pub fn blake3(
+    inputs: Vec<(Expression, u32)>,
+    outputs: Vec<Witness>,
+    mut num_witness: u32,
+) -> (u32, Vec<Opcode>) {
+    let mut new_opcodes = Vec::new();
+    let mut new_inputs = Vec::new();
+
+    // Decompose the input field elements into bytes and collect the resulting witnesses.
+    for (witness, num_bits) in inputs {
+        let num_bytes = round_to_nearest_byte(num_bits);
+        let (extra_opcodes, extra_inputs, updated_witness_counter) =
+            byte_decomposition(witness, num_bytes, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        new_inputs.extend(extra_inputs);
+        num_witness = updated_witness_counter;
+    }
+
+    let (result, num_witness, extra_opcodes) = create_blake3_constraint(new_inputs, num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    // constrain the outputs to be the same as the result of the circuit
+    for i in 0..outputs.len() {
+        let mut expr = Expression::from(outputs[i]);
+        expr.push_addition_term(-FieldElement::one(), result[i]);
+        new_opcodes.push(Opcode::Arithmetic(expr));
+    }
+    (num_witness, new_opcodes)
+}
+
+fn create_blake3_constraint(
+    input: Vec<Witness>,
+    num_witness: u32,
+) -> (Vec<Witness>, u32, Vec<Opcode>) {
+    let mut new_opcodes = Vec::new();
+
+    // prepare constants
+    let (iv, extra_opcodes, num_witness) = prepare_iv(num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (zero, extra_opcodes, num_witness) = UInt32::load_constant(0_u32, num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    // An empty message is hashed as a single empty chunk.
+    let chunks: Vec<&[Witness]> =
+        if input.is_empty() { vec![&[]] } else { input.chunks(BLAKE3_CHUNK_LEN).collect() };
+
+    let (root_hash, extra_opcodes, mut num_witness) =
+        blake3_subtree(&chunks, 0, true, &iv, zero.inner, num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    // decompose the result bytes in u32 to u8
+    let mut result = Vec::new();
+    for word in root_hash {
+        let (extra_opcodes, mut bytes, updated_witness_counter) =
+            byte_decomposition(Expression::from(word.inner), 4, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        bytes.reverse();
+        result.extend(bytes);
+        num_witness = updated_witness_counter;
+    }
+
+    (result, num_witness, new_opcodes)
+}
+
+/// Computes the chaining value of the subtree of the Blake3 hash tree which covers `chunks`.
+///
+/// `chunk_counter` is the index of the first of these chunks within the message.
+fn blake3_subtree(
+    chunks: &[&[Witness]],
+    chunk_counter: u64,
+    is_root: bool,
+    iv: &[UInt32],
+    zero: Witness,
+    num_witness: u32,
+) -> (Vec<UInt32>, Vec<Opcode>, u32) {
+    if chunks.len() == 1 {
+        return blake3_chunk(chunks[0], chunk_counter, is_root, iv, zero, num_witness);
+    }
+
+    // The left subtree holds the largest power of two number of chunks which leaves the right subtree non-empty.
+    let left_len = 1 << (usize::BITS - 1 - (chunks.len() - 1).leading_zeros());
+    let mut new_opcodes = Vec::new();
+    let (left, extra_opcodes, num_witness) =
+        blake3_subtree(&chunks[..left_len], chunk_counter, false, iv, zero, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (right, extra_opcodes, num_witness) = blake3_subtree(
+        &chunks[left_len..],
+        chunk_counter + left_len as u64,
+        false,
+        iv,
+        zero,
+        num_witness,
+    );
+    new_opcodes.extend(extra_opcodes);
+
+    let block: Vec<UInt32> = left.into_iter().chain(right).collect();
+    let flags = if is_root { PARENT | ROOT } else { PARENT };
+    let (parent, extra_opcodes, num_witness) =
+        blake3_compress(iv, &block, 0, BLAKE3_BLOCK_LEN as u32, flags, iv, num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    (parent, new_opcodes, num_witness)
+}
+
+/// Computes the chaining value of a single chunk of at most [`BLAKE3_CHUNK_LEN`] bytes.
+fn blake3_chunk(
+    chunk: &[Witness],
+    chunk_counter: u64,
+    is_root: bool,
+    iv: &[UInt32],
+    zero: Witness,
+    mut num_witness: u32,
+) -> (Vec<UInt32>, Vec<Opcode>, u32) {
+    let mut new_opcodes = Vec::new();
+    // An empty chunk is compressed as a single empty block.
+    let blocks: Vec<&[Witness]> =
+        if chunk.is_empty() { vec![&[]] } else { chunk.chunks(BLAKE3_BLOCK_LEN).collect() };
+
+    let mut chaining_value = iv.to_vec();
+    for (i, block) in blocks.iter().enumerate() {
+        let mut flags = 0;
+        if i == 0 {
+            flags |= CHUNK_START;
+        }
+        if i == blocks.len() - 1 {
+            flags |= CHUNK_END;
+            if is_root {
+                flags |= ROOT;
+            }
+        }
+
+        // pad the block with zeros and read it as little endian words
+        let mut padded_block = block.to_vec();
+        padded_block.resize(BLAKE3_BLOCK_LEN, zero);
+        let mut words = Vec::new();
+        for word_bytes in padded_block.chunks(4) {
+            let mut word_bytes = word_bytes.to_vec();
+            word_bytes.reverse();
+            let (word, extra_opcodes, updated_witness_counter) =
+                UInt32::from_witnesses(&word_bytes, num_witness);
+            new_opcodes.extend(extra_opcodes);
+            words.push(word[0]);
+            num_witness = updated_witness_counter;
+        }
+
+        let (new_chaining_value, extra_opcodes, updated_witness_counter) = blake3_compress(
+            &chaining_value,
+            &words,
+            chunk_counter,
+            block.len() as u32,
+            flags,
+            iv,
+            num_witness,
+        );
+        new_opcodes.extend(extra_opcodes);
+        chaining_value = new_chaining_value;
+        num_witness = updated_witness_counter;
+    }
+
+    (chaining_value, new_opcodes, num_witness)
+}
+
+/// The Blake3 compression function, truncated to the eight words which form a chaining value.
+fn blake3_compress(
+    chaining_value: &[UInt32],
+    block: &[UInt32],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+    iv: &[UInt32],
+    num_witness: u32,
+) -> (Vec<UInt32>, Vec<Opcode>, u32) {
+    let mut new_opcodes = Vec::new();
+    let mut v = Vec::new();
+
+    v.extend_from_slice(chaining_value);
+    v.extend_from_slice(&iv[0..4]);
+    let (counter_low, extra_opcodes, num_witness) =
+        UInt32::load_constant(counter as u32, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    v.push(counter_low);
+    let (counter_high, extra_opcodes, num_witness) =
+        UInt32::load_constant((counter >> 32) as u32, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    v.push(counter_high);
+    let (block_len, extra_opcodes, num_witness) = UInt32::load_constant(block_len, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    v.push(block_len);
+    let (flags, extra_opcodes, mut num_witness) = UInt32::load_constant(flags, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    v.push(flags);
+
+    let mut m = block.to_vec();
+    for round in 0..7 {
+        let (extra_opcodes, updated_witness_counter) = blake3_round(&mut v, &m, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        num_witness = updated_witness_counter;
+
+        // permute the message words for the next round
+        if round < 6 {
+            m = MSG_PERMUTATION.iter().map(|&i| m[i]).collect();
+        }
+    }
+
+    let mut output = Vec::new();
+    for i in 0..8 {
+        let (word, extra_opcodes, updated_witness_counter) = v[i].xor(&v[i + 8], num_witness);
+        new_opcodes.extend(extra_opcodes);
+        output.push(word);
+        num_witness = updated_witness_counter;
+    }
+
+    (output, new_opcodes, num_witness)
+}
+
+fn blake3_round(state: &mut [UInt32], msg: &[UInt32], num_witness: u32) -> (Vec<Opcode>, u32) {
+    let mut new_opcodes = Vec::new();
+
+    // Mix the columns.
+    let (extra_opcodes, num_witness) = g(state, 0, 4, 8, 12, msg[0], msg[1], num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, num_witness) = g(state, 1, 5, 9, 13, msg[2], msg[3], num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, num_witness) = g(state, 2, 6, 10, 14, msg[4], msg[5], num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, num_witness) = g(state, 3, 7, 11, 15, msg[6], msg[7], num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    // Mix the diagonals.
+    let (extra_opcodes, num_witness) = g(state, 0, 5, 10, 15, msg[8], msg[9], num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, num_witness) = g(state, 1, 6, 11, 12, msg[10], msg[11], num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, num_witness) = g(state, 2, 7, 8, 13, msg[12], msg[13], num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, num_witness) = g(state, 3, 4, 9, 14, msg[14], msg[15], num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    (new_opcodes, num_witness)
+}
+
+/// Load the IV (Initialization Vector) of Blake3
+fn prepare_iv(mut num_witness: u32) -> (Vec<UInt32>, Vec<Opcode>, u32) {
+    let mut new_opcodes = Vec::new();
+    let mut iv = Vec::new();
+
+    for iv_v in IV_VALUE {
+        let (new_witness, extra_opcodes, updated_witness_counter) =
+            UInt32::load_constant(iv_v, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        iv.push(new_witness);
+        num_witness = updated_witness_counter;
+    }
+
+    (iv, new_opcodes, num_witness)
+}
@@ -0,0 +1,422 @@
+//! ECDSA public key recovery over secp256k1.
+//!
+//! The public key `Q`, and the point `R` whose x-coordinate is `r` and whose y-coordinate has the parity given by
+//! the recovery id, are computed by Brillig hints. Both are constrained to lie on the curve, after which the
+//! recovery equation `r * Q = s * R - z * G` is checked as `z * G + r * Q - s * R = O` using a single
+//! double-and-add loop over the bits of the three scalars.
+//!
+//! Points are held in projective coordinates and combined using the complete formulae of
+//! [Renes, Costello and Batina][rcb], so the point at infinity needs no special handling.
+//!
+//! As with the reference implementation, only the recovery ids 0 and 1 are supported. Larger recovery ids, which
+//! denote an `R` whose x-coordinate exceeds the order of the curve, leave the constraints unsatisfiable.
+//!
+//! [rcb]: https://eprint.iacr.org/2015/1060
+use super::{
+    secp256k1_field::{assert_less_than, assert_zero, mul, Element, MODULUS},
+    utils::bit_decomposition,
+};
+use crate::helpers::VariableStore;
+use acir::{
+    brillig::{self, BlackBoxOp, HeapArray, HeapVector, RegisterIndex},
+    circuit::{
+        brillig::{Brillig, BrilligInputs, BrilligOutputs},
+        opcodes::{BlackBoxFuncCall, FunctionInput},
+        Opcode,
+    },
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+/// The order of the curve, least significant limb first.
+const ORDER: [u64; 4] =
+    [0xBFD25E8CD0364141, 0xBAAEDCE6AF48A03B, 0xFFFFFFFFFFFFFFFE, 0xFFFFFFFFFFFFFFFF];
+const GENERATOR_X: [u64; 4] =
+    [0x59F2815B16F81798, 0x029BFCDB2DCE28D9, 0x55A06295CE870B07, 0x79BE667EF9DCBBAC];
+const GENERATOR_Y: [u64; 4] =
+    [0x9C47D08FFB10D4B8, 0xFD17B448A6855419, 0x5DA4FBFC0E1108A8, 0x483ADA7726A3C465];
+/// `3 * b` for the curve `y^2 = x^3 + b`.
+const B3: u64 = 21;
+
+/// A point in projective coordinates `(X : Y : Z)`, representing `(X / Z, Y / Z)`.
+#[derive(Clone)]
+struct Point {
+    x: Element,
+    y: Element,
+    z: Element,
+}
+
+impl Point {
+    fn affine(x: Element, y: Element) -> Self {
+        Point { x, y, z: Element::constant([1, 0, 0, 0]) }
+    }
+
+    fn infinity() -> Self {
+        Point {
+            x: Element::constant([0; 4]),
+            y: Element::constant([1, 0, 0, 0]),
+            z: Element::constant([0; 4]),
+        }
+    }
+
+    fn negate(&self) -> Self {
+        Point { x: self.x.clone(), y: self.y.negate(), z: self.z.clone() }
+    }
+}
+
+/// Recovers the public key which produced `signature` over `hashed_message`, constraining its coordinates to
+/// equal `public_key_x` and `public_key_y`.
+///
+/// The signature is the 64 bytes of `r` followed by `s`, and the hashed message must be 32 bytes. All values are
+/// big endian.
+pub fn ecdsa_secp256k1_recover(
+    signature: Vec<Witness>,
+    recovery_id: Witness,
+    hashed_message: Vec<Witness>,
+    public_key_x: Vec<Witness>,
+    public_key_y: Vec<Witness>,
+    num_witness: u32,
+) -> (u32, Vec<Opcode>) {
+    assert_eq!(signature.len(), 64, "signature must be 64 bytes");
+    assert_eq!(hashed_message.len(), 32, "hashed message must be 32 bytes");
+    let mut new_opcodes = Vec::new();
+
+    // Decomposing each byte of the scalars into bits also constrains them to be bytes.
+    let (extra_opcodes, hash_bits, num_witness) = scalar_bits(&hashed_message, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, r_bits, num_witness) = scalar_bits(&signature[..32], num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, s_bits, mut num_witness) = scalar_bits(&signature[32..], num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    let mut variables = VariableStore::new(&mut num_witness);
+    let opcodes = &mut new_opcodes;
+
+    // The recovery id must be 0 or 1.
+    let recovery_id_expr = Expression::from(recovery_id);
+    let boolean = (&recovery_id_expr * &recovery_id_expr).expect("recovery id is linear");
+    opcodes.push(Opcode::Arithmetic(&boolean - &recovery_id_expr));
+
+    // 1 <= r, s < n
+    let r = Element::from_be_bytes(&signature[..32]);
+    let s = Element::from_be_bytes(&signature[32..]);
+    for (scalar, bytes) in [(&r, &signature[..32]), (&s, &signature[32..])] {
+        assert_less_than(scalar, ORDER, &mut variables, opcodes);
+        assert_non_zero(bytes, &mut variables, opcodes);
+    }
+
+    // The public key is canonical and lies on the curve.
+    let to_expressions = |bytes: &[Witness]| bytes.iter().copied().map(Expression::from).collect();
+    opcodes.push(recovery_hint(
+        to_expressions(&hashed_message),
+        to_expressions(&signature),
+        recovery_id,
+        Some(&public_key_x),
+        &public_key_y,
+    ));
+    for &byte in public_key_x.iter().chain(&public_key_y) {
+        opcodes.push(range(byte, 8));
+    }
+    let public_key_x = Element::from_be_bytes(&public_key_x);
+    let public_key_y = Element::from_be_bytes(&public_key_y);
+    for coordinate in [&public_key_x, &public_key_y] {
+        assert_less_than(coordinate, MODULUS, &mut variables, opcodes);
+    }
+    assert_on_curve(&public_key_x, &public_key_y, &mut variables, opcodes);
+    let public_key = Point::affine(public_key_x, public_key_y);
+
+    // R is the key recovered from the signature (r, r) over a zero hash, for which s * R - z * G = r * R.
+    let r_point_y: Vec<Witness> = (0..32).map(|_| variables.new_variable()).collect();
+    let r_signature = [&signature[..32], &signature[..32]].concat();
+    opcodes.push(recovery_hint(
+        vec![Expression::zero(); 32],
+        to_expressions(&r_signature),
+        recovery_id,
+        None,
+        &r_point_y,
+    ));
+    for &byte in &r_point_y[..31] {
+        opcodes.push(range(byte, 8));
+    }
+    constrain_parity(r_point_y[31], recovery_id, &mut variables, opcodes);
+    let r_point_y = Element::from_be_bytes(&r_point_y);
+    assert_less_than(&r_point_y, MODULUS, &mut variables, opcodes);
+    assert_on_curve(&r, &r_point_y, &mut variables, opcodes);
+    let r_point = Point::affine(r.clone(), r_point_y);
+
+    // table[i] = i_0 * G + i_1 * Q - i_2 * R, where i_j is the j-th bit of i.
+    let generator = Point::affine(Element::constant(GENERATOR_X), Element::constant(GENERATOR_Y));
+    let negated_r_point = r_point.negate();
+    let generator_plus_key = add(&generator, &public_key, &mut variables, opcodes);
+    let table = [
+        Point::infinity(),
+        generator.clone(),
+        public_key.clone(),
+        generator_plus_key.clone(),
+        negated_r_point.clone(),
+        add(&generator, &negated_r_point, &mut variables, opcodes),
+        add(&public_key, &negated_r_point, &mut variables, opcodes),
+        add(&generator_plus_key, &negated_r_point, &mut variables, opcodes),
+    ];
+
+    // z * G + r * Q - s * R, from the most significant bit.
+    let mut accumulator: Option<Point> = None;
+    for ((&hash_bit, &r_bit), &s_bit) in hash_bits.iter().zip(&r_bits).zip(&s_bits) {
+        let selected = select(&table, [hash_bit, r_bit, s_bit], &mut variables, opcodes);
+        accumulator = Some(match accumulator {
+            Some(accumulator) => {
+                let doubled = double(&accumulator, &mut variables, opcodes);
+                add(&doubled, &selected, &mut variables, opcodes)
+            }
+            None => selected,
+        });
+    }
+    let accumulator = accumulator.expect("scalars are not empty");
+    assert_zero(&accumulator.z, &mut variables, opcodes);
+
+    (variables.finalize(), new_opcodes)
+}
+
+/// Decomposes the big endian `bytes` of a scalar into bits, most significant first.
+fn scalar_bits(bytes: &[Witness], mut num_witness: u32) -> (Vec<Opcode>, Vec<Witness>, u32) {
+    let mut new_opcodes = Vec::new();
+    let mut bits = Vec::with_capacity(8 * bytes.len());
+    for &byte in bytes {
+        let (extra_opcodes, byte_bits, updated_witness_counter) =
+            bit_decomposition(Expression::from(byte), 8, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        bits.extend(byte_bits.into_iter().rev());
+        num_witness = updated_witness_counter;
+    }
+    (new_opcodes, bits, num_witness)
+}
+
+/// Returns a Brillig hint which recovers the public key for the given signature, writing the requested
+/// coordinates as big endian bytes.
+fn recovery_hint(
+    hashed_message: Vec<Expression>,
+    signature: Vec<Expression>,
+    recovery_id: Witness,
+    public_key_x: Option<&[Witness]>,
+    public_key_y: &[Witness],
+) -> Opcode {
+    // The inputs place the hashed message in memory followed by the signature, with pointers to them in
+    // registers 0 and 1 and the recovery id in register 2. The public key is written after the signature.
+    let hashed_message_len = hashed_message.len();
+    let hashed_message_size = RegisterIndex::from(3);
+    let public_key_x_pointer = RegisterIndex::from(4);
+    let public_key_y_pointer = RegisterIndex::from(5);
+    let public_key_x_address = hashed_message_len + signature.len();
+    let mut bytecode = vec![
+        brillig::Opcode::Const {
+            destination: hashed_message_size,
+            value: brillig::Value::from(hashed_message_len),
+        },
+        brillig::Opcode::Const {
+            destination: public_key_x_pointer,
+            value: brillig::Value::from(public_key_x_address),
+        },
+        brillig::Opcode::Const {
+            destination: public_key_y_pointer,
+            value: brillig::Value::from(public_key_x_address + 32),
+        },
+        brillig::Opcode::BlackBox(BlackBoxOp::EcdsaSecp256k1Recover {
+            hashed_msg: HeapVector { pointer: RegisterIndex::from(0), size: hashed_message_size },
+            signature: HeapArray { pointer: RegisterIndex::from(1), size: 64 },
+            recovery_id: RegisterIndex::from(2),
+            public_key_x: HeapArray { pointer: public_key_x_pointer, size: 32 },
+            public_key_y: HeapArray { pointer: public_key_y_pointer, size: 32 },
+        }),
+    ];
+
+    let mut outputs = Vec::new();
+    if let Some(public_key_x) = public_key_x {
+        outputs.push((public_key_x_pointer, public_key_x));
+    }
+    outputs.push((public_key_y_pointer, public_key_y));
+    for (i, (pointer, _)) in outputs.iter().enumerate() {
+        bytecode
+            .push(brillig::Opcode::Mov { destination: RegisterIndex::from(i), source: *pointer });
+    }
+
+    Opcode::Brillig(Brillig {
+        inputs: vec![
+            BrilligInputs::Array(hashed_message),
+            BrilligInputs::Array(signature),
+            BrilligInputs::Single(recovery_id.into()),
+        ],
+        outputs: outputs
+            .into_iter()
+            .map(|(_, witnesses)| BrilligOutputs::Array(witnesses.to_vec()))
+            .collect(),
+        foreign_call_results: vec![],
+        bytecode,
+        predicate: None,
+    })
+}
+
+fn range(witness: Witness, num_bits: u32) -> Opcode {
+    Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input: FunctionInput { witness, num_bits } })
+}
+
+/// Constrains `byte` to be a byte whose least significant bit is `parity`, which must be boolean.
+fn constrain_parity(
+    byte: Witness,
+    parity: Witness,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) {
+    // byte = 2 * half + parity, from which `half` is solved as `byte` and `parity` are known.
+    let half = variables.new_variable();
+    let mut decomposition = Expression::from(byte);
+    decomposition.push_addition_term(-FieldElement::from(2_u128), half);
+    decomposition.push_addition_term(-FieldElement::one(), parity);
+    opcodes.push(Opcode::Arithmetic(decomposition));
+    opcodes.push(range(half, 7));
+}
+
+/// Constrains the integer with the big endian `bytes` to be non-zero.
+fn assert_non_zero(bytes: &[Witness], variables: &mut VariableStore, opcodes: &mut Vec<Opcode>) {
+    // The sum of the bytes cannot wrap around the field, so it is zero exactly when every byte is zero.
+    let mut sum = Expression::zero();
+    for &byte in bytes {
+        sum.push_addition_term(FieldElement::one(), byte);
+    }
+
+    let inverse = variables.new_variable();
+    // Brillig division by zero results in zero, which the constraint below then rejects.
+    opcodes.push(Opcode::Brillig(Brillig {
+        inputs: vec![BrilligInputs::Single(sum.clone())],
+        outputs: vec![BrilligOutputs::Simple(inverse)],
+        foreign_call_results: vec![],
+        bytecode: vec![
+            brillig::Opcode::Const {
+                destination: RegisterIndex::from(1),
+                value: brillig::Value::from(FieldElement::one()),
+            },
+            brillig::Opcode::BinaryFieldOp {
+                op: brillig::BinaryFieldOp::Div,
+                lhs: RegisterIndex::from(1),
+                rhs: RegisterIndex::from(0),
+                destination: RegisterIndex::from(0),
+            },
+        ],
+        predicate: None,
+    }));
+    // sum * inverse - 1 = 0
+    let product = (&sum * &Expression::from(inverse)).expect("sum is linear");
+    opcodes.push(Opcode::Arithmetic(product - FieldElement::one()));
+}
+
+/// Constrains `(x, y)` to satisfy `y^2 = x^3 + 7`.
+fn assert_on_curve(
+    x: &Element,
+    y: &Element,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) {
+    let x_squared = mul(x, x, variables, opcodes);
+    let x_cubed = mul(&x_squared, x, variables, opcodes);
+    let y_squared = mul(y, y, variables, opcodes);
+    let difference = y_squared.sub(&x_cubed).sub(&Element::constant([7, 0, 0, 0]));
+    assert_zero(&difference, variables, opcodes);
+}
+
+/// Returns `table[i]`, where `i` is the integer whose little endian bits are `bits`.
+fn select(
+    table: &[Point; 8],
+    [bit_0, bit_1, bit_2]: [Witness; 3],
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Point {
+    // Build an indicator for each entry, which is one for the selected entry and zero otherwise.
+    let bit_0_and_1 = variables.new_variable();
+    let product = (&Expression::from(bit_0) * &Expression::from(bit_1)).expect("bits are linear");
+    opcodes.push(Opcode::Arithmetic(&product - &Expression::from(bit_0_and_1)));
+    let low_indicators = [
+        &(&(&Expression::one() - bit_0) - bit_1) + bit_0_and_1,
+        &Expression::from(bit_0) - bit_0_and_1,
+        &Expression::from(bit_1) - bit_0_and_1,
+        Expression::from(bit_0_and_1),
+    ];
+    let mut indicators = Vec::with_capacity(8);
+    let mut high_indicators = Vec::with_capacity(4);
+    for low_indicator in &low_indicators {
+        let high_indicator = variables.new_variable();
+        let product = (low_indicator * &Expression::from(bit_2)).expect("indicator is linear");
+        opcodes.push(Opcode::Arithmetic(&product - &Expression::from(high_indicator)));
+        indicators.push(low_indicator - &Expression::from(high_indicator));
+        high_indicators.push(Expression::from(high_indicator));
+    }
+    indicators.extend(high_indicators);
+
+    let mut select_coordinate = |coordinate: fn(&Point) -> &Element| {
+        let entries: Vec<&Element> = table.iter().map(coordinate).collect();
+        let limbs = std::array::from_fn(|i| {
+            // limb = sum(indicator * entry_limb)
+            let limb = variables.new_variable();
+            let mut constraint = -&Expression::from(limb);
+            for (indicator, entry) in indicators.iter().zip(&entries) {
+                let product = (indicator * &entry.limbs()[i]).expect("limbs are linear");
+                constraint = &constraint + &product;
+            }
+            opcodes.push(Opcode::Arithmetic(constraint));
+            Expression::from(limb)
+        });
+        Element::selected_from(limbs, &entries)
+    };
+    Point {
+        x: select_coordinate(|point| &point.x),
+        y: select_coordinate(|point| &point.y),
+        z: select_coordinate(|point| &point.z),
+    }
+}
+
+/// Adds two points using algorithm 7 of [Renes, Costello and Batina][rcb].
+///
+/// [rcb]: https://eprint.iacr.org/2015/1060
+fn add(
+    lhs: &Point,
+    rhs: &Point,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Point {
+    let mut mul = |a: &Element, b: &Element| mul(a, b, variables, opcodes);
+
+    let t0 = mul(&lhs.x, &rhs.x);
+    let t1 = mul(&lhs.y, &rhs.y);
+    let t2 = mul(&lhs.z, &rhs.z);
+    let t3 = mul(&lhs.x.add(&lhs.y), &rhs.x.add(&rhs.y)).sub(&t0.add(&t1));
+    let t4 = mul(&lhs.y.add(&lhs.z), &rhs.y.add(&rhs.z)).sub(&t1.add(&t2));
+    let y3 = mul(&lhs.x.add(&lhs.z), &rhs.x.add(&rhs.z)).sub(&t0.add(&t2)).scale(B3);
+    let t0 = t0.scale(3);
+    let t2 = t2.scale(B3);
+    let z3 = t1.add(&t2);
+    let t1 = t1.sub(&t2);
+
+    Point {
+        x: mul(&t3, &t1).sub(&mul(&t4, &y3)),
+        y: mul(&t1, &z3).add(&mul(&y3, &t0)),
+        z: mul(&z3, &t4).add(&mul(&t0, &t3)),
+    }
+}
+
+/// Doubles a point using algorithm 9 of [Renes, Costello and Batina][rcb].
+///
+/// [rcb]: https://eprint.iacr.org/2015/1060
+fn double(point: &Point, variables: &mut VariableStore, opcodes: &mut Vec<Opcode>) -> Point {
+    let mut mul = |a: &Element, b: &Element| mul(a, b, variables, opcodes);
+
+    let t0 = mul(&point.y, &point.y);
+    let z3 = t0.scale(8);
+    let t1 = mul(&point.y, &point.z);
+    let t2 = mul(&point.z, &point.z).scale(B3);
+    let x3 = mul(&t2, &z3);
+    let y3 = t0.add(&t2);
+    let z3 = mul(&t1, &z3);
+    let t0 = t0.sub(&t2.scale(3));
+    let y3 = x3.add(&mul(&t0, &y3));
+    let t1 = mul(&point.x, &point.y);
+
+    Point { x: mul(&t0, &t1).scale(2), y: y3, z: z3 }
+}
@@ -0,0 +1,266 @@
+//! Fallbacks for arithmetic over the embedded curve, `y^2 = x^3 + b`.
+//!
+//! As with the reference implementation, the point at infinity is represented by `(0, 0)`. Points are added
+//! using the affine formulae, with the cases for which these are undefined (adding a point to itself or to its
+//! negation, and either input being the point at infinity) handled by selecting between the possible results.
+use super::utils::bit_decomposition;
+use crate::helpers::VariableStore;
+use acir::{
+    brillig::{self, RegisterIndex},
+    circuit::{
+        brillig::{Brillig, BrilligInputs, BrilligOutputs},
+        Opcode,
+    },
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+type Point = (Witness, Witness);
+
+/// Adds `input1` and `input2`, constraining the result to equal `outputs`.
+pub fn embedded_curve_add(
+    input1: Point,
+    input2: Point,
+    outputs: Point,
+    mut num_witness: u32,
+) -> (u32, Vec<Opcode>) {
+    let mut new_opcodes = Vec::new();
+    let mut variables = VariableStore::new(&mut num_witness);
+
+    let result = add(input1, input2, &mut variables, &mut new_opcodes);
+    constrain_equal(result, outputs, &mut new_opcodes);
+
+    (variables.finalize(), new_opcodes)
+}
+
+/// Multiplies `point` by the scalar `scalar_low + scalar_high * 2^128`, constraining the result to equal `outputs`.
+///
+/// Each limb of the scalar is constrained to fit in 128 bits.
+pub fn variable_base_scalar_mul(
+    point: Point,
+    scalar_low: Witness,
+    scalar_high: Witness,
+    outputs: Point,
+    num_witness: u32,
+) -> (u32, Vec<Opcode>) {
+    let mut new_opcodes = Vec::new();
+
+    let (extra_opcodes, low_bits, num_witness) =
+        bit_decomposition(Expression::from(scalar_low), 128, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, high_bits, mut num_witness) =
+        bit_decomposition(Expression::from(scalar_high), 128, num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    let mut variables = VariableStore::new(&mut num_witness);
+
+    // Double-and-add from the most significant bit.
+    let mut accumulator: Option<Point> = None;
+    for bit in high_bits.into_iter().rev().chain(low_bits.into_iter().rev()) {
+        accumulator = Some(match accumulator {
+            Some(accumulator) => {
+                let doubled = double(accumulator, &mut variables, &mut new_opcodes);
+                let sum = add(doubled, point, &mut variables, &mut new_opcodes);
+                select(bit, sum, doubled, &mut variables, &mut new_opcodes)
+            }
+            // The accumulator starts as `point` if the bit is set and as the point at infinity otherwise.
+            None => mask(&(&Expression::one() - bit), point, &mut variables, &mut new_opcodes),
+        });
+    }
+    let result = accumulator.expect("scalar is not empty");
+    constrain_equal(result, outputs, &mut new_opcodes);
+
+    (variables.finalize(), new_opcodes)
+}
+
+/// Returns `expression * witness`, where `expression` must be linear.
+fn product(expression: &Expression, witness: Witness) -> Expression {
+    (expression * &Expression::from(witness)).expect("expression should be linear")
+}
+
+/// Returns a witness which is one if `value` is zero and zero otherwise, along with the inverse of `value`
+/// which is zero if `value` is zero.
+fn is_zero(
+    value: Expression,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> (Witness, Witness) {
+    let inverse = variables.new_variable();
+
+    // Brillig division by zero results in zero.
+    opcodes.push(Opcode::Brillig(Brillig {
+        inputs: vec![BrilligInputs::Single(value.clone())],
+        outputs: vec![BrilligOutputs::Simple(inverse)],
+        foreign_call_results: vec![],
+        bytecode: vec![
+            brillig::Opcode::Const {
+                destination: RegisterIndex::from(1),
+                value: brillig::Value::from(FieldElement::one()),
+            },
+            brillig::Opcode::BinaryFieldOp {
+                op: brillig::BinaryFieldOp::Div,
+                lhs: RegisterIndex::from(1),
+                rhs: RegisterIndex::from(0),
+                destination: RegisterIndex::from(0),
+            },
+        ],
+        predicate: None,
+    }));
+
+    // is_zero = 1 - value * inverse
+    let is_zero = variables.new_variable();
+    let is_zero_constraint =
+        &(&Expression::from(is_zero) + &product(&value, inverse)) - &Expression::one();
+    opcodes.push(Opcode::Arithmetic(is_zero_constraint));
+    // value * is_zero = 0, so that `is_zero` can only be one if `value` is zero.
+    opcodes.push(Opcode::Arithmetic(product(&value, is_zero)));
+
+    (is_zero, inverse)
+}
+
+/// Returns a witness which is one if `x` is zero and `y_is_zero` is one, so that the point is `(0, 0)`.
+fn is_infinity(
+    x: Witness,
+    y_is_zero: Witness,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Witness {
+    let (x_is_zero, _) = is_zero(x.into(), variables, opcodes);
+    let is_infinity = variables.new_variable();
+    opcodes.push(Opcode::Arithmetic(
+        &Expression::from(is_infinity) - &product(&x_is_zero.into(), y_is_zero),
+    ));
+    is_infinity
+}
+
+/// Adds two points.
+fn add(
+    (x1, y1): Point,
+    (x2, y2): Point,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Point {
+    let (y1_is_zero, y1_inverse) = is_zero(y1.into(), variables, opcodes);
+    let (y2_is_zero, _) = is_zero(y2.into(), variables, opcodes);
+    let input1_is_infinity = is_infinity(x1, y1_is_zero, variables, opcodes);
+    let input2_is_infinity = is_infinity(x2, y2_is_zero, variables, opcodes);
+    let (same_x, dx_inverse) = is_zero(&Expression::from(x2) - x1, variables, opcodes);
+    let (same_y, _) = is_zero(&Expression::from(y2) - y1, variables, opcodes);
+
+    // chord = (y2 - y1) / (x2 - x1), which is only used if the x-coordinates are distinct.
+    let chord = variables.new_variable();
+    let dy = &Expression::from(y2) - y1;
+    opcodes.push(Opcode::Arithmetic(&Expression::from(chord) - &product(&dy, dx_inverse)));
+    let tangent = tangent_gradient((x1, y1_inverse), variables, opcodes);
+    let lambda = select(same_x, (tangent, tangent), (chord, chord), variables, opcodes).0;
+    let sum = line_intersection(lambda, (x1, y1), x2, variables, opcodes);
+
+    // Points with the same x-coordinate sum to the point at infinity unless they are equal and their
+    // y-coordinate is non-zero, in which case the sum is the doubled point.
+    let doubling = variables.new_variable();
+    let doubling_constraint =
+        &(&Expression::from(doubling) - same_y) + &product(&same_y.into(), y1_is_zero);
+    opcodes.push(Opcode::Arithmetic(doubling_constraint));
+    let cancels = variables.new_variable();
+    let cancels_constraint =
+        &(&Expression::from(cancels) - same_x) + &product(&same_x.into(), doubling);
+    opcodes.push(Opcode::Arithmetic(cancels_constraint));
+    let sum = mask(&cancels.into(), sum, variables, opcodes);
+
+    let sum = select(input2_is_infinity, (x1, y1), sum, variables, opcodes);
+    select(input1_is_infinity, (x2, y2), sum, variables, opcodes)
+}
+
+/// Doubles a point.
+fn double((x, y): Point, variables: &mut VariableStore, opcodes: &mut Vec<Opcode>) -> Point {
+    let (y_is_zero, y_inverse) = is_zero(y.into(), variables, opcodes);
+    let lambda = tangent_gradient((x, y_inverse), variables, opcodes);
+    let doubled = line_intersection(lambda, (x, y), x, variables, opcodes);
+
+    // Doubling the point at infinity, or a point of order two, results in the point at infinity.
+    mask(&y_is_zero.into(), doubled, variables, opcodes)
+}
+
+/// Returns the gradient `3 * x^2 / (2 * y)` of the tangent to the curve at the point with x-coordinate `x`,
+/// given the inverse of its y-coordinate.
+fn tangent_gradient(
+    (x, y_inverse): (Witness, Witness),
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Witness {
+    let x_squared = variables.new_variable();
+    opcodes.push(Opcode::Arithmetic(&Expression::from(x_squared) - &product(&x.into(), x)));
+
+    let lambda = variables.new_variable();
+    let three_halves = FieldElement::from(3_u128) / FieldElement::from(2_u128);
+    let lambda_constraint =
+        Expression::from(lambda).add_mul(-three_halves, &product(&x_squared.into(), y_inverse));
+    opcodes.push(Opcode::Arithmetic(lambda_constraint));
+    lambda
+}
+
+/// Returns the reflection of the third point at which the line of gradient `lambda` through `(x1, y1)` and
+/// a point with x-coordinate `x2` meets the curve.
+fn line_intersection(
+    lambda: Witness,
+    (x1, y1): Point,
+    x2: Witness,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Point {
+    // x3 = lambda^2 - x1 - x2
+    let x3 = variables.new_variable();
+    let x3_constraint = &(&(&Expression::from(x3) - &product(&lambda.into(), lambda)) + x1) + x2;
+    opcodes.push(Opcode::Arithmetic(x3_constraint));
+
+    // y3 = lambda * (x1 - x3) - y1
+    let y3 = variables.new_variable();
+    let y3_constraint =
+        &(&Expression::from(y3) - &product(&(&Expression::from(x1) - x3), lambda)) + y1;
+    opcodes.push(Opcode::Arithmetic(y3_constraint));
+
+    (x3, y3)
+}
+
+/// Returns `if_true` if `condition` is one and `if_false` if it is zero.
+fn select(
+    condition: Witness,
+    if_true: Point,
+    if_false: Point,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Point {
+    let mut select_coordinate = |if_true: Witness, if_false: Witness| {
+        // result = if_false + condition * (if_true - if_false)
+        let result = variables.new_variable();
+        let difference = &Expression::from(if_true) - if_false;
+        let constraint = &(&Expression::from(result) - if_false) - &product(&difference, condition);
+        opcodes.push(Opcode::Arithmetic(constraint));
+        result
+    };
+    (select_coordinate(if_true.0, if_false.0), select_coordinate(if_true.1, if_false.1))
+}
+
+/// Returns the point at infinity if `condition` is one and `point` if it is zero.
+fn mask(
+    condition: &Expression,
+    point: Point,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Point {
+    let mut mask_coordinate = |coordinate: Witness| {
+        // result = coordinate - condition * coordinate
+        let result = variables.new_variable();
+        let constraint =
+            &(&Expression::from(result) - coordinate) + &product(condition, coordinate);
+        opcodes.push(Opcode::Arithmetic(constraint));
+        result
+    };
+    (mask_coordinate(point.0), mask_coordinate(point.1))
+}
+
+fn constrain_equal(lhs: Point, rhs: Point, opcodes: &mut Vec<Opcode>) {
+    for (lhs, rhs) in [(lhs.0, rhs.0), (lhs.1, rhs.1)] {
+        opcodes.push(Opcode::Arithmetic(&Expression::from(rhs) - lhs));
+    }
+}
@@ -1,8 +1,13 @@
 mod blake2s;
+mod blake3;
+mod ecdsa_secp256k1;
+mod embedded_curve;
 mod hash_to_field;
 mod keccak256;
 mod logic_fallbacks;
+mod secp256k1_field;
 mod sha256;
+mod sha512;
 #[macro_use]
 mod uint;
 mod uint32;
@@ -10,10 +15,14 @@ mod uint64;
 mod uint8;
 mod utils;
 pub use blake2s::blake2s;
+pub use blake3::blake3;
+pub use ecdsa_secp256k1::ecdsa_secp256k1_recover;
+pub use embedded_curve::{embedded_curve_add, variable_base_scalar_mul};
 pub use hash_to_field::hash_to_field;
 pub use keccak256::keccak256;
 pub use logic_fallbacks::{and, range, xor};
 pub use sha256::sha256;
+pub use sha512::sha512;
 pub use uint32::UInt32;
 pub use uint64::UInt64;
 pub use uint8::UInt8;
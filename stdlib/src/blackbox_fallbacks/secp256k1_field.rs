@@ -0,0 +1,400 @@
+//! Arithmetic modulo the secp256k1 base field prime `p = 2^256 - 2^32 - 977`.
+//!
+//! `p` is larger than the field on which ACIR is defined, so an [`Element`] is split into four little endian
+//! limbs of 64 bits. Addition, subtraction and multiplication by a small constant only combine the expressions of
+//! the limbs, which may then leave the range `[0, 2^64)`; the bounds of each limb are tracked so that they can be
+//! accounted for when the element is multiplied.
+//!
+//! Multiplication is the only operation which creates witnesses. A Brillig hint computes the quotient `q` and
+//! remainder `r` of `a * b` by `p`, and `a * b = q * p + r` is then constrained column by column, carrying between
+//! columns so that the identity holds over the integers. The remainder is less than `2^256` but is not necessarily
+//! reduced below `p`.
+//!
+//! The carries must fit in the native field without wrapping, so these fallbacks require a field of at least
+//! 200 bits.
+use crate::helpers::VariableStore;
+use acir::{
+    brillig::{self, BinaryIntOp, RegisterIndex},
+    circuit::{
+        brillig::{Brillig, BrilligInputs, BrilligOutputs},
+        directives::{Directive, QuotientDirective},
+        opcodes::{BlackBoxFuncCall, FunctionInput},
+        Opcode,
+    },
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+const NUM_LIMBS: usize = 4;
+const LIMB_BITS: u32 = 64;
+const LIMB_MAX: u128 = u64::MAX as u128;
+
+/// The limbs of `p`, least significant first.
+pub(super) const MODULUS: [u64; NUM_LIMBS] =
+    [0xFFFFFFFEFFFFFC2F, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF];
+/// `2^256 - p`, such that `2^256 = MODULUS_COMPLEMENT (mod p)`.
+const MODULUS_COMPLEMENT: u128 = 0x1000003D1;
+
+/// The largest product of the bounds, in multiples of `2^64`, of the limbs of two factors.
+///
+/// This keeps each column of a product below `2^190` and so each carry below `2^127` in magnitude.
+const MAX_BOUND_PRODUCT: u64 = 1 << 60;
+const CARRY_BITS: u32 = 128;
+/// The integer size used by the multiplication hint, which exceeds any intermediate value it computes.
+const HINT_BIT_SIZE: u32 = 200;
+
+/// An element of the secp256k1 base field, whose value is `sum(limbs[i] * 2^(64 * i))`.
+#[derive(Clone, Debug)]
+pub(super) struct Element {
+    limbs: [Expression; NUM_LIMBS],
+    /// Each limb lies within `[-negative * (2^64 - 1), positive * (2^64 - 1)]`.
+    positive: u64,
+    negative: u64,
+}
+
+impl Element {
+    /// Creates a constant element from its limbs, least significant first.
+    pub(super) fn constant(limbs: [u64; NUM_LIMBS]) -> Self {
+        Element {
+            limbs: limbs.map(|limb| Expression::from(FieldElement::from(limb as u128))),
+            positive: 1,
+            negative: 0,
+        }
+    }
+
+    /// Creates an element from its big endian bytes, which must already be constrained to be bytes.
+    pub(super) fn from_be_bytes(bytes: &[Witness]) -> Self {
+        assert_eq!(bytes.len(), NUM_LIMBS * 8, "an element must be made of 32 bytes");
+        let limbs = std::array::from_fn(|i| {
+            let mut limb = Expression::zero();
+            let limb_bytes = bytes.iter().rev().skip(8 * i).take(8);
+            for (j, &byte) in limb_bytes.enumerate() {
+                limb.push_addition_term(FieldElement::from(1_u128 << (8 * j)), byte);
+            }
+            limb
+        });
+        Element { limbs, positive: 1, negative: 0 }
+    }
+
+    /// Creates an element from limbs which are each equal to the corresponding limb of one of `elements`.
+    pub(super) fn selected_from(limbs: [Expression; NUM_LIMBS], elements: &[&Element]) -> Self {
+        Element {
+            limbs,
+            positive: elements.iter().map(|element| element.positive).max().unwrap_or(0),
+            negative: elements.iter().map(|element| element.negative).max().unwrap_or(0),
+        }
+    }
+
+    pub(super) fn limbs(&self) -> &[Expression; NUM_LIMBS] {
+        &self.limbs
+    }
+
+    pub(super) fn add(&self, other: &Element) -> Element {
+        Element {
+            limbs: std::array::from_fn(|i| &self.limbs[i] + &other.limbs[i]),
+            positive: self.positive + other.positive,
+            negative: self.negative + other.negative,
+        }
+    }
+
+    pub(super) fn sub(&self, other: &Element) -> Element {
+        self.add(&other.negate())
+    }
+
+    pub(super) fn negate(&self) -> Element {
+        Element {
+            limbs: std::array::from_fn(|i| -&self.limbs[i]),
+            positive: self.negative,
+            negative: self.positive,
+        }
+    }
+
+    pub(super) fn scale(&self, factor: u64) -> Element {
+        Element {
+            limbs: std::array::from_fn(|i| &self.limbs[i] * FieldElement::from(factor as u128)),
+            positive: self.positive * factor,
+            negative: self.negative * factor,
+        }
+    }
+
+    /// Returns limbs which represent this element plus a multiple of `p` and are all non-negative, along with
+    /// a bound on them in multiples of `2^64`.
+    fn non_negative_limbs(&self) -> ([Expression; NUM_LIMBS], u64) {
+        if self.negative == 0 {
+            return (self.limbs.clone(), self.positive);
+        }
+
+        // Adding `(negative + 1) * p` makes the value non-negative. Its limbs are adjusted by borrowing
+        // `negative` from each of the upper three limbs so that each is at least `negative * (2^64 - 1)`.
+        let multiple = self.negative as u128 + 1;
+        let borrow = self.negative as u128;
+        let offsets: [u128; NUM_LIMBS] = std::array::from_fn(|i| {
+            let mut offset = multiple * MODULUS[i] as u128;
+            if i < NUM_LIMBS - 1 {
+                offset += borrow << LIMB_BITS;
+            }
+            if i > 0 {
+                offset -= borrow;
+            }
+            offset
+        });
+        let limbs = std::array::from_fn(|i| self.limbs[i].clone() + FieldElement::from(offsets[i]));
+        (limbs, self.positive + 2 * self.negative + 1)
+    }
+}
+
+/// Returns an element equal to `lhs * rhs` modulo `p`, whose limbs are constrained to fit in 64 bits.
+pub(super) fn mul(
+    lhs: &Element,
+    rhs: &Element,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) -> Element {
+    let (lhs_limbs, lhs_bound) = lhs.non_negative_limbs();
+    let (rhs_limbs, rhs_bound) = rhs.non_negative_limbs();
+    assert!(
+        lhs_bound.saturating_mul(rhs_bound) <= MAX_BOUND_PRODUCT,
+        "factors are too large to be multiplied"
+    );
+
+    let quotient: [Witness; NUM_LIMBS + 1] = std::array::from_fn(|_| variables.new_variable());
+    let remainder: [Witness; NUM_LIMBS] = std::array::from_fn(|_| variables.new_variable());
+    opcodes.push(Opcode::Brillig(Brillig {
+        inputs: lhs_limbs.iter().chain(&rhs_limbs).cloned().map(BrilligInputs::Single).collect(),
+        outputs: quotient.iter().chain(&remainder).copied().map(BrilligOutputs::Simple).collect(),
+        foreign_call_results: vec![],
+        bytecode: mul_hint_bytecode(),
+        predicate: None,
+    }));
+    for &limb in quotient.iter().chain(&remainder) {
+        opcodes.push(range(limb, LIMB_BITS));
+    }
+
+    // Constrain each column of `lhs * rhs - quotient * p - remainder`, plus the carry from the previous column,
+    // to be a multiple of 2^64. The carries are offset by 2^127 so that they are range checked as non-negative.
+    let limb_base = FieldElement::from(1_u128 << LIMB_BITS);
+    let carry_offset = FieldElement::from(1_u128 << (CARRY_BITS - 1));
+    let num_columns = 2 * NUM_LIMBS;
+    let mut carry = Expression::zero();
+    for column in 0..num_columns {
+        let mut sum = std::mem::take(&mut carry);
+        for (i, &quotient_limb) in quotient.iter().enumerate() {
+            let Some(j) = column.checked_sub(i).filter(|&j| j < NUM_LIMBS) else {
+                continue;
+            };
+            if i < NUM_LIMBS {
+                let product = (&lhs_limbs[i] * &rhs_limbs[j]).expect("limbs are linear");
+                sum = &sum + &product;
+            }
+            sum.push_addition_term(-FieldElement::from(MODULUS[j] as u128), quotient_limb);
+        }
+        if let Some(&remainder_limb) = remainder.get(column) {
+            sum.push_addition_term(-FieldElement::one(), remainder_limb);
+        }
+
+        if column == num_columns - 1 {
+            opcodes.push(Opcode::Arithmetic(sum));
+        } else {
+            let shifted_carry = variables.new_variable();
+            let next_carry = Expression::from(shifted_carry) - carry_offset;
+            opcodes.push(Opcode::Arithmetic(sum.add_mul(-limb_base, &next_carry)));
+            opcodes.push(range(shifted_carry, CARRY_BITS));
+            carry = next_carry;
+        }
+    }
+
+    Element { limbs: remainder.map(Expression::from), positive: 1, negative: 0 }
+}
+
+/// Constrains `value` to be equal to zero modulo `p`.
+pub(super) fn assert_zero(
+    value: &Element,
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) {
+    let reduced = mul(value, &Element::constant([1, 0, 0, 0]), variables, opcodes);
+    for limb in reduced.limbs {
+        opcodes.push(Opcode::Arithmetic(limb));
+    }
+}
+
+/// Constrains `value`, whose limbs must already be constrained to fit in 64 bits, to be less than `bound`.
+pub(super) fn assert_less_than(
+    value: &Element,
+    bound: [u64; NUM_LIMBS],
+    variables: &mut VariableStore,
+    opcodes: &mut Vec<Opcode>,
+) {
+    assert!(value.positive == 1 && value.negative == 0, "limbs must fit in 64 bits");
+
+    // `value < bound` exactly when adding `2^256 - bound` to `value` does not carry out of the top limb.
+    let mut complement = [0_u128; NUM_LIMBS];
+    let mut borrow = 1;
+    for (complement_limb, &bound_limb) in complement.iter_mut().zip(&bound) {
+        let limb = (!bound_limb) as u128 + borrow;
+        *complement_limb = limb & LIMB_MAX;
+        borrow = limb >> LIMB_BITS;
+    }
+
+    let mut carry = Expression::zero();
+    for (i, (value_limb, &complement_limb)) in value.limbs.iter().zip(&complement).enumerate() {
+        let sum = &(value_limb.clone() + FieldElement::from(complement_limb)) + &carry;
+        let limb = variables.new_variable();
+        if i == NUM_LIMBS - 1 {
+            opcodes.push(Opcode::Arithmetic(&sum - &Expression::from(limb)));
+            opcodes.push(range(limb, LIMB_BITS));
+            break;
+        }
+
+        let next_carry = variables.new_variable();
+        opcodes.push(Opcode::Directive(Directive::Quotient(QuotientDirective {
+            a: sum.clone(),
+            b: Expression::from(FieldElement::from(1_u128 << LIMB_BITS)),
+            q: next_carry,
+            r: limb,
+            predicate: None,
+        })));
+        opcodes.push(range(limb, LIMB_BITS));
+        // sum = next_carry * 2^64 + limb, where next_carry is a bit
+        let decomposition = (&sum - &Expression::from(limb))
+            .add_mul(-FieldElement::from(1_u128 << LIMB_BITS), &next_carry.into());
+        opcodes.push(Opcode::Arithmetic(decomposition));
+        let next_carry_expr = Expression::from(next_carry);
+        let boolean = (&next_carry_expr * &next_carry_expr).expect("carry is linear");
+        opcodes.push(Opcode::Arithmetic(&boolean - &next_carry_expr));
+        carry = next_carry_expr;
+    }
+}
+
+fn range(witness: Witness, num_bits: u32) -> Opcode {
+    Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input: FunctionInput { witness, num_bits } })
+}
+
+/// Returns the bytecode of a Brillig hint which, given the limbs of two factors in registers `0..8`, returns
+/// the five limbs of the quotient and the four limbs of the remainder of their product by `p` in registers `0..9`.
+///
+/// The factors' limbs must be non-negative and bounded as in [`mul`].
+fn mul_hint_bytecode() -> Vec<brillig::Opcode> {
+    // The registers holding the inputs and outputs are only written once the outputs are known.
+    let mut hint = HintBuilder::new(2 * NUM_LIMBS + 1);
+    let lhs: Vec<RegisterIndex> = (0..NUM_LIMBS).map(RegisterIndex::from).collect();
+    let rhs: Vec<RegisterIndex> = (NUM_LIMBS..2 * NUM_LIMBS).map(RegisterIndex::from).collect();
+    let complement = hint.constant(MODULUS_COMPLEMENT);
+
+    // Schoolbook multiplication, followed by splitting the product into nine limbs.
+    let mut columns = Vec::new();
+    for (i, &lhs_limb) in lhs.iter().enumerate() {
+        for (j, &rhs_limb) in rhs.iter().enumerate() {
+            let product = hint.op(BinaryIntOp::Mul, lhs_limb, rhs_limb);
+            if i + j < columns.len() {
+                columns[i + j] = hint.op(BinaryIntOp::Add, columns[i + j], product);
+            } else {
+                columns.push(product);
+            }
+        }
+    }
+    let mut limbs = hint.normalize(&columns, 2 * NUM_LIMBS + 1);
+
+    // Repeatedly fold the limbs above 2^256 back in using 2^256 = p + MODULUS_COMPLEMENT, adding each folded
+    // value to the quotient. The product shrinks below 2^354, then below 2^257 and finally below 2^256.
+    let zero = hint.constant(0);
+    let mut quotient = vec![zero; NUM_LIMBS + 1];
+    for num_limbs in [NUM_LIMBS + 2, NUM_LIMBS + 1, NUM_LIMBS] {
+        let (low, high) = limbs.split_at(NUM_LIMBS);
+        let mut columns = low.to_vec();
+        for (i, &high_limb) in high.iter().enumerate() {
+            quotient[i] = hint.op(BinaryIntOp::Add, quotient[i], high_limb);
+            let folded = hint.op(BinaryIntOp::Mul, high_limb, complement);
+            if i < columns.len() {
+                columns[i] = hint.op(BinaryIntOp::Add, columns[i], folded);
+            } else {
+                columns.push(folded);
+            }
+        }
+        limbs = hint.normalize(&columns, num_limbs);
+    }
+
+    // Subtract p once more if the value is at least p, which is when adding MODULUS_COMPLEMENT carries out.
+    let mut columns = limbs.clone();
+    columns[0] = hint.op(BinaryIntOp::Add, columns[0], complement);
+    let overflow = hint.normalize(&columns, NUM_LIMBS + 1)[NUM_LIMBS];
+    let mut columns = limbs;
+    let correction = hint.op(BinaryIntOp::Mul, overflow, complement);
+    columns[0] = hint.op(BinaryIntOp::Add, columns[0], correction);
+    let remainder = hint.normalize(&columns, NUM_LIMBS);
+    quotient[0] = hint.op(BinaryIntOp::Add, quotient[0], overflow);
+    let quotient = hint.normalize(&quotient, NUM_LIMBS + 1);
+
+    for (i, &source) in quotient.iter().chain(&remainder).enumerate() {
+        hint.bytecode.push(brillig::Opcode::Mov { destination: RegisterIndex::from(i), source });
+    }
+    hint.bytecode
+}
+
+/// Emits Brillig bytecode operating on integers of [`HINT_BIT_SIZE`] bits, each stored in a fresh register.
+struct HintBuilder {
+    bytecode: Vec<brillig::Opcode>,
+    next_register: usize,
+    limb_mask: RegisterIndex,
+    limb_base: RegisterIndex,
+}
+
+impl HintBuilder {
+    fn new(first_free_register: usize) -> Self {
+        let mut builder = HintBuilder {
+            bytecode: Vec::new(),
+            next_register: first_free_register,
+            limb_mask: RegisterIndex::from(0),
+            limb_base: RegisterIndex::from(0),
+        };
+        builder.limb_mask = builder.constant(LIMB_MAX);
+        builder.limb_base = builder.constant(1 << LIMB_BITS);
+        builder
+    }
+
+    fn register(&mut self) -> RegisterIndex {
+        let register = RegisterIndex::from(self.next_register);
+        self.next_register += 1;
+        register
+    }
+
+    fn constant(&mut self, value: u128) -> RegisterIndex {
+        let destination = self.register();
+        self.bytecode.push(brillig::Opcode::Const {
+            destination,
+            value: brillig::Value::from(FieldElement::from(value)),
+        });
+        destination
+    }
+
+    fn op(&mut self, op: BinaryIntOp, lhs: RegisterIndex, rhs: RegisterIndex) -> RegisterIndex {
+        let destination = self.register();
+        self.bytecode.push(brillig::Opcode::BinaryIntOp {
+            op,
+            bit_size: HINT_BIT_SIZE,
+            lhs,
+            rhs,
+            destination,
+        });
+        destination
+    }
+
+    /// Propagates carries through `columns`, returning the value they represent split into `num_limbs` limbs of
+    /// 64 bits. Any carry out of the last limb is discarded.
+    fn normalize(&mut self, columns: &[RegisterIndex], num_limbs: usize) -> Vec<RegisterIndex> {
+        let mut limbs = Vec::with_capacity(num_limbs);
+        let mut carry = None;
+        for i in 0..num_limbs {
+            let value = match (columns.get(i), carry) {
+                (Some(&column), Some(carry)) => self.op(BinaryIntOp::Add, column, carry),
+                (Some(&column), None) => column,
+                (None, Some(carry)) => carry,
+                (None, None) => unreachable!("there is at least one column"),
+            };
+            limbs.push(self.op(BinaryIntOp::And, value, self.limb_mask));
+            // Brillig only shifts integers of up to 128 bits, so the carry is found by division instead.
+            carry = Some(self.op(BinaryIntOp::UnsignedDiv, value, self.limb_base));
+        }
+        limbs
+    }
+}
@@ -0,0 +1,387 @@
+//! Sha512 fallback function.
+use super::sha256::pad;
+use super::uint64::UInt64;
+use super::utils::{byte_decomposition, round_to_nearest_byte};
+use acir::{
+    circuit::Opcode,
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+
+const INIT_CONSTANTS: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const ROUND_CONSTANTS: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+pub fn sha512(
+    inputs: Vec<(Expression, u32)>,
+    outputs: Vec<Witness>,
+    mut num_witness: u32,
+) -> (u32, Vec<Opcode>) {
+    let mut new_opcodes = Vec::new();
+    let mut new_inputs = Vec::new();
+    let mut total_num_bytes = 0;
+
+    // Decompose the input field elements into bytes and collect the resulting witnesses.
+    for (witness, num_bits) in inputs {
+        let num_bytes = round_to_nearest_byte(num_bits);
+        total_num_bytes += num_bytes;
+        let (extra_opcodes, extra_inputs, updated_witness_counter) =
+            byte_decomposition(witness, num_bytes, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        new_inputs.extend(extra_inputs);
+        num_witness = updated_witness_counter;
+    }
+
+    let (result, num_witness, extra_opcodes) =
+        create_sha512_constraint(new_inputs, total_num_bytes, num_witness);
+    new_opcodes.extend(extra_opcodes);
+
+    // constrain the outputs to be the same as the result of the circuit
+    for i in 0..outputs.len() {
+        let mut expr = Expression::from(outputs[i]);
+        expr.push_addition_term(-FieldElement::one(), result[i]);
+        new_opcodes.push(Opcode::Arithmetic(expr));
+    }
+    (num_witness, new_opcodes)
+}
+
+fn create_sha512_constraint(
+    mut input: Vec<Witness>,
+    total_num_bytes: u32,
+    num_witness: u32,
+) -> (Vec<Witness>, u32, Vec<Opcode>) {
+    let mut new_opcodes = Vec::new();
+
+    // pad the bytes according to sha512 padding rules
+    let message_bits = total_num_bytes * 8;
+    let (mut num_witness, pad_witness, extra_opcodes) = pad(128, 8, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    input.push(pad_witness);
+    let bytes_per_block = 128;
+    let num_bytes = (input.len() + 16) as u32;
+    let num_blocks = num_bytes / bytes_per_block + ((num_bytes % bytes_per_block != 0) as u32);
+    let num_total_bytes = num_blocks * bytes_per_block;
+    // The message length is appended as a 128 bit integer, the upper 64 bits of which are always zero.
+    for _ in num_bytes..num_total_bytes + 8 {
+        let (updated_witness_counter, pad_witness, extra_opcodes) = pad(0, 8, num_witness);
+        num_witness = updated_witness_counter;
+        new_opcodes.extend(extra_opcodes);
+        input.push(pad_witness);
+    }
+    let (num_witness, pad_witness, extra_opcodes) = pad(message_bits, 64, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (extra_opcodes, pad_witness, num_witness) =
+        byte_decomposition(pad_witness.into(), 8, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    input.extend(pad_witness);
+
+    // turn witness into u64 and load sha512 state
+    let (input, extra_opcodes, num_witness) = UInt64::from_witnesses(&input, num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (mut rolling_hash, extra_opcodes, num_witness) = prepare_state_constants(num_witness);
+    new_opcodes.extend(extra_opcodes);
+    let (round_constants, extra_opcodes, mut num_witness) = prepare_round_constants(num_witness);
+    new_opcodes.extend(extra_opcodes);
+    // split the input into blocks of size 16
+    let input: Vec<Vec<UInt64>> = input.chunks(16).map(|block| block.to_vec()).collect();
+
+    // process sha512 blocks
+    for i in &input {
+        let (new_rolling_hash, extra_opcodes, updated_witness_counter) =
+            sha512_block(i, rolling_hash.clone(), round_constants.clone(), num_witness);
+        new_opcodes.extend(extra_opcodes);
+        num_witness = updated_witness_counter;
+        rolling_hash = new_rolling_hash;
+    }
+
+    // decompose the result bytes in u64 to u8
+    let mut result = Vec::new();
+    for word in rolling_hash {
+        let (extra_opcodes, bytes, updated_witness_counter) =
+            byte_decomposition(Expression::from(word.inner), 8, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        result.extend(bytes);
+        num_witness = updated_witness_counter;
+    }
+
+    (result, num_witness, new_opcodes)
+}
+
+fn sha512_block(
+    input: &[UInt64],
+    rolling_hash: Vec<UInt64>,
+    round_constants: Vec<UInt64>,
+    mut num_witness: u32,
+) -> (Vec<UInt64>, Vec<Opcode>, u32) {
+    let mut new_opcodes = Vec::new();
+    let mut w = Vec::new();
+    w.extend(input.to_owned());
+
+    for i in 16..80 {
+        // calculate s0 `w[i - 15].ror(1) ^ w[i - 15].ror(8) ^ (w[i - 15] >> 7)`
+        let (a1, extra_opcodes, updated_witness_counter) = w[i - 15].ror(1, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        let (a2, extra_opcodes, updated_witness_counter) =
+            w[i - 15].ror(8, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (a3, extra_opcodes, updated_witness_counter) =
+            w[i - 15].rightshift(7, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (a4, extra_opcodes, updated_witness_counter) = a1.xor(&a2, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (s0, extra_opcodes, updated_witness_counter) = a4.xor(&a3, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // calculate s1 `w[i - 2].ror(19) ^ w[i - 2].ror(61) ^ (w[i - 2] >> 6)`
+        let (b1, extra_opcodes, updated_witness_counter) =
+            w[i - 2].ror(19, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (b2, extra_opcodes, updated_witness_counter) =
+            w[i - 2].ror(61, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (b3, extra_opcodes, updated_witness_counter) =
+            w[i - 2].rightshift(6, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (b4, extra_opcodes, updated_witness_counter) = b1.xor(&b2, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (s1, extra_opcodes, updated_witness_counter) = b4.xor(&b3, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // calculate w[i] `w[i - 16] + w[i - 7] + s0 + s1`
+        let (c1, extra_opcodes, updated_witness_counter) =
+            w[i - 16].add(&w[i - 7], updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (c2, extra_opcodes, updated_witness_counter) = c1.add(&s0, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (c3, extra_opcodes, updated_witness_counter) = c2.add(&s1, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        w.push(c3);
+        num_witness = updated_witness_counter;
+    }
+
+    let mut a = rolling_hash[0];
+    let mut b = rolling_hash[1];
+    let mut c = rolling_hash[2];
+    let mut d = rolling_hash[3];
+    let mut e = rolling_hash[4];
+    let mut f = rolling_hash[5];
+    let mut g = rolling_hash[6];
+    let mut h = rolling_hash[7];
+
+    #[allow(non_snake_case)]
+    for i in 0..80 {
+        // calculate S1 `e.ror(14) ^ e.ror(18) ^ e.ror(41)`
+        let (a1, extra_opcodes, updated_witness_counter) = e.ror(14, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        let (a2, extra_opcodes, updated_witness_counter) = e.ror(18, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (a3, extra_opcodes, updated_witness_counter) = e.ror(41, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (a4, extra_opcodes, updated_witness_counter) = a1.xor(&a2, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (S1, extra_opcodes, updated_witness_counter) = a4.xor(&a3, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // calculate ch `(e & f) + (~e & g)`
+        let (b1, extra_opcodes, updated_witness_counter) = e.and(&f, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (b2, extra_opcodes, updated_witness_counter) = e.not(updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (b3, extra_opcodes, updated_witness_counter) = b2.and(&g, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (ch, extra_opcodes, updated_witness_counter) = b1.add(&b3, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // caculate temp1 `h + S1 + ch + round_constants[i] + w[i]`
+        let (c1, extra_opcodes, updated_witness_counter) = h.add(&S1, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (c2, extra_opcodes, updated_witness_counter) = c1.add(&ch, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (c3, extra_opcodes, updated_witness_counter) =
+            c2.add(&round_constants[i], updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (temp1, extra_opcodes, updated_witness_counter) =
+            c3.add(&w[i], updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // calculate S0 `a.ror(28) ^ a.ror(34) ^ a.ror(39)`
+        let (d1, extra_opcodes, updated_witness_counter) = a.ror(28, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (d2, extra_opcodes, updated_witness_counter) = a.ror(34, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (d3, extra_opcodes, updated_witness_counter) = a.ror(39, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (d4, extra_opcodes, updated_witness_counter) = d1.xor(&d2, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (S0, extra_opcodes, updated_witness_counter) = d4.xor(&d3, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // calculate T0 `b & c`
+        let (T0, extra_opcodes, updated_witness_counter) = b.and(&c, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // calculate maj `(a & (b + c - (T0 + T0))) + T0` which is the same as `(a & b) ^ (a & c) ^ (b & c)`
+        let (e1, extra_opcodes, updated_witness_counter) = T0.add(&T0, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (e2, extra_opcodes, updated_witness_counter) = c.sub(&e1, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (e3, extra_opcodes, updated_witness_counter) = b.add(&e2, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (e4, extra_opcodes, updated_witness_counter) = a.and(&e3, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        let (maj, extra_opcodes, updated_witness_counter) = e4.add(&T0, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        // calculate temp2 `S0 + maj`
+        let (temp2, extra_opcodes, updated_witness_counter) = S0.add(&maj, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+
+        h = g;
+        g = f;
+        f = e;
+        let (new_e, extra_opcodes, updated_witness_counter) =
+            d.add(&temp1, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        d = c;
+        c = b;
+        b = a;
+        let (new_a, extra_opcodes, updated_witness_counter) =
+            temp1.add(&temp2, updated_witness_counter);
+        new_opcodes.extend(extra_opcodes);
+        num_witness = updated_witness_counter;
+        a = new_a;
+        e = new_e;
+    }
+
+    let mut output = Vec::new();
+    for (word, previous) in [a, b, c, d, e, f, g, h].iter().zip(rolling_hash.iter()) {
+        let (new_word, extra_opcodes, updated_witness_counter) = word.add(previous, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        output.push(new_word);
+        num_witness = updated_witness_counter;
+    }
+
+    (output, new_opcodes, num_witness)
+}
+
+/// Load initial state constants of Sha512
+fn prepare_state_constants(mut num_witness: u32) -> (Vec<UInt64>, Vec<Opcode>, u32) {
+    let mut new_opcodes = Vec::new();
+    let mut new_witnesses = Vec::new();
+
+    for i in INIT_CONSTANTS {
+        let (new_witness, extra_opcodes, updated_witness_counter) =
+            UInt64::load_constant(i, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        new_witnesses.push(new_witness);
+        num_witness = updated_witness_counter;
+    }
+
+    (new_witnesses, new_opcodes, num_witness)
+}
+
+/// Load round constants of Sha512
+fn prepare_round_constants(mut num_witness: u32) -> (Vec<UInt64>, Vec<Opcode>, u32) {
+    let mut new_opcodes = Vec::new();
+    let mut new_witnesses = Vec::new();
+
+    for i in ROUND_CONSTANTS {
+        let (new_witness, extra_opcodes, updated_witness_counter) =
+            UInt64::load_constant(i, num_witness);
+        new_opcodes.extend(extra_opcodes);
+        new_witnesses.push(new_witness);
+        num_witness = updated_witness_counter;
+    }
+
+    (new_witnesses, new_opcodes, num_witness)
+}